@@ -0,0 +1,86 @@
+//! Rectangle operations, bezier flattening and polygon triangulation. Run
+//! with `cargo bench --features test-util --bench geometry`.
+
+extern crate criterion;
+extern crate math2d;
+
+mod support;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::hint::black_box;
+
+use math2d::test_util::Generator;
+use math2d::triangulation;
+use math2d::Polygon;
+
+const RECTS: usize = 10_000;
+const BEZIERS: usize = 1_000;
+
+fn rect_ops(c: &mut Criterion) {
+    let mut gen = Generator::new(support::SEED);
+    let rects = gen.rects(RECTS);
+    let points = gen.points(RECTS);
+
+    let mut group = c.benchmark_group("rect_ops");
+    group.throughput(Throughput::Elements(RECTS as u64));
+    group.bench_function("contains_point", |b| {
+        b.iter(|| {
+            rects
+                .iter()
+                .zip(&points)
+                .filter(|(r, &p)| r.contains_point(p))
+                .count()
+        })
+    });
+    group.bench_function("overlaps", |b| {
+        b.iter(|| rects.windows(2).filter(|w| w[0].overlaps(&w[1])).count())
+    });
+    group.bench_function("combined_with", |b| {
+        b.iter(|| rects.iter().fold(rects[0], |acc, r| acc.combined_with(*r)))
+    });
+    let polygon = Polygon::new(points);
+    group.bench_function("bounds", |b| b.iter(|| black_box(&polygon).bounds()));
+    group.finish();
+}
+
+fn bezier_flattening(c: &mut Criterion) {
+    let beziers = Generator::new(support::SEED).beziers(BEZIERS);
+
+    let mut group = c.benchmark_group("bezier_flattening");
+    group.throughput(Throughput::Elements(BEZIERS as u64));
+    for &tolerance in &[1.0, 0.1, 0.01] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(tolerance),
+            &tolerance,
+            |b, &tolerance| {
+                b.iter(|| {
+                    for (start, bezier) in &beziers {
+                        black_box(bezier.flatten(*start, tolerance));
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn polygon_triangulation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("polygon_triangulation");
+    for &vertices in &[16, 128, 1024] {
+        let polygon = Generator::new(support::SEED).polygon(vertices);
+        group.throughput(Throughput::Elements(vertices as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(vertices),
+            &polygon.points,
+            |b, points| b.iter(|| triangulation::ear_clip(points)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = support::config();
+    targets = rect_ops, bezier_flattening, polygon_triangulation
+}
+criterion_main!(benches);
@@ -0,0 +1,105 @@
+//! Matrix multiplication and point, vector and rectangle transforms,
+//! comparing the special-cased matrix paths against the general ones. Run
+//! with `cargo bench --features test-util --bench matrix_transform`.
+
+extern crate criterion;
+extern crate math2d;
+
+mod support;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use std::hint::black_box;
+
+use math2d::test_util::Generator;
+use math2d::{Matrix3x2f, Point2f};
+
+const POINTS: usize = 10_000;
+
+fn matrix_multiply(c: &mut Criterion) {
+    let matrices = Generator::new(support::SEED).matrices(POINTS);
+
+    let mut group = c.benchmark_group("matrix_multiply");
+    group.throughput(Throughput::Elements(POINTS as u64));
+    group.bench_function("product", |b| {
+        b.iter(|| {
+            matrices
+                .iter()
+                .fold(Matrix3x2f::IDENTITY, |acc, &m| black_box(acc * m))
+        })
+    });
+    group.bench_function("inverse", |b| {
+        b.iter(|| {
+            for m in &matrices {
+                black_box(m.inverse());
+            }
+        })
+    });
+    group.finish();
+}
+
+/// The matrices with a special-cased path, followed by a general one.
+fn labeled_matrices() -> [(&'static str, Matrix3x2f); 3] {
+    let general = Generator::new(support::SEED).matrices(1)[0];
+    [
+        ("translation", Matrix3x2f::translation([3.0, -7.0])),
+        ("scale", Matrix3x2f::scaling([2.0, 0.5], (1.0, 1.0))),
+        ("general", general),
+    ]
+}
+
+fn point_transforms(c: &mut Criterion) {
+    let points = Generator::new(support::SEED).points(POINTS);
+
+    let mut group = c.benchmark_group("point_transforms");
+    group.throughput(Throughput::Elements(POINTS as u64));
+    for (label, matrix) in labeled_matrices().iter() {
+        group.bench_function(format!("{}/point_mul", label), |b| {
+            b.iter_batched_ref(
+                || points.clone(),
+                |out| {
+                    let matrix = *black_box(matrix);
+                    for p in out.iter_mut() {
+                        *p = *p * matrix;
+                    }
+                },
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_function(format!("{}/transform_points", label), |b| {
+            b.iter_batched_ref(
+                || points.clone(),
+                |out| black_box(matrix).transform_points(out),
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_function(format!("{}/transform_points_into", label), |b| {
+            let mut out = vec![Point2f::ORIGIN; points.len()];
+            b.iter(|| black_box(matrix).transform_points_into(&points, &mut out))
+        });
+    }
+    group.finish();
+}
+
+fn rect_transforms(c: &mut Criterion) {
+    let rects = Generator::new(support::SEED).rects(POINTS);
+
+    let mut group = c.benchmark_group("rect_transforms");
+    group.throughput(Throughput::Elements(POINTS as u64));
+    for (label, matrix) in labeled_matrices().iter() {
+        group.bench_function(*label, |b| {
+            b.iter(|| {
+                for r in &rects {
+                    black_box(black_box(matrix).transform_rect(r));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = support::config();
+    targets = matrix_multiply, point_transforms, rect_transforms
+}
+criterion_main!(benches);
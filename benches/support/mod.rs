@@ -0,0 +1,27 @@
+//! Settings shared by every benchmark in the suite.
+
+use criterion::Criterion;
+use std::time::Duration;
+
+/// Seed for the input generators, fixed so that every run measures the same
+/// inputs and saved baselines stay comparable.
+pub const SEED: u64 = 0x6d61_7468_3264;
+
+/// Criterion settings with the regression thresholds for the suite.
+///
+/// A change is only reported when it is statistically significant at the
+/// 1% level and moves the mean by more than 3%, so that ordinary run to run
+/// noise is not reported as a regression. Compare a branch against a stored baseline
+/// with:
+///
+/// ```text
+/// cargo bench --features test-util -- --save-baseline master
+/// cargo bench --features test-util -- --baseline master
+/// ```
+pub fn config() -> Criterion {
+    Criterion::default()
+        .significance_level(0.01)
+        .noise_threshold(0.03)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(3))
+}
@@ -0,0 +1,131 @@
+//! Wrapping and interpolating angles in radians.
+//!
+//! Angles a whole turn apart describe the same direction, so subtracting or
+//! interpolating them directly can take the long way around: animating from
+//! 350° to 10° would spin backwards through 340° instead of turning 20°.
+//! These functions always work with the shortest rotation between two
+//! angles.
+//!
+//! Most of math2d takes angles as plain `f32` radians, except where it
+//! mirrors an API which uses degrees, such as `ArcSegment::rotation_angle`.
+//! `Angle` carries its unit with it for APIs where mixing them up is easy.
+
+use std::f32::consts::PI;
+
+const TAU: f32 = 2.0 * PI;
+
+/// An angle which can be given and read back in either degrees or radians.
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Angle {
+    radians: f32,
+}
+
+impl Angle {
+    /// The angle of no rotation.
+    pub const ZERO: Angle = Angle { radians: 0.0 };
+
+    /// Constructs an angle measured in radians.
+    #[inline]
+    pub fn from_radians(radians: f32) -> Angle {
+        Angle { radians }
+    }
+
+    /// Constructs an angle measured in degrees.
+    #[inline]
+    pub fn from_degrees(degrees: f32) -> Angle {
+        Angle::from_radians(degrees.to_radians())
+    }
+
+    /// The angle in radians.
+    #[inline]
+    pub fn radians(self) -> f32 {
+        self.radians
+    }
+
+    /// The angle in degrees.
+    #[inline]
+    pub fn degrees(self) -> f32 {
+        self.radians.to_degrees()
+    }
+
+    /// The equivalent angle in the range `(-π, π]`, as with `wrap_to_pi`.
+    #[inline]
+    pub fn wrapped(self) -> Angle {
+        Angle::from_radians(wrap_to_pi(self.radians))
+    }
+}
+
+/// Wraps an angle into the range `(-π, π]`.
+#[inline]
+pub fn wrap_to_pi(angle: f32) -> f32 {
+    let wrapped = wrap_to_2pi(angle);
+    if wrapped > PI {
+        wrapped - TAU
+    } else {
+        wrapped
+    }
+}
+
+/// Wraps an angle into the range `[0, 2π)`.
+#[inline]
+pub fn wrap_to_2pi(angle: f32) -> f32 {
+    // `rem_euclid` can round up to exactly 2π for tiny negative angles
+    let wrapped = angle.rem_euclid(TAU);
+    if wrapped >= TAU {
+        0.0
+    } else {
+        wrapped
+    }
+}
+
+/// The signed rotation which takes angle `a` to angle `b` the short way
+/// around, in the range `(-π, π]`. Positive values turn clockwise in y-down
+/// coordinates. Angles exactly opposite each other turn by `π`.
+#[inline]
+pub fn shortest_delta(a: f32, b: f32) -> f32 {
+    wrap_to_pi(b - a)
+}
+
+/// Interpolates from angle `a` to angle `b` along the shortest rotation
+/// between them. `0.0` returns `a` and `1.0` returns an angle equivalent to
+/// `b`. The result is not wrapped, so animating with increasing `t` changes
+/// it continuously.
+#[inline]
+pub fn lerp_angle(a: f32, b: f32, t: f32) -> f32 {
+    a + shortest_delta(a, b) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::angle::{lerp_angle, shortest_delta, wrap_to_2pi, wrap_to_pi, Angle};
+
+    use std::f32::consts::PI;
+
+    #[test]
+    fn wrapping() {
+        assert!((wrap_to_pi(3.0 * PI / 2.0) + PI / 2.0).abs() < 1e-5);
+        assert_eq!(wrap_to_pi(PI), PI);
+        assert!((wrap_to_pi(-PI) - PI).abs() < 1e-5);
+        assert!((wrap_to_2pi(-PI / 2.0) - 3.0 * PI / 2.0).abs() < 1e-5);
+        assert_eq!(wrap_to_2pi(-1e-9), 0.0);
+
+        // 350° to 10° turns forwards by 20°
+        let (a, b) = (350f32.to_radians(), 10f32.to_radians());
+        assert!((shortest_delta(a, b) - 20f32.to_radians()).abs() < 1e-5);
+        assert!((shortest_delta(b, a) + 20f32.to_radians()).abs() < 1e-5);
+        let halfway = lerp_angle(a, b, 0.5);
+        assert!(shortest_delta(halfway, 0.0).abs() < 1e-5);
+        assert_eq!(lerp_angle(a, b, 0.0), a);
+
+        let right = Angle::from_degrees(90.0);
+        assert_eq!(right.radians(), PI / 2.0);
+        assert!((Angle::from_degrees(450.0).wrapped().degrees() - 90.0).abs() < 1e-4);
+    }
+}
@@ -2,17 +2,35 @@
 //! and are designed to be part of a Path. See Direct2D, SVG, etc for
 //! an overview of the Path concept.
 
+use crate::angle::Angle;
+use crate::bezier_segment::BezierSegment;
+use crate::center_arc::CenterArc;
+use crate::matrix3x2f::Matrix3x2f;
 use crate::point2f::Point2f;
+use crate::rectf::Rectf;
 use crate::sizef::Sizef;
+use crate::tolerance::Tolerance;
+use crate::vector2f::Vector2f;
+
+use std::f32::consts::PI;
+use std::ops::Not;
 
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::d2d1::D2D1_ARC_SEGMENT;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D as d2d;
 
 /// Describes an elliptical arc between two points. The starting point
 /// is implicit when an ArcSegment is used as part of a Path, as it is a
 /// continuation from the previous segment.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(C)]
 pub struct ArcSegment {
     /// The end point of the arc.
@@ -48,11 +66,296 @@ impl ArcSegment {
             arc_size,
         }
     }
+
+    /// Starts building an arc to `point` with the given radii, which sweeps
+    /// counterclockwise along the smaller of the two possible arcs of an
+    /// unrotated ellipse unless told otherwise.
+    #[inline]
+    pub fn builder(point: impl Into<Point2f>, radii: impl Into<Sizef>) -> ArcSegmentBuilder {
+        ArcSegmentBuilder {
+            point: point.into(),
+            radii: radii.into(),
+            rotation: Angle::ZERO,
+            clockwise: false,
+            large_arc: false,
+        }
+    }
+
+    /// Constructs an arc from its center parameterization, returning the
+    /// start point of the arc alongside the segment. Unlike `rotation_angle`,
+    /// all angles here are in radians, with positive sweeps travelling
+    /// clockwise. See `CenterArc` for details.
+    #[inline]
+    pub fn from_center(
+        center: impl Into<Point2f>,
+        radii: impl Into<Vector2f>,
+        rotation: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+    ) -> (Point2f, ArcSegment) {
+        CenterArc::new(center, radii, start_angle, sweep_angle, rotation).to_arc_segment()
+    }
+
+    /// Constructs the circular arc which starts at `p0`, passes through `p1`
+    /// and ends at `p2`. Returns `None` if the points are collinear (or
+    /// coincident), since no circle passes through them.
+    pub fn from_three_points(
+        p0: impl Into<Point2f>,
+        p1: impl Into<Point2f>,
+        p2: impl Into<Point2f>,
+    ) -> Option<ArcSegment> {
+        let (p0, p1, p2) = (p0.into(), p1.into(), p2.into());
+        let b = p1 - p0;
+        let c = p2 - p0;
+        let d = 2.0 * b.cross(c);
+        let scale = b.len_squared().max(c.len_squared());
+        if d.abs() <= scale * 1e-6 {
+            return None;
+        }
+
+        // Circumcenter relative to p0
+        let (b2, c2) = (b.len_squared(), c.len_squared());
+        let offset = Vector2f::new(c.y * b2 - b.y * c2, b.x * c2 - c.x * b2) / d;
+        let center = p0 + offset;
+        let radius = offset.len();
+
+        let angle = |p: Point2f| (p.y - center.y).atan2(p.x - center.x);
+        let (a0, a2) = (angle(p0), angle(p2));
+        let clockwise = (p1 - p0).cross(p2 - p1) > 0.0;
+        let sweep = if clockwise { a2 - a0 } else { a0 - a2 }.rem_euclid(2.0 * PI);
+
+        Some(ArcSegment {
+            point: p2,
+            size: Sizef::new(radius, radius),
+            rotation_angle: 0.0,
+            sweep_direction: clockwise.into(),
+            arc_size: (sweep > PI).into(),
+        })
+    }
+
+    /// The point on the arc at the parameter `t`, given the start point of
+    /// the segment. Degenerate arcs are treated as straight lines.
+    #[inline]
+    pub fn point_at(&self, start: impl Into<Point2f>, t: f32) -> Point2f {
+        let start = start.into();
+        match self.to_center_form(start) {
+            Some(arc) => arc.point_at(t),
+            None => start + (self.point - start) * t,
+        }
+    }
+
+    /// The derivative of the arc at the parameter `t`, given the start point
+    /// of the segment. Degenerate arcs are treated as straight lines.
+    #[inline]
+    pub fn tangent_at(&self, start: impl Into<Point2f>, t: f32) -> Vector2f {
+        let start = start.into();
+        match self.to_center_form(start) {
+            Some(arc) => arc.tangent_at(t),
+            None => self.point - start,
+        }
+    }
+
+    /// The length of the arc, given the start point of the segment.
+    #[inline]
+    pub fn arc_length(&self, start: impl Into<Point2f>) -> f32 {
+        let start = start.into();
+        match self.to_center_form(start) {
+            Some(arc) => arc.arc_length(),
+            None => (self.point - start).len(),
+        }
+    }
+
+    /// Determines if the arc ends where it starts. As in SVG, such an arc is
+    /// not drawn at all, whatever its radii.
+    #[inline]
+    pub fn is_degenerate(&self, start: impl Into<Point2f>) -> bool {
+        self.point == start.into()
+    }
+
+    /// The tight axis-aligned bounding box of the arc, given the start point
+    /// of the segment.
+    #[inline]
+    pub fn bounds(&self, start: impl Into<Point2f>) -> Rectf {
+        let start = start.into();
+        match self.to_center_form(start) {
+            Some(arc) => arc.bounds(),
+            None => Rectf::from_points(start, self.point),
+        }
+    }
+
+    /// Approximates the arc with line segments no further than `tolerance`
+    /// from the true curve. The start point is not included in the result,
+    /// but the end point always is (exactly).
+    #[inline]
+    pub fn flatten(
+        &self,
+        start: impl Into<Point2f>,
+        tolerance: impl Into<Tolerance>,
+    ) -> Vec<Point2f> {
+        let start = start.into();
+        let mut points = match self.to_center_form(start) {
+            Some(arc) => arc.flatten(tolerance),
+            None => vec![self.point],
+        };
+        if let Some(last) = points.last_mut() {
+            *last = self.point;
+        }
+        points
+    }
+
+    /// Converts the arc into a chain of cubic bezier segments starting at
+    /// `start`, each sweeping no more than `max_angle_per_segment` radians.
+    /// Arcs with a zero radius become a single straight bezier segment, and
+    /// arcs whose endpoints coincide produce an empty chain.
+    pub fn to_beziers(
+        &self,
+        start: impl Into<Point2f>,
+        max_angle_per_segment: f32,
+    ) -> Vec<BezierSegment> {
+        let start = start.into();
+        if start == self.point {
+            return Vec::new();
+        }
+
+        match self.to_center_form(start) {
+            Some(arc) => {
+                let mut beziers = arc.to_beziers(max_angle_per_segment);
+                if let Some(last) = beziers.last_mut() {
+                    last.p3 = self.point;
+                }
+                beziers
+            }
+            None => {
+                let third = (self.point - start) / 3.0;
+                vec![BezierSegment::new(
+                    start + third,
+                    self.point - third,
+                    self.point,
+                )]
+            }
+        }
+    }
+
+    /// Applies an affine transformation to the arc. The image of an ellipse
+    /// under an affine transformation is another ellipse, so this is exact:
+    /// the new radii and rotation are the singular values and principal axis
+    /// of the transformed ellipse, and the sweep direction is mirrored by
+    /// transformations which flip orientation. The start point must be
+    /// transformed separately.
+    pub fn transformed(&self, matrix: &Matrix3x2f) -> ArcSegment {
+        let point = self.point * *matrix;
+        let (sin, cos) = self.rotation_angle.to_radians().sin_cos();
+        let (rx, ry) = (self.size.width.abs(), self.size.height.abs());
+
+        // The images of the ellipse's (scaled) principal axes
+        let u = Vector2f::new(rx * cos, rx * sin) * *matrix;
+        let v = Vector2f::new(-ry * sin, ry * cos) * *matrix;
+
+        // Eigen-decomposition of E * E^T where E = [u v]
+        let p = u.x * u.x + v.x * v.x;
+        let q = u.x * u.y + v.x * v.y;
+        let r = u.y * u.y + v.y * v.y;
+        let mean = (p + r) / 2.0;
+        let spread = (((p - r) / 2.0).powi(2) + q * q).sqrt();
+        let major = (mean + spread).sqrt();
+        let minor = (mean - spread).max(0.0).sqrt();
+        let angle = 0.5 * (2.0 * q).atan2(p - r);
+
+        let sweep_direction = if matrix.determinant() < 0.0 {
+            !self.sweep_direction
+        } else {
+            self.sweep_direction
+        };
+
+        ArcSegment {
+            point,
+            size: Sizef::new(major, minor),
+            rotation_angle: angle.to_degrees(),
+            sweep_direction,
+            arc_size: self.arc_size,
+        }
+    }
+
+    /// Converts the arc from its endpoint parameterization to its center
+    /// parameterization, given the start point of the segment. Follows the
+    /// algorithm described in SVG 1.1 Appendix F.6.5, including the
+    /// correction of radii which are too small to span the endpoints (F.6.6).
+    ///
+    /// Returns `None` if the arc is degenerate, i.e. the endpoints coincide
+    /// (the arc is omitted) or one of the radii is zero (the arc is a
+    /// straight line).
+    pub fn to_center_form(&self, start: impl Into<Point2f>) -> Option<CenterArc> {
+        let p1 = start.into();
+        let p2 = self.point;
+        let mut rx = self.size.width.abs();
+        let mut ry = self.size.height.abs();
+        if p1 == p2 || rx == 0.0 || ry == 0.0 {
+            return None;
+        }
+
+        let rotation = self.rotation_angle.to_radians();
+        let (sin, cos) = rotation.sin_cos();
+
+        // Step 1: Transform the midpoint into the ellipse's coordinate space
+        let half = (p1 - p2) / 2.0;
+        let x1p = cos * half.x + sin * half.y;
+        let y1p = -sin * half.x + cos * half.y;
+
+        // Correct out-of-range radii by scaling them up uniformly
+        let lambda = (x1p * x1p) / (rx * rx) + (y1p * y1p) / (ry * ry);
+        if lambda > 1.0 {
+            let scale = lambda.sqrt();
+            rx *= scale;
+            ry *= scale;
+        }
+
+        // Step 2: Compute the transformed center
+        let rx2 = rx * rx;
+        let ry2 = ry * ry;
+        let num = rx2 * ry2 - rx2 * y1p * y1p - ry2 * x1p * x1p;
+        let den = rx2 * y1p * y1p + ry2 * x1p * x1p;
+        let mut coef = (num / den).max(0.0).sqrt();
+        let clockwise = bool::from(self.sweep_direction);
+        if bool::from(self.arc_size) == clockwise {
+            coef = -coef;
+        }
+        let cxp = coef * rx * y1p / ry;
+        let cyp = -coef * ry * x1p / rx;
+
+        // Step 3: Transform the center back to the original coordinate space
+        let mid = Point2f::new((p1.x + p2.x) / 2.0, (p1.y + p2.y) / 2.0);
+        let center = mid + Vector2f::new(cos * cxp - sin * cyp, sin * cxp + cos * cyp);
+
+        // Step 4: Compute the start and sweep angles
+        let u = Vector2f::new((x1p - cxp) / rx, (y1p - cyp) / ry);
+        let v = Vector2f::new((-x1p - cxp) / rx, (-y1p - cyp) / ry);
+        let start_angle = u.y.atan2(u.x);
+        let mut sweep_angle = u.cross(v).atan2(u.dot(v));
+        if !clockwise && sweep_angle > 0.0 {
+            sweep_angle -= 2.0 * PI;
+        } else if clockwise && sweep_angle < 0.0 {
+            sweep_angle += 2.0 * PI;
+        }
+
+        Some(CenterArc {
+            center,
+            radii: Vector2f::new(rx, ry),
+            start_angle,
+            sweep_angle,
+            rotation,
+        })
+    }
 }
 
 /// Defines the direction that an elliptical arc is drawn.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(u32)]
 pub enum SweepDirection {
     /// Arcs are drawn in a counterclockwise (negative-angle) direction.
@@ -61,6 +364,16 @@ pub enum SweepDirection {
     Clockwise = 1,
 }
 
+impl SweepDirection {
+    /// The direction of a figure with the given signed area, such as from
+    /// `Figure::signed_area`, where positive areas wind clockwise on screen.
+    /// Figures with no area are treated as counterclockwise.
+    #[inline]
+    pub fn from_signed_area(area: f32) -> SweepDirection {
+        SweepDirection::from(area > 0.0)
+    }
+}
+
 impl Default for SweepDirection {
     #[inline]
     fn default() -> Self {
@@ -68,9 +381,48 @@ impl Default for SweepDirection {
     }
 }
 
+/// `true` is clockwise, as with SVG's `sweep-flag`.
+impl From<bool> for SweepDirection {
+    #[inline]
+    fn from(clockwise: bool) -> SweepDirection {
+        if clockwise {
+            SweepDirection::Clockwise
+        } else {
+            SweepDirection::CounterClockwise
+        }
+    }
+}
+
+/// Clockwise is `true`, as with SVG's `sweep-flag`.
+impl From<SweepDirection> for bool {
+    #[inline]
+    fn from(dir: SweepDirection) -> bool {
+        dir == SweepDirection::Clockwise
+    }
+}
+
+/// The opposite direction.
+impl Not for SweepDirection {
+    type Output = SweepDirection;
+
+    #[inline]
+    fn not(self) -> SweepDirection {
+        match self {
+            SweepDirection::Clockwise => SweepDirection::CounterClockwise,
+            SweepDirection::CounterClockwise => SweepDirection::Clockwise,
+        }
+    }
+}
+
 /// Specifies whether an arc should be greater than 180 degrees.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(u32)]
 pub enum ArcSize {
     /// An arc's sweep should be 180 degrees or less.
@@ -86,6 +438,120 @@ impl Default for ArcSize {
     }
 }
 
+/// `true` is the large arc, as with SVG's `large-arc-flag`.
+impl From<bool> for ArcSize {
+    #[inline]
+    fn from(large_arc: bool) -> ArcSize {
+        if large_arc {
+            ArcSize::Large
+        } else {
+            ArcSize::Small
+        }
+    }
+}
+
+/// The large arc is `true`, as with SVG's `large-arc-flag`.
+impl From<ArcSize> for bool {
+    #[inline]
+    fn from(size: ArcSize) -> bool {
+        size == ArcSize::Large
+    }
+}
+
+/// The other of the two arcs between the same points.
+impl Not for ArcSize {
+    type Output = ArcSize;
+
+    #[inline]
+    fn not(self) -> ArcSize {
+        match self {
+            ArcSize::Small => ArcSize::Large,
+            ArcSize::Large => ArcSize::Small,
+        }
+    }
+}
+
+/// Builds an `ArcSegment` from named flags, in the style of SVG's arc
+/// command. Created by `ArcSegment::builder`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ArcSegmentBuilder {
+    point: Point2f,
+    radii: Sizef,
+    rotation: Angle,
+    clockwise: bool,
+    large_arc: bool,
+}
+
+impl ArcSegmentBuilder {
+    /// Rotates the ellipse the arc follows clockwise by `rotation`.
+    #[inline]
+    pub fn rotation(self, rotation: Angle) -> Self {
+        ArcSegmentBuilder { rotation, ..self }
+    }
+
+    /// Sweeps the arc clockwise if `clockwise` is true, which is SVG's
+    /// `sweep-flag`.
+    #[inline]
+    pub fn clockwise(self, clockwise: bool) -> Self {
+        ArcSegmentBuilder { clockwise, ..self }
+    }
+
+    /// Takes the larger of the two possible arcs if `large_arc` is true,
+    /// which is SVG's `large-arc-flag`.
+    #[inline]
+    pub fn large_arc(self, large_arc: bool) -> Self {
+        ArcSegmentBuilder { large_arc, ..self }
+    }
+
+    /// Builds the arc. Returns `None` if either radius is negative or NaN.
+    #[inline]
+    pub fn build(self) -> Option<ArcSegment> {
+        let valid = |r: f32| r >= 0.0;
+        if !valid(self.radii.width) || !valid(self.radii.height) {
+            return None;
+        }
+        Some(ArcSegment {
+            point: self.point,
+            size: self.radii,
+            rotation_angle: self.rotation.degrees(),
+            sweep_direction: self.clockwise.into(),
+            arc_size: self.large_arc.into(),
+        })
+    }
+}
+
+#[cfg(feature = "lyon")]
+impl ArcSegment {
+    /// Converts the segment into a lyon SVG-style arc beginning at `start`.
+    #[inline]
+    pub fn to_lyon(&self, start: impl Into<Point2f>) -> lyon_path::geom::SvgArc<f32> {
+        lyon_path::geom::SvgArc {
+            from: start.into().into(),
+            to: self.point.into(),
+            radii: lyon_path::geom::vector(self.size.width, self.size.height),
+            x_rotation: lyon_path::geom::Angle::degrees(self.rotation_angle),
+            flags: lyon_path::geom::ArcFlags {
+                large_arc: self.arc_size.into(),
+                sweep: self.sweep_direction.into(),
+            },
+        }
+    }
+
+    /// Splits a lyon SVG-style arc into its start point and the segment that
+    /// continues from it.
+    #[inline]
+    pub fn from_lyon(arc: lyon_path::geom::SvgArc<f32>) -> (Point2f, ArcSegment) {
+        let segment = ArcSegment::new(
+            arc.to,
+            (arc.radii.x, arc.radii.y),
+            arc.x_rotation.to_degrees(),
+            arc.flags.sweep.into(),
+            arc.flags.large_arc.into(),
+        );
+        (arc.from.into(), segment)
+    }
+}
+
 #[cfg(all(windows, feature = "d2d"))]
 impl From<ArcSegment> for D2D1_ARC_SEGMENT {
     #[inline]
@@ -100,6 +566,20 @@ impl From<ArcSegment> for D2D1_ARC_SEGMENT {
     }
 }
 
+#[cfg(all(windows, feature = "windows"))]
+impl From<ArcSegment> for d2d::D2D1_ARC_SEGMENT {
+    #[inline]
+    fn from(seg: ArcSegment) -> d2d::D2D1_ARC_SEGMENT {
+        d2d::D2D1_ARC_SEGMENT {
+            point: seg.point.into(),
+            size: seg.size.into(),
+            rotationAngle: seg.rotation_angle,
+            sweepDirection: d2d::D2D1_SWEEP_DIRECTION(seg.sweep_direction as i32),
+            arcSize: d2d::D2D1_ARC_SIZE(seg.arc_size as i32),
+        }
+    }
+}
+
 #[cfg(all(test, windows, feature = "d2d"))]
 #[test]
 fn arc_d2d_bin_compat() {
@@ -128,3 +608,154 @@ fn arc_d2d_bin_compat() {
     assert!(ptr_eq(&arc.arc_size, &d2d.arcSize));
     assert_eq!(size_of_val(&arc), size_of_val(d2d));
 }
+
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn arc_windows_bin_compat() {
+    use std::mem::size_of_val;
+
+    fn ptr_eq<T, U>(a: &T, b: &U) -> bool {
+        assert_eq!(size_of_val(a), size_of_val(b));
+        (a as *const T) == (b as *const U as *const T)
+    }
+
+    let arc = ArcSegment::new(
+        (0.0, 0.0),
+        (1.0, 1.0),
+        90.0,
+        SweepDirection::CounterClockwise,
+        ArcSize::Small,
+    );
+    let d2d = unsafe { &*((&arc) as *const _ as *const d2d::D2D1_ARC_SEGMENT) };
+
+    assert!(ptr_eq(&arc.point.x, &d2d.point.x));
+    assert!(ptr_eq(&arc.point.y, &d2d.point.y));
+    assert!(ptr_eq(&arc.size.width, &d2d.size.width));
+    assert!(ptr_eq(&arc.size.height, &d2d.size.height));
+    assert!(ptr_eq(&arc.rotation_angle, &d2d.rotationAngle));
+    assert!(ptr_eq(&arc.sweep_direction, &d2d.sweepDirection));
+    assert!(ptr_eq(&arc.arc_size, &d2d.arcSize));
+    assert_eq!(size_of_val(&arc), size_of_val(d2d));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::angle::Angle;
+    use crate::arc_segment::{ArcSegment, ArcSize, SweepDirection};
+    use crate::matrix3x2f::Matrix3x2f;
+    use crate::path::Path;
+    use crate::point2f::Point2f;
+    use crate::rectf::Rectf;
+
+    use std::f32::consts::PI;
+
+    #[test]
+    fn builder() {
+        let arc = ArcSegment::builder((10.0, 0.0), (5.0, 5.0))
+            .rotation(Angle::from_radians(PI / 2.0))
+            .clockwise(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            arc,
+            ArcSegment::new(
+                (10.0, 0.0),
+                (5.0, 5.0),
+                90.0,
+                SweepDirection::Clockwise,
+                ArcSize::Small
+            )
+        );
+
+        let large = ArcSegment::builder((1.0, 1.0), (2.0, 0.0))
+            .large_arc(true)
+            .build()
+            .unwrap();
+        assert_eq!(large.arc_size, ArcSize::Large);
+        assert_eq!(large.sweep_direction, SweepDirection::CounterClockwise);
+        assert!(ArcSegment::builder((1.0, 1.0), (-2.0, 2.0))
+            .build()
+            .is_none());
+        assert!(ArcSegment::builder((1.0, 1.0), (f32::NAN, 2.0))
+            .build()
+            .is_none());
+    }
+
+    #[test]
+    fn flag_conversions() {
+        // SVG's "A 5 5 0 1 0 10 0" flags, in order
+        let (large_arc, sweep) = (true, false);
+        let arc = ArcSegment::new((10.0, 0.0), (5.0, 5.0), 0.0, sweep.into(), large_arc.into());
+        assert_eq!(arc.arc_size, ArcSize::Large);
+        assert_eq!(arc.sweep_direction, SweepDirection::CounterClockwise);
+        assert!(bool::from(arc.arc_size) && !bool::from(arc.sweep_direction));
+        assert_eq!(!arc.sweep_direction, SweepDirection::Clockwise);
+        assert_eq!(!ArcSize::Small, ArcSize::Large);
+
+        let square = Path::from(Rectf::new(0.0, 0.0, 1.0, 1.0));
+        let winding = SweepDirection::from_signed_area(square.figures[0].signed_area());
+        assert_eq!(winding, SweepDirection::Clockwise);
+        assert_eq!(
+            SweepDirection::from_signed_area(0.0),
+            SweepDirection::CounterClockwise
+        );
+    }
+
+    #[test]
+    fn from_three_points() {
+        let p0 = Point2f::new(6.0, 1.0);
+        let p2 = Point2f::new(1.0, 6.0);
+
+        // Short way around a circle of radius 5 centered at (1, 1)
+        let short = ArcSegment::from_three_points(p0, (4.535_534, 4.535_534), p2).unwrap();
+        assert_eq!(short.sweep_direction, SweepDirection::Clockwise);
+        assert_eq!(short.arc_size, ArcSize::Small);
+        assert!((short.size.width - 5.0).abs() < 1e-4);
+
+        // Long way around the same circle, through the opposite side
+        let long = ArcSegment::from_three_points(p0, (-2.535_534, -2.535_534), p2).unwrap();
+        assert_eq!(long.sweep_direction, SweepDirection::CounterClockwise);
+        assert_eq!(long.arc_size, ArcSize::Large);
+
+        let center = long.to_center_form(p0).unwrap();
+        assert!(center.center.is_approx_eq((1.0, 1.0), 1e-4));
+        assert!(center
+            .point_at(0.5)
+            .is_approx_eq((-2.535_534, -2.535_534), 1e-3));
+
+        assert!(ArcSegment::from_three_points((0.0, 0.0), (1.0, 1.0), (2.0, 2.0)).is_none());
+    }
+
+    #[test]
+    fn from_center() {
+        let (start, arc) = ArcSegment::from_center((0.0, 0.0), [2.0, 1.0], 0.0, 0.0, -PI * 1.5);
+        assert!(start.is_approx_eq((2.0, 0.0), 1e-5));
+        assert!(arc.point.is_approx_eq((0.0, 1.0), 1e-5));
+        assert_eq!(arc.sweep_direction, SweepDirection::CounterClockwise);
+        assert_eq!(arc.arc_size, ArcSize::Large);
+    }
+
+    #[test]
+    fn transformed() {
+        let start = Point2f::new(6.0, 1.0);
+        let arc = ArcSegment::new(
+            (1.0, 6.0),
+            (5.0, 3.0),
+            30.0,
+            SweepDirection::Clockwise,
+            ArcSize::Large,
+        );
+
+        let skewed = Matrix3x2f::rotation(0.5, (1.0, 2.0)) * Matrix3x2f::skew(0.3, 0.0, (0.0, 0.0));
+        let mirrored = Matrix3x2f::scaling([-2.0, 1.0], (0.0, 0.0));
+        for matrix in &[skewed, mirrored] {
+            let new_start = start * *matrix;
+            let new_arc = arc.transformed(matrix);
+            for i in 0..=8 {
+                let t = i as f32 / 8.0;
+                let expected = arc.point_at(start, t) * *matrix;
+                assert!(new_arc.point_at(new_start, t).is_approx_eq(expected, 1e-2));
+            }
+        }
+    }
+}
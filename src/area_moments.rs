@@ -0,0 +1,82 @@
+//! Second moments of area, describing how a shape's area is distributed
+//! around a reference point.
+
+use crate::point2f::Point2f;
+
+/// The second moments of area (area moments of inertia) of a shape about a
+/// pair of axes. Multiplied by a uniform density, these give the moments of
+/// inertia used by rigid body physics.
+///
+/// Moments are always measured as if the shape wound clockwise, so opposite
+/// windings of the same shape have the same moments.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AreaMoments {
+    /// The moment about the x axis, the integral of y² over the area.
+    pub ixx: f32,
+    /// The moment about the y axis, the integral of x² over the area.
+    pub iyy: f32,
+    /// The product of inertia, the integral of xy over the area.
+    pub ixy: f32,
+}
+
+impl AreaMoments {
+    /// Constructs the moments from their components.
+    #[inline]
+    pub fn new(ixx: f32, iyy: f32, ixy: f32) -> AreaMoments {
+        AreaMoments { ixx, iyy, ixy }
+    }
+
+    /// The polar moment about the point where the axes cross, which resists
+    /// rotation within the plane.
+    #[inline]
+    pub fn polar(&self) -> f32 {
+        self.ixx + self.iyy
+    }
+
+    /// The moments about the principal axes, where the product of inertia is
+    /// zero, as the larger and then the smaller moment.
+    #[inline]
+    pub fn principal(&self) -> (f32, f32) {
+        let mid = (self.ixx + self.iyy) / 2.0;
+        let half_diff = (self.ixx - self.iyy) / 2.0;
+        let r = (half_diff * half_diff + self.ixy * self.ixy).sqrt();
+        (mid + r, mid - r)
+    }
+
+    /// The angle in radians from the x axis to the principal axis with the
+    /// smaller moment, which the shape's area is spread along.
+    #[inline]
+    pub fn principal_angle(&self) -> f32 {
+        (2.0 * self.ixy).atan2(self.iyy - self.ixx) / 2.0
+    }
+}
+
+/// The second moments of the closed polygon through `points` about the axes
+/// crossing at `origin`.
+pub(crate) fn polygon_moments(points: &[Point2f], origin: Point2f) -> AreaMoments {
+    let n = points.len();
+    let (mut area, mut ixx, mut iyy, mut ixy) = (0.0, 0.0, 0.0, 0.0);
+    for i in 0..n {
+        let a = points[i] - origin;
+        let b = points[(i + 1) % n] - origin;
+        let cross = a.cross(b);
+        area += cross;
+        ixx += cross * (a.y * a.y + a.y * b.y + b.y * b.y);
+        iyy += cross * (a.x * a.x + a.x * b.x + b.x * b.x);
+        ixy += cross * (a.x * b.y + 2.0 * a.x * a.y + 2.0 * b.x * b.y + b.x * a.y);
+    }
+
+    let sign = area.signum();
+    AreaMoments {
+        ixx: sign * ixx / 12.0,
+        iyy: sign * iyy / 12.0,
+        ixy: sign * ixy / 24.0,
+    }
+}
@@ -0,0 +1,88 @@
+//! Horizontal and vertical axes, for writing an algorithm once and running
+//! it in either direction.
+
+/// One of the two axes of the plane. Points, vectors, sizes and rectangles
+/// can be indexed by axis, so that layout code such as a flexbox-style row
+/// or column can be written in terms of a main axis and its `other` cross
+/// axis instead of being duplicated for `x` and `y`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Axis {
+    /// The horizontal axis, along which `x`, `width`, `left` and `right`
+    /// are measured.
+    X,
+    /// The vertical axis, along which `y`, `height`, `top` and `bottom` are
+    /// measured.
+    Y,
+}
+
+impl Axis {
+    /// Both axes, horizontal first.
+    pub const ALL: [Axis; 2] = [Axis::X, Axis::Y];
+
+    /// The perpendicular axis.
+    #[inline]
+    pub fn other(self) -> Axis {
+        match self {
+            Axis::X => Axis::Y,
+            Axis::Y => Axis::X,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::axis::Axis;
+    use crate::interval::Interval;
+    use crate::point2f::Point2f;
+    use crate::rectf::Rectf;
+    use crate::sizef::Sizef;
+    use crate::vector2f::Vector2f;
+
+    /// Stacks items along `axis` with a gap between them, centering each one
+    /// on the cross axis, as a flexbox row or column would.
+    fn stack(container: Rectf, items: &[Sizef], gap: f32, axis: Axis) -> Vec<Rectf> {
+        let cross = axis.other();
+        let mut pos = container.extent(axis).min;
+        let center = container.extent(cross).center();
+        items
+            .iter()
+            .map(|size| {
+                let (along, across) = (size.along(axis), size.along(cross));
+                let main = Interval::new(pos, pos + along);
+                pos += along + gap;
+                let cross = Interval::new(center - across / 2.0, center + across / 2.0);
+                Rectf::from_extents(axis, main, cross)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn axis_generic_layout() {
+        let container = Rectf::new(0.0, 0.0, 100.0, 20.0);
+        let items = [Sizef::new(10.0, 10.0), Sizef::new(20.0, 4.0)];
+        let row = stack(container, &items, 5.0, Axis::X);
+        assert_eq!(row[1], Rectf::new(15.0, 8.0, 35.0, 12.0));
+
+        // The same layout turned on its side
+        let container = Rectf::new(0.0, 0.0, 20.0, 100.0);
+        let items = [Sizef::new(10.0, 10.0), Sizef::new(4.0, 20.0)];
+        let column = stack(container, &items, 5.0, Axis::Y);
+        assert_eq!(column[1], Rectf::new(8.0, 15.0, 12.0, 35.0));
+
+        let mut p = Point2f::from_axes(Axis::Y, 1.0, 2.0);
+        assert_eq!(p, Point2f::new(2.0, 1.0));
+        p.set(Axis::X, 5.0);
+        assert_eq!(p.get(Axis::X), 5.0);
+        let mut v = Vector2f::new(3.0, 4.0);
+        *v.get_mut(Axis::Y) += 1.0;
+        assert_eq!(v.get(Axis::Y), 5.0);
+        assert_eq!(Axis::ALL.map(|a| v.get(a)), [3.0, 5.0]);
+    }
+}
@@ -2,15 +2,34 @@
 //! cubic bezier segment i.e. a bezier line segment with 4 points,
 //! the two center ones acting as control points.
 
+use crate::arc_segment::ArcSegment;
+use crate::interval::Interval;
+use crate::matrix3x2f::Matrix3x2f;
 use crate::point2f::Point2f;
+use crate::polynomial::{solve_quadratic, solve_quadratic_clamped};
+use crate::rectf::Rectf;
+use crate::tolerance::Tolerance;
+use crate::vector2f::Vector2f;
+
+/// Bounds the recursion when measuring the length of a segment, since each
+/// level halves the remaining error.
+const MAX_LENGTH_DEPTH: u32 = 16;
 
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::d2d1::D2D1_BEZIER_SEGMENT;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D::Common as d2d_common;
 
 /// Represents a cubic bezier segment drawn between two points. The first point
 /// in the bezier segment is implicitly the end point of the previous segment.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(C)]
 pub struct BezierSegment {
     /// The first control point
@@ -36,6 +55,217 @@ impl BezierSegment {
             p3: p3.into(),
         }
     }
+
+    /// Evaluates the point on the curve at the parameter `t`, where `0.0` is
+    /// the start point and `1.0` is the end point `p3`. The start point must
+    /// be provided as it is implicit in the segment.
+    #[inline]
+    pub fn point_at(&self, start: impl Into<Point2f>, t: f32) -> Point2f {
+        let (p0, p1, p2, p3) = self.control_vectors(start.into());
+        let ti = 1.0 - t;
+        let v = (ti * ti * ti) * p0
+            + (3.0 * ti * ti * t) * p1
+            + (3.0 * ti * t * t) * p2
+            + (t * t * t) * p3;
+        v.to_point()
+    }
+
+    /// The first derivative (tangent vector) of the curve at the parameter `t`.
+    #[inline]
+    pub fn derivative_at(&self, start: impl Into<Point2f>, t: f32) -> Vector2f {
+        let (p0, p1, p2, p3) = self.control_vectors(start.into());
+        let ti = 1.0 - t;
+        (3.0 * ti * ti) * (p1 - p0) + (6.0 * ti * t) * (p2 - p1) + (3.0 * t * t) * (p3 - p2)
+    }
+
+    /// The second derivative of the curve at the parameter `t`.
+    #[inline]
+    pub fn second_derivative_at(&self, start: impl Into<Point2f>, t: f32) -> Vector2f {
+        let (p0, p1, p2, p3) = self.control_vectors(start.into());
+        (6.0 * (1.0 - t)) * (p2 - 2.0 * p1 + p0) + (6.0 * t) * (p3 - 2.0 * p2 + p1)
+    }
+
+    /// The signed curvature of the curve at the parameter `t`, i.e. the
+    /// reciprocal of the radius of the osculating circle. Positive values
+    /// turn clockwise in the y-down coordinate system. At a cusp, where the
+    /// derivative vanishes, the curvature is undefined and the result will
+    /// be infinite or NaN.
+    #[inline]
+    pub fn curvature_at(&self, start: impl Into<Point2f>, t: f32) -> f32 {
+        let start = start.into();
+        let d1 = self.derivative_at(start, t);
+        let d2 = self.second_derivative_at(start, t);
+        let len = d1.len();
+        d1.cross(d2) / (len * len * len)
+    }
+
+    /// Finds the parameters in the open range (0, 1) where the curvature of
+    /// the segment changes sign, sorted in ascending order. A cubic bezier
+    /// segment can have at most 2 inflection points.
+    pub fn inflection_points(&self, start: impl Into<Point2f>) -> Vec<f32> {
+        let (p0, p1, p2, p3) = self.control_vectors(start.into());
+        let a = p1 - p0;
+        let b = p2 - 2.0 * p1 + p0;
+        let c = p3 - 3.0 * p2 + 3.0 * p1 - p0;
+
+        // cross(B'(t), B''(t)) reduces to this quadratic in t.
+        let mut roots = solve_quadratic(b.cross(c), a.cross(c), a.cross(b));
+        roots.retain(|&t| t > 0.0 && t < 1.0);
+        roots
+    }
+
+    /// Splits the segment at the parameter `t` using de Casteljau's
+    /// algorithm. The first half starts at `start` and the second half starts
+    /// at the end point of the first.
+    #[inline]
+    pub fn split_at(&self, start: impl Into<Point2f>, t: f32) -> (BezierSegment, BezierSegment) {
+        let (p0, p1, p2, p3) = self.control_vectors(start.into());
+        let lerp = |a: Vector2f, b: Vector2f| a + (b - a) * t;
+        let p01 = lerp(p0, p1);
+        let p12 = lerp(p1, p2);
+        let p23 = lerp(p2, p3);
+        let p012 = lerp(p01, p12);
+        let p123 = lerp(p12, p23);
+        let mid = lerp(p012, p123);
+
+        (
+            BezierSegment::new(p01.to_point(), p012.to_point(), mid.to_point()),
+            BezierSegment::new(p123.to_point(), p23.to_point(), self.p3),
+        )
+    }
+
+    /// Approximates the segment with a chain of circular arcs, in pairs of
+    /// biarcs which match the tangent of the curve at each of their ends.
+    /// The segment is split at its inflection points and then subdivided until
+    /// every biarc is within `tolerance` of the curve.
+    pub fn to_biarcs(&self, start: impl Into<Point2f>, tolerance: f32) -> Vec<ArcSegment> {
+        let mut arcs = Vec::new();
+        let mut start = start.into();
+        let mut rest = *self;
+        let mut prev_t = 0.0;
+        for t in self.inflection_points(start) {
+            let (head, tail) = rest.split_at(start, (t - prev_t) / (1.0 - prev_t));
+            crate::fit::bezier_biarcs(start, &head, tolerance, 0, &mut arcs);
+            start = head.p3;
+            rest = tail;
+            prev_t = t;
+        }
+        crate::fit::bezier_biarcs(start, &rest, tolerance, 0, &mut arcs);
+        arcs
+    }
+
+    /// Determines if the curve has zero length, with every control point at
+    /// the start point.
+    #[inline]
+    pub fn is_degenerate(&self, start: impl Into<Point2f>) -> bool {
+        let start = start.into();
+        self.p1 == start && self.p2 == start && self.p3 == start
+    }
+
+    /// The tight axis-aligned bounding box of the curve, found by including
+    /// the extrema where the derivative of each axis is zero.
+    pub fn bounds(&self, start: impl Into<Point2f>) -> Rectf {
+        let start = start.into();
+        let (p0, p1, p2, p3) = self.control_vectors(start);
+
+        // B'(t) / 3 = a*t^2 + b*t + c, solved independently per axis
+        let a = p3 - 3.0 * p2 + 3.0 * p1 - p0;
+        let b = 2.0 * (p2 - 2.0 * p1 + p0);
+        let c = p1 - p0;
+
+        let extent = |p0: f32, p1: f32, p2: f32, p3: f32, a: f32, b: f32, c: f32| {
+            let extrema = solve_quadratic_clamped(a, b, c).into_iter().map(|t| {
+                let mt = 1.0 - t;
+                mt * mt * mt * p0 + 3.0 * mt * t * (mt * p1 + t * p2) + t * t * t * p3
+            });
+            Interval::between(p0, p3).union(&Interval::covering(extrema))
+        };
+        Rectf::from_intervals(
+            extent(p0.x, p1.x, p2.x, p3.x, a.x, b.x, c.x),
+            extent(p0.y, p1.y, p2.y, p3.y, a.y, b.y, c.y),
+        )
+    }
+
+    /// The bounding box of the control polygon. This is cheaper to compute
+    /// than `bounds` and always contains the curve, but may be larger.
+    #[inline]
+    pub fn fast_bounds(&self, start: impl Into<Point2f>) -> Rectf {
+        Rectf::from_points(start, self.p3).combined_with(Rectf::from_points(self.p1, self.p2))
+    }
+
+    /// Approximates the length of the curve to within `tolerance`, by
+    /// subdividing until the control polygon closely matches the chord.
+    #[inline]
+    pub fn arc_length(&self, start: impl Into<Point2f>, tolerance: f32) -> f32 {
+        self.arc_length_impl(start.into(), tolerance, 0)
+    }
+
+    fn arc_length_impl(&self, start: Point2f, tolerance: f32, depth: u32) -> f32 {
+        let chord = (self.p3 - start).len();
+        let hull = (self.p1 - start).len() + (self.p2 - self.p1).len() + (self.p3 - self.p2).len();
+
+        // The true length lies between the chord and the control polygon
+        if hull - chord <= tolerance || depth >= MAX_LENGTH_DEPTH {
+            return (chord + hull) / 2.0;
+        }
+
+        let (head, tail) = self.split_at(start, 0.5);
+        head.arc_length_impl(start, tolerance / 2.0, depth + 1)
+            + tail.arc_length_impl(head.p3, tolerance / 2.0, depth + 1)
+    }
+
+    /// Approximates the curve with line segments no further than `tolerance`
+    /// from the true curve, using uniform subdivision bounded by the second
+    /// derivative. The start point is not included in the result, but the
+    /// end point always is (exactly). Relative tolerances are measured
+    /// against the size of the control polygon. Tolerances finer than `f32`
+    /// can resolve at that size, including zero and negative ones, are
+    /// raised to `f32::EPSILON` times the size.
+    pub fn flatten(
+        &self,
+        start: impl Into<Point2f>,
+        tolerance: impl Into<Tolerance>,
+    ) -> Vec<Point2f> {
+        let start = start.into();
+        let tolerance = tolerance
+            .into()
+            .resolve_flattening(&self.fast_bounds(start));
+        let (p0, p1, p2, p3) = self.control_vectors(start);
+        let dd = (p0 - 2.0 * p1 + p2).len().max((p1 - 2.0 * p2 + p3).len());
+        let count = if tolerance > 0.0 {
+            (0.75 * dd / tolerance).sqrt().ceil().max(1.0) as usize
+        } else {
+            1
+        };
+
+        let mut points: Vec<_> = (1..count)
+            .map(|i| self.point_at(start, i as f32 / count as f32))
+            .collect();
+        points.push(self.p3);
+        points
+    }
+
+    /// Applies an affine transformation to the segment. Bezier curves are
+    /// affine invariant, so transforming the control points is exact. The
+    /// start point must be transformed separately.
+    #[inline]
+    pub fn transformed(&self, matrix: &Matrix3x2f) -> BezierSegment {
+        BezierSegment {
+            p1: self.p1 * *matrix,
+            p2: self.p2 * *matrix,
+            p3: self.p3 * *matrix,
+        }
+    }
+
+    #[inline]
+    fn control_vectors(&self, start: Point2f) -> (Vector2f, Vector2f, Vector2f, Vector2f) {
+        (
+            start.to_vector(),
+            self.p1.to_vector(),
+            self.p2.to_vector(),
+            self.p3.to_vector(),
+        )
+    }
 }
 
 impl<P1, P2, P3> From<(P1, P2, P3)> for BezierSegment
@@ -54,6 +284,46 @@ where
     }
 }
 
+#[cfg(feature = "kurbo")]
+impl BezierSegment {
+    /// Converts the segment into a kurbo curve beginning at `start`.
+    #[inline]
+    pub fn to_kurbo(&self, start: impl Into<Point2f>) -> kurbo::CubicBez {
+        kurbo::CubicBez::new(start.into(), self.p1, self.p2, self.p3)
+    }
+
+    /// Splits a kurbo curve into its start point and the segment that
+    /// continues from it.
+    #[inline]
+    pub fn from_kurbo(bez: kurbo::CubicBez) -> (Point2f, BezierSegment) {
+        (bez.p0.into(), BezierSegment::new(bez.p1, bez.p2, bez.p3))
+    }
+}
+
+#[cfg(feature = "lyon")]
+impl BezierSegment {
+    /// Converts the segment into a lyon curve beginning at `start`.
+    #[inline]
+    pub fn to_lyon(&self, start: impl Into<Point2f>) -> lyon_path::geom::CubicBezierSegment<f32> {
+        lyon_path::geom::CubicBezierSegment {
+            from: start.into().into(),
+            ctrl1: self.p1.into(),
+            ctrl2: self.p2.into(),
+            to: self.p3.into(),
+        }
+    }
+
+    /// Splits a lyon curve into its start point and the segment that
+    /// continues from it.
+    #[inline]
+    pub fn from_lyon(bez: lyon_path::geom::CubicBezierSegment<f32>) -> (Point2f, BezierSegment) {
+        (
+            bez.from.into(),
+            BezierSegment::new(bez.ctrl1, bez.ctrl2, bez.to),
+        )
+    }
+}
+
 #[cfg(all(windows, feature = "d2d"))]
 impl From<BezierSegment> for D2D1_BEZIER_SEGMENT {
     #[inline]
@@ -78,6 +348,30 @@ impl From<D2D1_BEZIER_SEGMENT> for BezierSegment {
     }
 }
 
+#[cfg(all(windows, feature = "windows"))]
+impl From<BezierSegment> for d2d_common::D2D1_BEZIER_SEGMENT {
+    #[inline]
+    fn from(seg: BezierSegment) -> d2d_common::D2D1_BEZIER_SEGMENT {
+        d2d_common::D2D1_BEZIER_SEGMENT {
+            point1: seg.p1.into(),
+            point2: seg.p2.into(),
+            point3: seg.p3.into(),
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<d2d_common::D2D1_BEZIER_SEGMENT> for BezierSegment {
+    #[inline]
+    fn from(seg: d2d_common::D2D1_BEZIER_SEGMENT) -> BezierSegment {
+        BezierSegment {
+            p1: seg.point1.into(),
+            p2: seg.point2.into(),
+            p3: seg.point3.into(),
+        }
+    }
+}
+
 #[cfg(all(test, windows, feature = "d2d"))]
 #[test]
 fn bezier_d2d_bin_compat() {
@@ -98,3 +392,84 @@ fn bezier_d2d_bin_compat() {
     assert!(ptr_eq(&bez.p3.y, &d2d.point3.y));
     assert_eq!(size_of_val(&bez), size_of_val(d2d));
 }
+
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn bezier_windows_bin_compat() {
+    use std::mem::size_of_val;
+
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
+    }
+
+    let bez = BezierSegment::new((0.0, 0.0), (1.0, 0.0), (1.0, 1.0));
+    let d2d = unsafe { &*((&bez) as *const _ as *const d2d_common::D2D1_BEZIER_SEGMENT) };
+
+    assert!(ptr_eq(&bez.p1.x, &d2d.point1.x));
+    assert!(ptr_eq(&bez.p1.y, &d2d.point1.y));
+    assert!(ptr_eq(&bez.p2.x, &d2d.point2.x));
+    assert!(ptr_eq(&bez.p2.y, &d2d.point2.y));
+    assert!(ptr_eq(&bez.p3.x, &d2d.point3.x));
+    assert!(ptr_eq(&bez.p3.y, &d2d.point3.y));
+    assert_eq!(size_of_val(&bez), size_of_val(d2d));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier_segment::BezierSegment;
+
+    #[test]
+    fn bounds_and_length() {
+        // Quarter of a circle of radius 10, bulging out past its chord
+        let k = 10.0 * 0.552_284_8;
+        let quarter = BezierSegment::new((k, 0.0), (10.0, 10.0 - k), (10.0, 10.0));
+        let bounds = quarter.bounds((0.0, 0.0));
+        assert!(bounds.left.abs() < 1e-5 && bounds.top.abs() < 1e-5);
+        assert!((bounds.right - 10.0).abs() < 1e-5 && (bounds.bottom - 10.0).abs() < 1e-5);
+
+        let length = quarter.arc_length((0.0, 0.0), 1e-3);
+        assert!((length - 5.0 * std::f32::consts::PI).abs() < 1e-2);
+
+        // An S-curve overshoots the box of its endpoints
+        let s_curve = BezierSegment::new((0.0, 4.0), (4.0, -4.0), (4.0, 0.0));
+        let bounds = s_curve.bounds((0.0, 0.0));
+        assert!(bounds.top < 0.0 && bounds.bottom > 0.0);
+        assert!(s_curve.fast_bounds((0.0, 0.0)).top <= bounds.top);
+    }
+
+    #[test]
+    fn flatten_without_tolerance() {
+        // Asking for no error gives the finest flattening f32 can resolve,
+        // not a single chord
+        let arch = BezierSegment::new((0.0, 10.0), (10.0, 10.0), (10.0, 0.0));
+        let finest = arch.flatten((0.0, 0.0), 0.0);
+        assert!(finest.len() > 100);
+        assert_eq!(arch.flatten((0.0, 0.0), -1.0), finest);
+        assert_eq!(*finest.last().unwrap(), arch.p3);
+    }
+
+    #[test]
+    fn inflection_points() {
+        // Symmetric S-curve inflects exactly at its midpoint
+        let s_curve = BezierSegment::new((0.0, 1.0), (1.0, -1.0), (1.0, 0.0));
+        let points = s_curve.inflection_points((0.0, 0.0));
+        assert_eq!(points.len(), 1);
+        assert!((points[0] - 0.5).abs() < 1e-5);
+
+        // A simple arch never changes its direction of curvature
+        let arch = BezierSegment::new((0.0, 1.0), (1.0, 1.0), (1.0, 0.0));
+        assert!(arch.inflection_points((0.0, 0.0)).is_empty());
+    }
+
+    #[test]
+    fn curvature() {
+        let line = BezierSegment::new((1.0, 1.0), (2.0, 2.0), (3.0, 3.0));
+        assert!(line.curvature_at((0.0, 0.0), 0.5).abs() < 1e-5);
+
+        // Circular arc approximation of radius 10 should be close to 1/10
+        let k = 0.552_284_7 * 10.0;
+        let quarter = BezierSegment::new((10.0, k), (k, 10.0), (0.0, 10.0));
+        let c = quarter.curvature_at((10.0, 0.0), 0.5);
+        assert!((c - 0.1).abs() < 1e-3, "{}", c);
+    }
+}
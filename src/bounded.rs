@@ -0,0 +1,274 @@
+//! Axis-aligned bounding boxes for every shape, segment and curve.
+
+use crate::arc_segment::ArcSegment;
+use crate::bezier_segment::BezierSegment;
+use crate::capsule::Capsule;
+use crate::center_arc::CenterArc;
+use crate::circle::Circle;
+use crate::ellipse::Ellipse;
+use crate::geometry_group::{Geometry, GeometryGroup};
+use crate::oriented_ellipse::OrientedEllipse;
+use crate::oriented_rect::OrientedRect;
+use crate::path::{Figure, Path, PathSegment};
+use crate::point2f::Point2f;
+use crate::polygon::{self, Polygon};
+use crate::polyline::Polyline;
+use crate::quad_bezier_segment::QuadBezierSegment;
+use crate::rectf::Rectf;
+use crate::rounded_rect::RoundedRect;
+use crate::shape::AnyShape;
+use crate::triangle::Triangle;
+
+/// Values which occupy a bounded region of the plane, so that culling and
+/// spatial indexing code can be written once for all of them. The trait is
+/// object safe.
+///
+/// Path segments do not store their start point, so they are bounded as a
+/// `(start, segment)` pair, the same form `BezierSegment::from_kurbo` and
+/// `PathSegment::split_at` produce.
+pub trait Bounded {
+    /// The tight axis-aligned bounding box.
+    fn bounds(&self) -> Rectf;
+
+    /// A box which contains `bounds` but may be larger, for broad-phase
+    /// culling. Curves use the bounds of their control points, which avoids
+    /// solving for their extrema. The default is `bounds` itself.
+    #[inline]
+    fn fast_bounds(&self) -> Rectf {
+        self.bounds()
+    }
+}
+
+impl Bounded for Point2f {
+    /// A zero-sized box at the point.
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        Rectf::from_points(*self, *self)
+    }
+}
+
+impl Bounded for Rectf {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        self.normalized()
+    }
+}
+
+impl Bounded for RoundedRect {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        self.rect.normalized()
+    }
+}
+
+impl Bounded for Ellipse {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        Rectf::from_center_half_extent(self.center, [self.radius_x.abs(), self.radius_y.abs()])
+    }
+}
+
+impl Bounded for OrientedRect {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        OrientedRect::bounds(self)
+    }
+}
+
+impl Bounded for OrientedEllipse {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        OrientedEllipse::bounds(self)
+    }
+}
+
+impl Bounded for Circle {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        Circle::bounds(self)
+    }
+}
+
+impl Bounded for Capsule {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        Capsule::bounds(self)
+    }
+}
+
+impl Bounded for Triangle {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        polygon::point_bounds(&[self.p1, self.p2, self.p3])
+    }
+}
+
+impl Bounded for Polygon {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        Polygon::bounds(self)
+    }
+}
+
+impl Bounded for Polyline {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        Polyline::bounds(self)
+    }
+}
+
+impl Bounded for CenterArc {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        CenterArc::bounds(self)
+    }
+}
+
+impl Bounded for (Point2f, BezierSegment) {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        self.1.bounds(self.0)
+    }
+
+    #[inline]
+    fn fast_bounds(&self) -> Rectf {
+        self.1.fast_bounds(self.0)
+    }
+}
+
+impl Bounded for (Point2f, QuadBezierSegment) {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        self.1.bounds(self.0)
+    }
+
+    #[inline]
+    fn fast_bounds(&self) -> Rectf {
+        self.1.fast_bounds(self.0)
+    }
+}
+
+impl Bounded for (Point2f, ArcSegment) {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        self.1.bounds(self.0)
+    }
+}
+
+impl Bounded for (Point2f, PathSegment) {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        self.1.bounds(self.0)
+    }
+
+    #[inline]
+    fn fast_bounds(&self) -> Rectf {
+        self.1.fast_bounds(self.0)
+    }
+}
+
+impl Bounded for Figure {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        Figure::bounds(self)
+    }
+
+    #[inline]
+    fn fast_bounds(&self) -> Rectf {
+        Figure::fast_bounds(self)
+    }
+}
+
+impl Bounded for Path {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        Path::bounds(self)
+    }
+
+    #[inline]
+    fn fast_bounds(&self) -> Rectf {
+        Path::fast_bounds(self)
+    }
+}
+
+impl Bounded for Geometry {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        Geometry::bounds(self)
+    }
+
+    #[inline]
+    fn fast_bounds(&self) -> Rectf {
+        match *self {
+            Geometry::Path(ref path) => path.fast_bounds(),
+            _ => Geometry::bounds(self),
+        }
+    }
+}
+
+impl Bounded for GeometryGroup {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        GeometryGroup::bounds(self)
+    }
+
+    /// An empty group has a zero-sized box at the origin, as with `bounds`.
+    #[inline]
+    fn fast_bounds(&self) -> Rectf {
+        let mut bounds = self.geometries.iter().map(Bounded::fast_bounds);
+        match bounds.next() {
+            Some(first) => bounds.fold(first, |acc, b| acc.combined_with(b)),
+            None => Rectf::default(),
+        }
+    }
+}
+
+impl Bounded for AnyShape {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        self.as_shape().bounds()
+    }
+
+    #[inline]
+    fn fast_bounds(&self) -> Rectf {
+        self.as_shape().fast_bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier_segment::BezierSegment;
+    use crate::bounded::Bounded;
+    use crate::path::Path;
+    use crate::point2f::Point2f;
+    use crate::polyline::Polyline;
+    use crate::rectf::Rectf;
+    use crate::shape::AnyShape;
+    use crate::triangle::Triangle;
+
+    /// Culls shapes without knowing their types.
+    fn visible(shapes: &[&dyn Bounded], viewport: &Rectf) -> usize {
+        shapes
+            .iter()
+            .filter(|s| s.fast_bounds().overlaps(viewport))
+            .count()
+    }
+
+    #[test]
+    fn bounds_for_culling() {
+        let start = Point2f::new(0.0, 0.0);
+        let bez = BezierSegment::new((0.0, 10.0), (10.0, 10.0), (10.0, 0.0));
+        let curve = (start, bez);
+        assert_eq!(curve.fast_bounds(), Rectf::new(0.0, 0.0, 10.0, 10.0));
+        let tight = curve.bounds();
+        assert!((tight.bottom - 7.5).abs() < 1e-5);
+        assert!(curve.fast_bounds().contains_point((5.0, 7.5)));
+
+        let line = Polyline::new(vec![Point2f::new(20.0, 20.0), Point2f::new(30.0, 25.0)]);
+        let triangle = Triangle::from(((50.0, 0.0), (60.0, 0.0), (50.0, 10.0)));
+        let shape = AnyShape::from(Path::from(Rectf::new(-5.0, -5.0, -1.0, -1.0)));
+        let shapes: [&dyn Bounded; 4] = [&curve, &line, &triangle, &shape];
+        assert_eq!(visible(&shapes, &Rectf::new(0.0, 0.0, 40.0, 40.0)), 2);
+        assert_eq!(visible(&shapes, &Rectf::new(-10.0, -10.0, 100.0, 100.0)), 4);
+    }
+}
@@ -0,0 +1,437 @@
+//! A bounding volume hierarchy of rectangles for large, mostly static
+//! scenes.
+
+use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+use crate::vector2f::Vector2f;
+
+use std::iter::FromIterator;
+
+/// The number of items a node holds before it is split in two.
+const LEAF_CAPACITY: usize = 4;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Node {
+    bounds: Rectf,
+    /// The range of `Bvh::order` holding the items under this node.
+    start: usize,
+    end: usize,
+    /// Index of the first of two child nodes. Children always come after
+    /// their parent, so refitting in reverse order visits them first.
+    children: Option<usize>,
+}
+
+/// A spatial index of values by their bounding rectangles, built all at
+/// once from a whole scene.
+///
+/// Where `QuadTree` subdivides a fixed area and supports inserting and
+/// removing values one at a time, a BVH adapts to however the items are
+/// distributed, which keeps queries fast for scenes of hundreds of thousands
+/// of shapes. Items keep their position in the list the tree was built from
+/// as their index. Items can be moved with `set_rect` followed by `refit`,
+/// which stays efficient as long as they move locally; rebuild the tree if
+/// the scene changes substantially.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bvh<T> {
+    nodes: Vec<Node>,
+    items: Vec<(Rectf, T)>,
+    /// Item indices, arranged so that every node's items are contiguous.
+    order: Vec<usize>,
+}
+
+impl<T> Bvh<T> {
+    /// Builds a tree over the items, splitting each node at the median of
+    /// the item centers along its longest axis.
+    pub fn new(items: Vec<(Rectf, T)>) -> Bvh<T> {
+        let items: Vec<_> = items
+            .into_iter()
+            .map(|(rect, value)| (rect.normalized(), value))
+            .collect();
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            order: (0..items.len()).collect(),
+            items,
+        };
+        if !bvh.items.is_empty() {
+            bvh.push_node(0, bvh.items.len());
+            bvh.split(0);
+        }
+        bvh
+    }
+
+    /// The smallest rectangle containing every item. An empty tree has a
+    /// zero-sized box at the origin.
+    #[inline]
+    pub fn bounds(&self) -> Rectf {
+        match self.nodes.first() {
+            Some(root) => root.bounds,
+            None => Rectf::default(),
+        }
+    }
+
+    /// The number of values in the tree.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Determines if the tree has no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The rectangle and value at the given index.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<(Rectf, &T)> {
+        self.items.get(index).map(|(rect, value)| (*rect, value))
+    }
+
+    /// The value at the given index, for modification. Use `set_rect` to
+    /// move it.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.items.get_mut(index).map(|(_, value)| value)
+    }
+
+    /// Iterates over the index, rectangle and value of every item in the
+    /// tree, ordered by index.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (usize, Rectf, &T)> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(index, (rect, value))| (index, *rect, value))
+    }
+
+    /// Changes the rectangle of the item at `index`. Queries are not correct
+    /// again until `refit` is called, so that many items can be moved before
+    /// the tree is updated once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn set_rect(&mut self, index: usize, rect: impl Into<Rectf>) {
+        self.items[index].0 = rect.into().normalized();
+    }
+
+    /// Recomputes the bounds of every node from the current item rectangles,
+    /// keeping the structure of the tree.
+    pub fn refit(&mut self) {
+        for n in (0..self.nodes.len()).rev() {
+            let bounds = match self.nodes[n].children {
+                Some(first) => self.nodes[first]
+                    .bounds
+                    .combined_with(self.nodes[first + 1].bounds),
+                None => self.range_bounds(self.nodes[n].start, self.nodes[n].end),
+            };
+            self.nodes[n].bounds = bounds;
+        }
+    }
+
+    /// Finds every item whose rectangle overlaps `rect`, including those
+    /// which only touch its edges. The results are in no particular order.
+    pub fn query_rect(&self, rect: impl Into<Rectf>) -> Vec<(usize, &T)> {
+        let rect = rect.into().normalized();
+        let mut results = Vec::new();
+        self.visit(
+            |r| touches(r, &rect),
+            |index, r, value| {
+                if touches(r, &rect) {
+                    results.push((index, value));
+                }
+            },
+        );
+        results
+    }
+
+    /// Finds every item whose rectangle is hit by the ray from `origin`
+    /// along `direction`, within `max_t` lengths of `direction` of the
+    /// origin. Each hit comes with the parameter `t` at which the ray enters
+    /// the rectangle, which is zero if the origin is inside it, and the hits
+    /// are sorted by it from nearest to furthest. Pass `f32::INFINITY` as
+    /// `max_t` for an unbounded ray.
+    pub fn query_ray(
+        &self,
+        origin: impl Into<Point2f>,
+        direction: impl Into<Vector2f>,
+        max_t: f32,
+    ) -> Vec<(usize, f32, &T)> {
+        let (origin, direction) = (origin.into(), direction.into());
+        let mut results = Vec::new();
+        self.visit(
+            |r| ray_entry(r, origin, direction, max_t).is_some(),
+            |index, r, value| {
+                if let Some(t) = ray_entry(r, origin, direction, max_t) {
+                    results.push((index, t, value));
+                }
+            },
+        );
+        results.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        results
+    }
+
+    /// Finds the item whose rectangle is closest to `point`, returning its
+    /// index and value. Rectangles containing the point are at distance
+    /// zero, and ties are broken arbitrarily. Returns `None` if the tree is
+    /// empty.
+    pub fn nearest(&self, point: impl Into<Point2f>) -> Option<(usize, &T)> {
+        let point = point.into();
+        let mut best = None;
+        let mut best_dist = f32::INFINITY;
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if distance_squared_to_rect(&node.bounds, point) >= best_dist {
+                continue;
+            }
+
+            match node.children {
+                Some(first) => {
+                    // Visit the nearer child first so that the other is more
+                    // likely to be pruned.
+                    let da = distance_squared_to_rect(&self.nodes[first].bounds, point);
+                    let db = distance_squared_to_rect(&self.nodes[first + 1].bounds, point);
+                    if da < db {
+                        stack.extend_from_slice(&[first + 1, first]);
+                    } else {
+                        stack.extend_from_slice(&[first, first + 1]);
+                    }
+                }
+                None => {
+                    for &i in &self.order[node.start..node.end] {
+                        let dist = distance_squared_to_rect(&self.items[i].0, point);
+                        if dist < best_dist {
+                            best = Some(i);
+                            best_dist = dist;
+                        }
+                    }
+                }
+            }
+        }
+
+        best.map(|i| (i, &self.items[i].1))
+    }
+
+    /// Calls `f` for the items in every leaf whose bounds are accepted by
+    /// `filter`, skipping the subtrees of nodes it rejects.
+    fn visit<'a>(
+        &'a self,
+        filter: impl Fn(&Rectf) -> bool,
+        mut f: impl FnMut(usize, &Rectf, &'a T),
+    ) {
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if !filter(&node.bounds) {
+                continue;
+            }
+            match node.children {
+                Some(first) => stack.extend_from_slice(&[first, first + 1]),
+                None => {
+                    for &i in &self.order[node.start..node.end] {
+                        let (ref rect, ref value) = self.items[i];
+                        f(i, rect, value);
+                    }
+                }
+            }
+        }
+    }
+
+    fn push_node(&mut self, start: usize, end: usize) -> usize {
+        let bounds = self.range_bounds(start, end);
+        self.nodes.push(Node {
+            bounds,
+            start,
+            end,
+            children: None,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn split(&mut self, root: usize) {
+        let mut stack = vec![root];
+        while let Some(n) = stack.pop() {
+            let (start, end) = (self.nodes[n].start, self.nodes[n].end);
+            if end - start <= LEAF_CAPACITY {
+                continue;
+            }
+
+            let mut centers = self.order[start..end]
+                .iter()
+                .map(|&i| self.items[i].0.center());
+            let first = centers.next().unwrap();
+            let spread = centers.fold(Rectf::from_points(first, first), |acc, c| {
+                acc.combined_with(Rectf::from_points(c, c))
+            });
+            let horizontal = spread.width() >= spread.height();
+            let key = |rect: &Rectf| {
+                let c = rect.center();
+                if horizontal {
+                    c.x
+                } else {
+                    c.y
+                }
+            };
+
+            let mid = (end - start) / 2;
+            let items = &self.items;
+            self.order[start..end].select_nth_unstable_by(mid, |&a, &b| {
+                let (ka, kb) = (key(&items[a].0), key(&items[b].0));
+                ka.partial_cmp(&kb).unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+            let left = self.push_node(start, start + mid);
+            let right = self.push_node(start + mid, end);
+            self.nodes[n].children = Some(left);
+            stack.extend_from_slice(&[left, right]);
+        }
+    }
+
+    /// The smallest rectangle containing the items in `order[start..end]`,
+    /// which must not be empty.
+    fn range_bounds(&self, start: usize, end: usize) -> Rectf {
+        let mut rects = self.order[start..end].iter().map(|&i| self.items[i].0);
+        let first = rects.next().unwrap();
+        rects.fold(first, |acc, r| acc.combined_with(r))
+    }
+}
+
+impl<T> Default for Bvh<T> {
+    /// An empty tree.
+    #[inline]
+    fn default() -> Bvh<T> {
+        Bvh::new(Vec::new())
+    }
+}
+
+impl<R: Into<Rectf>, T> FromIterator<(R, T)> for Bvh<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (R, T)>>(items: I) -> Bvh<T> {
+        Bvh::new(
+            items
+                .into_iter()
+                .map(|(rect, value)| (rect.into(), value))
+                .collect(),
+        )
+    }
+}
+
+/// Determines if two rectangles overlap or share an edge.
+#[inline]
+fn touches(a: &Rectf, b: &Rectf) -> bool {
+    a.left <= b.right && a.right >= b.left && a.top <= b.bottom && a.bottom >= b.top
+}
+
+/// The squared distance from `point` to the nearest point of `rect`, or zero
+/// if it is inside.
+#[inline]
+fn distance_squared_to_rect(rect: &Rectf, point: Point2f) -> f32 {
+    let dx = (rect.left - point.x).max(point.x - rect.right).max(0.0);
+    let dy = (rect.top - point.y).max(point.y - rect.bottom).max(0.0);
+    dx * dx + dy * dy
+}
+
+/// The parameter in `[0, max_t]` at which the ray enters `rect`, if it hits
+/// it at all.
+#[inline]
+fn ray_entry(rect: &Rectf, origin: Point2f, direction: Vector2f, max_t: f32) -> Option<f32> {
+    let (mut t0, mut t1) = (0.0f32, max_t);
+    let slabs = [
+        (origin.x, direction.x, rect.left, rect.right),
+        (origin.y, direction.y, rect.top, rect.bottom),
+    ];
+    for &(o, d, min, max) in &slabs {
+        if d == 0.0 {
+            // Parallel to the slab, so the ray is either always or never
+            // within it
+            if o < min || o > max {
+                return None;
+            }
+            continue;
+        }
+        let (a, b) = ((min - o) / d, (max - o) / d);
+        t0 = t0.max(a.min(b));
+        t1 = t1.min(a.max(b));
+    }
+    if t0 <= t1 {
+        Some(t0)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bvh::Bvh;
+    use crate::rectf::Rectf;
+
+    #[test]
+    fn queries() {
+        let mut items = Vec::new();
+        for i in 0..40 {
+            for j in 0..25 {
+                let (x, y) = (i as f32 * 5.0, j as f32 * 4.0);
+                items.push((Rectf::new(x, y, x + 2.0 + (i % 3) as f32, y + 2.0), (i, j)));
+            }
+        }
+        let mut bvh: Bvh<_> = items.iter().cloned().collect();
+        assert_eq!(bvh.len(), 1000);
+        assert_eq!(bvh.bounds(), Rectf::new(0.0, 0.0, 197.0, 98.0));
+
+        let view = Rectf::new(20.0, 20.0, 40.0, 30.0);
+        let mut visible: Vec<_> = bvh.query_rect(view).into_iter().map(|(i, _)| i).collect();
+        visible.sort_unstable();
+        let expected: Vec<_> = bvh
+            .iter()
+            .filter(|&(_, r, _)| {
+                r.left <= 40.0 && r.right >= 20.0 && r.top <= 30.0 && r.bottom >= 20.0
+            })
+            .map(|(i, _, _)| i)
+            .collect();
+        assert_eq!(visible, expected);
+
+        // A horizontal ray along the second row, stopping where column 10 starts
+        let hits = bvh.query_ray((-10.0, 5.0), [1.0, 0.0], 60.0);
+        let columns: Vec<_> = hits.iter().map(|&(_, _, &(i, j))| (i, j)).collect();
+        assert_eq!(columns, (0..11).map(|i| (i, 1)).collect::<Vec<_>>());
+        assert_eq!(hits[0].1, 10.0);
+        assert!(bvh
+            .query_ray((-10.0, 3.0), [1.0, 0.0], f32::INFINITY)
+            .is_empty());
+        assert_eq!(bvh.query_ray((1.0, 1.0), [0.0, 1.0], 0.0)[0].2, &(0, 0));
+
+        assert_eq!(bvh.nearest((103.0, 41.0)), Some((20 * 25 + 10, &(20, 10))));
+        assert_eq!(bvh.nearest((-50.0, -50.0)).unwrap().1, &(0, 0));
+
+        // Moved items are found in their new places once the tree is refit
+        bvh.set_rect(0, Rectf::new(300.0, 300.0, 301.0, 301.0));
+        bvh.refit();
+        assert_eq!(bvh.bounds(), Rectf::new(0.0, 0.0, 301.0, 301.0));
+        assert_eq!(
+            bvh.query_rect(Rectf::new(299.0, 299.0, 302.0, 302.0)),
+            vec![(0, &(0, 0))]
+        );
+        assert_eq!(bvh.nearest((-50.0, -50.0)).unwrap().1, &(0, 1));
+        *bvh.get_mut(0).unwrap() = (99, 99);
+        assert_eq!(
+            bvh.get(0),
+            Some((Rectf::new(300.0, 300.0, 301.0, 301.0), &(99, 99)))
+        );
+
+        let empty = Bvh::<()>::default();
+        assert!(empty.is_empty() && empty.nearest((0.0, 0.0)).is_none());
+        assert!(empty.query_rect(Rectf::new(0.0, 0.0, 1.0, 1.0)).is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+//! A shape which remembers its bounding box.
+//!
+//! The tight bounds of a path mean solving for the extrema of every curve in
+//! it, which adds up when static shapes are culled or hit tested every
+//! frame. `CachedBounds` computes them on first use and keeps them until the
+//! shape is next borrowed mutably.
+
+use crate::bounded::Bounded;
+use crate::hit_test::HitTest;
+use crate::matrix3x2f::Matrix3x2f;
+use crate::path::Path;
+use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+use crate::shape::{AnyShape, Shape2D};
+
+use std::cell::Cell;
+use std::fmt;
+use std::ops::Deref;
+
+/// A shape together with its memoized `bounds` and `fast_bounds`. The shape
+/// can be read through `Deref`, and changed through `get_mut`, which clears
+/// the cache.
+///
+/// Hit tests reject points outside the cached bounds before testing the
+/// shape itself.
+#[derive(Clone, Default)]
+pub struct CachedBounds<T> {
+    shape: T,
+    bounds: Cell<Option<Rectf>>,
+    fast_bounds: Cell<Option<Rectf>>,
+}
+
+impl<T> CachedBounds<T> {
+    /// Wraps a shape. Its bounds are not computed until they are first asked
+    /// for.
+    #[inline]
+    pub fn new(shape: T) -> CachedBounds<T> {
+        CachedBounds {
+            shape,
+            bounds: Cell::new(None),
+            fast_bounds: Cell::new(None),
+        }
+    }
+
+    /// The shape.
+    #[inline]
+    pub fn get(&self) -> &T {
+        &self.shape
+    }
+
+    /// Borrows the shape mutably. The cached bounds are cleared whether or
+    /// not the shape is actually changed.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.invalidate();
+        &mut self.shape
+    }
+
+    /// Clears the cached bounds, so that they are computed again when next
+    /// asked for.
+    #[inline]
+    pub fn invalidate(&mut self) {
+        self.bounds.set(None);
+        self.fast_bounds.set(None);
+    }
+
+    /// Unwraps the shape, discarding the cache.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.shape
+    }
+}
+
+impl<T: Bounded> Bounded for CachedBounds<T> {
+    #[inline]
+    fn bounds(&self) -> Rectf {
+        match self.bounds.get() {
+            Some(bounds) => bounds,
+            None => {
+                let bounds = self.shape.bounds();
+                self.bounds.set(Some(bounds));
+                bounds
+            }
+        }
+    }
+
+    #[inline]
+    fn fast_bounds(&self) -> Rectf {
+        match self.fast_bounds.get() {
+            Some(bounds) => bounds,
+            None => {
+                let bounds = self.shape.fast_bounds();
+                self.fast_bounds.set(Some(bounds));
+                bounds
+            }
+        }
+    }
+}
+
+impl<T: Bounded + HitTest> HitTest for CachedBounds<T> {
+    #[inline]
+    fn hit(&self, point: Point2f) -> bool {
+        self.fast_bounds().contains_point(point) && self.shape.hit(point)
+    }
+
+    #[inline]
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool {
+        self.fast_bounds()
+            .expanded_by(tolerance.max(0.0))
+            .contains_point(point)
+            && self.shape.hit_with_tolerance(point, tolerance)
+    }
+}
+
+impl<T: Shape2D> Shape2D for CachedBounds<T> {
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> AnyShape {
+        Shape2D::transformed(&self.shape, matrix)
+    }
+
+    #[inline]
+    fn to_path(&self) -> Path {
+        self.shape.to_path()
+    }
+}
+
+impl<T> Deref for CachedBounds<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.shape
+    }
+}
+
+impl<T> From<T> for CachedBounds<T> {
+    #[inline]
+    fn from(shape: T) -> CachedBounds<T> {
+        CachedBounds::new(shape)
+    }
+}
+
+/// Shapes are equal if the shapes they wrap are, regardless of what either
+/// has cached.
+impl<T: PartialEq> PartialEq for CachedBounds<T> {
+    #[inline]
+    fn eq(&self, other: &CachedBounds<T>) -> bool {
+        self.shape == other.shape
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CachedBounds<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CachedBounds")
+            .field("shape", &self.shape)
+            .field("bounds", &self.bounds.get())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bounded::Bounded;
+    use crate::cached_bounds::CachedBounds;
+    use crate::ellipse::Ellipse;
+    use crate::hit_test::HitTest;
+    use crate::path::Path;
+    use crate::point2f::Point2f;
+    use crate::polygon::Polygon;
+    use crate::rectf::Rectf;
+
+    #[test]
+    fn cached_until_mutated() {
+        let mut circle = Path::from(Ellipse::new((5.0, 5.0), 5.0, 5.0)).into_cached();
+        let bounds = circle.bounds();
+        assert_eq!(bounds, circle.get().bounds());
+        assert_eq!(circle.bounds(), bounds);
+        assert!(circle.hit((5.0, 5.0).into()));
+        assert!(!circle.hit((20.0, 5.0).into()));
+        assert_eq!(circle.figures.len(), 1);
+
+        circle
+            .get_mut()
+            .figures
+            .extend(Path::from(Rectf::new(20.0, 0.0, 30.0, 10.0)).figures);
+        assert_eq!(circle.bounds().right, 30.0);
+        assert!(circle.hit((25.0, 5.0).into()));
+
+        let triangle = Polygon::new(vec![
+            Point2f::new(0.0, 0.0),
+            Point2f::new(10.0, 0.0),
+            Point2f::new(0.0, 10.0),
+        ])
+        .into_cached();
+        assert!(triangle.hit_with_tolerance((-0.5, 5.0).into(), 1.0));
+        assert!(!triangle.hit_with_tolerance((-2.0, 5.0).into(), 1.0));
+        assert_eq!(triangle.fast_bounds(), Rectf::new(0.0, 0.0, 10.0, 10.0));
+        assert_eq!(triangle, CachedBounds::from(triangle.clone().into_inner()));
+    }
+}
@@ -0,0 +1,181 @@
+//! A camera for viewing a 2D world through a screen viewport, with panning,
+//! zooming and rotation.
+
+use crate::matrix3x2f::Matrix3x2f;
+use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+use crate::vector2f::Vector2f;
+
+/// Maps between world coordinates and the screen coordinates of a viewport,
+/// as for a pan and zoom canvas. The world point at `center` is shown at the
+/// center of the viewport, scaled by `zoom` and rotated by `-rotation`, so
+/// that the camera itself appears turned by `rotation`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Camera2D {
+    /// The world point shown at the center of the viewport.
+    pub center: Point2f,
+    /// How many screen units one world unit covers.
+    pub zoom: f32,
+    /// How far the camera is turned, in radians.
+    pub rotation: f32,
+    /// The area of the screen the world is shown in.
+    pub viewport: Rectf,
+}
+
+impl Camera2D {
+    /// Constructs a camera for the given viewport which maps world
+    /// coordinates directly to screen coordinates.
+    #[inline]
+    pub fn new(viewport: impl Into<Rectf>) -> Camera2D {
+        let viewport = viewport.into();
+        Camera2D {
+            center: viewport.center(),
+            zoom: 1.0,
+            rotation: 0.0,
+            viewport,
+        }
+    }
+
+    /// The transform from world coordinates to screen coordinates.
+    #[inline]
+    pub fn world_to_screen(&self) -> Matrix3x2f {
+        Matrix3x2f::translation(-self.center.to_vector())
+            * Matrix3x2f::rotation(-self.rotation, Point2f::ORIGIN)
+            * Matrix3x2f::scaling([self.zoom, self.zoom], Point2f::ORIGIN)
+            * Matrix3x2f::translation(self.viewport.center().to_vector())
+    }
+
+    /// The transform from screen coordinates to world coordinates.
+    #[inline]
+    pub fn screen_to_world(&self) -> Matrix3x2f {
+        let scale = 1.0 / self.zoom;
+        Matrix3x2f::translation(-self.viewport.center().to_vector())
+            * Matrix3x2f::scaling([scale, scale], Point2f::ORIGIN)
+            * Matrix3x2f::rotation(self.rotation, Point2f::ORIGIN)
+            * Matrix3x2f::translation(self.center.to_vector())
+    }
+
+    /// Where `point` in the world appears on screen.
+    #[inline]
+    pub fn world_to_screen_point(&self, point: impl Into<Point2f>) -> Point2f {
+        self.world_to_screen().transform_point(point)
+    }
+
+    /// The world point shown at `point` on screen.
+    #[inline]
+    pub fn screen_to_world_point(&self, point: impl Into<Point2f>) -> Point2f {
+        self.screen_to_world().transform_point(point)
+    }
+
+    /// The smallest world space rectangle containing everything visible in
+    /// the viewport, for culling.
+    #[inline]
+    pub fn visible_bounds(&self) -> Rectf {
+        let transform = self.screen_to_world();
+        let vp = self.viewport;
+        let corners = [
+            (vp.left, vp.top),
+            (vp.right, vp.top),
+            (vp.right, vp.bottom),
+            (vp.left, vp.bottom),
+        ];
+        let first = transform.transform_point(corners[0]);
+        corners[1..]
+            .iter()
+            .fold(Rectf::from_points(first, first), |acc, &c| {
+                let p = transform.transform_point(c);
+                acc.combined_with(Rectf::from_points(p, p))
+            })
+    }
+
+    /// Multiplies the zoom by `factor`, keeping the world point shown at
+    /// `screen_point` in place, as when zooming with the mouse wheel.
+    #[inline]
+    pub fn zoom_about(&mut self, screen_point: impl Into<Point2f>, factor: f32) {
+        let screen_point = screen_point.into();
+        let before = self.screen_to_world_point(screen_point);
+        self.zoom *= factor;
+        let after = self.screen_to_world_point(screen_point);
+        self.center += before - after;
+    }
+
+    /// Moves the view so the world follows a drag of `screen_delta` across
+    /// the screen.
+    #[inline]
+    pub fn pan(&mut self, screen_delta: impl Into<Vector2f>) {
+        let delta = self.screen_to_world().transform_vector(screen_delta);
+        self.center -= delta;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::camera2d::Camera2D;
+    use crate::point2f::Point2f;
+    use crate::rectf::Rectf;
+
+    fn approx(a: Point2f, b: impl Into<Point2f>) -> bool {
+        (a - b.into()).len() < 1e-3
+    }
+
+    #[test]
+    fn mapping() {
+        let mut camera = Camera2D::new(Rectf::new(0.0, 0.0, 800.0, 600.0));
+        assert!(approx(
+            camera.world_to_screen_point((10.0, 20.0)),
+            (10.0, 20.0)
+        ));
+
+        camera.center = (0.0, 0.0).into();
+        camera.zoom = 2.0;
+        assert!(approx(
+            camera.world_to_screen_point((0.0, 0.0)),
+            (400.0, 300.0)
+        ));
+        assert!(approx(
+            camera.world_to_screen_point((10.0, 0.0)),
+            (420.0, 300.0)
+        ));
+
+        // Turning the camera clockwise on screen turns the world the other way
+        camera.rotation = std::f32::consts::FRAC_PI_2;
+        assert!(approx(
+            camera.world_to_screen_point((10.0, 0.0)),
+            (400.0, 280.0)
+        ));
+
+        let m = camera.world_to_screen() * camera.screen_to_world();
+        assert!(m.is_approx_eq(&Default::default(), 1e-5));
+        assert!(approx(
+            camera.screen_to_world_point((400.0, 280.0)),
+            (10.0, 0.0)
+        ));
+
+        let visible = camera.visible_bounds();
+        assert!((visible.right - visible.left - 300.0).abs() < 1e-3);
+        assert!((visible.bottom - visible.top - 400.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn navigation() {
+        let mut camera = Camera2D::new(Rectf::new(0.0, 0.0, 800.0, 600.0));
+        camera.rotation = 0.3;
+
+        let anchor = camera.screen_to_world_point((100.0, 50.0));
+        camera.zoom_about((100.0, 50.0), 3.0);
+        assert!(approx(camera.world_to_screen_point(anchor), (100.0, 50.0)));
+        assert_eq!(camera.zoom, 3.0);
+
+        let before = camera.world_to_screen_point(anchor);
+        camera.pan([25.0, -10.0]);
+        let after = camera.world_to_screen_point(anchor);
+        assert!(approx(after, before + [25.0, -10.0]));
+    }
+}
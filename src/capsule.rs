@@ -0,0 +1,63 @@
+//! A line segment thickened by a radius, with rounded ends.
+
+use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+
+/// The set of points within `radius` of the line segment from `p1` to `p2`,
+/// which is a rectangle with semicircular caps. A capsule with no radius is
+/// just the line segment.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Capsule {
+    /// The start of the center line.
+    pub p1: Point2f,
+    /// The end of the center line.
+    pub p2: Point2f,
+    /// The distance from the center line to the edge of the capsule.
+    pub radius: f32,
+}
+
+impl Capsule {
+    /// Constructs a capsule from its components
+    #[inline]
+    pub fn new(p1: impl Into<Point2f>, p2: impl Into<Point2f>, radius: f32) -> Capsule {
+        Capsule {
+            p1: p1.into(),
+            p2: p2.into(),
+            radius,
+        }
+    }
+
+    /// The point on the center line closest to `point`.
+    #[inline]
+    pub fn closest_center_point(&self, point: impl Into<Point2f>) -> Point2f {
+        let point = point.into();
+        let d = self.p2 - self.p1;
+        let len_sq = d.len_squared();
+        if len_sq == 0.0 {
+            return self.p1;
+        }
+        let t = ((point - self.p1).dot(d) / len_sq).clamp(0.0, 1.0);
+        self.p1 + d * t
+    }
+
+    /// Checks if the capsule contains a point, including points on its edge.
+    #[inline]
+    pub fn contains_point(&self, point: impl Into<Point2f>) -> bool {
+        let point = point.into();
+        (point - self.closest_center_point(point)).len_squared() <= self.radius * self.radius
+    }
+
+    /// The smallest rectangle containing the capsule.
+    #[inline]
+    pub fn bounds(&self) -> Rectf {
+        let r = self.radius.abs();
+        Rectf::from_points(self.p1, self.p2).expanded_by(r)
+    }
+}
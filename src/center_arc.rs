@@ -0,0 +1,368 @@
+//! Center parameterization of an elliptical arc, which is much easier to
+//! evaluate and measure than the endpoint parameterization used by
+//! ArcSegment.
+
+use crate::arc_segment::{ArcSegment, ArcSize, SweepDirection};
+use crate::bezier_segment::BezierSegment;
+use crate::matrix3x2f::Matrix3x2f;
+use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+use crate::tolerance::Tolerance;
+use crate::vector2f::Vector2f;
+
+use std::f32::consts::PI;
+
+/// The most pieces `flatten` and `to_beziers` split an arc into, which keeps
+/// their output bounded when the tolerance or angle is vanishingly small.
+const MAX_SEGMENTS: usize = 1 << 16;
+
+/// Describes an elliptical arc by the ellipse it follows and the range of
+/// angles it sweeps over. All angles are in radians, with positive angles
+/// being clockwise in the y-down coordinate system (matching the convention
+/// of `Matrix3x2f::rotation`).
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct CenterArc {
+    /// The center point of the ellipse.
+    pub center: Point2f,
+    /// The x and y radius of the ellipse, before rotation.
+    pub radii: Vector2f,
+    /// The angle of the start point on the ellipse, measured before the
+    /// ellipse is rotated.
+    pub start_angle: f32,
+    /// The angle swept from the start point to the end point. Positive values
+    /// sweep clockwise and negative values sweep counterclockwise.
+    pub sweep_angle: f32,
+    /// The rotation of the ellipse's x-axis relative to the coordinate system.
+    pub rotation: f32,
+}
+
+impl CenterArc {
+    /// Constructs the arc from its components.
+    #[inline]
+    pub fn new(
+        center: impl Into<Point2f>,
+        radii: impl Into<Vector2f>,
+        start_angle: f32,
+        sweep_angle: f32,
+        rotation: f32,
+    ) -> CenterArc {
+        CenterArc {
+            center: center.into(),
+            radii: radii.into(),
+            start_angle,
+            sweep_angle,
+            rotation,
+        }
+    }
+
+    /// The point on the (rotated) ellipse at the given angle.
+    #[inline]
+    pub fn point_at_angle(&self, angle: f32) -> Point2f {
+        let (sin, cos) = angle.sin_cos();
+        let local = Vector2f::new(self.radii.x * cos, self.radii.y * sin);
+        self.center + local * Matrix3x2f::rotation(self.rotation, Point2f::ORIGIN)
+    }
+
+    /// The point where the arc begins.
+    #[inline]
+    pub fn start_point(&self) -> Point2f {
+        self.point_at_angle(self.start_angle)
+    }
+
+    /// The point where the arc ends.
+    #[inline]
+    pub fn end_point(&self) -> Point2f {
+        self.point_at_angle(self.start_angle + self.sweep_angle)
+    }
+
+    /// The point on the arc at the parameter `t`, where `0.0` is the start
+    /// point and `1.0` is the end point. The parameter is proportional to
+    /// the angle swept, not the distance travelled.
+    #[inline]
+    pub fn point_at(&self, t: f32) -> Point2f {
+        self.point_at_angle(self.start_angle + self.sweep_angle * t)
+    }
+
+    /// The derivative of the arc with respect to the parameter `t`, which
+    /// points in the direction of travel.
+    #[inline]
+    pub fn tangent_at(&self, t: f32) -> Vector2f {
+        let (sin, cos) = (self.start_angle + self.sweep_angle * t).sin_cos();
+        let local = Vector2f::new(-self.radii.x * sin, self.radii.y * cos);
+        self.sweep_angle * (local * Matrix3x2f::rotation(self.rotation, Point2f::ORIGIN))
+    }
+
+    /// The length of the arc. Elliptical arcs have no closed-form length, so
+    /// this is computed by Gauss-Legendre quadrature over pieces of at most
+    /// 45 degrees, which converges very quickly for the smooth speed function
+    /// of an ellipse.
+    pub fn arc_length(&self) -> f32 {
+        // 8-point Gauss-Legendre nodes and weights on [-1, 1]
+        const GAUSS: [(f32, f32); 4] = [
+            (0.183_434_64, 0.362_683_78),
+            (0.525_532_4, 0.313_706_64),
+            (0.796_666_5, 0.222_381_03),
+            (0.960_289_9, 0.101_228_54),
+        ];
+
+        let pieces = (self.sweep_angle.abs() / (PI / 4.0)).ceil().max(1.0);
+        let step = 1.0 / pieces;
+        let mut length = 0.0;
+        for i in 0..pieces as usize {
+            let mid = (i as f32 + 0.5) * step;
+            let half = step / 2.0;
+            for &(x, w) in &GAUSS {
+                length += w * half * self.tangent_at(mid - half * x).len();
+                length += w * half * self.tangent_at(mid + half * x).len();
+            }
+        }
+        length
+    }
+
+    /// The tight axis-aligned bounding box of the arc, accounting for the
+    /// extreme points of the ellipse which fall within the swept range.
+    pub fn bounds(&self) -> Rectf {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (rx, ry) = (self.radii.x, self.radii.y);
+        let x_extreme = (-ry * sin).atan2(rx * cos);
+        let y_extreme = (ry * cos).atan2(rx * sin);
+
+        let mut bounds = Rectf::from_points(self.start_point(), self.end_point());
+        for &angle in &[x_extreme, x_extreme + PI, y_extreme, y_extreme + PI] {
+            if self.sweeps_over(angle) {
+                let p = self.point_at_angle(angle);
+                bounds = bounds.combined_with(Rectf::from_points(p, p));
+            }
+        }
+        bounds
+    }
+
+    /// Approximates the arc with line segments such that no point on the
+    /// arc is further than `tolerance` from the resulting polyline. The start
+    /// point is not included, but the end point always is. Relative
+    /// tolerances are measured against the bounds of the arc. Tolerances
+    /// finer than `f32` can resolve at the size of the arc, including zero
+    /// and negative ones, are raised to `f32::EPSILON` times the size, and
+    /// the arc is split into at most 65536 segments.
+    pub fn flatten(&self, tolerance: impl Into<Tolerance>) -> Vec<Point2f> {
+        let tolerance = tolerance.into().resolve_flattening(&self.bounds());
+        let radius = self.radii.x.abs().max(self.radii.y.abs());
+        let count = if tolerance > 0.0 && tolerance < radius {
+            // 2 * acos(1 - x), in a form which does not round to zero when x
+            // is too small to change 1 - x
+            let step = 4.0 * (tolerance / (2.0 * radius)).sqrt().asin();
+            ((self.sweep_angle.abs() / step).ceil() as usize).clamp(1, MAX_SEGMENTS)
+        } else {
+            1
+        };
+
+        (1..=count)
+            .map(|i| self.point_at(i as f32 / count as f32))
+            .collect()
+    }
+
+    /// Approximates the arc with a chain of cubic bezier segments starting
+    /// at `start_point()`, each of which sweeps no more than
+    /// `max_angle_per_segment` radians. Angles of 90 degrees or less give
+    /// results indistinguishable from the true arc at typical scales; values
+    /// which are not positive fall back to 90 degrees. As with `flatten`, the
+    /// arc is split into at most 65536 segments.
+    pub fn to_beziers(&self, max_angle_per_segment: f32) -> Vec<BezierSegment> {
+        let max_angle = if max_angle_per_segment > 0.0 {
+            max_angle_per_segment
+        } else {
+            PI / 2.0
+        };
+        let count = ((self.sweep_angle.abs() / max_angle).ceil() as usize).clamp(1, MAX_SEGMENTS);
+        let delta = self.sweep_angle / count as f32;
+        let k = 4.0 / 3.0 * (delta / 4.0).tan();
+
+        let rotate = Matrix3x2f::rotation(self.rotation, Point2f::ORIGIN);
+        let derivative = |angle: f32| {
+            let (sin, cos) = angle.sin_cos();
+            Vector2f::new(-self.radii.x * sin, self.radii.y * cos) * rotate
+        };
+
+        (0..count)
+            .map(|i| {
+                let a1 = self.start_angle + delta * i as f32;
+                let a2 = a1 + delta;
+                let p0 = self.point_at_angle(a1);
+                let p3 = self.point_at_angle(a2);
+                BezierSegment {
+                    p1: p0 + k * derivative(a1),
+                    p2: p3 - k * derivative(a2),
+                    p3,
+                }
+            })
+            .collect()
+    }
+
+    /// Determines if the given angle on the ellipse lies within the range
+    /// swept by the arc.
+    #[inline]
+    pub(crate) fn sweeps_over(&self, angle: f32) -> bool {
+        let offset = if self.sweep_angle >= 0.0 {
+            angle - self.start_angle
+        } else {
+            self.start_angle - angle
+        };
+        offset.rem_euclid(2.0 * PI) <= self.sweep_angle.abs()
+    }
+
+    /// Converts the arc to its endpoint parameterization, returning the start
+    /// point alongside the segment. See SVG 1.1 Appendix F.6.4.
+    ///
+    /// An arc sweeping a full turn or more cannot be represented by a single
+    /// ArcSegment since its start and end points coincide.
+    #[inline]
+    pub fn to_arc_segment(&self) -> (Point2f, ArcSegment) {
+        let sweep_direction = SweepDirection::from(self.sweep_angle > 0.0);
+        let arc_size = ArcSize::from(self.sweep_angle.abs() > PI);
+
+        let segment = ArcSegment {
+            point: self.end_point(),
+            size: self.radii.abs().to_size(),
+            rotation_angle: self.rotation.to_degrees(),
+            sweep_direction,
+            arc_size,
+        };
+        (self.start_point(), segment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arc_segment::{ArcSegment, ArcSize, SweepDirection};
+    use crate::center_arc::CenterArc;
+    use crate::point2f::Point2f;
+
+    use std::f32::consts::PI;
+
+    #[test]
+    fn center_form_round_trip() {
+        let start = Point2f::new(10.0, 5.0);
+        for &rotation in &[0.0, 30.0, -75.0] {
+            for &sweep in &[SweepDirection::Clockwise, SweepDirection::CounterClockwise] {
+                for &size in &[ArcSize::Small, ArcSize::Large] {
+                    let arc = ArcSegment::new((14.0, 9.0), (6.0, 3.0), rotation, sweep, size);
+                    let center = arc.to_center_form(start).unwrap();
+
+                    assert!(center.start_point().is_approx_eq(start, 1e-4));
+                    assert!(center.end_point().is_approx_eq(arc.point, 1e-4));
+                    assert_eq!(center.sweep_angle > 0.0, sweep == SweepDirection::Clockwise);
+                    assert_eq!(center.sweep_angle.abs() > PI, size == ArcSize::Large);
+
+                    let (p, back) = center.to_arc_segment();
+                    assert!(p.is_approx_eq(start, 1e-4));
+                    assert!(back.point.is_approx_eq(arc.point, 1e-4));
+                    assert_eq!(back.sweep_direction, sweep);
+                    assert_eq!(back.arc_size, size);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn radius_correction() {
+        // Radius is too small to span the endpoints; it should be scaled up
+        // to a half circle with radius 1.
+        let arc = ArcSegment::new(
+            (2.0, 0.0),
+            (0.5, 0.5),
+            0.0,
+            SweepDirection::Clockwise,
+            ArcSize::Small,
+        );
+        let center = arc.to_center_form((0.0, 0.0)).unwrap();
+        assert!(center.center.is_approx_eq((1.0, 0.0), 1e-5));
+        assert!(center.radii.is_approx_eq([1.0, 1.0], 1e-5));
+        assert!((center.sweep_angle - PI).abs() < 1e-5);
+
+        assert!(arc.to_center_form((2.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn measurements() {
+        // Clockwise quarter circle of radius 10 from the right to the bottom
+        let arc = CenterArc::new((0.0, 0.0), [10.0, 10.0], 0.0, PI / 2.0, 0.0);
+        assert!((arc.arc_length() - 5.0 * PI).abs() < 1e-4);
+        assert!(arc.point_at(0.5).is_approx_eq((7.071_068, 7.071_068), 1e-4));
+        assert!(arc
+            .tangent_at(0.0)
+            .normalized()
+            .is_approx_eq([0.0, 1.0], 1e-5));
+
+        let bounds = arc.bounds();
+        assert!((bounds.left - 0.0).abs() < 1e-4 && (bounds.right - 10.0).abs() < 1e-4);
+        assert!((bounds.top - 0.0).abs() < 1e-4 && (bounds.bottom - 10.0).abs() < 1e-4);
+
+        // Half circle over the top reaches the top of the circle
+        let top = CenterArc::new((0.0, 0.0), [10.0, 10.0], PI, PI, 0.0);
+        assert!((top.bounds().top + 10.0).abs() < 1e-4);
+
+        let tolerance = 0.1;
+        let points = arc.flatten(tolerance);
+        assert!(points.last().unwrap().is_approx_eq(arc.end_point(), 1e-5));
+        let mut prev = arc.start_point();
+        for &p in &points {
+            let mid = Point2f::new((prev.x + p.x) / 2.0, (prev.y + p.y) / 2.0);
+            assert!(10.0 - mid.to_vector().len() <= tolerance + 1e-4);
+            prev = p;
+        }
+    }
+
+    #[test]
+    fn bezier_conversion() {
+        let start = Point2f::new(0.0, 0.0);
+        let arc = ArcSegment::new(
+            (40.0, 10.0),
+            (30.0, 15.0),
+            20.0,
+            SweepDirection::CounterClockwise,
+            ArcSize::Large,
+        );
+        let center = arc.to_center_form(start).unwrap();
+        let beziers = arc.to_beziers(start, PI / 2.0);
+        assert_eq!(beziers.last().unwrap().p3, arc.point);
+        assert!(beziers.len() as f32 >= center.sweep_angle.abs() / (PI / 2.0));
+
+        // Each curve should closely follow the arc it replaces
+        let mut p0 = start;
+        for (i, bez) in beziers.iter().enumerate() {
+            let t = (i as f32 + 0.5) / beziers.len() as f32;
+            assert!(bez.point_at(p0, 0.5).is_approx_eq(center.point_at(t), 0.05));
+            p0 = bez.p3;
+        }
+    }
+
+    #[test]
+    fn tiny_tolerances() {
+        // The tolerance is too small to change `1 - tolerance / radius`
+        let circle = CenterArc::new((0.0, 0.0), [1000.0, 1000.0], 0.0, 2.0 * PI, 0.0);
+        let points = circle.flatten(1e-5);
+        assert!(points.len() > 1000 && points.len() <= 1 << 16);
+        assert!(points
+            .last()
+            .unwrap()
+            .is_approx_eq(circle.end_point(), 1e-2));
+        for p in &points {
+            assert!((p.to_vector().len() - 1000.0).abs() < 1e-2);
+        }
+
+        assert_eq!(circle.to_beziers(1e-30).len(), 1 << 16);
+        // Tolerances are raised to what f32 can resolve at this size, rather
+        // than falling back to a single chord
+        let finest = circle.flatten(0.0);
+        assert!(finest.len() > 1000);
+        assert_eq!(circle.flatten(-1.0), finest);
+        assert_eq!(circle.flatten(f32::MIN_POSITIVE), finest);
+    }
+}
@@ -0,0 +1,240 @@
+//! A circle constructed from a center point and radius.
+
+use crate::ellipse::Ellipse;
+use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+
+use std::cmp::Ordering;
+
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+/// Contains the center point and radius of a circle.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Circle {
+    /// The center point of the circle.
+    pub center: Point2f,
+    /// The radius of the circle.
+    pub radius: f32,
+}
+
+impl Circle {
+    /// Constructs a circle from its components
+    #[inline]
+    pub fn new(center: impl Into<Point2f>, radius: f32) -> Circle {
+        Circle {
+            center: center.into(),
+            radius,
+        }
+    }
+
+    /// The smallest circle containing `rect`, which passes through its
+    /// corners.
+    #[inline]
+    pub fn from_rect(rect: impl Into<Rectf>) -> Circle {
+        let rect = rect.into();
+        Circle::new(rect.center(), rect.half_extent().len())
+    }
+
+    /// The area enclosed by the circle.
+    #[inline]
+    pub fn area(&self) -> f32 {
+        std::f32::consts::PI * self.radius * self.radius
+    }
+
+    /// Determines if the circle has a radius of zero, or NaN.
+    #[inline]
+    pub fn is_degenerate(&self) -> bool {
+        self.radius == 0.0 || self.radius.is_nan()
+    }
+
+    /// The smallest rectangle containing the circle.
+    #[inline]
+    pub fn bounds(&self) -> Rectf {
+        let r = self.radius.abs();
+        Rectf::from_center_half_extent(self.center, [r, r])
+    }
+
+    /// Checks if the circle contains a point, including points on its edge.
+    #[inline]
+    pub fn contains_point(&self, point: impl Into<Point2f>) -> bool {
+        (point.into() - self.center).len_squared() <= self.radius * self.radius
+    }
+
+    /// Picks a point inside the circle at random, with every part of its area
+    /// equally likely.
+    #[cfg(feature = "rand")]
+    #[inline]
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point2f {
+        // The square root counters the crowding of uniform radii at the center
+        let r = self.radius * rng.gen::<f32>().sqrt();
+        let (sin, cos) = (rng.gen::<f32>() * 2.0 * std::f32::consts::PI).sin_cos();
+        self.center + [r * cos, r * sin]
+    }
+
+    /// Formats the `cx`, `cy` and `r` attributes of an SVG `<circle>`
+    /// element matching this circle.
+    pub fn to_svg_attributes(&self) -> String {
+        format!(
+            r#"cx="{}" cy="{}" r="{}""#,
+            self.center.x,
+            self.center.y,
+            self.radius.abs()
+        )
+    }
+}
+
+/// The smallest circle containing every point, found with Welzl's
+/// algorithm ("Smallest enclosing disks (balls and ellipsoids)", 1991) in
+/// expected linear time. Returns a zero-sized circle at the origin if there
+/// are no points.
+///
+/// The points are visited in a shuffled order, which keeps sorted input such
+/// as the vertices of a polygon from triggering the quadratic worst case. The
+/// shuffle is deterministic, so the result is the same on every run.
+pub fn bounding_circle(points: &[Point2f]) -> Circle {
+    let mut points = points.to_vec();
+    shuffle(&mut points);
+
+    let mut circle = match points.first() {
+        Some(&p) => Circle::new(p, 0.0),
+        None => return Circle::default(),
+    };
+    for i in 1..points.len() {
+        if encloses(&circle, points[i]) {
+            continue;
+        }
+        circle = Circle::new(points[i], 0.0);
+        for j in 0..i {
+            if encloses(&circle, points[j]) {
+                continue;
+            }
+            circle = diameter_circle(points[i], points[j]);
+            for k in 0..j {
+                if !encloses(&circle, points[k]) {
+                    circle = circumcircle(points[i], points[j], points[k]);
+                }
+            }
+        }
+    }
+    circle
+}
+
+/// Containment with some slack for the rounding of the circle's center and
+/// radius, which would otherwise send the algorithm back to a smaller
+/// support set.
+#[inline]
+fn encloses(circle: &Circle, point: Point2f) -> bool {
+    let slack = 1e-5 * (1.0 + circle.radius);
+    (point - circle.center).len() <= circle.radius + slack
+}
+
+#[inline]
+fn diameter_circle(a: Point2f, b: Point2f) -> Circle {
+    Circle::new(a + (b - a) / 2.0, (b - a).len() / 2.0)
+}
+
+/// The circle through three points, or the smallest circle containing them
+/// all if they are collinear.
+fn circumcircle(a: Point2f, b: Point2f, c: Point2f) -> Circle {
+    let (ab, ac) = ((b - a).map(f64::from), (c - a).map(f64::from));
+    let d = 2.0 * (ab.x * ac.y - ab.y * ac.x);
+    if d == 0.0 {
+        let candidates = [
+            diameter_circle(a, b),
+            diameter_circle(a, c),
+            diameter_circle(b, c),
+        ];
+        let widest = candidates
+            .iter()
+            .max_by(|x, y| x.radius.partial_cmp(&y.radius).unwrap_or(Ordering::Equal));
+        return *widest.unwrap();
+    }
+
+    let (ab_sq, ac_sq) = (ab.x * ab.x + ab.y * ab.y, ac.x * ac.x + ac.y * ac.y);
+    let ux = (ac.y * ab_sq - ab.y * ac_sq) / d;
+    let uy = (ab.x * ac_sq - ac.x * ab_sq) / d;
+    let center = a + [ux as f32, uy as f32];
+    Circle::new(center, (ux * ux + uy * uy).sqrt() as f32)
+}
+
+/// Shuffles the points with a fixed xorshift sequence.
+fn shuffle(points: &mut [Point2f]) {
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+    for i in (1..points.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        points.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+}
+
+impl<P> From<(P, f32)> for Circle
+where
+    P: Into<Point2f>,
+{
+    #[inline]
+    fn from((center, radius): (P, f32)) -> Circle {
+        Circle::new(center, radius)
+    }
+}
+
+impl From<Circle> for Ellipse {
+    #[inline]
+    fn from(circle: Circle) -> Ellipse {
+        Ellipse::new(circle.center, circle.radius, circle.radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circle::{bounding_circle, Circle};
+    use crate::point2f::Point2f;
+    use crate::rectf::Rectf;
+
+    #[test]
+    fn enclosing_circles() {
+        let rect = Rectf::new(0.0, 0.0, 6.0, 8.0);
+        assert_eq!(Circle::from_rect(rect), Circle::new((3.0, 4.0), 5.0));
+        assert_eq!(bounding_circle(&[]), Circle::default());
+
+        // Two points on the diameter, with the rest inside
+        let points: Vec<Point2f> = vec![
+            (0.0, 0.0).into(),
+            (10.0, 0.0).into(),
+            (5.0, 1.0).into(),
+            (4.0, -2.0).into(),
+        ];
+        let circle = bounding_circle(&points);
+        assert!(circle.center.is_approx_eq((5.0, 0.0), 1e-4));
+        assert!((circle.radius - 5.0).abs() < 1e-4);
+
+        // An equilateral triangle needs all three points
+        let h = 3f32.sqrt() / 2.0;
+        let triangle = [(0.0, 0.0).into(), (1.0, 0.0).into(), (0.5, h).into()];
+        let circle = bounding_circle(&triangle);
+        assert!((circle.radius - 1.0 / 3f32.sqrt()).abs() < 1e-5);
+
+        // Sorted input, such as the corners of a regular polygon
+        let polygon: Vec<Point2f> = (0..64)
+            .map(|i| {
+                let (sin, cos) = (i as f32 * std::f32::consts::PI / 32.0).sin_cos();
+                Point2f::new(100.0 + 20.0 * cos, 50.0 + 20.0 * sin)
+            })
+            .collect();
+        let circle = bounding_circle(&polygon);
+        assert!(circle.center.is_approx_eq((100.0, 50.0), 1e-3));
+        assert!((circle.radius - 20.0).abs() < 1e-3);
+        let collinear = [(0.0, 0.0).into(), (2.0, 2.0).into(), (1.0, 1.0).into()];
+        assert!(bounding_circle(&collinear)
+            .center
+            .is_approx_eq((1.0, 1.0), 1e-5));
+    }
+}
@@ -2,12 +2,27 @@
 
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::d2dbasetypes::D2D_COLOR_F;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D::Common as d2d_common;
 
 mod trie;
 
 /// Describes the red, green, blue, and alpha components of a color.
 #[derive(Copy, Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde-compact",
+    serde(
+        into = "crate::compact::Array<[f32; 4]>",
+        from = "crate::compact::Array<[f32; 4]>"
+    )
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(C)]
 pub struct Color {
     /// Red channel [0.0, 1.0]
@@ -151,6 +166,23 @@ impl Color {
             _ => ColorParseResult::BadHexFormat,
         }
     }
+
+    /// Formats the color for CSS and SVG paint attributes. Opaque colors
+    /// are written as `#RRGGBB`, and translucent ones as `rgba()` since
+    /// older SVG renderers do not understand 8 digit hex colors.
+    pub fn to_css(&self) -> String {
+        let byte = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        if byte(self.a) == 0xFF {
+            return self.to_string();
+        }
+        format!(
+            "rgba({}, {}, {}, {})",
+            byte(self.r),
+            byte(self.g),
+            byte(self.b),
+            (self.a.clamp(0.0, 1.0) * 1000.0).round() / 1000.0
+        )
+    }
 }
 
 enum ColorParseResult {
@@ -178,6 +210,41 @@ fn color_lookups() {
     assert_eq!(Color::lookup("alice-blue-"), None);
 }
 
+#[cfg(test)]
+#[test]
+fn color_css() {
+    assert_eq!(Color::new(1.0, 0.5, 0.0, 1.0).to_css(), "#FF8000");
+    assert_eq!(
+        Color::new(1.0, 0.5, 0.0, 0.25).to_css(),
+        "rgba(255, 128, 0, 0.25)"
+    );
+}
+
+#[cfg(all(test, feature = "serde", not(feature = "serde-compact")))]
+#[test]
+fn color_serde() {
+    use serde_test::{assert_tokens, Token};
+
+    assert_tokens(
+        &Color::new(1.0, 0.5, 0.0, 1.0),
+        &[
+            Token::Struct {
+                name: "Color",
+                len: 4,
+            },
+            Token::Str("r"),
+            Token::F32(1.0),
+            Token::Str("g"),
+            Token::F32(0.5),
+            Token::Str("b"),
+            Token::F32(0.0),
+            Token::Str("a"),
+            Token::F32(1.0),
+            Token::StructEnd,
+        ],
+    );
+}
+
 #[derive(Debug)]
 pub enum ColorParseError {
     ColorNotFound,
@@ -206,6 +273,21 @@ impl std::str::FromStr for Color {
     }
 }
 
+/// Writes the color as hex, `#RRGGBB` when it is opaque and `#RRGGBBAA`
+/// otherwise, which parses back to the same color to within 8 bits per
+/// channel.
+impl std::fmt::Display for Color {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let byte = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        let (r, g, b, a) = (byte(self.r), byte(self.g), byte(self.b), byte(self.a));
+        if a == 0xFF {
+            write!(fmt, "#{:02X}{:02X}{:02X}", r, g, b)
+        } else {
+            write!(fmt, "#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+        }
+    }
+}
+
 impl Default for Color {
     #[inline]
     fn default() -> Self {
@@ -252,6 +334,65 @@ impl From<D2D_COLOR_F> for Color {
     }
 }
 
+#[cfg(all(windows, feature = "windows"))]
+impl From<Color> for d2d_common::D2D1_COLOR_F {
+    #[inline]
+    fn from(color: Color) -> d2d_common::D2D1_COLOR_F {
+        let Color { r, g, b, a } = color;
+        d2d_common::D2D1_COLOR_F { r, g, b, a }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<d2d_common::D2D1_COLOR_F> for Color {
+    #[inline]
+    fn from(color: d2d_common::D2D1_COLOR_F) -> Color {
+        let d2d_common::D2D1_COLOR_F { r, g, b, a } = color;
+        Color { r, g, b, a }
+    }
+}
+
+#[cfg(feature = "skia")]
+impl From<Color> for skia_safe::Color4f {
+    #[inline]
+    fn from(color: Color) -> skia_safe::Color4f {
+        let Color { r, g, b, a } = color;
+        skia_safe::Color4f { r, g, b, a }
+    }
+}
+
+#[cfg(feature = "skia")]
+impl From<skia_safe::Color4f> for Color {
+    #[inline]
+    fn from(color: skia_safe::Color4f) -> Color {
+        let skia_safe::Color4f { r, g, b, a } = color;
+        Color { r, g, b, a }
+    }
+}
+
+/// Components are clamped to [0, 1] and rounded to the nearest byte.
+#[cfg(feature = "sdl2")]
+impl From<Color> for sdl2::pixels::Color {
+    #[inline]
+    fn from(color: Color) -> sdl2::pixels::Color {
+        let byte = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        sdl2::pixels::Color::RGBA(byte(color.r), byte(color.g), byte(color.b), byte(color.a))
+    }
+}
+
+#[cfg(feature = "sdl2")]
+impl From<sdl2::pixels::Color> for Color {
+    #[inline]
+    fn from(color: sdl2::pixels::Color) -> Color {
+        Color {
+            r: color.r as f32 / 255.0,
+            g: color.g as f32 / 255.0,
+            b: color.b as f32 / 255.0,
+            a: color.a as f32 / 255.0,
+        }
+    }
+}
+
 // TODO: Replace this with a const fn when float ops in const fn is stable
 macro_rules! define_color {
     ($r:expr, $g:expr, $b:expr) => {
@@ -846,3 +987,52 @@ fn color_d2d_bin_compat() {
     assert!(ptr_eq(&col.a, &d2d.a));
     assert_eq!(size_of_val(&col), size_of_val(d2d));
 }
+
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn color_windows_bin_compat() {
+    use std::mem::size_of_val;
+
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
+    }
+
+    let col = Color::from(0);
+    let d2d = unsafe { &*((&col) as *const _ as *const d2d_common::D2D1_COLOR_F) };
+
+    assert!(ptr_eq(&col.r, &d2d.r));
+    assert!(ptr_eq(&col.g, &d2d.g));
+    assert!(ptr_eq(&col.b, &d2d.b));
+    assert!(ptr_eq(&col.a, &d2d.a));
+    assert_eq!(size_of_val(&col), size_of_val(d2d));
+}
+
+// The struct is `#[repr(C)]` with every field of type `f32`, so it has
+// the same layout as `[f32; 4]`.
+#[cfg(feature = "encase")]
+impl encase::vector::AsRefVectorParts<f32, 4> for Color {
+    #[inline]
+    fn as_ref_parts(&self) -> &[f32; 4] {
+        unsafe { &*(self as *const Color as *const [f32; 4]) }
+    }
+}
+
+#[cfg(feature = "encase")]
+impl encase::vector::AsMutVectorParts<f32, 4> for Color {
+    #[inline]
+    fn as_mut_parts(&mut self) -> &mut [f32; 4] {
+        unsafe { &mut *(self as *mut Color as *mut [f32; 4]) }
+    }
+}
+
+#[cfg(feature = "encase")]
+impl encase::vector::FromVectorParts<f32, 4> for Color {
+    #[inline]
+    fn from_parts([r, g, b, a]: [f32; 4]) -> Color {
+        Color { r, g, b, a }
+    }
+}
+
+// Colors are laid out as a `vec4<f32>` of (r, g, b, a) in shader buffers.
+#[cfg(feature = "encase")]
+encase::impl_vector!(4, Color, f32);
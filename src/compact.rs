@@ -0,0 +1,203 @@
+//! The array representations used for serde with the `serde-compact`
+//! feature, so that e.g. points are written as `[x, y]` instead of
+//! `{"x": x, "y": y}`.
+
+use crate::color::Color;
+use crate::matrix3x2f::Matrix3x2f;
+use crate::point2::Point2;
+use crate::rect::Rect;
+use crate::size::Size;
+use crate::thicknessf::Thicknessf;
+use crate::vector2::Vector2;
+
+/// Serializes exactly like the array it holds.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct Array<A>(pub A);
+
+impl<T> From<Point2<T>> for Array<[T; 2]> {
+    #[inline]
+    fn from(p: Point2<T>) -> Self {
+        Array([p.x, p.y])
+    }
+}
+
+impl<T> From<Array<[T; 2]>> for Point2<T> {
+    #[inline]
+    fn from(Array([x, y]): Array<[T; 2]>) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> From<Vector2<T>> for Array<[T; 2]> {
+    #[inline]
+    fn from(v: Vector2<T>) -> Self {
+        Array([v.x, v.y])
+    }
+}
+
+impl<T> From<Array<[T; 2]>> for Vector2<T> {
+    #[inline]
+    fn from(Array([x, y]): Array<[T; 2]>) -> Self {
+        Vector2 { x, y }
+    }
+}
+
+impl<T> From<Size<T>> for Array<[T; 2]> {
+    #[inline]
+    fn from(s: Size<T>) -> Self {
+        Array([s.width, s.height])
+    }
+}
+
+impl<T> From<Array<[T; 2]>> for Size<T> {
+    #[inline]
+    fn from(Array([width, height]): Array<[T; 2]>) -> Self {
+        Size { width, height }
+    }
+}
+
+impl<T> From<Rect<T>> for Array<[T; 4]> {
+    #[inline]
+    fn from(r: Rect<T>) -> Self {
+        Array([r.left, r.top, r.right, r.bottom])
+    }
+}
+
+impl<T> From<Array<[T; 4]>> for Rect<T> {
+    #[inline]
+    fn from(Array([left, top, right, bottom]): Array<[T; 4]>) -> Self {
+        Rect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+}
+
+impl From<Thicknessf> for Array<[f32; 4]> {
+    #[inline]
+    fn from(t: Thicknessf) -> Self {
+        Array([t.left, t.top, t.right, t.bottom])
+    }
+}
+
+impl From<Array<[f32; 4]>> for Thicknessf {
+    #[inline]
+    fn from(Array([left, top, right, bottom]): Array<[f32; 4]>) -> Self {
+        Thicknessf {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+}
+
+impl From<Color> for Array<[f32; 4]> {
+    #[inline]
+    fn from(c: Color) -> Self {
+        Array([c.r, c.g, c.b, c.a])
+    }
+}
+
+impl From<Array<[f32; 4]>> for Color {
+    #[inline]
+    fn from(Array([r, g, b, a]): Array<[f32; 4]>) -> Self {
+        Color { r, g, b, a }
+    }
+}
+
+impl From<Matrix3x2f> for Array<[f32; 6]> {
+    #[inline]
+    fn from(m: Matrix3x2f) -> Self {
+        Array([m.a, m.b, m.c, m.d, m.x, m.y])
+    }
+}
+
+impl From<Array<[f32; 6]>> for Matrix3x2f {
+    #[inline]
+    fn from(Array([a, b, c, d, x, y]): Array<[f32; 6]>) -> Self {
+        Matrix3x2f { a, b, c, d, x, y }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circle::Circle;
+    use crate::color::Color;
+    use crate::matrix3x2f::Matrix3x2f;
+    use crate::point2f::Point2f;
+    use crate::recti::Recti;
+
+    use serde_test::{assert_tokens, Token};
+
+    #[test]
+    fn formats() {
+        assert_tokens(
+            &Point2f::new(1.0, 2.0),
+            &[
+                Token::Tuple { len: 2 },
+                Token::F32(1.0),
+                Token::F32(2.0),
+                Token::TupleEnd,
+            ],
+        );
+        assert_tokens(
+            &Recti::new(0, 0, 100, 50),
+            &[
+                Token::Tuple { len: 4 },
+                Token::I32(0),
+                Token::I32(0),
+                Token::I32(100),
+                Token::I32(50),
+                Token::TupleEnd,
+            ],
+        );
+        assert_tokens(
+            &Color::new(1.0, 0.5, 0.0, 1.0),
+            &[
+                Token::Tuple { len: 4 },
+                Token::F32(1.0),
+                Token::F32(0.5),
+                Token::F32(0.0),
+                Token::F32(1.0),
+                Token::TupleEnd,
+            ],
+        );
+        assert_tokens(
+            &Matrix3x2f::IDENTITY,
+            &[
+                Token::Tuple { len: 6 },
+                Token::F32(1.0),
+                Token::F32(0.0),
+                Token::F32(0.0),
+                Token::F32(1.0),
+                Token::F32(0.0),
+                Token::F32(0.0),
+                Token::TupleEnd,
+            ],
+        );
+
+        // Types made of points keep their fields, with compact points inside
+        assert_tokens(
+            &Circle::new((3.0, 4.0), 5.0),
+            &[
+                Token::Struct {
+                    name: "Circle",
+                    len: 2,
+                },
+                Token::Str("center"),
+                Token::Tuple { len: 2 },
+                Token::F32(3.0),
+                Token::F32(4.0),
+                Token::TupleEnd,
+                Token::Str("radius"),
+                Token::F32(5.0),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
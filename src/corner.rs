@@ -0,0 +1,64 @@
+//! Corner geometry shared by the smoothing and filleting operations on
+//! polylines and polygons.
+
+use crate::arc_segment::SweepDirection;
+use crate::point2f::Point2f;
+
+/// The points at fractions `t_in` and `t_out` of the way back along the
+/// edges meeting at `vertex`, towards `prev` and `next` respectively.
+#[inline]
+pub(crate) fn cut(
+    prev: Point2f,
+    vertex: Point2f,
+    next: Point2f,
+    t_in: f32,
+    t_out: f32,
+) -> (Point2f, Point2f) {
+    (
+        vertex + (prev - vertex) * t_in,
+        vertex + (next - vertex) * t_out,
+    )
+}
+
+/// A circular arc rounding off a corner, tangent to both of its edges.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Fillet {
+    /// Where the arc leaves the incoming edge.
+    pub start: Point2f,
+    /// Where the arc joins the outgoing edge.
+    pub end: Point2f,
+    /// The radius of the arc, which may be smaller than requested.
+    pub radius: f32,
+    /// The direction the arc is swept from `start` to `end`.
+    pub sweep: SweepDirection,
+}
+
+/// Fits an arc of the given radius into the corner at `vertex`. The radius
+/// shrinks where needed so the arc uses at most half of each edge, leaving
+/// room for the neighbouring corners. Returns `None` if the edges are
+/// collinear or degenerate and there is no corner to round.
+pub(crate) fn fillet(prev: Point2f, vertex: Point2f, next: Point2f, radius: f32) -> Option<Fillet> {
+    let (into, out) = (vertex - prev, next - vertex);
+    let (len_in, len_out) = (into.len(), out.len());
+    if len_in == 0.0 || len_out == 0.0 || radius <= 0.0 {
+        return None;
+    }
+
+    let (u, v) = ((prev - vertex) / len_in, (next - vertex) / len_out);
+    let sin = u.cross(v).abs();
+    if sin < 1e-6 {
+        return None;
+    }
+
+    // Distance from the vertex to the tangent points is r / tan(angle / 2)
+    let half_tan = sin / (1.0 + u.dot(v));
+    let dist = (radius / half_tan).min(len_in.min(len_out) / 2.0);
+    let sweep = SweepDirection::from(into.cross(out) > 0.0);
+
+    Some(Fillet {
+        start: vertex + u * dist,
+        end: vertex + v * dist,
+        radius: dist * half_tan,
+        sweep,
+    })
+}
@@ -0,0 +1,80 @@
+//! Zero-copy views of math2d slices as arrays of the Direct2D types which
+//! share their layout, for passing straight to methods such as
+//! `ID2D1GeometrySink::AddLines` and `AddBeziers`.
+//!
+//! The functions work with both the `winapi` types of the `d2d` feature and
+//! the `windows` crate types, picking the output from the context it is
+//! used in. They are shorthands for `LayoutCompatible::view_slice`.
+//!
+//! ```ignore
+//! sink.AddBeziers(math2d::d2d::as_d2d_beziers(&segments));
+//! ```
+
+use crate::bezier_segment::BezierSegment;
+use crate::layout::LayoutCompatible;
+use crate::point2f::Point2f;
+use crate::quad_bezier_segment::QuadBezierSegment;
+use crate::triangle::Triangle;
+
+/// Views the points as an array of `D2D_POINT_2F`, e.g. for `AddLines`.
+#[inline]
+pub fn as_d2d_points<P: LayoutCompatible<Point2f>>(points: &[Point2f]) -> &[P] {
+    LayoutCompatible::view_slice(points)
+}
+
+/// Views the triangles as an array of `D2D1_TRIANGLE`, e.g. for
+/// `ID2D1TessellationSink::AddTriangles`.
+#[inline]
+pub fn as_d2d_triangles<T: LayoutCompatible<Triangle>>(triangles: &[Triangle]) -> &[T] {
+    LayoutCompatible::view_slice(triangles)
+}
+
+/// Views the segments as an array of `D2D1_BEZIER_SEGMENT`, e.g. for
+/// `AddBeziers`.
+#[inline]
+pub fn as_d2d_beziers<B: LayoutCompatible<BezierSegment>>(segments: &[BezierSegment]) -> &[B] {
+    LayoutCompatible::view_slice(segments)
+}
+
+/// Views the segments as an array of `D2D1_QUADRATIC_BEZIER_SEGMENT`, e.g.
+/// for `AddQuadraticBeziers`.
+#[inline]
+pub fn as_d2d_quadratic_beziers<Q: LayoutCompatible<QuadBezierSegment>>(
+    segments: &[QuadBezierSegment],
+) -> &[Q] {
+    LayoutCompatible::view_slice(segments)
+}
+
+#[cfg(all(test, feature = "d2d"))]
+#[test]
+fn winapi_slices() {
+    use winapi::um::d2d1::D2D1_BEZIER_SEGMENT;
+    use winapi::um::dcommon::D2D_POINT_2F;
+
+    let points = [Point2f::new(1.0, 2.0), Point2f::new(3.0, 4.0)];
+    let raw: &[D2D_POINT_2F] = as_d2d_points(&points);
+    assert_eq!(raw.len(), 2);
+    assert_eq!((raw[1].x, raw[1].y), (3.0, 4.0));
+
+    let segments = [BezierSegment::new((0.0, 1.0), (2.0, 3.0), (4.0, 5.0))];
+    let raw: &[D2D1_BEZIER_SEGMENT] = as_d2d_beziers(&segments);
+    assert_eq!((raw[0].point3.x, raw[0].point3.y), (4.0, 5.0));
+}
+
+#[cfg(all(test, feature = "windows"))]
+#[test]
+fn windows_slices() {
+    use ::windows::Win32::Graphics::Direct2D as d2d;
+
+    let triangles = [Triangle {
+        p1: Point2f::new(0.0, 0.0),
+        p2: Point2f::new(1.0, 0.0),
+        p3: Point2f::new(0.0, 1.0),
+    }];
+    let raw: &[d2d::D2D1_TRIANGLE] = as_d2d_triangles(&triangles);
+    assert_eq!((raw[0].point2.x, raw[0].point2.y), (1.0, 0.0));
+
+    let segments = [QuadBezierSegment::new((0.0, 1.0), (2.0, 3.0))];
+    let raw: &[d2d::D2D1_QUADRATIC_BEZIER_SEGMENT] = as_d2d_quadratic_beziers(&segments);
+    assert_eq!((raw[0].point1.x, raw[0].point1.y), (0.0, 1.0));
+}
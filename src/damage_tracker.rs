@@ -0,0 +1,197 @@
+//! Accumulates the areas of a scene which need to be redrawn.
+
+use crate::rectf::Rectf;
+use crate::recti::Recti;
+
+/// Collects invalidated rectangles over the course of a frame, merging them
+/// so that the renderer has at most a fixed number of rectangles to redraw.
+///
+/// Overlapping rectangles, and rectangles which line up edge to edge, are
+/// always merged. Past the budget, the pair whose combined bounds covers the
+/// least extra area is merged, so the set stays close to the damaged area
+/// without growing into many tiny rectangles.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DamageTracker {
+    rects: Vec<Rectf>,
+    max_rects: usize,
+}
+
+impl DamageTracker {
+    /// Constructs an empty tracker which keeps at most `max_rects`
+    /// rectangles. A budget of zero is treated as one.
+    #[inline]
+    pub fn new(max_rects: usize) -> DamageTracker {
+        DamageTracker {
+            rects: Vec::new(),
+            max_rects: max_rects.max(1),
+        }
+    }
+
+    /// The most rectangles the tracker will keep.
+    #[inline]
+    pub fn max_rects(&self) -> usize {
+        self.max_rects
+    }
+
+    /// Checks if nothing has been damaged.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// The rectangles which need to be redrawn. They never overlap each
+    /// other.
+    #[inline]
+    pub fn rects(&self) -> &[Rectf] {
+        &self.rects
+    }
+
+    /// The damaged rectangles expanded outward to whole pixels, for
+    /// renderers which redraw integer regions.
+    #[inline]
+    pub fn pixel_rects(&self) -> Vec<Recti> {
+        self.rects
+            .iter()
+            .map(|r| {
+                Recti::new(
+                    r.left.floor() as i32,
+                    r.top.floor() as i32,
+                    r.right.ceil() as i32,
+                    r.bottom.ceil() as i32,
+                )
+            })
+            .collect()
+    }
+
+    /// The smallest rectangle containing all of the damage, if there is any.
+    #[inline]
+    pub fn bounds(&self) -> Option<Rectf> {
+        let (first, rest) = self.rects.split_first()?;
+        Some(rest.iter().fold(*first, |acc, r| acc.combined_with(*r)))
+    }
+
+    /// Marks `rect` as needing to be redrawn. Rectangles with no area are
+    /// ignored.
+    pub fn add(&mut self, rect: impl Into<Rectf>) {
+        let rect = rect.into().normalized();
+        if rect.left >= rect.right || rect.top >= rect.bottom {
+            return;
+        }
+        self.insert(rect);
+
+        while self.rects.len() > self.max_rects {
+            let (mut best, mut best_cost) = ((0, 1), f32::INFINITY);
+            for i in 0..self.rects.len() {
+                for j in i + 1..self.rects.len() {
+                    let cost = merge_cost(&self.rects[i], &self.rects[j]);
+                    if cost < best_cost {
+                        best = (i, j);
+                        best_cost = cost;
+                    }
+                }
+            }
+
+            let b = self.rects.swap_remove(best.1);
+            let a = self.rects.swap_remove(best.0);
+            self.insert(a.combined_with(b));
+        }
+    }
+
+    /// Takes the damage accumulated so far, leaving the tracker empty for
+    /// the next frame.
+    #[inline]
+    pub fn take(&mut self) -> Vec<Rectf> {
+        std::mem::take(&mut self.rects)
+    }
+
+    /// Forgets all of the damage.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.rects.clear();
+    }
+
+    /// Adds `rect`, first absorbing every rectangle it should be merged with.
+    /// Absorbing one can grow it to reach others, so this repeats until it
+    /// stops growing.
+    fn insert(&mut self, mut rect: Rectf) {
+        loop {
+            let count = self.rects.len();
+            self.rects.retain(|r| {
+                let merge = r.overlaps(&rect) || merge_cost(r, &rect) <= 0.0;
+                if merge {
+                    rect = rect.combined_with(*r);
+                }
+                !merge
+            });
+            if self.rects.len() == count {
+                break;
+            }
+        }
+        self.rects.push(rect);
+    }
+}
+
+impl Default for DamageTracker {
+    /// A tracker keeping up to 8 rectangles.
+    #[inline]
+    fn default() -> Self {
+        DamageTracker::new(8)
+    }
+}
+
+/// The area the bounds of two rectangles covers which neither of them does.
+#[inline]
+fn merge_cost(a: &Rectf, b: &Rectf) -> f32 {
+    let area = |r: &Rectf| (r.right - r.left).max(0.0) * (r.bottom - r.top).max(0.0);
+    let overlap = Rectf::new(
+        a.left.max(b.left),
+        a.top.max(b.top),
+        a.right.min(b.right),
+        a.bottom.min(b.bottom),
+    );
+    area(&a.combined_with(*b)) - area(a) - area(b) + area(&overlap)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::damage_tracker::DamageTracker;
+    use crate::rectf::Rectf;
+    use crate::recti::Recti;
+
+    #[test]
+    fn merging() {
+        let mut damage = DamageTracker::new(3);
+        damage.add(Rectf::new(0.0, 0.0, 10.0, 10.0));
+        damage.add(Rectf::new(5.0, 5.0, 15.0, 15.0));
+        assert_eq!(damage.rects(), &[Rectf::new(0.0, 0.0, 15.0, 15.0)]);
+
+        // Edge to edge rectangles become one, empty ones are ignored
+        damage.add(Recti::new(15, 0, 20, 15));
+        damage.add(Rectf::new(50.0, 50.0, 50.0, 60.0));
+        assert_eq!(damage.rects(), &[Rectf::new(0.0, 0.0, 20.0, 15.0)]);
+
+        // Past the budget, the nearest rectangles are merged
+        damage.add(Rectf::new(100.0, 0.0, 110.0, 10.0));
+        damage.add(Rectf::new(100.0, 100.0, 110.0, 110.0));
+        damage.add(Rectf::new(112.0, 100.0, 120.0, 110.0));
+        assert_eq!(damage.rects().len(), 3);
+        assert!(damage
+            .rects()
+            .contains(&Rectf::new(100.0, 100.0, 120.0, 110.0)));
+        for (i, a) in damage.rects().iter().enumerate() {
+            for b in &damage.rects()[i + 1..] {
+                assert!(!a.overlaps(b));
+            }
+        }
+        assert_eq!(damage.bounds(), Some(Rectf::new(0.0, 0.0, 120.0, 110.0)));
+
+        let frame = damage.take();
+        assert_eq!(frame.len(), 3);
+        assert!(damage.is_empty());
+        assert_eq!(damage.bounds(), None);
+
+        damage.add(Rectf::new(0.5, 0.5, 2.5, 2.0));
+        assert_eq!(damage.pixel_rects(), vec![Recti::new(0, 0, 3, 2)]);
+    }
+}
@@ -0,0 +1,358 @@
+//! Conversions between device independent pixels (DIPs) and physical pixels.
+
+use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+use crate::recti::Recti;
+use crate::sizef::Sizef;
+use crate::thicknessf::Thicknessf;
+use crate::vector2f::Vector2f;
+
+/// The resolution of a display or render target in dots per inch, which
+/// relates device independent pixels to physical ones. One DIP is 1/96th of
+/// an inch, so at the default of 96 DPI they are the same size.
+///
+/// Horizontal and vertical resolutions are kept separately, as Direct2D
+/// reports them separately, though they are almost always the same.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Dpi {
+    /// The horizontal resolution.
+    pub x: f32,
+    /// The vertical resolution.
+    pub y: f32,
+}
+
+/// How to round the edges of a rectangle when converting it to whole
+/// pixels.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum RectRounding {
+    /// Moves each edge outward, so the pixels cover all of the rectangle.
+    /// Use this for clips and invalidation, where losing a sliver of pixels
+    /// leaves stale content behind.
+    Out,
+    /// Moves each edge to the nearest pixel boundary. Use this for layout,
+    /// where neighboring rectangles must stay edge to edge.
+    Nearest,
+    /// Moves each edge inward, so the pixels lie entirely inside the
+    /// rectangle.
+    In,
+}
+
+impl Dpi {
+    /// The resolution at which DIPs and pixels are the same size.
+    pub const DEFAULT: Dpi = Dpi { x: 96.0, y: 96.0 };
+
+    /// Constructs a resolution from its horizontal and vertical components.
+    #[inline]
+    pub fn new(x: f32, y: f32) -> Dpi {
+        Dpi { x, y }
+    }
+
+    /// Constructs a resolution which is the same along both axes.
+    #[inline]
+    pub fn uniform(dpi: f32) -> Dpi {
+        Dpi { x: dpi, y: dpi }
+    }
+
+    /// Constructs the resolution at which each DIP covers `scale` pixels, as
+    /// given by the display scale factor of many windowing systems.
+    #[inline]
+    pub fn from_scale_factor(scale: f32) -> Dpi {
+        Dpi::uniform(96.0 * scale)
+    }
+
+    /// The number of pixels each DIP covers along each axis.
+    #[inline]
+    pub fn scale(self) -> Vector2f {
+        Vector2f::new(self.x / 96.0, self.y / 96.0)
+    }
+
+    /// Converts a value from DIPs to physical pixels.
+    #[inline]
+    pub fn dips_to_pixels<T: DpiScalable>(self, value: T) -> T {
+        value.scaled_by(self.scale())
+    }
+
+    /// Converts a value from physical pixels to DIPs.
+    #[inline]
+    pub fn pixels_to_dips<T: DpiScalable>(self, value: T) -> T {
+        value.scaled_by(self.scale().reciprocal())
+    }
+
+    /// Converts a rectangle in DIPs to whole physical pixels, rounding its
+    /// edges as requested.
+    #[inline]
+    pub fn dips_to_pixel_rect(self, rect: impl Into<Rectf>, rounding: RectRounding) -> Recti {
+        let r = self.dips_to_pixels(rect.into().normalized());
+        let (left, top, right, bottom) = match rounding {
+            RectRounding::Out => (
+                r.left.floor(),
+                r.top.floor(),
+                r.right.ceil(),
+                r.bottom.ceil(),
+            ),
+            RectRounding::Nearest => (
+                r.left.round(),
+                r.top.round(),
+                r.right.round(),
+                r.bottom.round(),
+            ),
+            RectRounding::In => (
+                r.left.ceil(),
+                r.top.ceil(),
+                r.right.floor(),
+                r.bottom.floor(),
+            ),
+        };
+        // Rounding inward can cross the edges over on thin rectangles
+        Recti::new(
+            left as i32,
+            top as i32,
+            right.max(left) as i32,
+            bottom.max(top) as i32,
+        )
+    }
+
+    /// Converts a rectangle of whole physical pixels to DIPs.
+    #[inline]
+    pub fn pixel_rect_to_dips(self, rect: Recti) -> Rectf {
+        self.pixels_to_dips(rect.to_f32())
+    }
+}
+
+/// Helpers for winit, whose windows report their size and cursor position
+/// in physical pixels. Construct the `Dpi` from the window's scale factor
+/// with `Dpi::from_scale_factor`.
+#[cfg(feature = "winit")]
+impl Dpi {
+    /// Converts a physical size, such as the one carried by a resize event,
+    /// to DIPs.
+    #[inline]
+    pub fn physical_size_to_dips<P: winit_dpi::Pixel>(
+        self,
+        size: winit_dpi::PhysicalSize<P>,
+    ) -> Sizef {
+        let size = size.cast::<f32>();
+        self.pixels_to_dips(Sizef::new(size.width, size.height))
+    }
+
+    /// Converts a physical position, such as a cursor position, to DIPs.
+    #[inline]
+    pub fn physical_position_to_dips<P: winit_dpi::Pixel>(
+        self,
+        position: winit_dpi::PhysicalPosition<P>,
+    ) -> Point2f {
+        let position = position.cast::<f32>();
+        self.pixels_to_dips(Point2f::new(position.x, position.y))
+    }
+
+    /// Converts a size in DIPs to whole physical pixels, rounding up so the
+    /// content fits.
+    #[inline]
+    pub fn dips_to_physical_size(self, size: Sizef) -> winit_dpi::PhysicalSize<u32> {
+        let size = self.dips_to_pixels(size);
+        winit_dpi::PhysicalSize::new(size.width.ceil() as u32, size.height.ceil() as u32)
+    }
+
+    /// Converts a position in DIPs to the nearest physical pixel.
+    #[inline]
+    pub fn dips_to_physical_position(self, point: Point2f) -> winit_dpi::PhysicalPosition<i32> {
+        let point = self.dips_to_pixels(point);
+        winit_dpi::PhysicalPosition::new(point.x.round() as i32, point.y.round() as i32)
+    }
+}
+
+impl Default for Dpi {
+    #[inline]
+    fn default() -> Self {
+        Dpi::DEFAULT
+    }
+}
+
+impl From<f32> for Dpi {
+    #[inline]
+    fn from(dpi: f32) -> Dpi {
+        Dpi::uniform(dpi)
+    }
+}
+
+impl From<(f32, f32)> for Dpi {
+    #[inline]
+    fn from((x, y): (f32, f32)) -> Dpi {
+        Dpi::new(x, y)
+    }
+}
+
+/// Values measured in DIPs or pixels which can be converted between them by
+/// a `Dpi`.
+pub trait DpiScalable {
+    /// Scales the value by `scale.x` horizontally and `scale.y` vertically.
+    fn scaled_by(self, scale: Vector2f) -> Self;
+}
+
+impl DpiScalable for f32 {
+    /// Lone lengths, such as stroke widths, are scaled horizontally.
+    #[inline]
+    fn scaled_by(self, scale: Vector2f) -> f32 {
+        self * scale.x
+    }
+}
+
+impl DpiScalable for Point2f {
+    #[inline]
+    fn scaled_by(self, scale: Vector2f) -> Point2f {
+        Point2f::new(self.x * scale.x, self.y * scale.y)
+    }
+}
+
+impl DpiScalable for Vector2f {
+    #[inline]
+    fn scaled_by(self, scale: Vector2f) -> Vector2f {
+        Vector2f::new(self.x * scale.x, self.y * scale.y)
+    }
+}
+
+impl DpiScalable for Sizef {
+    #[inline]
+    fn scaled_by(self, scale: Vector2f) -> Sizef {
+        Sizef::new(self.width * scale.x, self.height * scale.y)
+    }
+}
+
+impl DpiScalable for Rectf {
+    #[inline]
+    fn scaled_by(self, scale: Vector2f) -> Rectf {
+        Rectf::new(
+            self.left * scale.x,
+            self.top * scale.y,
+            self.right * scale.x,
+            self.bottom * scale.y,
+        )
+    }
+}
+
+impl DpiScalable for Thicknessf {
+    #[inline]
+    fn scaled_by(self, scale: Vector2f) -> Thicknessf {
+        Thicknessf::new(
+            self.left * scale.x,
+            self.top * scale.y,
+            self.right * scale.x,
+            self.bottom * scale.y,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dpi::{Dpi, RectRounding};
+    use crate::point2f::Point2f;
+    use crate::rectf::Rectf;
+    use crate::recti::Recti;
+    use crate::sizef::Sizef;
+    use crate::thicknessf::Thicknessf;
+
+    #[test]
+    fn conversions() {
+        let dpi = Dpi::new(144.0, 192.0);
+        let p = dpi.dips_to_pixels(Point2f::new(10.0, 10.0));
+        assert_eq!(p, (15.0, 20.0).into());
+        assert_eq!(dpi.pixels_to_dips(p), (10.0, 10.0).into());
+        assert_eq!(
+            dpi.dips_to_pixels(Sizef::new(2.0, 2.0)),
+            Sizef::new(3.0, 4.0)
+        );
+        assert_eq!(
+            dpi.dips_to_pixels(Thicknessf::from(2.0)),
+            Thicknessf::new(3.0, 4.0, 3.0, 4.0)
+        );
+        assert_eq!(Dpi::from_scale_factor(1.5).scale(), [1.5, 1.5].into());
+        assert_eq!(Dpi::default().dips_to_pixels(7.0), 7.0);
+    }
+
+    #[test]
+    fn rect_rounding() {
+        let dpi = Dpi::from_scale_factor(1.5);
+        // 1.5..7.5 by 1.5..3.3 pixels
+        let rect = Rectf::new(1.0, 1.0, 5.0, 2.2);
+        assert_eq!(
+            dpi.dips_to_pixel_rect(rect, RectRounding::Out),
+            Recti::new(1, 1, 8, 4)
+        );
+        assert_eq!(
+            dpi.dips_to_pixel_rect(rect, RectRounding::Nearest),
+            Recti::new(2, 2, 8, 3)
+        );
+        assert_eq!(
+            dpi.dips_to_pixel_rect(rect, RectRounding::In),
+            Recti::new(2, 2, 7, 3)
+        );
+        let thin = Rectf::new(1.1, 1.1, 1.2, 1.2);
+        let inner = dpi.dips_to_pixel_rect(thin, RectRounding::In);
+        assert_eq!(inner.width(), 0);
+        assert_eq!(inner.height(), 0);
+        assert_eq!(
+            dpi.pixel_rect_to_dips(Recti::new(3, 3, 6, 6)),
+            Rectf::new(2.0, 2.0, 4.0, 4.0)
+        );
+    }
+
+    #[test]
+    fn stroke_snapping() {
+        // Odd widths center the stroke on a row of pixels
+        let rect = Rectf::new(10.2, 10.0, 19.9, 20.4);
+        assert_eq!(
+            rect.snapped_for_stroke(1.0, Dpi::DEFAULT),
+            Rectf::new(10.5, 10.5, 19.5, 20.5)
+        );
+        assert_eq!(
+            rect.snapped_for_stroke(2.0, Dpi::DEFAULT),
+            Rectf::new(10.0, 10.0, 20.0, 20.0)
+        );
+
+        // At 150% a 1 DIP stroke rounds to 2 pixels, and 2/3 DIP to 1
+        let dpi = Dpi::from_scale_factor(1.5);
+        let rect = Rectf::new(10.0, 10.0, 20.0, 20.0);
+        let even = dpi.dips_to_pixels(rect.snapped_for_stroke(1.0, dpi));
+        assert_eq!(even, Rectf::new(15.0, 15.0, 30.0, 30.0));
+        let odd = dpi.dips_to_pixels(rect.snapped_for_stroke(2.0 / 3.0, dpi));
+        assert!(odd.is_approx_eq_ulps(&Rectf::new(15.5, 15.5, 30.5, 30.5), 4));
+    }
+
+    #[cfg(feature = "winit")]
+    #[test]
+    fn winit_conversions() {
+        use crate::point2i::Point2i;
+        use crate::sizeu::Sizeu;
+
+        let dpi = Dpi::from_scale_factor(1.5);
+        let physical = winit_dpi::PhysicalSize::new(300u32, 151u32);
+        assert_eq!(Sizeu::from(physical), Sizeu::new(300, 151));
+        let dips = dpi.physical_size_to_dips(physical);
+        assert_eq!(dips.width, 200.0);
+        assert_eq!(dpi.dips_to_physical_size(dips), physical);
+
+        let cursor = winit_dpi::PhysicalPosition::new(30.0f64, 45.0);
+        assert_eq!(dpi.physical_position_to_dips(cursor), (20.0, 30.0).into());
+        assert_eq!(
+            dpi.dips_to_physical_position(Point2f::new(20.0, 30.1)),
+            Point2i::new(30, 45).into()
+        );
+
+        let logical: winit_dpi::LogicalSize<f32> = Sizef::new(2.0, 3.0).into();
+        assert_eq!(logical.to_physical::<f32>(1.5), Sizef::new(3.0, 4.5).into());
+    }
+}
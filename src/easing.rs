@@ -0,0 +1,154 @@
+//! Easing curves which shape the progress of an animation over time.
+
+/// A curve mapping the linear progress of an animation, from 0 at its start
+/// to 1 at its end, to how far along its values should be.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Easing {
+    /// Progresses at a constant rate.
+    Linear,
+    /// Holds the starting value until the end, then jumps.
+    Hold,
+    /// Starts slowly and speeds up.
+    EaseIn,
+    /// Starts quickly and slows down.
+    EaseOut,
+    /// Starts and ends slowly.
+    EaseInOut,
+    /// A cubic Bézier curve from `(0, 0)` to `(1, 1)` through the control
+    /// points `(x1, y1)` and `(x2, y2)`, as in CSS's `cubic-bezier()`. The
+    /// x coordinates must be between 0 and 1.
+    CubicBezier {
+        /// The x coordinate of the first control point.
+        x1: f32,
+        /// The y coordinate of the first control point.
+        y1: f32,
+        /// The x coordinate of the second control point.
+        x2: f32,
+        /// The y coordinate of the second control point.
+        y2: f32,
+    },
+}
+
+impl Easing {
+    /// Maps a linear progress between 0 and 1 through the curve. Progress
+    /// outside of that range is clamped.
+    #[inline]
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::Hold => {
+                if t < 1.0 {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => {
+                let u = 1.0 - t;
+                1.0 - u * u * u
+            }
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+            Easing::CubicBezier { x1, y1, x2, y2 } => {
+                let s = solve_bezier(x1, x2, t);
+                bezier(y1, y2, s)
+            }
+        }
+    }
+}
+
+impl Default for Easing {
+    #[inline]
+    fn default() -> Self {
+        Easing::Linear
+    }
+}
+
+/// One coordinate of a cubic Bézier curve from 0 to 1 with the given
+/// control values.
+#[inline]
+fn bezier(p1: f32, p2: f32, s: f32) -> f32 {
+    let u = 1.0 - s;
+    3.0 * u * u * s * p1 + 3.0 * u * s * s * p2 + s * s * s
+}
+
+/// Finds the curve parameter at which the x coordinate reaches `x`, with a
+/// few Newton steps and bisection as a fallback where the slope is flat.
+fn solve_bezier(x1: f32, x2: f32, x: f32) -> f32 {
+    let mut s = x;
+    for _ in 0..8 {
+        let error = bezier(x1, x2, s) - x;
+        if error.abs() < 1e-6 {
+            return s;
+        }
+        let u = 1.0 - s;
+        let slope = 3.0 * u * u * x1 + 6.0 * u * s * (x2 - x1) + 3.0 * s * s * (1.0 - x2);
+        if slope.abs() < 1e-6 {
+            break;
+        }
+        s -= error / slope;
+    }
+
+    let (mut lo, mut hi) = (0.0, 1.0);
+    s = x;
+    for _ in 0..32 {
+        if bezier(x1, x2, s) < x {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) / 2.0;
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::easing::Easing;
+
+    #[test]
+    fn curves() {
+        let curves = [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::CubicBezier {
+                x1: 0.25,
+                y1: 0.1,
+                x2: 0.25,
+                y2: 1.0,
+            },
+        ];
+        for curve in &curves {
+            assert!(curve.apply(0.0).abs() < 1e-5);
+            assert!((curve.apply(1.0) - 1.0).abs() < 1e-5);
+            let mut last = 0.0;
+            for i in 1..=20 {
+                let v = curve.apply(i as f32 / 20.0);
+                assert!(v >= last);
+                last = v;
+            }
+        }
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+        assert_eq!(Easing::Hold.apply(0.99), 0.0);
+
+        // A Bézier with control points on the diagonal is linear
+        let linear = Easing::CubicBezier {
+            x1: 0.3,
+            y1: 0.3,
+            x2: 0.7,
+            y2: 0.7,
+        };
+        assert!((linear.apply(0.4) - 0.4).abs() < 1e-4);
+    }
+}
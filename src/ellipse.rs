@@ -5,10 +5,18 @@ use crate::point2f::Point2f;
 
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::d2d1::D2D1_ELLIPSE;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D as d2d;
 
 /// Contains the center point, x-radius, and y-radius of an ellipse.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(C)]
 pub struct Ellipse {
     /// The center point of the ellipse.
@@ -30,6 +38,13 @@ impl Ellipse {
         }
     }
 
+    /// Determines if either radius of the ellipse is zero, or NaN, which
+    /// leaves it enclosing no area.
+    #[inline]
+    pub fn is_degenerate(&self) -> bool {
+        !(self.radius_x.abs() > 0.0 && self.radius_y.abs() > 0.0)
+    }
+
     /// Checks if an ellipse contains a point
     #[inline]
     pub fn contains_point(&self, point: impl Into<Point2f>) -> bool {
@@ -62,6 +77,28 @@ impl Ellipse {
             false
         }
     }
+
+    /// Formats the `cx`, `cy`, `rx` and `ry` attributes of an SVG
+    /// `<ellipse>` element matching this ellipse.
+    pub fn to_svg_attributes(&self) -> String {
+        format!(
+            r#"cx="{}" cy="{}" rx="{}" ry="{}""#,
+            self.center.x,
+            self.center.y,
+            self.radius_x.abs(),
+            self.radius_y.abs()
+        )
+    }
+}
+
+#[cfg(feature = "cairo")]
+impl Ellipse {
+    /// Appends the ellipse to the current path of `cr` as a closed sub-path
+    /// of cubic bezier curves.
+    #[inline]
+    pub fn append_to_cairo(&self, cr: &cairo::Context) {
+        crate::path::Path::from(*self).append_to_cairo(cr);
+    }
 }
 
 impl<P> From<(P, f32, f32)> for Ellipse
@@ -74,6 +111,29 @@ where
     }
 }
 
+#[cfg(feature = "kurbo")]
+impl From<Ellipse> for kurbo::Ellipse {
+    #[inline]
+    fn from(e: Ellipse) -> kurbo::Ellipse {
+        kurbo::Ellipse::new(
+            e.center,
+            (f64::from(e.radius_x), f64::from(e.radius_y)),
+            0.0,
+        )
+    }
+}
+
+/// Ellipses here are axis-aligned, so the rotation of a kurbo ellipse is
+/// dropped and only its center and radii are kept.
+#[cfg(feature = "kurbo")]
+impl From<kurbo::Ellipse> for Ellipse {
+    #[inline]
+    fn from(e: kurbo::Ellipse) -> Ellipse {
+        let radii = e.radii();
+        Ellipse::new(e.center(), radii.x as f32, radii.y as f32)
+    }
+}
+
 #[cfg(all(windows, feature = "d2d"))]
 impl From<Ellipse> for D2D1_ELLIPSE {
     #[inline]
@@ -98,6 +158,30 @@ impl From<D2D1_ELLIPSE> for Ellipse {
     }
 }
 
+#[cfg(all(windows, feature = "windows"))]
+impl From<Ellipse> for d2d::D2D1_ELLIPSE {
+    #[inline]
+    fn from(e: Ellipse) -> d2d::D2D1_ELLIPSE {
+        d2d::D2D1_ELLIPSE {
+            point: e.center.into(),
+            radiusX: e.radius_x,
+            radiusY: e.radius_y,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<d2d::D2D1_ELLIPSE> for Ellipse {
+    #[inline]
+    fn from(e: d2d::D2D1_ELLIPSE) -> Ellipse {
+        Ellipse {
+            center: e.point.into(),
+            radius_x: e.radiusX,
+            radius_y: e.radiusY,
+        }
+    }
+}
+
 #[cfg(all(test, windows, feature = "d2d"))]
 #[test]
 fn ellipse_d2d_bin_compat() {
@@ -116,3 +200,22 @@ fn ellipse_d2d_bin_compat() {
     assert!(ptr_eq(&ellipse.radius_y, &d2d.radiusY));
     assert_eq!(size_of_val(&ellipse), size_of_val(d2d));
 }
+
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn ellipse_windows_bin_compat() {
+    use std::mem::size_of_val;
+
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
+    }
+
+    let ellipse = Ellipse::new((0.0, 0.0), 1.0, 0.5);
+    let d2d = unsafe { &*((&ellipse) as *const _ as *const d2d::D2D1_ELLIPSE) };
+
+    assert!(ptr_eq(&ellipse.center.x, &d2d.point.x));
+    assert!(ptr_eq(&ellipse.center.y, &d2d.point.y));
+    assert!(ptr_eq(&ellipse.radius_x, &d2d.radiusX));
+    assert!(ptr_eq(&ellipse.radius_y, &d2d.radiusY));
+    assert_eq!(size_of_val(&ellipse), size_of_val(d2d));
+}
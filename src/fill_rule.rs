@@ -0,0 +1,84 @@
+//! Rules for deciding which areas enclosed by a shape's outline are filled.
+
+#[cfg(all(windows, feature = "d2d"))]
+use winapi::um::d2d1::D2D1_FILL_MODE;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D::Common as d2d_common;
+
+/// Decides which areas enclosed by a shape's outline are considered inside
+/// it, based on the number of times the outline winds around each point.
+///
+/// The discriminants match Direct2D's `D2D1_FILL_MODE`, where
+/// `D2D1_FILL_MODE_ALTERNATE` is even-odd and `D2D1_FILL_MODE_WINDING` is
+/// nonzero.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[repr(u32)]
+pub enum FillRule {
+    /// Points are inside when a ray from them crosses the outline an odd
+    /// number of times, so overlapping areas alternate between filled and
+    /// empty.
+    EvenOdd = 0,
+    /// Points are inside when the outline winds around them at all, in
+    /// either direction.
+    NonZero = 1,
+}
+
+impl FillRule {
+    /// Determines if a point with the given winding number is inside the
+    /// shape under this rule.
+    #[inline]
+    pub fn is_inside(self, winding_number: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding_number % 2 != 0,
+            FillRule::NonZero => winding_number != 0,
+        }
+    }
+
+    /// Converts the rule to the equivalent Direct2D fill mode, e.g. for
+    /// `ID2D1GeometrySink::SetFillMode`.
+    #[cfg(all(windows, feature = "d2d"))]
+    #[inline]
+    pub fn to_d2d(self) -> D2D1_FILL_MODE {
+        self as D2D1_FILL_MODE
+    }
+}
+
+impl Default for FillRule {
+    #[inline]
+    fn default() -> Self {
+        FillRule::EvenOdd
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<FillRule> for d2d_common::D2D1_FILL_MODE {
+    #[inline]
+    fn from(rule: FillRule) -> d2d_common::D2D1_FILL_MODE {
+        d2d_common::D2D1_FILL_MODE(rule as i32)
+    }
+}
+
+#[cfg(all(test, windows, feature = "d2d"))]
+#[test]
+fn winapi_fill_modes() {
+    use winapi::um::d2d1::{D2D1_FILL_MODE_ALTERNATE, D2D1_FILL_MODE_WINDING};
+
+    assert_eq!(FillRule::EvenOdd.to_d2d(), D2D1_FILL_MODE_ALTERNATE);
+    assert_eq!(FillRule::NonZero.to_d2d(), D2D1_FILL_MODE_WINDING);
+}
+
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn windows_fill_modes() {
+    let alternate: d2d_common::D2D1_FILL_MODE = FillRule::EvenOdd.into();
+    let winding: d2d_common::D2D1_FILL_MODE = FillRule::NonZero.into();
+    assert_eq!(alternate, d2d_common::D2D1_FILL_MODE_ALTERNATE);
+    assert_eq!(winding, d2d_common::D2D1_FILL_MODE_WINDING);
+}
@@ -0,0 +1,504 @@
+//! Curve fitting for converting sampled input (e.g. mouse or pen samples)
+//! into smooth, editable curves.
+
+use crate::arc_segment::{ArcSegment, ArcSize, SweepDirection};
+use crate::bezier_segment::BezierSegment;
+use crate::point2f::Point2f;
+use crate::sizef::Sizef;
+use crate::vector2f::Vector2f;
+
+/// The number of Newton-Raphson reparameterization passes attempted before
+/// a range is split in two.
+const MAX_REPARAMETERIZATIONS: usize = 4;
+
+/// How many times a bezier segment may be halved while searching for biarcs
+/// which fit it within tolerance.
+const MAX_BIARC_DEPTH: u32 = 12;
+
+/// Fits a chain of cubic bezier segments to a sequence of points using
+/// Schneider's algorithm ("An Algorithm for Automatically Fitting Digitized
+/// Curves", Graphics Gems, 1990).
+///
+/// The returned segments form a continuous chain starting at `points[0]`,
+/// which is the implicit start point of the first segment. Every input point
+/// will be within `tolerance` distance of the resulting curve. Consecutive
+/// duplicate points are ignored, and fewer than 2 distinct points produces
+/// an empty chain.
+pub fn fit_cubic(points: &[Point2f], tolerance: f32) -> Vec<BezierSegment> {
+    let mut pts: Vec<Point2f> = Vec::with_capacity(points.len());
+    for &p in points {
+        if pts.last() != Some(&p) {
+            pts.push(p);
+        }
+    }
+
+    let mut result = Vec::new();
+    if pts.len() < 2 {
+        return result;
+    }
+
+    let last = pts.len() - 1;
+    let left_tangent = (pts[1] - pts[0]).normalized();
+    let right_tangent = (pts[last - 1] - pts[last]).normalized();
+    fit_range(
+        &pts,
+        left_tangent,
+        right_tangent,
+        tolerance * tolerance,
+        &mut result,
+    );
+    result
+}
+
+fn fit_range(
+    pts: &[Point2f],
+    left_tangent: Vector2f,
+    right_tangent: Vector2f,
+    error_sq: f32,
+    result: &mut Vec<BezierSegment>,
+) {
+    let first = pts[0];
+    let last = pts[pts.len() - 1];
+
+    if pts.len() == 2 {
+        let dist = (last - first).len() / 3.0;
+        result.push(BezierSegment::new(
+            first + left_tangent * dist,
+            last + right_tangent * dist,
+            last,
+        ));
+        return;
+    }
+
+    let mut u = chord_length_parameterize(pts);
+    let mut bez = generate_bezier(pts, &u, left_tangent, right_tangent);
+    let (error, mut split) = max_error(pts, &bez, &u);
+    if error < error_sq {
+        result.push(bez);
+        return;
+    }
+
+    // If the error isn't too large, try to improve the parameterization
+    // before resorting to splitting the range.
+    if error < error_sq * 4.0 {
+        for _ in 0..MAX_REPARAMETERIZATIONS {
+            u = reparameterize(pts, &u, &bez);
+            bez = generate_bezier(pts, &u, left_tangent, right_tangent);
+            let (err, idx) = max_error(pts, &bez, &u);
+            if err < error_sq {
+                result.push(bez);
+                return;
+            }
+            split = idx;
+        }
+    }
+
+    let mut center = (pts[split - 1] - pts[split + 1]).normalized();
+    if !center.x.is_finite() || !center.y.is_finite() {
+        center = (pts[split - 1] - pts[split]).normalized();
+    }
+    fit_range(&pts[..=split], left_tangent, center, error_sq, result);
+    fit_range(&pts[split..], -center, right_tangent, error_sq, result);
+}
+
+/// Least-squares fit of the inner control points along the fixed end
+/// tangents for the given parameterization.
+fn generate_bezier(
+    pts: &[Point2f],
+    u: &[f32],
+    left_tangent: Vector2f,
+    right_tangent: Vector2f,
+) -> BezierSegment {
+    let first = pts[0];
+    let last = pts[pts.len() - 1];
+
+    let mut c = [[0.0f32; 2]; 2];
+    let mut x = [0.0f32; 2];
+    for (&p, &t) in pts.iter().zip(u) {
+        let ti = 1.0 - t;
+        let (b0, b1, b2, b3) = (ti * ti * ti, 3.0 * ti * ti * t, 3.0 * ti * t * t, t * t * t);
+        let a0 = left_tangent * b1;
+        let a1 = right_tangent * b2;
+
+        c[0][0] += a0.dot(a0);
+        c[0][1] += a0.dot(a1);
+        c[1][1] += a1.dot(a1);
+
+        let tmp = p.to_vector() - first.to_vector() * (b0 + b1) - last.to_vector() * (b2 + b3);
+        x[0] += a0.dot(tmp);
+        x[1] += a1.dot(tmp);
+    }
+    c[1][0] = c[0][1];
+
+    let det_c0_c1 = c[0][0] * c[1][1] - c[1][0] * c[0][1];
+    let det_c0_x = c[0][0] * x[1] - c[1][0] * x[0];
+    let det_x_c1 = x[0] * c[1][1] - x[1] * c[0][1];
+    let (alpha_l, alpha_r) = if det_c0_c1 == 0.0 {
+        (0.0, 0.0)
+    } else {
+        (det_x_c1 / det_c0_c1, det_c0_x / det_c0_c1)
+    };
+
+    // Degenerate or backwards solutions fall back to the Wu/Barsky heuristic
+    // of placing the control points a third of the way along the chord.
+    let seg_length = (last - first).len();
+    let epsilon = 1e-6 * seg_length;
+    let (alpha_l, alpha_r) = if alpha_l < epsilon || alpha_r < epsilon {
+        (seg_length / 3.0, seg_length / 3.0)
+    } else {
+        (alpha_l, alpha_r)
+    };
+
+    BezierSegment::new(
+        first + left_tangent * alpha_l,
+        last + right_tangent * alpha_r,
+        last,
+    )
+}
+
+fn chord_length_parameterize(pts: &[Point2f]) -> Vec<f32> {
+    let mut u = Vec::with_capacity(pts.len());
+    u.push(0.0);
+    for i in 1..pts.len() {
+        let prev = u[i - 1];
+        u.push(prev + (pts[i] - pts[i - 1]).len());
+    }
+    let total = u[u.len() - 1];
+    for t in &mut u {
+        *t /= total;
+    }
+    u
+}
+
+/// Improves the parameterization with a single Newton-Raphson step per point
+/// towards the closest point on the curve.
+fn reparameterize(pts: &[Point2f], u: &[f32], bez: &BezierSegment) -> Vec<f32> {
+    let start = pts[0];
+    pts.iter()
+        .zip(u)
+        .map(|(&p, &t)| {
+            let diff = bez.point_at(start, t) - p;
+            let d1 = bez.derivative_at(start, t);
+            let d2 = bez.second_derivative_at(start, t);
+            let numerator = diff.dot(d1);
+            let denominator = d1.dot(d1) + diff.dot(d2);
+            if denominator == 0.0 {
+                t
+            } else {
+                t - numerator / denominator
+            }
+        })
+        .collect()
+}
+
+/// Finds the squared distance and index of the input point furthest from
+/// the fitted curve. The index is never one of the end points, so that it
+/// is always valid to split the range there.
+fn max_error(pts: &[Point2f], bez: &BezierSegment, u: &[f32]) -> (f32, usize) {
+    let start = pts[0];
+    let mut split = pts.len() / 2;
+    let mut max_dist = 0.0;
+    for i in 1..pts.len() - 1 {
+        let dist = (bez.point_at(start, u[i]) - pts[i]).len_squared();
+        if dist >= max_dist {
+            max_dist = dist;
+            split = i;
+        }
+    }
+    (max_dist, split)
+}
+
+/// Fits a chain of circular arcs to a sequence of points, greedily extending
+/// each arc over as many points as possible while every point remains within
+/// `tolerance` of it.
+///
+/// The returned arcs form a continuous chain starting at `points[0]`. Runs of
+/// collinear points are represented by arcs with a zero radius, which are
+/// drawn as straight lines. Fewer than 2 distinct points produces an empty
+/// chain.
+pub fn fit_arcs(points: &[Point2f], tolerance: f32) -> Vec<ArcSegment> {
+    let mut pts: Vec<Point2f> = Vec::with_capacity(points.len());
+    for &p in points {
+        if pts.last() != Some(&p) {
+            pts.push(p);
+        }
+    }
+
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i + 1 < pts.len() {
+        let mut end = i + 1;
+        let mut best = line_arc(pts[end]);
+        for j in i + 2..pts.len() {
+            match arc_through(&pts[i..=j], tolerance) {
+                Some(arc) => {
+                    end = j;
+                    best = arc;
+                }
+                None => break,
+            }
+        }
+        result.push(best);
+        i = end;
+    }
+    result
+}
+
+/// Constructs a biarc: a pair of circular arcs which leave `p0` in the
+/// direction of `t0`, meet with a common tangent, and arrive at `p1` in the
+/// direction of `t1`. The tangents are normalized internally.
+///
+/// Returns `None` when there is no unique biarc, e.g. when the tangents are
+/// parallel and perpendicular to the chord between the points.
+pub fn biarc(
+    p0: impl Into<Point2f>,
+    t0: impl Into<Vector2f>,
+    p1: impl Into<Point2f>,
+    t1: impl Into<Vector2f>,
+) -> Option<(ArcSegment, ArcSegment)> {
+    let (p0, p1): (Point2f, Point2f) = (p0.into(), p1.into());
+    let t0 = t0.into().normalized();
+    let t1 = t1.into().normalized();
+    let v = p1 - p0;
+    let t = t0 + t1;
+    let v_dot_t = v.dot(t);
+    let denom = 2.0 * (1.0 - t0.dot(t1));
+
+    // Solve for equal tangent lengths on both sides of the joint
+    let d = if denom.abs() < 1e-6 {
+        let v_dot_t1 = v.dot(t1);
+        if v_dot_t1.abs() < 1e-6 {
+            return None;
+        }
+        v.dot(v) / (4.0 * v_dot_t1)
+    } else {
+        (-v_dot_t + (v_dot_t * v_dot_t + denom * v.dot(v)).sqrt()) / denom
+    };
+    if !d.is_finite() || d <= 0.0 {
+        return None;
+    }
+
+    let joint = (p0.to_vector() + p1.to_vector() + d * (t0 - t1)) / 2.0;
+    let joint = joint.to_point();
+    let first = arc_from_tangent(p0, t0, joint);
+    let second = reversed(p1, arc_from_tangent(p1, -t1, joint));
+    Some((first, second))
+}
+
+/// Recursively approximates a bezier segment (without inflections) with
+/// biarcs, halving it until each biarc fits within the tolerance.
+pub(crate) fn bezier_biarcs(
+    start: Point2f,
+    bez: &BezierSegment,
+    tolerance: f32,
+    depth: u32,
+    result: &mut Vec<ArcSegment>,
+) {
+    let (t0, t1) = end_tangents(start, bez);
+    let arcs = biarc(start, t0, bez.p3, t1);
+
+    if let Some((a1, a2)) = arcs {
+        let fits = depth >= MAX_BIARC_DEPTH
+            || (1..8).all(|i| {
+                let p = bez.point_at(start, i as f32 / 8.0);
+                arc_distance(start, &a1, p).min(arc_distance(a1.point, &a2, p)) <= tolerance
+            });
+        if fits {
+            result.push(a1);
+            result.push(a2);
+            return;
+        }
+    } else if depth >= MAX_BIARC_DEPTH {
+        result.push(line_arc(bez.p3));
+        return;
+    }
+
+    let (head, tail) = bez.split_at(start, 0.5);
+    bezier_biarcs(start, &head, tolerance, depth + 1, result);
+    bezier_biarcs(head.p3, &tail, tolerance, depth + 1, result);
+}
+
+/// Tries to find a single arc through all of the points, within tolerance.
+fn arc_through(pts: &[Point2f], tolerance: f32) -> Option<ArcSegment> {
+    let first = pts[0];
+    let last = pts[pts.len() - 1];
+    let arc = ArcSegment::from_three_points(first, pts[pts.len() / 2], last)
+        .unwrap_or_else(|| line_arc(last));
+
+    if pts[1..pts.len() - 1]
+        .iter()
+        .all(|&p| arc_distance(first, &arc, p) <= tolerance)
+    {
+        Some(arc)
+    } else {
+        None
+    }
+}
+
+/// A zero-radius arc, which is drawn as a straight line to `point`.
+fn line_arc(point: Point2f) -> ArcSegment {
+    ArcSegment::new(
+        point,
+        Sizef::new(0.0, 0.0),
+        0.0,
+        SweepDirection::Clockwise,
+        ArcSize::Small,
+    )
+}
+
+/// The circular arc leaving `start` in the direction `tangent` which ends at
+/// `end`. Becomes a straight line when `end` lies along the tangent.
+fn arc_from_tangent(start: Point2f, tangent: Vector2f, end: Point2f) -> ArcSegment {
+    let chord = end - start;
+    let normal = Vector2f::new(-tangent.y, tangent.x);
+    let n_dot_chord = normal.dot(chord);
+    if n_dot_chord.abs() <= chord.len() * 1e-6 {
+        return line_arc(end);
+    }
+
+    let radius = (chord.len_squared() / (2.0 * n_dot_chord)).abs();
+    ArcSegment {
+        point: end,
+        size: Sizef::new(radius, radius),
+        rotation_angle: 0.0,
+        sweep_direction: (tangent.cross(chord) > 0.0).into(),
+        arc_size: (tangent.dot(chord) < 0.0).into(),
+    }
+}
+
+/// Reverses an arc segment which begins at `start`, so that it instead
+/// begins at its current end point and ends at `start`.
+fn reversed(start: Point2f, arc: ArcSegment) -> ArcSegment {
+    ArcSegment {
+        point: start,
+        sweep_direction: match arc.sweep_direction {
+            SweepDirection::Clockwise => SweepDirection::CounterClockwise,
+            SweepDirection::CounterClockwise => SweepDirection::Clockwise,
+        },
+        ..arc
+    }
+}
+
+/// Distance from a point to a circular arc segment (or the line segment it
+/// degenerates to).
+fn arc_distance(start: Point2f, arc: &ArcSegment, p: Point2f) -> f32 {
+    match arc.to_center_form(start) {
+        Some(circle) => {
+            let offset = p - circle.center;
+            if circle.sweeps_over(offset.y.atan2(offset.x) - circle.rotation) {
+                (offset.len() - circle.radii.x).abs()
+            } else {
+                (p - start).len().min((p - arc.point).len())
+            }
+        }
+        None => {
+            let line = arc.point - start;
+            let t = ((p - start).dot(line) / line.len_squared()).clamp(0.0, 1.0);
+            if t.is_finite() {
+                (p - (start + line * t)).len()
+            } else {
+                (p - start).len()
+            }
+        }
+    }
+}
+
+/// The outgoing and incoming tangent directions at the ends of a bezier,
+/// falling back to further control points when the derivative vanishes.
+fn end_tangents(start: Point2f, bez: &BezierSegment) -> (Vector2f, Vector2f) {
+    let pick = |candidates: [Vector2f; 3]| {
+        candidates
+            .iter()
+            .cloned()
+            .find(|v| v.len_squared() > 0.0)
+            .unwrap_or(candidates[2])
+    };
+    let t0 = pick([bez.p1 - start, bez.p2 - start, bez.p3 - start]);
+    let t1 = pick([bez.p3 - bez.p2, bez.p3 - bez.p1, bez.p3 - start]);
+    (t0, t1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier_segment::BezierSegment;
+    use crate::fit::{fit_arcs, fit_cubic};
+    use crate::point2f::Point2f;
+
+    #[test]
+    fn fit_sine_wave() {
+        let points: Vec<Point2f> = (0..=100)
+            .map(|i| {
+                let x = i as f32 / 10.0;
+                Point2f::new(x * 10.0, x.sin() * 20.0)
+            })
+            .collect();
+
+        let tolerance = 0.5;
+        let curve = fit_cubic(&points, tolerance);
+        assert!(!curve.is_empty());
+        assert_eq!(curve.last().unwrap().p3, points[100]);
+
+        // Every sample should lie within tolerance of some part of the chain
+        for &p in &points {
+            let mut start = points[0];
+            let mut best = f32::INFINITY;
+            for seg in &curve {
+                for i in 0..=200 {
+                    let t = i as f32 / 200.0;
+                    best = best.min((seg.point_at(start, t) - p).len());
+                }
+                start = seg.p3;
+            }
+            assert!(best <= tolerance * 1.05, "{:?} was {} away", p, best);
+        }
+    }
+
+    #[test]
+    fn fit_degenerate() {
+        assert!(fit_cubic(&[], 1.0).is_empty());
+        assert!(fit_cubic(&[Point2f::new(1.0, 1.0); 3], 1.0).is_empty());
+
+        let line = fit_cubic(&[Point2f::new(0.0, 0.0), Point2f::new(3.0, 0.0)], 1.0);
+        assert_eq!(line.len(), 1);
+        assert!(line[0].p1.is_approx_eq((1.0, 0.0), 1e-6));
+        assert!(line[0].p2.is_approx_eq((2.0, 0.0), 1e-6));
+    }
+
+    #[test]
+    fn fit_arcs_to_circle_and_line() {
+        let circle: Vec<Point2f> = (0..=32)
+            .map(|i| {
+                let angle = i as f32 / 32.0 * std::f32::consts::PI;
+                Point2f::new(angle.cos() * 10.0, angle.sin() * 10.0)
+            })
+            .collect();
+        let arcs = fit_arcs(&circle, 0.01);
+        assert_eq!(arcs.len(), 1);
+        assert!((arcs[0].size.width - 10.0).abs() < 1e-3);
+        assert_eq!(arcs[0].point, circle[32]);
+
+        let line: Vec<Point2f> = (0..10).map(|i| Point2f::new(i as f32, 0.0)).collect();
+        let arcs = fit_arcs(&line, 0.01);
+        assert_eq!(arcs.len(), 1);
+        assert_eq!(arcs[0].size.width, 0.0);
+    }
+
+    #[test]
+    fn biarcs_follow_curve() {
+        let start = Point2f::new(0.0, 0.0);
+        let bez = BezierSegment::new((10.0, 20.0), (30.0, -10.0), (40.0, 10.0));
+        let tolerance = 0.1;
+        let arcs = bez.to_biarcs(start, tolerance);
+        assert!(arcs.len() >= 4);
+        assert_eq!(arcs.last().unwrap().point, bez.p3);
+
+        // Joints between arcs must be tangent-continuous
+        let mut p0 = start;
+        for w in arcs.windows(2) {
+            let incoming = w[0].tangent_at(p0, 1.0).normalized();
+            let outgoing = w[1].tangent_at(w[0].point, 0.0).normalized();
+            assert!(incoming.is_approx_eq(outgoing, 1e-2));
+            p0 = w[0].point;
+        }
+    }
+}
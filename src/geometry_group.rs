@@ -0,0 +1,259 @@
+//! A compound shape made of several geometries filled together, mirroring
+//! Direct2D's `ID2D1GeometryGroup`.
+
+use crate::ellipse::Ellipse;
+use crate::fill_rule::FillRule;
+use crate::matrix3x2f::Matrix3x2f;
+use crate::oriented_ellipse::OrientedEllipse;
+use crate::oriented_rect::OrientedRect;
+use crate::path::Path;
+use crate::point2f::Point2f;
+use crate::polygon::Polygon;
+use crate::rectf::Rectf;
+use crate::rounded_rect::RoundedRect;
+use crate::transformable::Transformable;
+
+/// One of the shapes which may be part of a `GeometryGroup`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Geometry {
+    /// An axis-aligned rectangle.
+    Rect(Rectf),
+    /// An axis-aligned rectangle with rounded corners.
+    RoundedRect(RoundedRect),
+    /// An axis-aligned ellipse.
+    Ellipse(Ellipse),
+    /// An arbitrary path.
+    Path(Path),
+    /// A polygon with straight edges.
+    Polygon(Polygon),
+}
+
+impl Geometry {
+    /// The tight axis-aligned bounding box of the geometry.
+    #[inline]
+    pub fn bounds(&self) -> Rectf {
+        match *self {
+            Geometry::Rect(ref rect) => rect.normalized(),
+            Geometry::RoundedRect(ref rr) => rr.rect.normalized(),
+            Geometry::Ellipse(ref e) => {
+                Rectf::from_center_half_extent(e.center, [e.radius_x.abs(), e.radius_y.abs()])
+            }
+            Geometry::Path(ref path) => path.bounds(),
+            Geometry::Polygon(ref polygon) => polygon.bounds(),
+        }
+    }
+
+    /// The number of times the outline of the geometry winds around `point`.
+    /// Rectangles, rounded rectangles and ellipses wind once clockwise around
+    /// the points they contain. Curves in paths are flattened to within
+    /// `tolerance`.
+    #[inline]
+    pub fn winding_number(&self, point: impl Into<Point2f>, tolerance: f32) -> i32 {
+        let point = point.into();
+        match *self {
+            Geometry::Rect(ref rect) => rect.normalized().contains_point(point) as i32,
+            Geometry::RoundedRect(ref rr) => RoundedRect {
+                rect: rr.rect.normalized(),
+                ..*rr
+            }
+            .contains_point(point) as i32,
+            Geometry::Ellipse(ref e) => e.contains_point(point) as i32,
+            Geometry::Path(ref path) => path.winding_number(point, tolerance),
+            Geometry::Polygon(ref polygon) => polygon.winding_number(point),
+        }
+    }
+
+    /// Applies an affine transformation to the geometry, producing what
+    /// `Transformable` does for the shape it holds. Oriented rectangles and
+    /// ellipses are stored as `Rect` and `Ellipse` while they are still
+    /// axis-aligned, and as a polygon or path otherwise.
+    #[inline]
+    pub fn transformed(&self, matrix: &Matrix3x2f) -> Geometry {
+        match *self {
+            Geometry::Rect(ref rect) => Transformable::transformed(rect, matrix).into(),
+            Geometry::RoundedRect(ref rr) => Transformable::transformed(rr, matrix).into(),
+            Geometry::Ellipse(ref e) => Transformable::transformed(e, matrix).into(),
+            Geometry::Path(ref path) => Geometry::Path(path.transformed(matrix)),
+            Geometry::Polygon(ref polygon) => Geometry::Polygon(polygon.transformed(matrix)),
+        }
+    }
+}
+
+impl From<Rectf> for Geometry {
+    #[inline]
+    fn from(rect: Rectf) -> Geometry {
+        Geometry::Rect(rect)
+    }
+}
+
+impl From<RoundedRect> for Geometry {
+    #[inline]
+    fn from(rr: RoundedRect) -> Geometry {
+        Geometry::RoundedRect(rr)
+    }
+}
+
+impl From<Ellipse> for Geometry {
+    #[inline]
+    fn from(e: Ellipse) -> Geometry {
+        Geometry::Ellipse(e)
+    }
+}
+
+impl From<OrientedRect> for Geometry {
+    #[inline]
+    fn from(rect: OrientedRect) -> Geometry {
+        match rect.to_rect() {
+            Some(rect) => Geometry::Rect(rect),
+            None => Geometry::Polygon(rect.to_polygon()),
+        }
+    }
+}
+
+impl From<OrientedEllipse> for Geometry {
+    #[inline]
+    fn from(e: OrientedEllipse) -> Geometry {
+        match e.to_ellipse() {
+            Some(e) => Geometry::Ellipse(e),
+            None => Geometry::Path(e.into()),
+        }
+    }
+}
+
+impl From<Path> for Geometry {
+    #[inline]
+    fn from(path: Path) -> Geometry {
+        Geometry::Path(path)
+    }
+}
+
+impl From<Polygon> for Geometry {
+    #[inline]
+    fn from(polygon: Polygon) -> Geometry {
+        Geometry::Polygon(polygon)
+    }
+}
+
+/// Several geometries which are filled together as a single shape. As with
+/// `ID2D1GeometryGroup`, the fill rule applies across the whole group, so
+/// overlapping geometries combine according to their total winding number
+/// rather than simply being unioned.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct GeometryGroup {
+    /// How the geometries combine to decide which areas are filled.
+    pub fill_rule: FillRule,
+    /// The geometries making up the group.
+    pub geometries: Vec<Geometry>,
+}
+
+impl GeometryGroup {
+    /// Constructs a group from its components.
+    #[inline]
+    pub fn new(fill_rule: FillRule, geometries: Vec<Geometry>) -> GeometryGroup {
+        GeometryGroup {
+            fill_rule,
+            geometries,
+        }
+    }
+
+    /// The tight axis-aligned bounding box of every geometry in the group. An
+    /// empty group has a zero-sized box at the origin.
+    #[inline]
+    pub fn bounds(&self) -> Rectf {
+        let mut bounds = self.geometries.iter().map(Geometry::bounds);
+        match bounds.next() {
+            Some(first) => bounds.fold(first, |acc, b| acc.combined_with(b)),
+            None => Rectf::default(),
+        }
+    }
+
+    /// Determines if the filled area of the group contains `point`. Curves in
+    /// paths are flattened to within `tolerance`.
+    #[inline]
+    pub fn contains_point(&self, point: impl Into<Point2f>, tolerance: f32) -> bool {
+        let point = point.into();
+        let winding = self
+            .geometries
+            .iter()
+            .map(|g| g.winding_number(point, tolerance))
+            .sum();
+        self.fill_rule.is_inside(winding)
+    }
+
+    /// Applies an affine transformation to every geometry in the group. See
+    /// `Geometry::transformed` for how each kind of geometry is affected.
+    #[inline]
+    pub fn transformed(&self, matrix: &Matrix3x2f) -> GeometryGroup {
+        GeometryGroup {
+            fill_rule: self.fill_rule,
+            geometries: self
+                .geometries
+                .iter()
+                .map(|g| g.transformed(matrix))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ellipse::Ellipse;
+    use crate::fill_rule::FillRule;
+    use crate::geometry_group::{Geometry, GeometryGroup};
+    use crate::matrix3x2f::Matrix3x2f;
+    use crate::rectf::Rectf;
+    use crate::rounded_rect::RoundedRect;
+
+    #[test]
+    fn combined_fill() {
+        let group = |fill_rule| {
+            GeometryGroup::new(
+                fill_rule,
+                vec![
+                    Rectf::new(0.0, 0.0, 10.0, 10.0).into(),
+                    Ellipse::new((10.0, 5.0), 4.0, 4.0).into(),
+                    RoundedRect::new(Rectf::new(20.0, 0.0, 30.0, 10.0), 3.0, 3.0).into(),
+                ],
+            )
+        };
+
+        let even_odd = group(FillRule::EvenOdd);
+        assert_eq!(even_odd.bounds(), Rectf::new(0.0, 0.0, 30.0, 10.0));
+        assert!(even_odd.contains_point((2.0, 5.0), 0.01));
+        assert!(!even_odd.contains_point((9.0, 5.0), 0.01));
+        assert!(even_odd.contains_point((12.0, 5.0), 0.01));
+        assert!(!even_odd.contains_point((20.2, 0.2), 0.01));
+        assert!(group(FillRule::NonZero).contains_point((9.0, 5.0), 0.01));
+
+        // Translation keeps the rectangle and ellipse, but the rounded
+        // rectangle becomes a path, which must keep the same fill
+        let moved = even_odd.transformed(&Matrix3x2f::translation([0.0, 100.0]));
+        assert!(matches!(moved.geometries[0], Geometry::Rect(_)));
+        assert!(matches!(moved.geometries[1], Geometry::Ellipse(_)));
+        assert!(matches!(moved.geometries[2], Geometry::Path(_)));
+        assert!(moved.contains_point((2.0, 105.0), 0.01));
+        assert!(!moved.contains_point((9.0, 105.0), 0.01));
+        assert!(moved.contains_point((25.0, 105.0), 0.01));
+        assert!(!moved.contains_point((20.2, 100.2), 0.01));
+        let bounds = moved.bounds();
+        assert!((bounds.bottom - 110.0).abs() < 1e-4 && (bounds.right - 30.0).abs() < 1e-4);
+
+        let rotated = even_odd.transformed(&Matrix3x2f::rotation(0.5, (0.0, 0.0)));
+        assert!(matches!(rotated.geometries[0], Geometry::Polygon(_)));
+        assert!(matches!(rotated.geometries[1], Geometry::Ellipse(_)));
+    }
+}
@@ -0,0 +1,331 @@
+//! Linear and radial color gradients, which can be evaluated on the CPU or
+//! handed to Direct2D as brush properties.
+
+use crate::color::Color;
+use crate::point2f::Point2f;
+use crate::vector2f::Vector2f;
+
+#[cfg(all(windows, feature = "d2d"))]
+use winapi::um::d2d1::{
+    D2D1_GRADIENT_STOP, D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES,
+    D2D1_RADIAL_GRADIENT_BRUSH_PROPERTIES,
+};
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D as d2d;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D::Common as d2d_common;
+
+/// A color at a position along a gradient.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[repr(C)]
+pub struct GradientStop {
+    /// How far along the gradient the color is, from 0 at its start to 1 at
+    /// its end.
+    pub position: f32,
+    /// The color at `position`.
+    pub color: Color,
+}
+
+impl GradientStop {
+    /// Constructs a stop from its components.
+    #[inline]
+    pub fn new(position: f32, color: impl Into<Color>) -> GradientStop {
+        GradientStop {
+            position,
+            color: color.into(),
+        }
+    }
+}
+
+/// A gradient whose colors change along the line from `start` to `end`, and
+/// stay the same across it.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct LinearGradient {
+    /// Where the gradient is at position 0.
+    pub start: Point2f,
+    /// Where the gradient is at position 1.
+    pub end: Point2f,
+    /// The colors of the gradient, sorted by position.
+    pub stops: Vec<GradientStop>,
+}
+
+impl LinearGradient {
+    /// Constructs a gradient from its components. The stops must be sorted
+    /// by position.
+    #[inline]
+    pub fn new(
+        start: impl Into<Point2f>,
+        end: impl Into<Point2f>,
+        stops: impl Into<Vec<GradientStop>>,
+    ) -> LinearGradient {
+        LinearGradient {
+            start: start.into(),
+            end: end.into(),
+            stops: stops.into(),
+        }
+    }
+
+    /// The position along the gradient of `point`, before it is clamped
+    /// between 0 and 1.
+    #[inline]
+    pub fn position_at(&self, point: impl Into<Point2f>) -> f32 {
+        let d = self.end - self.start;
+        let len_sq = d.len_squared();
+        if len_sq == 0.0 {
+            0.0
+        } else {
+            (point.into() - self.start).dot(d) / len_sq
+        }
+    }
+
+    /// The color of the gradient at `point`. Points beyond either end take
+    /// the color of the nearest stop, as with Direct2D's clamp extend mode.
+    #[inline]
+    pub fn sample_at(&self, point: impl Into<Point2f>) -> Color {
+        sample_stops(&self.stops, self.position_at(point))
+    }
+}
+
+/// A gradient whose colors change outward from an origin to the edge of an
+/// ellipse. Position 0 is at the gradient origin, `center + offset`, and
+/// position 1 is on the ellipse.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RadialGradient {
+    /// The center of the ellipse.
+    pub center: Point2f,
+    /// The offset of the gradient origin from the center. The origin should
+    /// be inside the ellipse.
+    pub offset: Vector2f,
+    /// The horizontal and vertical radii of the ellipse.
+    pub radii: Vector2f,
+    /// The colors of the gradient, sorted by position.
+    pub stops: Vec<GradientStop>,
+}
+
+impl RadialGradient {
+    /// Constructs a gradient from its components. The stops must be sorted
+    /// by position.
+    #[inline]
+    pub fn new(
+        center: impl Into<Point2f>,
+        offset: impl Into<Vector2f>,
+        radii: impl Into<Vector2f>,
+        stops: impl Into<Vec<GradientStop>>,
+    ) -> RadialGradient {
+        RadialGradient {
+            center: center.into(),
+            offset: offset.into(),
+            radii: radii.into(),
+            stops: stops.into(),
+        }
+    }
+
+    /// The position along the gradient of `point`, before it is clamped
+    /// between 0 and 1.
+    pub fn position_at(&self, point: impl Into<Point2f>) -> f32 {
+        if self.radii.x == 0.0 || self.radii.y == 0.0 {
+            return 1.0;
+        }
+
+        // In the space where the ellipse is a unit circle, find how far
+        // along the ray from the origin through the point the edge is
+        let scale = |v: Vector2f| Vector2f::new(v.x / self.radii.x, v.y / self.radii.y);
+        let origin = scale(self.offset);
+        let d = scale(point.into() - self.center) - origin;
+        let len_sq = d.len_squared();
+        if len_sq == 0.0 {
+            return 0.0;
+        }
+        let od = origin.dot(d);
+        let discriminant = od * od - len_sq * (origin.len_squared() - 1.0);
+        if discriminant < 0.0 {
+            return 1.0;
+        }
+        let edge = (-od + discriminant.sqrt()) / len_sq;
+        if edge <= 0.0 {
+            1.0
+        } else {
+            1.0 / edge
+        }
+    }
+
+    /// The color of the gradient at `point`. Points beyond the ellipse take
+    /// the color of the last stop, as with Direct2D's clamp extend mode.
+    #[inline]
+    pub fn sample_at(&self, point: impl Into<Point2f>) -> Color {
+        sample_stops(&self.stops, self.position_at(point))
+    }
+}
+
+/// The color at `position` between the sorted `stops`.
+fn sample_stops(stops: &[GradientStop], position: f32) -> Color {
+    let next = stops.partition_point(|s| s.position <= position);
+    match (next.checked_sub(1).map(|i| &stops[i]), stops.get(next)) {
+        (Some(a), Some(b)) => a.color.lerp(
+            &b.color,
+            (position - a.position) / (b.position - a.position),
+        ),
+        (Some(stop), None) | (None, Some(stop)) => stop.color,
+        (None, None) => Color::new(0.0, 0.0, 0.0, 0.0),
+    }
+}
+
+#[cfg(all(windows, feature = "d2d"))]
+impl From<GradientStop> for D2D1_GRADIENT_STOP {
+    #[inline]
+    fn from(stop: GradientStop) -> D2D1_GRADIENT_STOP {
+        D2D1_GRADIENT_STOP {
+            position: stop.position,
+            color: stop.color.into(),
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "d2d"))]
+impl From<D2D1_GRADIENT_STOP> for GradientStop {
+    #[inline]
+    fn from(stop: D2D1_GRADIENT_STOP) -> GradientStop {
+        GradientStop {
+            position: stop.position,
+            color: stop.color.into(),
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "d2d"))]
+impl<'a> From<&'a LinearGradient> for D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES {
+    /// The stops are not part of the brush properties, and are instead
+    /// converted to a gradient stop collection separately.
+    #[inline]
+    fn from(gradient: &'a LinearGradient) -> D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES {
+        D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES {
+            startPoint: gradient.start.into(),
+            endPoint: gradient.end.into(),
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "d2d"))]
+impl<'a> From<&'a RadialGradient> for D2D1_RADIAL_GRADIENT_BRUSH_PROPERTIES {
+    /// The stops are not part of the brush properties, and are instead
+    /// converted to a gradient stop collection separately.
+    #[inline]
+    fn from(gradient: &'a RadialGradient) -> D2D1_RADIAL_GRADIENT_BRUSH_PROPERTIES {
+        D2D1_RADIAL_GRADIENT_BRUSH_PROPERTIES {
+            center: gradient.center.into(),
+            gradientOriginOffset: gradient.offset.to_point().into(),
+            radiusX: gradient.radii.x,
+            radiusY: gradient.radii.y,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<GradientStop> for d2d_common::D2D1_GRADIENT_STOP {
+    #[inline]
+    fn from(stop: GradientStop) -> d2d_common::D2D1_GRADIENT_STOP {
+        d2d_common::D2D1_GRADIENT_STOP {
+            position: stop.position,
+            color: stop.color.into(),
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<d2d_common::D2D1_GRADIENT_STOP> for GradientStop {
+    #[inline]
+    fn from(stop: d2d_common::D2D1_GRADIENT_STOP) -> GradientStop {
+        GradientStop {
+            position: stop.position,
+            color: stop.color.into(),
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl<'a> From<&'a LinearGradient> for d2d::D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES {
+    /// The stops are not part of the brush properties, and are instead
+    /// converted to a gradient stop collection separately.
+    #[inline]
+    fn from(gradient: &'a LinearGradient) -> d2d::D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES {
+        d2d::D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES {
+            startPoint: gradient.start.into(),
+            endPoint: gradient.end.into(),
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl<'a> From<&'a RadialGradient> for d2d::D2D1_RADIAL_GRADIENT_BRUSH_PROPERTIES {
+    /// The stops are not part of the brush properties, and are instead
+    /// converted to a gradient stop collection separately.
+    #[inline]
+    fn from(gradient: &'a RadialGradient) -> d2d::D2D1_RADIAL_GRADIENT_BRUSH_PROPERTIES {
+        d2d::D2D1_RADIAL_GRADIENT_BRUSH_PROPERTIES {
+            center: gradient.center.into(),
+            gradientOriginOffset: gradient.offset.to_point().into(),
+            radiusX: gradient.radii.x,
+            radiusY: gradient.radii.y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::Color;
+    use crate::gradient::{GradientStop, LinearGradient, RadialGradient};
+
+    fn approx(a: Color, b: Color) -> bool {
+        (a.r - b.r).abs() < 1e-4
+            && (a.g - b.g).abs() < 1e-4
+            && (a.b - b.b).abs() < 1e-4
+            && (a.a - b.a).abs() < 1e-4
+    }
+
+    #[test]
+    fn sampling() {
+        let (red, blue) = (
+            Color::new(1.0, 0.0, 0.0, 1.0),
+            Color::new(0.0, 0.0, 1.0, 1.0),
+        );
+        let stops = vec![GradientStop::new(0.25, red), GradientStop::new(0.75, blue)];
+
+        let linear = LinearGradient::new((0.0, 0.0), (100.0, 0.0), stops.clone());
+        assert!(approx(linear.sample_at((0.0, 30.0)), red));
+        assert!(approx(linear.sample_at((50.0, -8.0)), red.lerp(&blue, 0.5)));
+        assert!(approx(linear.sample_at((200.0, 0.0)), blue));
+
+        let radial = RadialGradient::new((0.0, 0.0), [0.0, 0.0], [100.0, 50.0], stops.clone());
+        assert_eq!(radial.position_at((50.0, 0.0)), 0.5);
+        assert_eq!(radial.position_at((0.0, 25.0)), 0.5);
+        assert!(approx(radial.sample_at((0.0, 0.0)), red));
+
+        // With the origin moved off center, position 1 is still on the edge
+        let shifted = RadialGradient::new((0.0, 0.0), [50.0, 0.0], [100.0, 100.0], stops);
+        assert_eq!(shifted.position_at((50.0, 0.0)), 0.0);
+        assert!((shifted.position_at((-100.0, 0.0)) - 1.0).abs() < 1e-5);
+        assert!((shifted.position_at((75.0, 0.0)) - 0.5).abs() < 1e-5);
+        assert!((shifted.position_at((-25.0, 0.0)) - 0.5).abs() < 1e-5);
+    }
+}
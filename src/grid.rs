@@ -0,0 +1,329 @@
+//! A uniform grid of rectangular cells, for tile maps, rulers and snapping.
+
+use crate::point2f::Point2f;
+use crate::point2i::Point2i;
+use crate::rectf::Rectf;
+use crate::sizef::Sizef;
+
+/// Divides the plane into equally sized cells, with the cell `(0, 0)` having
+/// its upper-left corner at `origin`. Cells are addressed by their column
+/// and row, counting up to the right and downward.
+///
+/// Cells contain their upper and left edges, so every point belongs to
+/// exactly one cell. The cell size must be positive in both dimensions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Grid {
+    /// The upper-left corner of the cell `(0, 0)`.
+    pub origin: Point2f,
+    /// The size of each cell.
+    pub cell_size: Sizef,
+}
+
+impl Grid {
+    /// Constructs a grid from its origin and the size of its cells.
+    #[inline]
+    pub fn new(origin: impl Into<Point2f>, cell_size: impl Into<Sizef>) -> Grid {
+        Grid {
+            origin: origin.into(),
+            cell_size: cell_size.into(),
+        }
+    }
+
+    /// The cell containing `point`.
+    #[inline]
+    pub fn cell_of(&self, point: impl Into<Point2f>) -> Point2i {
+        let (x, y) = self.cell_coords(point.into());
+        Point2i::new(x.floor() as i32, y.floor() as i32)
+    }
+
+    /// The area covered by `cell`.
+    #[inline]
+    pub fn cell_rect(&self, cell: impl Into<Point2i>) -> Rectf {
+        let cell = cell.into();
+        Rectf::new(
+            self.origin.x + cell.x as f32 * self.cell_size.width,
+            self.origin.y + cell.y as f32 * self.cell_size.height,
+            self.origin.x + (cell.x + 1) as f32 * self.cell_size.width,
+            self.origin.y + (cell.y + 1) as f32 * self.cell_size.height,
+        )
+    }
+
+    /// Iterates over the cells which overlap `rect`, row by row. Cells which
+    /// only touch the rectangle's right or bottom edge are left out, but a
+    /// rectangle with no area still overlaps the cell containing it.
+    #[inline]
+    pub fn cells_overlapping(&self, rect: impl Into<Rectf>) -> GridCells {
+        let rect = rect.into().normalized();
+        let (left, top) = self.cell_coords((rect.left, rect.top).into());
+        let (right, bottom) = self.cell_coords((rect.right, rect.bottom).into());
+        let (left, top) = (left.floor() as i32, top.floor() as i32);
+        GridCells {
+            left,
+            right: (right.ceil() as i32).max(left + 1),
+            x: left,
+            y: top,
+            bottom: (bottom.ceil() as i32).max(top + 1),
+        }
+    }
+
+    /// Iterates over every cell the line segment from `start` to `end` passes
+    /// through, in order from `start`, using the traversal of Amanatides and
+    /// Woo. Unlike a Bresenham line, cells the segment only clips a corner of
+    /// are included, so this is suited to picking and visibility checks.
+    #[inline]
+    pub fn traverse(&self, start: impl Into<Point2f>, end: impl Into<Point2f>) -> GridTraversal {
+        let (sx, sy) = self.cell_coords(start.into());
+        let (ex, ey) = self.cell_coords(end.into());
+        let cell = Point2i::new(sx.floor() as i32, sy.floor() as i32);
+        let end = Point2i::new(ex.floor() as i32, ey.floor() as i32);
+
+        // How far along the segment, as a fraction of its length, the first
+        // boundary is crossed on each axis, and the distance between them
+        let axis = |start: f32, end: f32| {
+            let d = end - start;
+            if d > 0.0 {
+                (1, (start.floor() + 1.0 - start) / d, 1.0 / d)
+            } else if d < 0.0 {
+                (-1, (start - start.floor()) / -d, -1.0 / d)
+            } else {
+                (0, f32::INFINITY, f32::INFINITY)
+            }
+        };
+        let (step_x, t_max_x, t_delta_x) = axis(sx, ex);
+        let (step_y, t_max_y, t_delta_y) = axis(sy, ey);
+
+        GridTraversal {
+            cell,
+            end,
+            step: Point2i::new(step_x, step_y),
+            t_max: (t_max_x, t_max_y),
+            t_delta: (t_delta_x, t_delta_y),
+            remaining: ((end.x - cell.x).abs() + (end.y - cell.y).abs()) as usize + 1,
+        }
+    }
+
+    /// Moves `point` to the nearest corner of a cell.
+    #[inline]
+    pub fn snap_point(&self, point: impl Into<Point2f>) -> Point2f {
+        let (x, y) = self.cell_coords(point.into());
+        Point2f::new(
+            self.origin.x + x.round() * self.cell_size.width,
+            self.origin.y + y.round() * self.cell_size.height,
+        )
+    }
+
+    /// Moves each edge of `rect` to the nearest grid line.
+    #[inline]
+    pub fn snap_rect(&self, rect: impl Into<Rectf>) -> Rectf {
+        let rect = rect.into();
+        let top_left = self.snap_point((rect.left, rect.top));
+        let bottom_right = self.snap_point((rect.right, rect.bottom));
+        Rectf::new(top_left.x, top_left.y, bottom_right.x, bottom_right.y)
+    }
+
+    /// Moves each edge of `rect` outward to the nearest grid line, giving
+    /// the smallest rectangle of whole cells containing it.
+    #[inline]
+    pub fn snap_rect_outward(&self, rect: impl Into<Rectf>) -> Rectf {
+        let rect = rect.into().normalized();
+        let (left, top) = self.cell_coords((rect.left, rect.top).into());
+        let (right, bottom) = self.cell_coords((rect.right, rect.bottom).into());
+        Rectf::new(
+            self.origin.x + left.floor() * self.cell_size.width,
+            self.origin.y + top.floor() * self.cell_size.height,
+            self.origin.x + right.ceil() * self.cell_size.width,
+            self.origin.y + bottom.ceil() * self.cell_size.height,
+        )
+    }
+
+    /// The position of `point` measured in cells from the origin.
+    #[inline]
+    fn cell_coords(&self, point: Point2f) -> (f32, f32) {
+        (
+            (point.x - self.origin.x) / self.cell_size.width,
+            (point.y - self.origin.y) / self.cell_size.height,
+        )
+    }
+}
+
+impl Default for Grid {
+    /// A grid of unit cells with its origin at `(0, 0)`.
+    #[inline]
+    fn default() -> Self {
+        Grid::new((0.0, 0.0), 1.0)
+    }
+}
+
+/// Iterates over a rectangular block of cells, row by row. Created by
+/// `Grid::cells_overlapping`.
+#[derive(Clone, Debug)]
+pub struct GridCells {
+    left: i32,
+    right: i32,
+    x: i32,
+    y: i32,
+    bottom: i32,
+}
+
+impl Iterator for GridCells {
+    type Item = Point2i;
+
+    #[inline]
+    fn next(&mut self) -> Option<Point2i> {
+        if self.y >= self.bottom {
+            return None;
+        }
+        let cell = Point2i::new(self.x, self.y);
+        self.x += 1;
+        if self.x >= self.right {
+            self.x = self.left;
+            self.y += 1;
+        }
+        Some(cell)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.y >= self.bottom {
+            0
+        } else {
+            let width = (self.right - self.left) as usize;
+            let rows = (self.bottom - self.y) as usize;
+            rows * width - (self.x - self.left) as usize
+        };
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for GridCells {}
+
+/// Iterates over the cells a line segment passes through, in order. Each
+/// cell shares an edge with the one before it. Created by `Grid::traverse`.
+#[derive(Clone, Debug)]
+pub struct GridTraversal {
+    cell: Point2i,
+    end: Point2i,
+    step: Point2i,
+    /// The fraction of the segment at which the next boundary on each axis
+    /// is crossed.
+    t_max: (f32, f32),
+    /// The fraction of the segment between boundaries on each axis.
+    t_delta: (f32, f32),
+    remaining: usize,
+}
+
+impl Iterator for GridTraversal {
+    type Item = Point2i;
+
+    #[inline]
+    fn next(&mut self) -> Option<Point2i> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let cell = self.cell;
+        if self.remaining > 0 {
+            // Rounding can disagree with the end cell about which boundary
+            // comes first, so never step past the end along either axis
+            let step_x = self.cell.y == self.end.y
+                || (self.cell.x != self.end.x && self.t_max.0 < self.t_max.1);
+            if step_x {
+                self.cell.x += self.step.x;
+                self.t_max.0 += self.t_delta.0;
+            } else {
+                self.cell.y += self.step.y;
+                self.t_max.1 += self.t_delta.1;
+            }
+        }
+        Some(cell)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for GridTraversal {}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::Grid;
+    use crate::point2i::Point2i;
+    use crate::rectf::Rectf;
+
+    #[test]
+    fn cells() {
+        let grid = Grid::new((10.0, 20.0), (16.0, 8.0));
+        assert_eq!(grid.cell_of((10.0, 20.0)), Point2i::new(0, 0));
+        assert_eq!(grid.cell_of((25.9, 27.9)), Point2i::new(0, 0));
+        assert_eq!(grid.cell_of((26.0, 28.0)), Point2i::new(1, 1));
+        assert_eq!(grid.cell_of((9.0, 19.0)), Point2i::new(-1, -1));
+        assert_eq!(grid.cell_rect((2, -1)), Rectf::new(42.0, 12.0, 58.0, 20.0));
+
+        let cells = grid.cells_overlapping(Rectf::new(20.0, 20.0, 42.0, 36.0));
+        assert_eq!(cells.len(), 4);
+        let cells: Vec<_> = cells.collect();
+        let expected: Vec<Point2i> =
+            vec![(0, 0).into(), (1, 0).into(), (0, 1).into(), (1, 1).into()];
+        assert_eq!(cells, expected);
+
+        let point = Rectf::new(30.0, 30.0, 30.0, 30.0);
+        let cells: Vec<_> = grid.cells_overlapping(point).collect();
+        assert_eq!(cells, vec![Point2i::new(1, 1)]);
+    }
+
+    #[test]
+    fn snapping() {
+        let grid = Grid::new((0.0, 0.0), 10.0);
+        assert_eq!(grid.snap_point((14.0, 16.0)), (10.0, 20.0).into());
+        assert_eq!(grid.snap_point((-14.0, -6.0)), (-10.0, -10.0).into());
+        assert_eq!(
+            grid.snap_rect(Rectf::new(3.0, 6.0, 24.0, 26.0)),
+            Rectf::new(0.0, 10.0, 20.0, 30.0)
+        );
+        assert_eq!(
+            grid.snap_rect_outward(Rectf::new(3.0, 6.0, 24.0, 20.0)),
+            Rectf::new(0.0, 0.0, 30.0, 20.0)
+        );
+    }
+
+    #[test]
+    fn traversal() {
+        let grid = Grid::new((0.0, 0.0), 10.0);
+        let cells: Vec<_> = grid.traverse((5.0, 5.0), (35.0, 15.0)).collect();
+        let expected: Vec<Point2i> = vec![
+            (0, 0).into(),
+            (1, 0).into(),
+            (1, 1).into(),
+            (2, 1).into(),
+            (3, 1).into(),
+        ];
+        assert_eq!(cells, expected);
+
+        // Every cell the segment clips is visited, including the ones a
+        // Bresenham line would skip
+        let cells: Vec<_> = grid.traverse((1.0, 1.0), (28.0, 19.0)).collect();
+        assert_eq!(cells.first(), Some(&Point2i::new(0, 0)));
+        assert_eq!(cells.last(), Some(&Point2i::new(2, 1)));
+        assert_eq!(cells.len(), 4);
+        for w in cells.windows(2) {
+            assert_eq!(w[0].manhattan_distance(w[1]), 1);
+        }
+
+        let back: Vec<_> = grid.traverse((-5.0, 25.0), (-5.0, -5.0)).collect();
+        assert_eq!(back.len(), 4);
+        assert!(back.iter().all(|c| c.x == -1));
+        assert_eq!(back[0].y, 2);
+        assert_eq!(back[3].y, -1);
+        assert_eq!(grid.traverse((3.0, 3.0), (4.0, 4.0)).count(), 1);
+    }
+}
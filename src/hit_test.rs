@@ -0,0 +1,330 @@
+//! Hit testing closed shapes against points, with an optional tolerance for
+//! forgiving pointer input.
+
+use crate::bounded::Bounded;
+use crate::capsule::Capsule;
+use crate::circle::Circle;
+use crate::ellipse::Ellipse;
+use crate::fill_rule::FillRule;
+use crate::geometry_group::{Geometry, GeometryGroup};
+use crate::oriented_ellipse::OrientedEllipse;
+use crate::oriented_rect::OrientedRect;
+use crate::path::Path;
+use crate::point2f::Point2f;
+use crate::polygon::Polygon;
+use crate::rectf::Rectf;
+use crate::rounded_rect::RoundedRect;
+use crate::sdf::Sdf;
+use crate::shape::{AnyShape, DEFAULT_FLATTENING_TOLERANCE};
+use crate::triangle::Triangle;
+
+/// Closed shapes which can be tested for containing a point. The trait is
+/// object safe.
+///
+/// Points on the edge of a shape count as hits. Paths, polygons and
+/// geometries are filled with the default (even-odd) fill rule, and curves
+/// are flattened with `DEFAULT_FLATTENING_TOLERANCE`.
+pub trait HitTest {
+    /// Determines if the filled area of the shape contains `point`.
+    fn hit(&self, point: Point2f) -> bool;
+
+    /// Determines if `point` is inside the shape or within `tolerance` of its
+    /// edge, as if the shape were expanded by `tolerance` in every direction.
+    /// A tolerance of zero or less is the same as `hit`.
+    ///
+    /// Ellipses and the corners of rounded rectangles use the approximate
+    /// distances of their `Sdf` implementations, which are accurate for
+    /// tolerances that are small next to the radii.
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool;
+}
+
+/// Expands a shape by its signed distance function.
+#[inline]
+fn sdf_hit(shape: &(impl HitTest + Sdf), point: Point2f, tolerance: f32) -> bool {
+    shape.hit(point) || (tolerance > 0.0 && shape.signed_distance(point) <= tolerance)
+}
+
+/// The distance from `point` to the nearest edge of the flattened path, with
+/// open figures closed as they are when filled.
+fn outline_distance(path: &Path, point: Point2f) -> f32 {
+    let dist_sq = path
+        .flatten(DEFAULT_FLATTENING_TOLERANCE)
+        .iter()
+        .flat_map(|line| {
+            let points = &line.points;
+            points.iter().zip(points.iter().cycle().skip(1))
+        })
+        .map(|(&a, &b)| {
+            let edge = Capsule::new(a, b, 0.0);
+            (point - edge.closest_center_point(point)).len_squared()
+        })
+        .fold(f32::INFINITY, f32::min);
+    dist_sq.sqrt()
+}
+
+/// The distance from `point` to the nearest edge of the geometry.
+#[inline]
+fn geometry_distance(geometry: &Geometry, point: Point2f) -> f32 {
+    match *geometry {
+        Geometry::Rect(ref s) => s.signed_distance(point).abs(),
+        Geometry::RoundedRect(ref s) => s.signed_distance(point).abs(),
+        Geometry::Ellipse(ref s) => s.signed_distance(point).abs(),
+        Geometry::Path(ref s) => outline_distance(s, point),
+        Geometry::Polygon(ref s) => s.signed_distance(point).abs(),
+    }
+}
+
+/// Rejects points which are further than `tolerance` outside the bounds.
+#[inline]
+fn near_bounds(shape: &impl Bounded, point: Point2f, tolerance: f32) -> bool {
+    shape
+        .fast_bounds()
+        .expanded_by(tolerance)
+        .contains_point(point)
+}
+
+impl HitTest for AnyShape {
+    #[inline]
+    fn hit(&self, point: Point2f) -> bool {
+        self.as_shape().hit(point)
+    }
+
+    #[inline]
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool {
+        self.as_shape().hit_with_tolerance(point, tolerance)
+    }
+}
+
+impl HitTest for Rectf {
+    #[inline]
+    fn hit(&self, point: Point2f) -> bool {
+        self.normalized().contains_point(point)
+    }
+
+    #[inline]
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool {
+        sdf_hit(self, point, tolerance)
+    }
+}
+
+impl HitTest for RoundedRect {
+    #[inline]
+    fn hit(&self, point: Point2f) -> bool {
+        let normalized = RoundedRect {
+            rect: self.rect.normalized(),
+            ..*self
+        };
+        normalized.contains_point(point)
+    }
+
+    #[inline]
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool {
+        sdf_hit(self, point, tolerance)
+    }
+}
+
+impl HitTest for Ellipse {
+    #[inline]
+    fn hit(&self, point: Point2f) -> bool {
+        Ellipse::contains_point(self, point)
+    }
+
+    #[inline]
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool {
+        sdf_hit(self, point, tolerance)
+    }
+}
+
+impl HitTest for OrientedRect {
+    #[inline]
+    fn hit(&self, point: Point2f) -> bool {
+        OrientedRect::contains_point(self, point)
+    }
+
+    #[inline]
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool {
+        self.hit(point) || self.to_polygon().hit_with_tolerance(point, tolerance)
+    }
+}
+
+/// Points are rotated into the frame of the unrotated ellipse, which keeps
+/// their distances from it the same.
+impl HitTest for OrientedEllipse {
+    #[inline]
+    fn hit(&self, point: Point2f) -> bool {
+        OrientedEllipse::contains_point(self, point)
+    }
+
+    #[inline]
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool {
+        self.unrotated()
+            .hit_with_tolerance(self.unrotate_point(point), tolerance)
+    }
+}
+
+impl HitTest for Circle {
+    #[inline]
+    fn hit(&self, point: Point2f) -> bool {
+        Circle::contains_point(self, point)
+    }
+
+    #[inline]
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool {
+        sdf_hit(self, point, tolerance)
+    }
+}
+
+impl HitTest for Capsule {
+    #[inline]
+    fn hit(&self, point: Point2f) -> bool {
+        Capsule::contains_point(self, point)
+    }
+
+    #[inline]
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool {
+        sdf_hit(self, point, tolerance)
+    }
+}
+
+impl HitTest for Triangle {
+    #[inline]
+    fn hit(&self, point: Point2f) -> bool {
+        let d1 = (self.p2 - self.p1).cross(point - self.p1);
+        let d2 = (self.p3 - self.p2).cross(point - self.p2);
+        let d3 = (self.p1 - self.p3).cross(point - self.p3);
+        let negative = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+        let positive = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+        !(negative && positive)
+    }
+
+    #[inline]
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool {
+        sdf_hit(self, point, tolerance)
+    }
+}
+
+impl HitTest for Polygon {
+    #[inline]
+    fn hit(&self, point: Point2f) -> bool {
+        Polygon::contains_point(self, point, FillRule::default())
+    }
+
+    #[inline]
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool {
+        sdf_hit(self, point, tolerance)
+    }
+}
+
+impl HitTest for Path {
+    #[inline]
+    fn hit(&self, point: Point2f) -> bool {
+        Path::contains_point(
+            self,
+            point,
+            FillRule::default(),
+            DEFAULT_FLATTENING_TOLERANCE,
+        )
+    }
+
+    #[inline]
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool {
+        if tolerance <= 0.0 || !near_bounds(self, point, tolerance) {
+            return self.hit(point);
+        }
+        self.hit(point) || outline_distance(self, point) <= tolerance
+    }
+}
+
+impl HitTest for Geometry {
+    #[inline]
+    fn hit(&self, point: Point2f) -> bool {
+        let winding = self.winding_number(point, DEFAULT_FLATTENING_TOLERANCE);
+        FillRule::default().is_inside(winding)
+    }
+
+    #[inline]
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool {
+        if tolerance <= 0.0 || !near_bounds(self, point, tolerance) {
+            return self.hit(point);
+        }
+        self.hit(point) || geometry_distance(self, point) <= tolerance
+    }
+}
+
+/// The group is expanded as a whole, so points near the edge of a hole also
+/// count as hits.
+impl HitTest for GeometryGroup {
+    #[inline]
+    fn hit(&self, point: Point2f) -> bool {
+        GeometryGroup::contains_point(self, point, DEFAULT_FLATTENING_TOLERANCE)
+    }
+
+    #[inline]
+    fn hit_with_tolerance(&self, point: Point2f, tolerance: f32) -> bool {
+        if tolerance <= 0.0 || !near_bounds(self, point, tolerance) {
+            return self.hit(point);
+        }
+        self.hit(point)
+            || self
+                .geometries
+                .iter()
+                .any(|g| geometry_distance(g, point) <= tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::capsule::Capsule;
+    use crate::ellipse::Ellipse;
+    use crate::fill_rule::FillRule;
+    use crate::geometry_group::{Geometry, GeometryGroup};
+    use crate::hit_test::HitTest;
+    use crate::path::Path;
+    use crate::point2f::Point2f;
+    use crate::rectf::Rectf;
+    use crate::shape::AnyShape;
+
+    #[test]
+    fn tolerant_hits() {
+        let rect = Rectf::new(0.0, 0.0, 10.0, 10.0);
+        let p = Point2f::new(12.0, 5.0);
+        assert!(!rect.hit(p));
+        assert!(!rect.hit_with_tolerance(p, 1.5));
+        assert!(rect.hit_with_tolerance(p, 2.5));
+        assert!(rect.hit_with_tolerance((5.0, 5.0).into(), 0.0));
+
+        // The expanded corner is rounded
+        assert!(!rect.hit_with_tolerance((11.5, 11.5).into(), 2.0));
+
+        let capsule = Capsule::new((0.0, 0.0), (10.0, 0.0), 1.0);
+        assert!(capsule.hit_with_tolerance((5.0, 2.5).into(), 2.0));
+        assert!(!capsule.hit((5.0, 2.5).into()));
+
+        let path = Path::from(Ellipse::new((0.0, 0.0), 5.0, 5.0));
+        assert!(!path.hit((0.0, 6.0).into()));
+        assert!(path.hit_with_tolerance((0.0, 6.0).into(), 1.5));
+        assert!(!path.hit_with_tolerance((0.0, 7.0).into(), 1.5));
+
+        // Points inside a hole within the tolerance of its edge still hit
+        let ring = GeometryGroup::new(
+            FillRule::EvenOdd,
+            vec![
+                Geometry::Rect(Rectf::new(0.0, 0.0, 10.0, 10.0)),
+                Geometry::Rect(Rectf::new(2.0, 2.0, 8.0, 8.0)),
+            ],
+        );
+        assert!(!ring.hit((5.0, 5.0).into()));
+        assert!(ring.hit_with_tolerance((5.0, 2.5).into(), 1.0));
+        assert!(!ring.hit_with_tolerance((5.0, 5.0).into(), 1.0));
+
+        let shapes: Vec<AnyShape> = vec![rect.into(), path.into()];
+        let hits = |p: Point2f, tolerance| {
+            shapes
+                .iter()
+                .filter(|s| s.hit_with_tolerance(p, tolerance))
+                .count()
+        };
+        assert_eq!(hits(Point2f::new(-0.5, 4.0), 0.0), 1);
+        assert_eq!(hits(Point2f::new(-0.5, 4.0), 1.0), 2);
+    }
+}
@@ -0,0 +1,234 @@
+//! Closed ranges of real numbers.
+//!
+//! Many 2D algorithms split into independent 1D problems: a rectangle is an
+//! interval on each axis, two convex shapes are separated if their
+//! projections onto some axis are, and the bounds of a curve are the range
+//! of each of its coordinates.
+
+/// A closed range of real numbers from `min` to `max`, inclusive. Intervals
+/// with `min > max` are empty; `Interval::EMPTY` is the canonical one, which
+/// acts as the identity for `union` and `including`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Interval {
+    /// The smallest value in the interval.
+    pub min: f32,
+    /// The largest value in the interval.
+    pub max: f32,
+}
+
+impl Interval {
+    /// The interval which contains nothing.
+    pub const EMPTY: Interval = Interval {
+        min: f32::INFINITY,
+        max: f32::NEG_INFINITY,
+    };
+
+    /// Constructs an interval from its endpoints.
+    #[inline]
+    pub fn new(min: f32, max: f32) -> Interval {
+        Interval { min, max }
+    }
+
+    /// The interval between two values, in either order.
+    #[inline]
+    pub fn between(a: f32, b: f32) -> Interval {
+        Interval::new(a.min(b), a.max(b))
+    }
+
+    /// The smallest interval containing every value, which is empty if there
+    /// are none.
+    #[inline]
+    pub fn covering(values: impl IntoIterator<Item = f32>) -> Interval {
+        values
+            .into_iter()
+            .fold(Interval::EMPTY, |acc, v| acc.including(v))
+    }
+
+    /// Determines if the interval contains no values, including when either
+    /// endpoint is NaN.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.min > self.max || self.min.is_nan() || self.max.is_nan()
+    }
+
+    /// The distance from `min` to `max`, or zero for empty intervals.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        if self.is_empty() {
+            0.0
+        } else {
+            self.max - self.min
+        }
+    }
+
+    /// The value halfway between the endpoints.
+    #[inline]
+    pub fn center(&self) -> f32 {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Determines if the interval contains `value`, including its endpoints.
+    #[inline]
+    pub fn contains(&self, value: f32) -> bool {
+        value >= self.min && value <= self.max
+    }
+
+    /// Determines if every value in `other` is also in this interval. Empty
+    /// intervals are contained in every interval.
+    #[inline]
+    pub fn contains_interval(&self, other: &Interval) -> bool {
+        other.is_empty() || (other.min >= self.min && other.max <= self.max)
+    }
+
+    /// Determines if the intervals share more than a single endpoint, as with
+    /// `Rectf::overlaps`.
+    #[inline]
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.min < other.max && self.max > other.min
+    }
+
+    /// The values in both intervals, or `None` if they are disjoint.
+    /// Intervals which only touch intersect in a single value.
+    #[inline]
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let result = Interval::new(self.min.max(other.min), self.max.min(other.max));
+        if result.is_empty() {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    /// The smallest interval containing both intervals, including any gap
+    /// between them.
+    #[inline]
+    pub fn union(&self, other: &Interval) -> Interval {
+        if self.is_empty() {
+            *other
+        } else if other.is_empty() {
+            *self
+        } else {
+            Interval::new(self.min.min(other.min), self.max.max(other.max))
+        }
+    }
+
+    /// The smallest interval containing this one and `value`.
+    #[inline]
+    pub fn including(&self, value: f32) -> Interval {
+        if self.is_empty() {
+            Interval::new(value, value)
+        } else {
+            Interval::new(self.min.min(value), self.max.max(value))
+        }
+    }
+
+    /// The value in the interval closest to `value`. The interval must not be
+    /// empty.
+    #[inline]
+    pub fn clamp(&self, value: f32) -> f32 {
+        value.max(self.min).min(self.max)
+    }
+
+    /// Grows the interval by `amount` at both ends, or shrinks it if
+    /// `amount` is negative.
+    #[inline]
+    pub fn expanded_by(&self, amount: f32) -> Interval {
+        Interval::new(self.min - amount, self.max + amount)
+    }
+
+    /// Moves the interval by `offset`.
+    #[inline]
+    pub fn translated_by(&self, offset: f32) -> Interval {
+        Interval::new(self.min + offset, self.max + offset)
+    }
+}
+
+impl Default for Interval {
+    /// The empty interval.
+    #[inline]
+    fn default() -> Interval {
+        Interval::EMPTY
+    }
+}
+
+impl From<(f32, f32)> for Interval {
+    #[inline]
+    fn from((min, max): (f32, f32)) -> Interval {
+        Interval::new(min, max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier_segment::BezierSegment;
+    use crate::interval::Interval;
+    use crate::polygon::Polygon;
+    use crate::rectf::Rectf;
+    use crate::vector2f::Vector2f;
+
+    #[test]
+    fn interval_ops() {
+        let a = Interval::new(0.0, 10.0);
+        let b = Interval::between(15.0, 5.0);
+        assert_eq!(b, Interval::new(5.0, 15.0));
+        assert_eq!(a.intersection(&b), Some(Interval::new(5.0, 10.0)));
+        assert_eq!(a.union(&b), Interval::new(0.0, 15.0));
+        assert!(a.overlaps(&b));
+
+        // Touching intervals intersect at a point but do not overlap
+        let c = Interval::new(10.0, 20.0);
+        assert!(!a.overlaps(&c));
+        assert_eq!(a.intersection(&c), Some(Interval::new(10.0, 10.0)));
+        assert_eq!(a.intersection(&Interval::new(11.0, 12.0)), None);
+
+        assert_eq!(a.clamp(-3.0), 0.0);
+        assert!(a.contains(10.0) && !a.contains(10.5));
+        assert!(a.contains_interval(&Interval::new(2.0, 3.0)));
+        assert!(a.contains_interval(&Interval::EMPTY));
+        assert_eq!(Interval::EMPTY.union(&a), a);
+        assert_eq!(Interval::EMPTY.length(), 0.0);
+        assert_eq!(
+            Interval::covering([3.0, -1.0, 2.0]),
+            Interval::new(-1.0, 3.0)
+        );
+        assert!(Interval::covering(None).is_empty());
+    }
+
+    #[test]
+    fn decomposed_algorithms() {
+        let rect = Rectf::new(0.0, 5.0, 10.0, 20.0);
+        assert_eq!(rect.x_interval(), Interval::new(0.0, 10.0));
+        assert_eq!(
+            Rectf::from_intervals(rect.x_interval(), rect.y_interval()),
+            rect
+        );
+
+        // Two triangles separated along the diagonal, but not along either
+        // axis
+        let t1 = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (10.0, 0.0).into(),
+            (0.0, 10.0).into(),
+        ]);
+        let t2 = Polygon::new(vec![
+            (10.0, 10.0).into(),
+            (10.0, 2.0).into(),
+            (2.0, 10.0).into(),
+        ]);
+        assert!(t1.bounds().overlaps(&t2.bounds()));
+        let axis = Vector2f::new(1.0, 1.0);
+        assert!(!t1.projected_onto(axis).overlaps(&t2.projected_onto(axis)));
+
+        let bez = BezierSegment::new((0.0, 10.0), (10.0, 10.0), (10.0, 0.0));
+        let bounds = bez.bounds((0.0, 0.0));
+        assert!((bounds.bottom - 7.5).abs() < 1e-5);
+        assert_eq!(bounds.x_interval(), Interval::new(0.0, 10.0));
+    }
+}
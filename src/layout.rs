@@ -0,0 +1,267 @@
+//! Layout compatibility between math2d types and the foreign types they
+//! mirror.
+
+#[cfg(all(windows, any(feature = "d2d", feature = "windows")))]
+use crate::{
+    arc_segment::ArcSegment, bezier_segment::BezierSegment, color::Color, ellipse::Ellipse,
+    point2i::Point2i, point2u::Point2u, quad_bezier_segment::QuadBezierSegment, rectf::Rectf,
+    recti::Recti, rectu::Rectu, rounded_rect::RoundedRect, sizef::Sizef, sizei::Sizei,
+    sizeu::Sizeu, triangle::Triangle,
+};
+#[cfg(any(
+    feature = "mint",
+    all(windows, any(feature = "d2d", feature = "windows"))
+))]
+use crate::{matrix3x2f::Matrix3x2f, point2f::Point2f, vector2f::Vector2f};
+
+use std::slice;
+
+/// Marks `Self` as having the same size, alignment and field offsets as
+/// `T`, with every valid `T` also being a valid `Self`. A reference to a
+/// `T` may then be viewed as a reference to `Self` without copying.
+///
+/// math2d implements this for the Direct2D, DirectWrite, GDI and mint
+/// types which mirror its own, in both directions where the values of
+/// both types are interchangeable. Each of those implementations is
+/// checked at compile time, so a change to either side which would break
+/// the layout fails to build rather than making casts unsound.
+///
+/// ```ignore
+/// use math2d::LayoutCompatible;
+/// use winapi::um::dcommon::D2D_POINT_2F;
+///
+/// let points = [Point2f::new(0.0, 1.0), Point2f::new(2.0, 3.0)];
+/// let d2d: &[D2D_POINT_2F] = LayoutCompatible::view_slice(&points);
+/// ```
+///
+/// # Safety
+///
+/// Implementing this for other types is allowed, but the implementor must
+/// guarantee the layout and validity requirements above themselves.
+pub unsafe trait LayoutCompatible<T>: Sized {
+    /// Views a reference to `T` as a reference to this type.
+    #[inline]
+    fn view(value: &T) -> &Self {
+        unsafe { &*(value as *const T as *const Self) }
+    }
+
+    /// Views a slice of `T` as a slice of this type.
+    #[inline]
+    fn view_slice(values: &[T]) -> &[Self] {
+        unsafe { slice::from_raw_parts(values.as_ptr() as *const Self, values.len()) }
+    }
+}
+
+/// Checks the layout of each pair at compile time and implements
+/// `LayoutCompatible` for it. `A, B` implements it in both directions, and
+/// `A => B` only lets `A` be viewed as `B`.
+macro_rules! layout_compatible {
+    ($cfg:meta: $ours:ty, $theirs:ty { $($a:ident => $b:ident),* $(,)? }) => {
+        layout_compatible!($cfg: $ours => $theirs { $($a => $b),* });
+
+        #[cfg($cfg)]
+        unsafe impl LayoutCompatible<$theirs> for $ours {}
+    };
+    ($cfg:meta: $ours:ty => $theirs:ty { $($a:ident => $b:ident),* $(,)? }) => {
+        #[cfg($cfg)]
+        const _: () = {
+            use std::mem::{align_of, offset_of, size_of};
+
+            assert!(size_of::<$ours>() == size_of::<$theirs>());
+            assert!(align_of::<$ours>() == align_of::<$theirs>());
+            $(assert!(offset_of!($ours, $a) == offset_of!($theirs, $b));)*
+        };
+
+        #[cfg($cfg)]
+        unsafe impl LayoutCompatible<$ours> for $theirs {}
+    };
+}
+
+layout_compatible!(feature = "mint": Point2f, mint::Point2<f32> { x => x, y => y });
+layout_compatible!(feature = "mint": Vector2f, mint::Vector2<f32> { x => x, y => y });
+layout_compatible!(feature = "mint": Matrix3x2f, mint::RowMatrix3x2<f32> { a => x, c => y, x => z });
+
+layout_compatible!(all(windows, feature = "d2d"): Point2f, winapi::um::dcommon::D2D_POINT_2F { x => x, y => y });
+layout_compatible!(all(windows, feature = "d2d"): Point2i, winapi::um::dcommon::D2D_POINT_2L { x => x, y => y });
+layout_compatible!(all(windows, feature = "d2d"): Point2i, winapi::shared::windef::POINTL { x => x, y => y });
+layout_compatible!(all(windows, feature = "d2d"): Point2u, winapi::um::dcommon::D2D_POINT_2U { x => x, y => y });
+layout_compatible!(all(windows, feature = "d2d"): Vector2f, winapi::um::dcommon::D2D_VECTOR_2F { x => x, y => y });
+layout_compatible!(all(windows, feature = "d2d"): Vector2f, winapi::um::dwrite::DWRITE_GLYPH_OFFSET {
+    x => advanceOffset,
+    y => ascenderOffset,
+});
+layout_compatible!(all(windows, feature = "d2d"): Sizef, winapi::um::dcommon::D2D_SIZE_F { width => width, height => height });
+layout_compatible!(all(windows, feature = "d2d"): Sizeu, winapi::um::dcommon::D2D_SIZE_U { width => width, height => height });
+layout_compatible!(all(windows, feature = "d2d"): Sizei, winapi::shared::windef::SIZE { width => cx, height => cy });
+layout_compatible!(all(windows, feature = "d2d"): Rectf, winapi::um::dcommon::D2D_RECT_F {
+    left => left,
+    top => top,
+    right => right,
+    bottom => bottom,
+});
+layout_compatible!(all(windows, feature = "d2d"): Recti, winapi::um::dcommon::D2D_RECT_L {
+    left => left,
+    top => top,
+    right => right,
+    bottom => bottom,
+});
+layout_compatible!(all(windows, feature = "d2d"): Recti, winapi::shared::windef::RECTL {
+    left => left,
+    top => top,
+    right => right,
+    bottom => bottom,
+});
+layout_compatible!(all(windows, feature = "d2d"): Rectu, winapi::um::dcommon::D2D_RECT_U {
+    left => left,
+    top => top,
+    right => right,
+    bottom => bottom,
+});
+layout_compatible!(all(windows, feature = "d2d"): RoundedRect, winapi::um::d2d1::D2D1_ROUNDED_RECT {
+    rect => rect,
+    radius_x => radiusX,
+    radius_y => radiusY,
+});
+layout_compatible!(all(windows, feature = "d2d"): Ellipse, winapi::um::d2d1::D2D1_ELLIPSE {
+    center => point,
+    radius_x => radiusX,
+    radius_y => radiusY,
+});
+layout_compatible!(all(windows, feature = "d2d"): Color, winapi::um::d2d1::D2D1_COLOR_F { r => r, g => g, b => b, a => a });
+// The Direct2D matrix stores its values as a single nested array
+layout_compatible!(all(windows, feature = "d2d"): Matrix3x2f, winapi::um::dcommon::D2D_MATRIX_3X2_F { a => matrix });
+layout_compatible!(all(windows, feature = "d2d"): Matrix3x2f, winapi::um::dwrite::DWRITE_MATRIX {
+    a => m11,
+    b => m12,
+    c => m21,
+    d => m22,
+    x => dx,
+    y => dy,
+});
+layout_compatible!(all(windows, feature = "d2d"): Triangle, winapi::um::d2d1::D2D1_TRIANGLE {
+    p1 => point1,
+    p2 => point2,
+    p3 => point3,
+});
+layout_compatible!(all(windows, feature = "d2d"): BezierSegment, winapi::um::d2d1::D2D1_BEZIER_SEGMENT {
+    p1 => point1,
+    p2 => point2,
+    p3 => point3,
+});
+layout_compatible!(all(windows, feature = "d2d"): QuadBezierSegment, winapi::um::d2d1::D2D1_QUADRATIC_BEZIER_SEGMENT {
+    p1 => point1,
+    p2 => point2,
+});
+// Direct2D accepts any value for the enums, which math2d does not
+layout_compatible!(all(windows, feature = "d2d"): ArcSegment => winapi::um::d2d1::D2D1_ARC_SEGMENT {
+    point => point,
+    size => size,
+    rotation_angle => rotationAngle,
+    sweep_direction => sweepDirection,
+    arc_size => arcSize,
+});
+
+layout_compatible!(all(windows, feature = "windows"): Point2f, windows::Win32::Graphics::Direct2D::Common::D2D_POINT_2F { x => x, y => y });
+layout_compatible!(all(windows, feature = "windows"): Point2i, windows::Win32::Foundation::POINT { x => x, y => y });
+layout_compatible!(all(windows, feature = "windows"): Point2i, windows::Win32::Foundation::POINTL { x => x, y => y });
+layout_compatible!(all(windows, feature = "windows"): Point2u, windows::Win32::Graphics::Direct2D::Common::D2D_POINT_2U { x => x, y => y });
+layout_compatible!(all(windows, feature = "windows"): Vector2f, windows::Win32::Graphics::Direct2D::Common::D2D_VECTOR_2F { x => x, y => y });
+layout_compatible!(all(windows, feature = "windows"): Vector2f, windows::Win32::Graphics::DirectWrite::DWRITE_GLYPH_OFFSET {
+    x => advanceOffset,
+    y => ascenderOffset,
+});
+layout_compatible!(all(windows, feature = "windows"): Sizef, windows::Win32::Graphics::Direct2D::Common::D2D_SIZE_F { width => width, height => height });
+layout_compatible!(all(windows, feature = "windows"): Sizeu, windows::Win32::Graphics::Direct2D::Common::D2D_SIZE_U { width => width, height => height });
+layout_compatible!(all(windows, feature = "windows"): Sizei, windows::Win32::Foundation::SIZE { width => cx, height => cy });
+layout_compatible!(all(windows, feature = "windows"): Rectf, windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F {
+    left => left,
+    top => top,
+    right => right,
+    bottom => bottom,
+});
+layout_compatible!(all(windows, feature = "windows"): Recti, windows::Win32::Foundation::RECT {
+    left => left,
+    top => top,
+    right => right,
+    bottom => bottom,
+});
+layout_compatible!(all(windows, feature = "windows"): Recti, windows::Win32::Foundation::RECTL {
+    left => left,
+    top => top,
+    right => right,
+    bottom => bottom,
+});
+layout_compatible!(all(windows, feature = "windows"): Rectu, windows::Win32::Graphics::Direct2D::Common::D2D_RECT_U {
+    left => left,
+    top => top,
+    right => right,
+    bottom => bottom,
+});
+layout_compatible!(all(windows, feature = "windows"): RoundedRect, windows::Win32::Graphics::Direct2D::D2D1_ROUNDED_RECT {
+    rect => rect,
+    radius_x => radiusX,
+    radius_y => radiusY,
+});
+layout_compatible!(all(windows, feature = "windows"): Ellipse, windows::Win32::Graphics::Direct2D::D2D1_ELLIPSE {
+    center => point,
+    radius_x => radiusX,
+    radius_y => radiusY,
+});
+layout_compatible!(all(windows, feature = "windows"): Color, windows::Win32::Graphics::Direct2D::Common::D2D1_COLOR_F { r => r, g => g, b => b, a => a });
+layout_compatible!(all(windows, feature = "windows"): Matrix3x2f, windows::Foundation::Numerics::Matrix3x2 {
+    a => M11,
+    b => M12,
+    c => M21,
+    d => M22,
+    x => M31,
+    y => M32,
+});
+layout_compatible!(all(windows, feature = "windows"): Matrix3x2f, windows::Win32::Graphics::DirectWrite::DWRITE_MATRIX {
+    a => m11,
+    b => m12,
+    c => m21,
+    d => m22,
+    x => dx,
+    y => dy,
+});
+layout_compatible!(all(windows, feature = "windows"): Triangle, windows::Win32::Graphics::Direct2D::D2D1_TRIANGLE {
+    p1 => point1,
+    p2 => point2,
+    p3 => point3,
+});
+layout_compatible!(all(windows, feature = "windows"): BezierSegment, windows::Win32::Graphics::Direct2D::Common::D2D1_BEZIER_SEGMENT {
+    p1 => point1,
+    p2 => point2,
+    p3 => point3,
+});
+layout_compatible!(all(windows, feature = "windows"): QuadBezierSegment, windows::Win32::Graphics::Direct2D::D2D1_QUADRATIC_BEZIER_SEGMENT {
+    p1 => point1,
+    p2 => point2,
+});
+// Direct2D accepts any value for the enums, which math2d does not
+layout_compatible!(all(windows, feature = "windows"): ArcSegment => windows::Win32::Graphics::Direct2D::D2D1_ARC_SEGMENT {
+    point => point,
+    size => size,
+    rotation_angle => rotationAngle,
+    sweep_direction => sweepDirection,
+    arc_size => arcSize,
+});
+
+#[cfg(all(test, feature = "mint"))]
+#[test]
+fn mint_views() {
+    let points = [Point2f::new(1.0, 2.0), Point2f::new(3.0, 4.0)];
+    let view: &[mint::Point2<f32>] = LayoutCompatible::view_slice(&points);
+    assert_eq!(
+        view,
+        [
+            mint::Point2 { x: 1.0, y: 2.0 },
+            mint::Point2 { x: 3.0, y: 4.0 }
+        ]
+    );
+
+    let m = Matrix3x2f::new([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+    let rows: &mint::RowMatrix3x2<f32> = LayoutCompatible::view(&m);
+    assert_eq!(*rows, mint::RowMatrix3x2::from(m));
+    assert_eq!(*Matrix3x2f::view(rows), m);
+}
@@ -0,0 +1,117 @@
+//! Linear interpolation between values, for animation.
+
+use crate::angle;
+use crate::color::Color;
+use crate::matrix3x2f::Matrix3x2f;
+use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+use crate::sizef::Sizef;
+use crate::thicknessf::Thicknessf;
+use crate::vector2f::Vector2f;
+
+/// Values which can be blended smoothly into each other.
+pub trait Lerp {
+    /// Interpolates between two values. `0.0` returns `self` and `1.0`
+    /// returns `other`, and values outside of that range extrapolate.
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    #[inline]
+    fn lerp(&self, other: &f32, t: f32) -> f32 {
+        self + (other - self) * t
+    }
+}
+
+impl Lerp for Point2f {
+    #[inline]
+    fn lerp(&self, other: &Point2f, t: f32) -> Point2f {
+        *self + (*other - *self) * t
+    }
+}
+
+impl Lerp for Vector2f {
+    #[inline]
+    fn lerp(&self, other: &Vector2f, t: f32) -> Vector2f {
+        *self + (*other - *self) * t
+    }
+}
+
+impl Lerp for Sizef {
+    #[inline]
+    fn lerp(&self, other: &Sizef, t: f32) -> Sizef {
+        Sizef::new(
+            self.width.lerp(&other.width, t),
+            self.height.lerp(&other.height, t),
+        )
+    }
+}
+
+impl Lerp for Rectf {
+    #[inline]
+    fn lerp(&self, other: &Rectf, t: f32) -> Rectf {
+        Rectf::new(
+            self.left.lerp(&other.left, t),
+            self.top.lerp(&other.top, t),
+            self.right.lerp(&other.right, t),
+            self.bottom.lerp(&other.bottom, t),
+        )
+    }
+}
+
+impl Lerp for Thicknessf {
+    #[inline]
+    fn lerp(&self, other: &Thicknessf, t: f32) -> Thicknessf {
+        Thicknessf::new(
+            self.left.lerp(&other.left, t),
+            self.top.lerp(&other.top, t),
+            self.right.lerp(&other.right, t),
+            self.bottom.lerp(&other.bottom, t),
+        )
+    }
+}
+
+impl Lerp for Color {
+    #[inline]
+    fn lerp(&self, other: &Color, t: f32) -> Color {
+        Color::lerp(self, other, t)
+    }
+}
+
+impl Lerp for Matrix3x2f {
+    /// Matrices are interpolated by their decompositions, so that a rotation
+    /// turns smoothly through the shorter way around instead of collapsing
+    /// through a squashed matrix halfway. Matrices with skew lose it.
+    #[inline]
+    fn lerp(&self, other: &Matrix3x2f, t: f32) -> Matrix3x2f {
+        let (a, b) = (self.decompose(), other.decompose());
+        Matrix3x2f::compose(
+            a.scaling.lerp(&b.scaling, t),
+            angle::lerp_angle(a.rotation, b.rotation, t),
+            a.translation.lerp(&b.translation, t),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lerp::Lerp;
+    use crate::matrix3x2f::Matrix3x2f;
+    use crate::point2f::Point2f;
+
+    #[test]
+    fn matrices() {
+        let a = Matrix3x2f::compose([1.0, 1.0], 3.0, [0.0, 0.0]);
+        let b = Matrix3x2f::compose([3.0, 3.0], -3.0, [10.0, 20.0]);
+
+        // Halfway between 3 and -3 radians the short way round is pi
+        let mid = a.lerp(&b, 0.5);
+        let expected = Matrix3x2f::compose([2.0, 2.0], std::f32::consts::PI, [5.0, 10.0]);
+        assert!(mid.is_approx_eq(&expected, 1e-4));
+        assert!(a.lerp(&b, 0.0).is_approx_eq(&a, 1e-5));
+        assert!(a.lerp(&b, 1.0).is_approx_eq(&b, 1e-5));
+
+        let p = Point2f::new(0.0, 0.0).lerp(&(4.0, 8.0).into(), 0.25);
+        assert_eq!(p, (1.0, 2.0).into());
+    }
+}
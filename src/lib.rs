@@ -7,82 +7,334 @@
 //! free to open a pull request on the [repository][1].
 //!
 //! Currently compatible with:
-//! - Direct2D (winapi types)
+//! - Direct2D, through either the `winapi` types or the [windows][12] crate
 //! - [Mint][2]
+//! - [euclid][3]
+//! - [glam][4]
+//! - [nalgebra][5]
+//! - [kurbo][6]
+//! - [lyon][7], whose geometry types are the euclid ones
+//! - [Cairo][8], through `cairo-rs`
+//! - [Skia][9], through `skia-safe`
+//! - [tiny-skia][10]
+//! - [raqote][11], whose geometry types are the euclid ones
+//! - [winit][13], through its `dpi` crate
+//! - [SDL2][14], through the `sdl2` crate
+//! - DOM geometry and canvas transforms, through [web-sys][15]
+//! - GPU uniform and storage buffer layouts for wgpu, through [encase][16]
+//! - Parallel bulk operations on the [rayon][17] thread pool
+//! - Half precision point storage, through [half][18]
+//!
+//! [kurbo][6] works in double precision. Conversions from math2d's `f32`
+//! types into kurbo are lossless, while conversions back round each value to
+//! the nearest `f32`. The `f64` aliases such as `Point2d` share the generic
+//! operations of the `f32` types, which widen into them exactly.
 //!
 //! [1]: https://github.com/connicpu/math2d
 //! [2]: https://docs.rs/mint
+//! [3]: https://docs.rs/euclid
+//! [4]: https://docs.rs/glam
+//! [5]: https://docs.rs/nalgebra
+//! [6]: https://docs.rs/kurbo
+//! [7]: https://docs.rs/lyon
+//! [8]: https://docs.rs/cairo-rs
+//! [9]: https://docs.rs/skia-safe
+//! [10]: https://docs.rs/tiny-skia
+//! [11]: https://docs.rs/raqote
+//! [12]: https://docs.rs/windows
+//! [13]: https://docs.rs/winit
+//! [14]: https://docs.rs/sdl2
+//! [15]: https://docs.rs/web-sys
+//! [16]: https://docs.rs/encase
+//! [17]: https://docs.rs/rayon
+//! [18]: https://docs.rs/half
 
-#[cfg(all(feature = "serde", feature = "serde_derive"))]
-#[macro_use]
-extern crate serde_derive;
+#[cfg(feature = "cairo")]
+extern crate cairo;
+#[cfg(feature = "encase")]
+extern crate encase;
+#[cfg(feature = "half")]
+extern crate half;
 #[cfg(feature = "kurbo")]
 extern crate kurbo;
-#[cfg(all(feature = "serde", feature = "serde_derive"))]
+#[cfg(feature = "lyon")]
+extern crate lyon_path;
+#[cfg(feature = "sdl2")]
+extern crate sdl2;
+#[cfg(feature = "serde")]
 extern crate serde;
+#[cfg(feature = "geojson")]
+extern crate serde_json;
+#[cfg(feature = "skia")]
+extern crate skia_safe;
+#[cfg(feature = "tiny-skia")]
+extern crate tiny_skia;
+#[cfg(feature = "web")]
+extern crate web_sys;
 
+#[cfg(feature = "winit")]
+extern crate dpi as winit_dpi;
 #[cfg(all(windows, feature = "winapi"))]
 extern crate winapi;
+#[cfg(all(windows, feature = "windows"))]
+extern crate windows;
 
 #[cfg(feature = "mint")]
 extern crate mint;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "raqote")]
+extern crate raqote;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+#[cfg(feature = "euclid")]
+extern crate euclid;
+#[cfg(feature = "glam")]
+extern crate glam;
+#[cfg(feature = "proptest")]
+extern crate proptest;
+#[cfg(feature = "rkyv")]
+extern crate rkyv;
+#[cfg(feature = "schemars")]
+extern crate schemars;
 
 #[doc(inline)]
-pub use crate::arc_segment::{ArcSegment, ArcSize, SweepDirection};
+pub use crate::angle::Angle;
+#[doc(inline)]
+pub use crate::arc_segment::{ArcSegment, ArcSegmentBuilder, ArcSize, SweepDirection};
+#[doc(inline)]
+pub use crate::area_moments::AreaMoments;
+#[doc(inline)]
+pub use crate::axis::Axis;
 #[doc(inline)]
 pub use crate::bezier_segment::BezierSegment;
+#[doc(inline)]
+pub use crate::bounded::Bounded;
+#[doc(inline)]
+pub use crate::bvh::Bvh;
+#[doc(inline)]
+pub use crate::cached_bounds::CachedBounds;
+#[doc(inline)]
+pub use crate::camera2d::Camera2D;
+#[doc(inline)]
+pub use crate::capsule::Capsule;
+#[doc(inline)]
+pub use crate::center_arc::CenterArc;
+#[doc(inline)]
+pub use crate::circle::{bounding_circle, Circle};
 pub use crate::color::Color;
 #[doc(inline)]
+pub use crate::damage_tracker::DamageTracker;
+#[doc(inline)]
+pub use crate::dpi::{Dpi, DpiScalable, RectRounding};
+#[doc(inline)]
+pub use crate::easing::Easing;
+#[doc(inline)]
 pub use crate::ellipse::Ellipse;
 #[doc(inline)]
+pub use crate::fill_rule::FillRule;
+#[doc(inline)]
+pub use crate::geometry_group::{Geometry, GeometryGroup};
+#[doc(inline)]
+pub use crate::gradient::{GradientStop, LinearGradient, RadialGradient};
+#[doc(inline)]
+pub use crate::grid::{Grid, GridCells, GridTraversal};
+#[doc(inline)]
+pub use crate::hit_test::HitTest;
+#[doc(inline)]
+pub use crate::interval::Interval;
+#[doc(inline)]
+pub use crate::layout::LayoutCompatible;
+#[doc(inline)]
+pub use crate::lerp::Lerp;
+#[doc(inline)]
 pub use crate::matrix3x2f::Matrix3x2f;
 #[doc(inline)]
+pub use crate::offset::{Offset, Scale};
+#[doc(inline)]
+pub use crate::oriented_ellipse::OrientedEllipse;
+#[doc(inline)]
+pub use crate::oriented_rect::OrientedRect;
+#[doc(inline)]
+pub use crate::parse::ParseError;
+#[doc(inline)]
+pub use crate::path::{Figure, Path, PathBuilder, PathSegment, PathSink, Segments};
+#[doc(inline)]
+pub use crate::path_measure::{PathLocation, PathMeasure};
+#[doc(inline)]
+pub use crate::point2::{Point2, Point2d};
+#[doc(inline)]
 pub use crate::point2f::Point2f;
+#[cfg(feature = "half")]
+#[doc(inline)]
+pub use crate::point2f16::Point2f16;
 #[doc(inline)]
 pub use crate::point2i::Point2i;
 #[doc(inline)]
 pub use crate::point2u::Point2u;
 #[doc(inline)]
+pub use crate::point_quad_tree::PointQuadTree;
+#[doc(inline)]
+pub use crate::polygon::Polygon;
+#[doc(inline)]
+pub use crate::polyline::Polyline;
+#[doc(inline)]
 pub use crate::quad_bezier_segment::QuadBezierSegment;
 #[doc(inline)]
-pub use crate::rectf::{RectCorner, Rectf};
+pub use crate::quad_tree::QuadTree;
+#[doc(inline)]
+pub use crate::rect::{Rect, RectCorner, Rectd};
+#[doc(inline)]
+pub use crate::rect_packer::RectPacker;
+#[doc(inline)]
+pub use crate::rectf::Rectf;
 #[doc(inline)]
 pub use crate::recti::Recti;
 #[doc(inline)]
 pub use crate::rectu::Rectu;
 #[doc(inline)]
 pub use crate::rounded_rect::RoundedRect;
+pub use crate::sdf::Sdf;
+#[doc(inline)]
+pub use crate::shape::{AnyShape, Shape2D};
+#[doc(inline)]
+pub use crate::size::{Size, Sized64};
 #[doc(inline)]
 pub use crate::sizef::Sizef;
 #[doc(inline)]
+pub use crate::sizei::Sizei;
+#[doc(inline)]
 pub use crate::sizeu::Sizeu;
 #[doc(inline)]
+pub use crate::stroke::{LineCap, LineJoin};
+#[doc(inline)]
+pub use crate::text::{ParagraphAlignment, TextAlignment, TextMetrics};
+#[doc(inline)]
 pub use crate::thicknessf::Thicknessf;
 #[doc(inline)]
+pub use crate::timeline::{Keyframe, LoopMode, Timeline};
+#[doc(inline)]
+pub use crate::tolerance::Tolerance;
+#[doc(inline)]
+pub use crate::transform_stack::{TransformGuard, TransformStack};
+#[doc(inline)]
+pub use crate::transformable::Transformable;
+#[doc(inline)]
 pub use crate::triangle::Triangle;
 #[doc(inline)]
+pub use crate::vector2::{Vector2, Vector2d};
+#[doc(inline)]
 pub use crate::vector2f::Vector2f;
 #[doc(inline)]
 pub use crate::vector2i::Vector2i;
+#[doc(inline)]
+pub use crate::vector2u::Vector2u;
+#[doc(inline)]
+pub use crate::winding::{orientation, Winding};
 
+pub mod angle;
 #[doc(hidden)]
 pub mod arc_segment;
 #[doc(hidden)]
+pub mod area_moments;
+#[doc(hidden)]
+pub mod axis;
+#[doc(hidden)]
 pub mod bezier_segment;
+#[doc(hidden)]
+pub mod bounded;
+#[doc(hidden)]
+pub mod bvh;
+#[doc(hidden)]
+pub mod cached_bounds;
+#[doc(hidden)]
+pub mod camera2d;
+#[doc(hidden)]
+pub mod capsule;
+#[doc(hidden)]
+pub mod center_arc;
+#[doc(hidden)]
+pub mod circle;
 pub mod color;
+#[cfg(feature = "serde-compact")]
+mod compact;
+mod corner;
+#[cfg(all(windows, any(feature = "d2d", feature = "windows")))]
+pub mod d2d;
+#[doc(hidden)]
+pub mod damage_tracker;
+#[doc(hidden)]
+pub mod dpi;
+#[doc(hidden)]
+pub mod easing;
 #[doc(hidden)]
 pub mod ellipse;
 #[doc(hidden)]
+pub mod fill_rule;
+pub mod fit;
+#[doc(hidden)]
+pub mod geometry_group;
+#[doc(hidden)]
+pub mod gradient;
+#[doc(hidden)]
+pub mod grid;
+#[doc(hidden)]
+pub mod hit_test;
+#[doc(hidden)]
+pub mod interval;
+#[doc(hidden)]
+pub mod layout;
+#[doc(hidden)]
+pub mod lerp;
+#[doc(hidden)]
 pub mod matrix3x2f;
+pub mod morph;
+#[doc(hidden)]
+pub mod offset;
+#[doc(hidden)]
+pub mod oriented_ellipse;
+#[doc(hidden)]
+pub mod oriented_rect;
+pub mod parse;
+#[doc(hidden)]
+pub mod path;
+#[doc(hidden)]
+pub mod path_measure;
+#[doc(hidden)]
+pub mod point2;
 #[doc(hidden)]
 pub mod point2f;
+#[cfg(feature = "half")]
+#[doc(hidden)]
+pub mod point2f16;
 #[doc(hidden)]
 pub mod point2i;
 #[doc(hidden)]
 pub mod point2u;
 #[doc(hidden)]
+pub mod point_quad_tree;
+#[doc(hidden)]
+pub mod polygon;
+#[doc(hidden)]
+pub mod polyline;
+pub mod polynomial;
+pub mod predicates;
+#[doc(hidden)]
 pub mod quad_bezier_segment;
 #[doc(hidden)]
+pub mod quad_tree;
+pub mod raster;
+#[doc(hidden)]
+pub mod rect;
+#[doc(hidden)]
+pub mod rect_packer;
+#[doc(hidden)]
 pub mod rectf;
 #[doc(hidden)]
 pub mod recti;
@@ -90,15 +342,52 @@ pub mod recti;
 pub mod rectu;
 #[doc(hidden)]
 pub mod rounded_rect;
+pub mod sdf;
+#[doc(hidden)]
+pub mod shape;
+#[cfg(feature = "simd")]
+mod simd;
+#[doc(hidden)]
+pub mod size;
 #[doc(hidden)]
 pub mod sizef;
 #[doc(hidden)]
+pub mod sizei;
+#[doc(hidden)]
 pub mod sizeu;
+pub mod spline;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[doc(hidden)]
+pub mod stroke;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+#[doc(hidden)]
+pub mod text;
 #[doc(hidden)]
 pub mod thicknessf;
 #[doc(hidden)]
+pub mod timeline;
+#[doc(hidden)]
+pub mod tolerance;
+#[doc(hidden)]
+pub mod transform_stack;
+#[doc(hidden)]
+pub mod transformable;
+#[doc(hidden)]
 pub mod triangle;
+pub mod triangulation;
+pub mod typed;
+pub mod ulps;
+#[doc(hidden)]
+pub mod vector2;
 #[doc(hidden)]
 pub mod vector2f;
 #[doc(hidden)]
 pub mod vector2i;
+#[doc(hidden)]
+pub mod vector2u;
+#[doc(hidden)]
+pub mod winding;
+#[cfg(feature = "wkt")]
+pub mod wkt;
@@ -3,15 +3,24 @@
 //! See the actual struct documentation for more information.
 
 use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+use crate::tolerance::Tolerance;
+use crate::ulps::approx_eq_ulps;
 use crate::vector2f::Vector2f;
 
 use std::f32::EPSILON;
 use std::ops::Mul;
 
 #[cfg(all(windows, feature = "d2d"))]
-use winapi::um::dcommon::D2D_MATRIX_3X2_F;
+use winapi::um::dcommon::{D2D_MATRIX_3X2_F, D2D_MATRIX_4X4_F};
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::dwrite::DWRITE_MATRIX;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Foundation::Numerics::{Matrix3x2, Matrix4x4};
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D::Common as d2d_common;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::DirectWrite as dwrite;
 
 /// The 2D affine identity matrix.
 pub const IDENTITY: Matrix3x2f = Matrix3x2f::IDENTITY;
@@ -41,7 +50,20 @@ pub const IDENTITY: Matrix3x2f = Matrix3x2f::IDENTITY;
 /// This is also why points and vectors are the left-hand operand when multiplied
 /// with matrices.
 #[derive(Copy, Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde-compact",
+    serde(
+        into = "crate::compact::Array<[f32; 6]>",
+        from = "crate::compact::Array<[f32; 6]>"
+    )
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(C)]
 pub struct Matrix3x2f {
     /// Horizontal scaling / cosine of rotation
@@ -319,6 +341,43 @@ impl Matrix3x2f {
         point.into() * *self
     }
 
+    /// Offsets the point by the translation of the matrix, ignoring the rest
+    /// of it. This is only the full transformation when `is_translation`
+    /// holds.
+    #[inline]
+    pub fn apply_translation_only(&self, point: impl Into<Point2f>) -> Point2f {
+        let point = point.into();
+        Point2f {
+            x: point.x + self.x,
+            y: point.y + self.y,
+        }
+    }
+
+    /// The smallest axis-aligned rectangle containing `rect` after it has
+    /// been transformed. Translations and axis-aligned scales map the
+    /// rectangle exactly, while anything that rotates or skews it needs all
+    /// four corners to be transformed.
+    pub fn transform_rect(&self, rect: &Rectf) -> Rectf {
+        if self.is_translation() {
+            return rect.translated_by([self.x, self.y]);
+        }
+
+        let p1 = self.transform_point((rect.left, rect.top));
+        let p2 = self.transform_point((rect.right, rect.bottom));
+        if self.is_scale_translation() {
+            return Rectf::from_points(p1, p2);
+        }
+
+        let p3 = self.transform_point((rect.right, rect.top));
+        let p4 = self.transform_point((rect.left, rect.bottom));
+        Rectf {
+            left: p1.x.min(p2.x).min(p3.x).min(p4.x),
+            top: p1.y.min(p2.y).min(p3.y).min(p4.y),
+            right: p1.x.max(p2.x).max(p3.x).max(p4.x),
+            bottom: p1.y.max(p2.y).max(p3.y).max(p4.y),
+        }
+    }
+
     /// A more explicit way to do `vec * matrix`, while also allowing any type
     /// that may be converted into a Vector2F with a From/Into impl.
     #[inline]
@@ -326,6 +385,110 @@ impl Matrix3x2f {
         vec.into() * *self
     }
 
+    /// Transforms every point in the slice in place. The results are the
+    /// same as calling `transform_point` on each of them; with the `simd`
+    /// feature enabled, two points are transformed per instruction. Pure
+    /// translations only add the offset to each point.
+    #[inline]
+    pub fn transform_points(&self, points: &mut [Point2f]) {
+        if self.is_translation() {
+            for point in points {
+                *point = self.apply_translation_only(*point);
+            }
+            return;
+        }
+
+        #[cfg(feature = "simd")]
+        let points = {
+            // Point2f is #[repr(C)] with two f32 fields
+            let ptr = points.as_mut_ptr() as *mut f32;
+            let done = unsafe { crate::simd::transform(self, true, ptr, ptr, points.len()) };
+            &mut points[done..]
+        };
+
+        for point in points {
+            *point = *point * *self;
+        }
+    }
+
+    /// Transforms the points in `src`, writing the results to the same
+    /// positions in `dst`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two slices have different lengths.
+    #[inline]
+    pub fn transform_points_into(&self, src: &[Point2f], dst: &mut [Point2f]) {
+        assert_eq!(src.len(), dst.len(), "slices must have the same length");
+
+        if self.is_translation() {
+            for (out, point) in dst.iter_mut().zip(src) {
+                *out = self.apply_translation_only(*point);
+            }
+            return;
+        }
+
+        #[cfg(feature = "simd")]
+        let (src, dst) = {
+            let (from, to) = (src.as_ptr() as *const f32, dst.as_mut_ptr() as *mut f32);
+            let done = unsafe { crate::simd::transform(self, true, from, to, src.len()) };
+            (&src[done..], &mut dst[done..])
+        };
+
+        for (out, point) in dst.iter_mut().zip(src) {
+            *out = *point * *self;
+        }
+    }
+
+    /// Transforms every vector in the slice in place, ignoring the
+    /// translation like `transform_vector` does. Vectors are left untouched
+    /// by pure translations.
+    #[inline]
+    pub fn transform_vectors(&self, vectors: &mut [Vector2f]) {
+        if self.is_translation() {
+            return;
+        }
+
+        #[cfg(feature = "simd")]
+        let vectors = {
+            // Vector2f is #[repr(C)] with two f32 fields
+            let ptr = vectors.as_mut_ptr() as *mut f32;
+            let done = unsafe { crate::simd::transform(self, false, ptr, ptr, vectors.len()) };
+            &mut vectors[done..]
+        };
+
+        for vec in vectors {
+            *vec *= *self;
+        }
+    }
+
+    /// Transforms the vectors in `src`, writing the results to the same
+    /// positions in `dst`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two slices have different lengths.
+    #[inline]
+    pub fn transform_vectors_into(&self, src: &[Vector2f], dst: &mut [Vector2f]) {
+        assert_eq!(src.len(), dst.len(), "slices must have the same length");
+
+        if self.is_translation() {
+            dst.copy_from_slice(src);
+            return;
+        }
+
+        #[cfg(feature = "simd")]
+        let (src, dst) = {
+            let (from, to) = (src.as_ptr() as *const f32, dst.as_mut_ptr() as *mut f32);
+            let done = unsafe { crate::simd::transform(self, false, from, to, src.len()) };
+            (&src[done..], &mut dst[done..])
+        };
+
+        for (out, vec) in dst.iter_mut().zip(src) {
+            *out = *vec * *self;
+        }
+    }
+
     /// Returns this matrix as a 3x3 float array using the mathematical form
     /// described above.
     #[inline]
@@ -348,15 +511,46 @@ impl Matrix3x2f {
         ]
     }
 
-    /// Checks if two matrices are approximately equal given an epsilon value.
+    /// Returns the matrix embedded in a 4x4 row-major float array, leaving
+    /// z untouched. This is the form 3D and effect APIs expect for an
+    /// affine 2D transform.
     #[inline]
-    pub fn is_approx_eq(&self, other: &Matrix3x2f, epsilon: f32) -> bool {
-        return (self.a - other.a).abs() < epsilon
-            && (self.b - other.b).abs() < epsilon
-            && (self.c - other.c).abs() < epsilon
-            && (self.d - other.d).abs() < epsilon
-            && (self.x - other.x).abs() < epsilon
-            && (self.y - other.y).abs() < epsilon;
+    pub fn to_row_major_4x4(&self) -> [[f32; 4]; 4] {
+        [
+            [self.a, self.b, 0.0, 0.0],
+            [self.c, self.d, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [self.x, self.y, 0.0, 1.0],
+        ]
+    }
+
+    /// Checks if two matrices are approximately equal given an epsilon value,
+    /// which may be a plain `f32` or a `Tolerance`. A relative tolerance
+    /// scales with each component, which keeps large translations from
+    /// needing a looser epsilon than the linear part.
+    #[inline]
+    pub fn is_approx_eq(&self, other: &Matrix3x2f, epsilon: impl Into<Tolerance>) -> bool {
+        let epsilon = epsilon.into();
+        return epsilon.approx_eq(self.a, other.a)
+            && epsilon.approx_eq(self.b, other.b)
+            && epsilon.approx_eq(self.c, other.c)
+            && epsilon.approx_eq(self.d, other.d)
+            && epsilon.approx_eq(self.x, other.x)
+            && epsilon.approx_eq(self.y, other.y);
+    }
+
+    /// Checks if every component of two matrices is at most `max_ulps`
+    /// representable values apart. This suits matrices with large
+    /// translations, where an absolute epsilon is either too strict for the
+    /// translation or too loose for the linear part. See `math2d::ulps`.
+    #[inline]
+    pub fn is_approx_eq_ulps(&self, other: &Matrix3x2f, max_ulps: u32) -> bool {
+        approx_eq_ulps(self.a, other.a, max_ulps)
+            && approx_eq_ulps(self.b, other.b, max_ulps)
+            && approx_eq_ulps(self.c, other.c, max_ulps)
+            && approx_eq_ulps(self.d, other.d, max_ulps)
+            && approx_eq_ulps(self.x, other.x, max_ulps)
+            && approx_eq_ulps(self.y, other.y, max_ulps)
     }
 
     /// Checks if this matrix is equal to the identity matrix within 1e-5
@@ -365,10 +559,77 @@ impl Matrix3x2f {
         self.is_approx_eq(&Matrix3x2f::IDENTITY, 1e-5)
     }
 
+    /// Checks if the matrix does nothing but translate, i.e. its linear part
+    /// is exactly the identity. The identity matrix is also a translation.
+    #[inline]
+    pub fn is_translation(&self) -> bool {
+        self.a == 1.0 && self.b == 0.0 && self.c == 0.0 && self.d == 1.0
+    }
+
+    /// Checks if the matrix only scales along the axes and translates, i.e.
+    /// it has no rotation or skew. Such matrices keep rectangles aligned to
+    /// the axes.
+    #[inline]
+    pub fn is_scale_translation(&self) -> bool {
+        self.b == 0.0 && self.c == 0.0
+    }
+
+    /// Converts the matrix to an nalgebra similarity (uniform scale,
+    /// rotation and translation). Returns `None` if the matrix shears,
+    /// scales the axes by different amounts, reflects, or collapses space,
+    /// with `epsilon` as the tolerance on each of those checks.
+    #[cfg(feature = "nalgebra")]
+    #[inline]
+    pub fn to_similarity(&self, epsilon: f32) -> Option<nalgebra::Similarity2<f32>> {
+        let scale = (self.a * self.a + self.b * self.b).sqrt();
+        if (self.a - self.d).abs() > epsilon
+            || (self.b + self.c).abs() > epsilon
+            || scale <= epsilon
+        {
+            return None;
+        }
+
+        Some(nalgebra::Similarity2::from_parts(
+            nalgebra::Translation2::new(self.x, self.y),
+            nalgebra::UnitComplex::new(self.b.atan2(self.a)),
+            scale,
+        ))
+    }
+
+    /// Converts the matrix to an nalgebra isometry (rotation and
+    /// translation). Returns `None` where `to_similarity` would, or if the
+    /// scale differs from 1 by more than `epsilon`.
+    #[cfg(feature = "nalgebra")]
+    #[inline]
+    pub fn to_isometry(&self, epsilon: f32) -> Option<nalgebra::Isometry2<f32>> {
+        self.to_similarity(epsilon)
+            .filter(|sim| (sim.scaling() - 1.0).abs() <= epsilon)
+            .map(|sim| sim.isometry)
+    }
+
+    /// Converts the matrix to the 4x4 form used by Direct2D effects and
+    /// perspective transforms.
+    #[cfg(all(windows, feature = "d2d"))]
+    #[inline]
+    pub fn to_d2d_4x4(&self) -> D2D_MATRIX_4X4_F {
+        D2D_MATRIX_4X4_F {
+            matrix: self.to_row_major_4x4(),
+        }
+    }
+
     #[inline]
     fn det_shows_invertible(det: f32) -> bool {
         det.abs() > EPSILON
     }
+
+    /// Formats the matrix as an SVG `transform` attribute, e.g.
+    /// `transform="matrix(1 0 0 1 10 20)"`.
+    pub fn to_svg_attributes(&self) -> String {
+        format!(
+            r#"transform="matrix({} {} {} {} {} {})""#,
+            self.a, self.b, self.c, self.d, self.x, self.y
+        )
+    }
 }
 
 impl Mul for Matrix3x2f {
@@ -451,6 +712,14 @@ impl From<D2D_MATRIX_3X2_F> for Matrix3x2f {
     }
 }
 
+#[cfg(all(windows, feature = "d2d"))]
+impl From<Matrix3x2f> for D2D_MATRIX_4X4_F {
+    #[inline]
+    fn from(m: Matrix3x2f) -> D2D_MATRIX_4X4_F {
+        m.to_d2d_4x4()
+    }
+}
+
 #[cfg(all(windows, feature = "d2d"))]
 impl From<Matrix3x2f> for DWRITE_MATRIX {
     #[inline]
@@ -481,6 +750,108 @@ impl From<DWRITE_MATRIX> for Matrix3x2f {
     }
 }
 
+#[cfg(all(windows, feature = "windows"))]
+impl From<Matrix3x2f> for Matrix3x2 {
+    #[inline]
+    fn from(m: Matrix3x2f) -> Matrix3x2 {
+        Matrix3x2 {
+            M11: m.a,
+            M12: m.b,
+            M21: m.c,
+            M22: m.d,
+            M31: m.x,
+            M32: m.y,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<Matrix3x2> for Matrix3x2f {
+    #[inline]
+    fn from(m: Matrix3x2) -> Matrix3x2f {
+        Matrix3x2f {
+            a: m.M11,
+            b: m.M12,
+            c: m.M21,
+            d: m.M22,
+            x: m.M31,
+            y: m.M32,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<Matrix3x2f> for Matrix4x4 {
+    #[inline]
+    fn from(m: Matrix3x2f) -> Matrix4x4 {
+        let [[m11, m12, m13, m14], [m21, m22, m23, m24], [m31, m32, m33, m34], [m41, m42, m43, m44]] =
+            m.to_row_major_4x4();
+        Matrix4x4 {
+            M11: m11,
+            M12: m12,
+            M13: m13,
+            M14: m14,
+            M21: m21,
+            M22: m22,
+            M23: m23,
+            M24: m24,
+            M31: m31,
+            M32: m32,
+            M33: m33,
+            M34: m34,
+            M41: m41,
+            M42: m42,
+            M43: m43,
+            M44: m44,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<Matrix3x2f> for d2d_common::D2D_MATRIX_4X4_F {
+    #[inline]
+    fn from(m: Matrix3x2f) -> d2d_common::D2D_MATRIX_4X4_F {
+        let rows = m.to_row_major_4x4();
+        let mut flat = [0.0; 16];
+        for (i, row) in rows.iter().enumerate() {
+            flat[i * 4..i * 4 + 4].copy_from_slice(row);
+        }
+        d2d_common::D2D_MATRIX_4X4_F {
+            Anonymous: d2d_common::D2D_MATRIX_4X4_F_0 { m: flat },
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<Matrix3x2f> for dwrite::DWRITE_MATRIX {
+    #[inline]
+    fn from(m: Matrix3x2f) -> dwrite::DWRITE_MATRIX {
+        dwrite::DWRITE_MATRIX {
+            m11: m.a,
+            m12: m.b,
+            m21: m.c,
+            m22: m.d,
+            dx: m.x,
+            dy: m.y,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<dwrite::DWRITE_MATRIX> for Matrix3x2f {
+    #[inline]
+    fn from(m: dwrite::DWRITE_MATRIX) -> Matrix3x2f {
+        Matrix3x2f {
+            a: m.m11,
+            b: m.m12,
+            c: m.m21,
+            d: m.m22,
+            x: m.dx,
+            y: m.dy,
+        }
+    }
+}
+
 impl Default for Matrix3x2f {
     #[inline]
     fn default() -> Self {
@@ -490,6 +861,14 @@ impl Default for Matrix3x2f {
 
 /// Represents a decomposition of a non-skewing matrix i.e. one made up of
 /// only rotations, translations, and scalings.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct Decomposition {
     /// Total scaling applied in the transformation. This operation is applied
     /// first if the decomposition is recomposed.
@@ -521,6 +900,389 @@ impl From<Matrix3x2f> for mint::RowMatrix3x2<f32> {
     }
 }
 
+#[cfg(feature = "mint")]
+impl From<mint::RowMatrix3x2<f32>> for Matrix3x2f {
+    #[inline]
+    fn from(mat: mint::RowMatrix3x2<f32>) -> Matrix3x2f {
+        Matrix3x2f {
+            a: mat.x.x,
+            b: mat.x.y,
+            c: mat.y.x,
+            d: mat.y.y,
+            x: mat.z.x,
+            y: mat.z.y,
+        }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<Matrix3x2f> for mint::ColumnMatrix3x2<f32> {
+    #[inline]
+    fn from(mat: Matrix3x2f) -> mint::ColumnMatrix3x2<f32> {
+        mint::ColumnMatrix3x2 {
+            x: [mat.a, mat.c, mat.x].into(),
+            y: [mat.b, mat.d, mat.y].into(),
+        }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<mint::ColumnMatrix3x2<f32>> for Matrix3x2f {
+    #[inline]
+    fn from(mat: mint::ColumnMatrix3x2<f32>) -> Matrix3x2f {
+        Matrix3x2f {
+            a: mat.x.x,
+            b: mat.y.x,
+            c: mat.x.y,
+            d: mat.y.y,
+            x: mat.x.z,
+            y: mat.y.z,
+        }
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<Src, Dst> From<Matrix3x2f> for euclid::Transform2D<f32, Src, Dst> {
+    #[inline]
+    fn from(mat: Matrix3x2f) -> euclid::Transform2D<f32, Src, Dst> {
+        euclid::Transform2D::new(mat.a, mat.b, mat.c, mat.d, mat.x, mat.y)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<Src, Dst> From<euclid::Transform2D<f32, Src, Dst>> for Matrix3x2f {
+    #[inline]
+    fn from(t: euclid::Transform2D<f32, Src, Dst>) -> Matrix3x2f {
+        Matrix3x2f {
+            a: t.m11,
+            b: t.m12,
+            c: t.m21,
+            d: t.m22,
+            x: t.m31,
+            y: t.m32,
+        }
+    }
+}
+
+// glam multiplies column vectors, so the rows of this matrix are the columns
+// of glam's.
+
+// kurbo's affine coefficients are in the same order as the fields here.
+
+#[cfg(feature = "kurbo")]
+impl From<Matrix3x2f> for kurbo::Affine {
+    #[inline]
+    fn from(mat: Matrix3x2f) -> kurbo::Affine {
+        kurbo::Affine::new([
+            mat.a.into(),
+            mat.b.into(),
+            mat.c.into(),
+            mat.d.into(),
+            mat.x.into(),
+            mat.y.into(),
+        ])
+    }
+}
+
+#[cfg(feature = "kurbo")]
+impl From<kurbo::Affine> for Matrix3x2f {
+    #[inline]
+    fn from(mat: kurbo::Affine) -> Matrix3x2f {
+        let [a, b, c, d, x, y] = mat.as_coeffs();
+        Matrix3x2f {
+            a: a as f32,
+            b: b as f32,
+            c: c as f32,
+            d: d as f32,
+            x: x as f32,
+            y: y as f32,
+        }
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<Matrix3x2f> for glam::Affine2 {
+    #[inline]
+    fn from(mat: Matrix3x2f) -> glam::Affine2 {
+        glam::Affine2::from_cols_array(&[mat.a, mat.b, mat.c, mat.d, mat.x, mat.y])
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::Affine2> for Matrix3x2f {
+    #[inline]
+    fn from(affine: glam::Affine2) -> Matrix3x2f {
+        let [a, b, c, d, x, y] = affine.to_cols_array();
+        Matrix3x2f { a, b, c, d, x, y }
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<Matrix3x2f> for glam::Mat3 {
+    #[inline]
+    fn from(mat: Matrix3x2f) -> glam::Mat3 {
+        glam::Mat3::from_cols_array(&[mat.a, mat.b, 0.0, mat.c, mat.d, 0.0, mat.x, mat.y, 1.0])
+    }
+}
+
+/// Takes the affine part of the matrix. Any projective terms in the bottom
+/// row are dropped.
+#[cfg(feature = "glam")]
+impl From<glam::Mat3> for Matrix3x2f {
+    #[inline]
+    fn from(mat: glam::Mat3) -> Matrix3x2f {
+        Matrix3x2f {
+            a: mat.x_axis.x,
+            b: mat.x_axis.y,
+            c: mat.y_axis.x,
+            d: mat.y_axis.y,
+            x: mat.z_axis.x,
+            y: mat.z_axis.y,
+        }
+    }
+}
+
+// nalgebra multiplies column vectors, so its matrix is the transpose of the
+// one described above.
+
+#[cfg(feature = "nalgebra")]
+impl From<Matrix3x2f> for nalgebra::Matrix3<f32> {
+    #[inline]
+    fn from(mat: Matrix3x2f) -> nalgebra::Matrix3<f32> {
+        nalgebra::Matrix3::new(mat.a, mat.c, mat.x, mat.b, mat.d, mat.y, 0.0, 0.0, 1.0)
+    }
+}
+
+/// Takes the affine part of the matrix. Any projective terms in the bottom
+/// row are dropped.
+#[cfg(feature = "nalgebra")]
+impl From<nalgebra::Matrix3<f32>> for Matrix3x2f {
+    #[inline]
+    fn from(mat: nalgebra::Matrix3<f32>) -> Matrix3x2f {
+        Matrix3x2f {
+            a: mat[(0, 0)],
+            b: mat[(1, 0)],
+            c: mat[(0, 1)],
+            d: mat[(1, 1)],
+            x: mat[(0, 2)],
+            y: mat[(1, 2)],
+        }
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl From<nalgebra::Isometry2<f32>> for Matrix3x2f {
+    #[inline]
+    fn from(iso: nalgebra::Isometry2<f32>) -> Matrix3x2f {
+        iso.to_homogeneous().into()
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl From<nalgebra::Similarity2<f32>> for Matrix3x2f {
+    #[inline]
+    fn from(sim: nalgebra::Similarity2<f32>) -> Matrix3x2f {
+        sim.to_homogeneous().into()
+    }
+}
+
+#[cfg(feature = "cairo")]
+impl From<Matrix3x2f> for cairo::Matrix {
+    #[inline]
+    fn from(mat: Matrix3x2f) -> cairo::Matrix {
+        cairo::Matrix::new(
+            mat.a.into(),
+            mat.b.into(),
+            mat.c.into(),
+            mat.d.into(),
+            mat.x.into(),
+            mat.y.into(),
+        )
+    }
+}
+
+#[cfg(feature = "cairo")]
+impl From<cairo::Matrix> for Matrix3x2f {
+    #[inline]
+    fn from(mat: cairo::Matrix) -> Matrix3x2f {
+        Matrix3x2f {
+            a: mat.xx() as f32,
+            b: mat.yx() as f32,
+            c: mat.xy() as f32,
+            d: mat.yy() as f32,
+            x: mat.x0() as f32,
+            y: mat.y0() as f32,
+        }
+    }
+}
+
+#[cfg(feature = "skia")]
+impl From<Matrix3x2f> for skia_safe::Matrix {
+    #[inline]
+    fn from(mat: Matrix3x2f) -> skia_safe::Matrix {
+        skia_safe::Matrix::new_all(mat.a, mat.c, mat.x, mat.b, mat.d, mat.y, 0.0, 0.0, 1.0)
+    }
+}
+
+/// Takes the affine part of the matrix. Any perspective terms are dropped.
+#[cfg(feature = "skia")]
+impl From<skia_safe::Matrix> for Matrix3x2f {
+    #[inline]
+    fn from(mat: skia_safe::Matrix) -> Matrix3x2f {
+        Matrix3x2f {
+            a: mat.scale_x(),
+            b: mat.skew_y(),
+            c: mat.skew_x(),
+            d: mat.scale_y(),
+            x: mat.translate_x(),
+            y: mat.translate_y(),
+        }
+    }
+}
+
+#[cfg(feature = "tiny-skia")]
+impl From<Matrix3x2f> for tiny_skia::Transform {
+    #[inline]
+    fn from(mat: Matrix3x2f) -> tiny_skia::Transform {
+        tiny_skia::Transform::from_row(mat.a, mat.b, mat.c, mat.d, mat.x, mat.y)
+    }
+}
+
+#[cfg(feature = "tiny-skia")]
+impl From<tiny_skia::Transform> for Matrix3x2f {
+    #[inline]
+    fn from(t: tiny_skia::Transform) -> Matrix3x2f {
+        Matrix3x2f {
+            a: t.sx,
+            b: t.ky,
+            c: t.kx,
+            d: t.sy,
+            x: t.tx,
+            y: t.ty,
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl Matrix3x2f {
+    /// Parallel version of `transform_points`, which hands chunks of the
+    /// slice out to the rayon thread pool.
+    #[inline]
+    pub fn par_transform_points(&self, points: &mut [Point2f]) {
+        use rayon::prelude::*;
+
+        points
+            .par_chunks_mut(crate::polygon::PAR_CHUNK_LEN)
+            .for_each(|chunk| self.transform_points(chunk));
+    }
+
+    /// Parallel version of `transform_points_into`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two slices have different lengths.
+    #[inline]
+    pub fn par_transform_points_into(&self, src: &[Point2f], dst: &mut [Point2f]) {
+        use rayon::prelude::*;
+
+        assert_eq!(src.len(), dst.len(), "slices must have the same length");
+        src.par_chunks(crate::polygon::PAR_CHUNK_LEN)
+            .zip(dst.par_chunks_mut(crate::polygon::PAR_CHUNK_LEN))
+            .for_each(|(src, dst)| self.transform_points_into(src, dst));
+    }
+
+    /// Parallel version of `transform_vectors`.
+    #[inline]
+    pub fn par_transform_vectors(&self, vectors: &mut [Vector2f]) {
+        use rayon::prelude::*;
+
+        vectors
+            .par_chunks_mut(crate::polygon::PAR_CHUNK_LEN)
+            .for_each(|chunk| self.transform_vectors(chunk));
+    }
+
+    /// Parallel version of `transform_vectors_into`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two slices have different lengths.
+    #[inline]
+    pub fn par_transform_vectors_into(&self, src: &[Vector2f], dst: &mut [Vector2f]) {
+        use rayon::prelude::*;
+
+        assert_eq!(src.len(), dst.len(), "slices must have the same length");
+        src.par_chunks(crate::polygon::PAR_CHUNK_LEN)
+            .zip(dst.par_chunks_mut(crate::polygon::PAR_CHUNK_LEN))
+            .for_each(|(src, dst)| self.transform_vectors_into(src, dst));
+    }
+}
+
+#[cfg(feature = "web")]
+impl Matrix3x2f {
+    /// Returns the matrix components in the order taken by the canvas
+    /// `setTransform` and `transform` methods.
+    #[inline]
+    pub fn to_canvas_transform(&self) -> [f64; 6] {
+        [self.a, self.b, self.c, self.d, self.x, self.y].map(f64::from)
+    }
+
+    /// Constructs a 2D DOM matrix with the same components. This fails with
+    /// the exception thrown by the constructor, e.g. when there is no DOM.
+    #[inline]
+    pub fn to_dom_matrix(&self) -> Result<web_sys::DomMatrix, web_sys::wasm_bindgen::JsValue> {
+        web_sys::DomMatrix::new_with_array64(&mut self.to_canvas_transform())
+    }
+}
+
+/// DOM matrices may be 3D, in which case only the 2D components `a`
+/// through `f` are kept.
+#[cfg(feature = "web")]
+impl<'a> From<&'a web_sys::DomMatrix> for Matrix3x2f {
+    #[inline]
+    fn from(m: &'a web_sys::DomMatrix) -> Matrix3x2f {
+        Matrix3x2f {
+            a: m.a() as f32,
+            b: m.b() as f32,
+            c: m.c() as f32,
+            d: m.d() as f32,
+            x: m.e() as f32,
+            y: m.f() as f32,
+        }
+    }
+}
+
+// The struct is `#[repr(C)]` with six `f32` fields, which is the layout of
+// its three columns as `[[f32; 2]; 3]`.
+#[cfg(feature = "encase")]
+impl encase::matrix::AsRefMatrixParts<f32, 3, 2> for Matrix3x2f {
+    #[inline]
+    fn as_ref_parts(&self) -> &[[f32; 2]; 3] {
+        unsafe { &*(self as *const Matrix3x2f as *const [[f32; 2]; 3]) }
+    }
+}
+
+#[cfg(feature = "encase")]
+impl encase::matrix::AsMutMatrixParts<f32, 3, 2> for Matrix3x2f {
+    #[inline]
+    fn as_mut_parts(&mut self) -> &mut [[f32; 2]; 3] {
+        unsafe { &mut *(self as *mut Matrix3x2f as *mut [[f32; 2]; 3]) }
+    }
+}
+
+#[cfg(feature = "encase")]
+impl encase::matrix::FromMatrixParts<f32, 3, 2> for Matrix3x2f {
+    #[inline]
+    fn from_parts(parts: [[f32; 2]; 3]) -> Matrix3x2f {
+        Matrix3x2f::new(parts)
+    }
+}
+
+// The matrix is laid out as a `mat3x2<f32>`, whose columns are (a, b),
+// (c, d) and (x, y). Multiplying it with `vec3(p, 1.0)` in a shader gives the
+// same result as `transform_point`.
+#[cfg(feature = "encase")]
+encase::impl_matrix!(3, 2, Matrix3x2f, f32);
+
 #[cfg(all(test, windows, feature = "d2d"))]
 #[test]
 fn mat32_d2d_bin_compat() {
@@ -542,6 +1304,27 @@ fn mat32_d2d_bin_compat() {
     assert_eq!(size_of_val(&mat), size_of_val(d2d));
 }
 
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn mat32_windows_bin_compat() {
+    use std::mem::size_of_val;
+
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
+    }
+
+    let mat = Matrix3x2f::IDENTITY;
+    let numerics = unsafe { &*((&mat) as *const _ as *const Matrix3x2) };
+
+    assert!(ptr_eq(&mat.a, &numerics.M11));
+    assert!(ptr_eq(&mat.b, &numerics.M12));
+    assert!(ptr_eq(&mat.c, &numerics.M21));
+    assert!(ptr_eq(&mat.d, &numerics.M22));
+    assert!(ptr_eq(&mat.x, &numerics.M31));
+    assert!(ptr_eq(&mat.y, &numerics.M32));
+    assert_eq!(size_of_val(&mat), size_of_val(numerics));
+}
+
 #[test]
 fn rotation_centering() {
     use rand::{Rng, SeedableRng, XorShiftRng};
@@ -562,3 +1345,328 @@ fn rotation_centering() {
         assert!(m1.is_approx_eq(&m2, 0.0001));
     }
 }
+
+#[cfg(test)]
+#[test]
+fn special_cased_transforms() {
+    use crate::point2f::Point2f;
+
+    let p = Point2f::new(3.5, -2.25);
+    let translation = Matrix3x2f::translation([10.0, -4.0]);
+    let scaling = Matrix3x2f::scaling([2.0, -0.5], (1.0, 1.0)) * translation;
+    let rotation = Matrix3x2f::rotation(0.5, (1.0, 0.0));
+
+    assert!(IDENTITY.is_translation() && translation.is_translation());
+    assert!(!scaling.is_translation() && scaling.is_scale_translation());
+    assert!(!rotation.is_scale_translation());
+
+    // The fast paths give the same results as the full multiplication
+    for m in &[IDENTITY, translation, scaling, rotation] {
+        assert_eq!(m.transform_point(p), p * *m);
+    }
+    assert_eq!(
+        translation.apply_translation_only(p),
+        Point2f::new(13.5, -6.25)
+    );
+
+    let rect = Rectf::new(-1.0, 2.0, 4.0, 3.0);
+    assert_eq!(
+        translation.transform_rect(&rect),
+        Rectf::new(9.0, -2.0, 14.0, -1.0)
+    );
+    // Flipping the y axis swaps the top and bottom edges
+    assert_eq!(
+        scaling.transform_rect(&rect),
+        Rectf::new(7.0, -4.0, 17.0, -3.5)
+    );
+
+    let quarter = Matrix3x2f::rotation(std::f32::consts::FRAC_PI_2, (0.0, 0.0));
+    let rotated = quarter.transform_rect(&rect);
+    let expected = [-3.0, -1.0, -2.0, 4.0];
+    let actual = [rotated.left, rotated.top, rotated.right, rotated.bottom];
+    for (a, e) in actual.iter().zip(&expected) {
+        assert!((a - e).abs() < 1e-5);
+    }
+}
+
+#[cfg(all(test, feature = "serde", not(feature = "serde-compact")))]
+#[test]
+fn decomposition_serde() {
+    use serde_test::{assert_tokens, Token};
+
+    let decomp = Decomposition {
+        scaling: [2.0, 3.0].into(),
+        rotation: 0.5,
+        translation: [0.0, -1.0].into(),
+    };
+    assert_tokens(
+        &decomp,
+        &[
+            Token::Struct {
+                name: "Decomposition",
+                len: 3,
+            },
+            Token::Str("scaling"),
+            Token::Struct {
+                name: "Vector2",
+                len: 2,
+            },
+            Token::Str("x"),
+            Token::F32(2.0),
+            Token::Str("y"),
+            Token::F32(3.0),
+            Token::StructEnd,
+            Token::Str("rotation"),
+            Token::F32(0.5),
+            Token::Str("translation"),
+            Token::Struct {
+                name: "Vector2",
+                len: 2,
+            },
+            Token::Str("x"),
+            Token::F32(0.0),
+            Token::Str("y"),
+            Token::F32(-1.0),
+            Token::StructEnd,
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn row_major_4x4() {
+    let m = Matrix3x2f::rotation(0.5, (3.0, 4.0)) * Matrix3x2f::scaling([2.0, 3.0], (0.0, 0.0));
+    let p = Point2f::new(5.0, -2.0);
+    let rows = m.to_row_major_4x4();
+
+    let v = [p.x, p.y, 7.0, 1.0];
+    let mut out = [0.0; 4];
+    for (col, o) in out.iter_mut().enumerate() {
+        *o = (0..4).map(|row| v[row] * rows[row][col]).sum();
+    }
+
+    let expected = m.transform_point(p);
+    assert!((out[0] - expected.x).abs() < 1e-5);
+    assert!((out[1] - expected.y).abs() < 1e-5);
+    assert_eq!(out[2], 7.0);
+    assert_eq!(out[3], 1.0);
+}
+
+#[cfg(all(test, feature = "web"))]
+#[test]
+fn canvas_transform() {
+    let m = Matrix3x2f::new([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+    assert_eq!(m.to_canvas_transform(), [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+}
+
+#[cfg(test)]
+#[test]
+fn batch_transforms() {
+    use crate::point2f::Point2f;
+
+    let mat = Matrix3x2f::rotation(0.3, (1.0, 2.0))
+        * Matrix3x2f::scaling([1.5, -0.25], (0.0, 0.0))
+        * Matrix3x2f::translation([-4.0, 7.5]);
+
+    // An odd count leaves one point for the scalar path
+    let points: Vec<Point2f> = (0..7)
+        .map(|i| Point2f::new(i as f32 * 1.7 - 3.0, -0.0 - i as f32 * 0.3))
+        .collect();
+    let expected: Vec<Point2f> = points.iter().map(|&p| mat.transform_point(p)).collect();
+
+    let mut in_place = points.clone();
+    mat.transform_points(&mut in_place);
+    assert_eq!(in_place, expected);
+
+    let mut into = vec![Point2f::ORIGIN; points.len()];
+    mat.transform_points_into(&points, &mut into);
+    assert_eq!(into, expected);
+
+    let vectors: Vec<Vector2f> = points.iter().map(|p| p.to_vector()).collect();
+    let expected: Vec<Vector2f> = vectors.iter().map(|&v| mat.transform_vector(v)).collect();
+
+    let mut in_place = vectors.clone();
+    mat.transform_vectors(&mut in_place);
+    assert_eq!(in_place, expected);
+
+    let mut into = vec![Vector2f::ZERO; vectors.len()];
+    mat.transform_vectors_into(&vectors, &mut into);
+    assert_eq!(into, expected);
+}
+
+#[cfg(all(test, feature = "rayon"))]
+#[test]
+fn par_batch_transforms() {
+    use crate::point2f::Point2f;
+
+    let mat = Matrix3x2f::rotation(1.1, (4.0, -2.0)) * Matrix3x2f::translation([0.5, 3.0]);
+    let points: Vec<Point2f> = (0..10_001)
+        .map(|i| Point2f::new(i as f32 * 0.25, (i % 13) as f32))
+        .collect();
+    let mut expected = points.clone();
+    mat.transform_points(&mut expected);
+
+    let mut in_place = points.clone();
+    mat.par_transform_points(&mut in_place);
+    assert_eq!(in_place, expected);
+
+    let mut into = vec![Point2f::ORIGIN; points.len()];
+    mat.par_transform_points_into(&points, &mut into);
+    assert_eq!(into, expected);
+
+    let vectors: Vec<Vector2f> = points.iter().map(|p| p.to_vector()).collect();
+    let mut expected = vectors.clone();
+    mat.transform_vectors(&mut expected);
+
+    let mut in_place = vectors.clone();
+    mat.par_transform_vectors(&mut in_place);
+    assert_eq!(in_place, expected);
+
+    let mut into = vec![Vector2f::ZERO; vectors.len()];
+    mat.par_transform_vectors_into(&vectors, &mut into);
+    assert_eq!(into, expected);
+}
+
+#[cfg(all(test, feature = "mint"))]
+#[test]
+fn mint_conversions() {
+    let m = Matrix3x2f::new([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+
+    let row: mint::RowMatrix3x2<f32> = m.into();
+    assert_eq!(Matrix3x2f::from(row), m);
+
+    let col: mint::ColumnMatrix3x2<f32> = m.into();
+    assert_eq!(col.x, [1.0, 3.0, 5.0].into());
+    assert_eq!(Matrix3x2f::from(col), m);
+
+    // Both layouts describe the same matrix
+    assert_eq!(mint::ColumnMatrix3x2::from(row), col);
+}
+
+#[cfg(all(test, feature = "encase"))]
+#[test]
+// The derive emits layout check functions that are never called
+#[allow(dead_code)]
+fn encase_layout() {
+    use crate::color::Color;
+    use crate::point2f::Point2f;
+    use crate::rectf::Rectf;
+    use crate::sizef::Sizef;
+    use crate::vector2f::Vector2f;
+    use encase::{ShaderType, StorageBuffer, UniformBuffer};
+
+    #[derive(ShaderType, Debug, PartialEq)]
+    struct Uniforms {
+        transform: Matrix3x2f,
+        origin: Point2f,
+        offset: Vector2f,
+        size: Sizef,
+        bounds: Rectf,
+        color: Color,
+    }
+
+    let uniforms = Uniforms {
+        transform: Matrix3x2f::new([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]),
+        origin: Point2f::new(7.0, 8.0),
+        offset: Vector2f::new(9.0, 10.0),
+        size: Sizef::new(11.0, 12.0),
+        bounds: Rectf::new(13.0, 14.0, 15.0, 16.0),
+        color: Color::new(0.1, 0.2, 0.3, 0.4),
+    };
+
+    fn floats(bytes: &[u8]) -> Vec<f32> {
+        bytes
+            .chunks(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect()
+    }
+
+    // The vec4 members are aligned to 16 bytes, which leaves a gap after the
+    // vec2 members
+    let mut uniform = UniformBuffer::new(Vec::new());
+    uniform.write(&uniforms).unwrap();
+    let bytes = uniform.into_inner();
+    assert_eq!(Uniforms::min_size().get(), 80);
+    assert_eq!(bytes.len(), 80);
+    assert_eq!(
+        floats(&bytes[..48]),
+        [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0]
+    );
+    assert_eq!(
+        floats(&bytes[48..]),
+        [13.0, 14.0, 15.0, 16.0, 0.1, 0.2, 0.3, 0.4]
+    );
+
+    let mut storage = StorageBuffer::new(Vec::new());
+    storage.write(&uniforms).unwrap();
+    let bytes = storage.into_inner();
+    assert_eq!(bytes.len(), 80);
+
+    let read: Uniforms = StorageBuffer::new(bytes).create().unwrap();
+    assert_eq!(read, uniforms);
+}
+
+#[cfg(all(test, feature = "glam"))]
+#[test]
+fn glam_conversions() {
+    use crate::point2f::Point2f;
+
+    let mat = Matrix3x2f::rotation(0.7, (3.0, -1.0)) * Matrix3x2f::translation([5.0, 2.0]);
+    let p = Point2f::new(4.0, 9.0);
+    let expected = mat.transform_point(p);
+
+    let affine = glam::Affine2::from(mat);
+    let q: Point2f = affine.transform_point2(p.into()).into();
+    assert!(q.is_approx_eq(expected, 1e-4));
+    assert_eq!(Matrix3x2f::from(affine), mat);
+
+    let mat3 = glam::Mat3::from(mat);
+    let q: Point2f = mat3.transform_point2(p.into()).into();
+    assert!(q.is_approx_eq(expected, 1e-4));
+    assert_eq!(Matrix3x2f::from(mat3), mat);
+}
+
+#[cfg(all(test, feature = "nalgebra"))]
+#[test]
+fn nalgebra_conversions() {
+    use crate::point2f::Point2f;
+
+    let mat = Matrix3x2f::scaling([2.0, 2.0], (0.0, 0.0))
+        * Matrix3x2f::rotation(0.7, (3.0, -1.0))
+        * Matrix3x2f::translation([5.0, 2.0]);
+    let p = Point2f::new(4.0, 9.0);
+    let expected = mat.transform_point(p);
+
+    let homogeneous = nalgebra::Matrix3::from(mat);
+    assert_eq!(Matrix3x2f::from(homogeneous), mat);
+
+    let sim = mat.to_similarity(1e-5).unwrap();
+    let q: Point2f = (sim * nalgebra::Point2::from(p)).into();
+    assert!(q.is_approx_eq(expected, 1e-4));
+    assert!(Matrix3x2f::from(sim).is_approx_eq(&mat, 1e-4));
+    assert!(mat.to_isometry(1e-5).is_none());
+
+    let iso = Matrix3x2f::rotation(0.7, (3.0, -1.0))
+        .to_isometry(1e-5)
+        .unwrap();
+    assert!(Matrix3x2f::from(iso).is_approx_eq(&Matrix3x2f::rotation(0.7, (3.0, -1.0)), 1e-4));
+
+    let sheared = Matrix3x2f::skew(0.3, 0.0, (0.0, 0.0));
+    assert!(sheared.to_similarity(1e-5).is_none());
+    assert!(Matrix3x2f::scaling([1.0, -1.0], (0.0, 0.0))
+        .to_similarity(1e-5)
+        .is_none());
+}
+
+#[cfg(all(test, feature = "skia"))]
+#[test]
+fn skia_conversions() {
+    use crate::point2f::Point2f;
+
+    let mat = Matrix3x2f::rotation(0.7, (3.0, -1.0)) * Matrix3x2f::translation([5.0, 2.0]);
+    let p = Point2f::new(4.0, 9.0);
+    let sk = skia_safe::Matrix::from(mat);
+    let q: Point2f = sk.map_point(p).into();
+    assert!(q.is_approx_eq(mat.transform_point(p), 1e-4));
+    assert_eq!(Matrix3x2f::from(sk), mat);
+}
@@ -0,0 +1,231 @@
+//! Morphing between paths, e.g. for icon transition animations.
+//!
+//! Two paths can only be interpolated point by point if they have the same
+//! structure. `make_compatible` converts any two paths into equivalent ones
+//! which do, and `Path::morph_compatible` interpolates between them.
+
+use crate::bezier_segment::BezierSegment;
+use crate::lerp::Lerp;
+use crate::path::{Figure, Path, PathSegment};
+use crate::point2f::Point2f;
+
+use std::f32::consts::PI;
+
+/// A figure as a chain of cubic bezier segments.
+struct Chain {
+    start: Point2f,
+    curves: Vec<BezierSegment>,
+    closed: bool,
+}
+
+impl Chain {
+    /// Closed figures gain an explicit closing segment, so that the closing
+    /// edge morphs along with the others.
+    fn new(figure: &Figure) -> Chain {
+        let mut curves = Vec::with_capacity(figure.segments.len());
+        for (start, segment) in figure.segments() {
+            match *segment {
+                PathSegment::Line(p) => curves.push(line(start, p)),
+                PathSegment::Bezier(bez) => curves.push(bez),
+                PathSegment::QuadBezier(ref quad) => curves.push(quad.to_cubic(start)),
+                PathSegment::Arc(ref arc) => curves.extend(arc.to_beziers(start, PI / 2.0)),
+            }
+        }
+        let end = figure.end_point();
+        if figure.closed && end != figure.start {
+            curves.push(line(end, figure.start));
+        }
+        Chain {
+            start: figure.start,
+            curves,
+            closed: figure.closed,
+        }
+    }
+
+    /// A figure with no extent, which grows out of a single point.
+    fn point(at: Point2f, closed: bool) -> Chain {
+        Chain {
+            start: at,
+            curves: Vec::new(),
+            closed,
+        }
+    }
+
+    fn start_of(&self, i: usize) -> Point2f {
+        match i {
+            0 => self.start,
+            _ => self.curves[i - 1].p3,
+        }
+    }
+
+    /// Halves the longest curves until there are `count` of them.
+    fn subdivide(&mut self, count: usize) {
+        while self.curves.len() < count {
+            let longest = (0..self.curves.len()).max_by(|&i, &j| {
+                let (a, b) = (self.hull_length(i), self.hull_length(j));
+                a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+            });
+            match longest {
+                Some(i) => {
+                    let (head, tail) = self.curves[i].split_at(self.start_of(i), 0.5);
+                    self.curves[i] = tail;
+                    self.curves.insert(i, head);
+                }
+                None => {
+                    let p = self.start;
+                    self.curves.push(BezierSegment::new(p, p, p));
+                }
+            }
+        }
+    }
+
+    /// The length of the control polygon, which bounds the arc length.
+    fn hull_length(&self, i: usize) -> f32 {
+        let c = &self.curves[i];
+        (c.p1 - self.start_of(i)).len() + (c.p2 - c.p1).len() + (c.p3 - c.p2).len()
+    }
+
+    fn into_figure(self, closed: bool) -> Figure {
+        Figure {
+            start: self.start,
+            segments: self.curves.into_iter().map(PathSegment::Bezier).collect(),
+            closed,
+        }
+    }
+}
+
+#[inline]
+fn line(start: Point2f, end: Point2f) -> BezierSegment {
+    let third = (end - start) / 3.0;
+    BezierSegment::new(start + third, end - third, end)
+}
+
+/// Converts two paths into equivalent ones with the same structure, so
+/// that they can be interpolated with `Path::morph_compatible`.
+///
+/// Every segment becomes a cubic bezier, and closed figures gain an explicit
+/// closing segment. Figures are paired in order, and a path with fewer
+/// figures gains figures which grow out of the center of their partner.
+/// The longest segments of each pair of figures are then halved until both
+/// have the same number. A figure only stays closed if its partner is also
+/// closed, which keeps how it is filled but loses the join at the start
+/// point when stroked.
+///
+/// Figures are matched from their start points, so the morph looks best
+/// when corresponding figures start at corresponding points and wind the
+/// same way.
+pub fn make_compatible(a: &Path, b: &Path) -> (Path, Path) {
+    let count = a.figures.len().max(b.figures.len());
+    let (mut fa, mut fb) = (Vec::with_capacity(count), Vec::with_capacity(count));
+    for i in 0..count {
+        let (mut ca, mut cb) = match (a.figures.get(i), b.figures.get(i)) {
+            (Some(x), Some(y)) => (Chain::new(x), Chain::new(y)),
+            (Some(x), None) => (Chain::new(x), Chain::point(x.bounds().center(), x.closed)),
+            (None, Some(y)) => (Chain::point(y.bounds().center(), y.closed), Chain::new(y)),
+            (None, None) => unreachable!(),
+        };
+        let segments = ca.curves.len().max(cb.curves.len());
+        ca.subdivide(segments);
+        cb.subdivide(segments);
+        let closed = ca.closed && cb.closed;
+        fa.push(ca.into_figure(closed));
+        fb.push(cb.into_figure(closed));
+    }
+    (Path::from(fa), Path::from(fb))
+}
+
+/// Interpolates two segments of the same kind.
+fn morph_segment(a: &PathSegment, b: &PathSegment, t: f32) -> Option<PathSegment> {
+    let segment = match (*a, *b) {
+        (PathSegment::Line(p), PathSegment::Line(q)) => PathSegment::Line(p.lerp(&q, t)),
+        (PathSegment::Bezier(p), PathSegment::Bezier(q)) => PathSegment::Bezier(BezierSegment {
+            p1: p.p1.lerp(&q.p1, t),
+            p2: p.p2.lerp(&q.p2, t),
+            p3: p.p3.lerp(&q.p3, t),
+        }),
+        (PathSegment::QuadBezier(mut p), PathSegment::QuadBezier(q)) => {
+            p.p1 = p.p1.lerp(&q.p1, t);
+            p.p2 = p.p2.lerp(&q.p2, t);
+            PathSegment::QuadBezier(p)
+        }
+        (PathSegment::Arc(mut p), PathSegment::Arc(q))
+            if p.sweep_direction == q.sweep_direction && p.arc_size == q.arc_size =>
+        {
+            p.point = p.point.lerp(&q.point, t);
+            p.size = p.size.lerp(&q.size, t);
+            p.rotation_angle = p.rotation_angle.lerp(&q.rotation_angle, t);
+            PathSegment::Arc(p)
+        }
+        _ => return None,
+    };
+    Some(segment)
+}
+
+pub(crate) fn morph(a: &Path, b: &Path, t: f32) -> Option<Path> {
+    if a.figures.len() != b.figures.len() {
+        return None;
+    }
+    let mut figures = Vec::with_capacity(a.figures.len());
+    for (fa, fb) in a.figures.iter().zip(&b.figures) {
+        if fa.closed != fb.closed || fa.segments.len() != fb.segments.len() {
+            return None;
+        }
+        let segments = fa
+            .segments
+            .iter()
+            .zip(&fb.segments)
+            .map(|(sa, sb)| morph_segment(sa, sb, t))
+            .collect::<Option<Vec<_>>>()?;
+        figures.push(Figure {
+            start: fa.start.lerp(&fb.start, t),
+            segments,
+            closed: fa.closed,
+        });
+    }
+    Some(Path::from(figures))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ellipse::Ellipse;
+    use crate::morph::make_compatible;
+    use crate::path::Path;
+    use crate::rectf::Rectf;
+
+    #[test]
+    fn square_to_circle() {
+        let square = Path::from(Rectf::new(0.0, 0.0, 10.0, 10.0));
+        let circle = Path::from(Ellipse::new((5.0, 5.0), 5.0, 5.0));
+        assert!(square.morph_compatible(&circle, 0.5).is_none());
+
+        let (a, b) = make_compatible(&square, &circle);
+        assert_eq!(a.figures[0].segments.len(), b.figures[0].segments.len());
+        assert!(a.figures[0].closed && b.figures[0].closed);
+
+        // The endpoints of the morph match the original shapes
+        let close = |x: Rectf, y: Rectf| {
+            (x.left - y.left).abs() < 1e-4
+                && (x.top - y.top).abs() < 1e-4
+                && (x.right - y.right).abs() < 1e-4
+                && (x.bottom - y.bottom).abs() < 1e-4
+        };
+        let start = a.morph_compatible(&b, 0.0).unwrap();
+        assert!(close(start.bounds(), square.bounds()));
+        let end = a.morph_compatible(&b, 1.0).unwrap();
+        assert!(close(end.bounds(), circle.bounds()));
+        // Within the error of approximating the arcs with cubics
+        assert!((end.length(0.01) - circle.length(0.01)).abs() < 1e-2);
+
+        // A path with an extra figure grows it out of a point
+        let two = Path::from(vec![
+            square.figures[0].clone(),
+            Path::from(Rectf::new(20.0, 0.0, 30.0, 10.0)).figures[0].clone(),
+        ]);
+        let (a, b) = make_compatible(&circle, &two);
+        assert_eq!(a.figures.len(), 2);
+        let grown = a.morph_compatible(&b, 0.0).unwrap();
+        let speck = grown.figures[1].bounds();
+        assert_eq!(speck.size(), (0.0, 0.0).into());
+        assert_eq!(speck.center(), (25.0, 5.0).into());
+    }
+}
@@ -0,0 +1,346 @@
+//! Translation and scaling which keep a shape's type.
+//!
+//! Layout code mostly moves and resizes shapes, and wants to keep working
+//! with a `Rectf` or `Ellipse` afterwards. A full `Transformable` transform
+//! may rotate or skew, so it has to give up the axis-aligned types; these
+//! traits only allow the operations which every shape can represent.
+//!
+//! `Scale` shares the name of its method with `DpiScalable`, which scales
+//! about the origin. Code which imports both traits must call one of them
+//! through the trait, e.g. `Scale::scaled_by(&rect, 2.0, center)`.
+
+use crate::capsule::Capsule;
+use crate::circle::Circle;
+use crate::ellipse::Ellipse;
+use crate::geometry_group::{Geometry, GeometryGroup};
+use crate::matrix3x2f::Matrix3x2f;
+use crate::path::Path;
+use crate::point2f::Point2f;
+use crate::polygon::Polygon;
+use crate::polyline::Polyline;
+use crate::rectf::Rectf;
+use crate::rounded_rect::RoundedRect;
+use crate::triangle::Triangle;
+use crate::vector2f::Vector2f;
+
+/// Shapes which can be moved without changing their type.
+pub trait Offset {
+    /// Moves the shape by `offset`.
+    fn offset_by(&self, offset: Vector2f) -> Self;
+}
+
+/// Shapes which can be scaled about a point without changing their type.
+///
+/// Every shape can be scaled by a `Vector2f`, with separate horizontal and
+/// vertical factors, and is then also scalable by a single `f32`. Circles
+/// and capsules would stop being round under a nonuniform scale, so they can
+/// only be scaled by an `f32`.
+///
+/// Negative factors mirror the shape. Rectangles stay normalized and radii
+/// stay positive.
+pub trait Scale<S = Vector2f>: Sized {
+    /// Scales the shape by `scale`, keeping the point `about` fixed in place.
+    fn scaled_by(&self, scale: S, about: impl Into<Point2f>) -> Self;
+}
+
+impl<T: Scale<Vector2f>> Scale<f32> for T {
+    #[inline]
+    fn scaled_by(&self, scale: f32, about: impl Into<Point2f>) -> T {
+        Scale::<Vector2f>::scaled_by(self, Vector2f::new(scale, scale), about)
+    }
+}
+
+#[inline]
+fn scale_point(p: Point2f, scale: Vector2f, about: Point2f) -> Point2f {
+    Point2f::new(
+        about.x + (p.x - about.x) * scale.x,
+        about.y + (p.y - about.y) * scale.y,
+    )
+}
+
+impl Offset for Point2f {
+    #[inline]
+    fn offset_by(&self, offset: Vector2f) -> Point2f {
+        *self + offset
+    }
+}
+
+impl Scale for Point2f {
+    #[inline]
+    fn scaled_by(&self, scale: Vector2f, about: impl Into<Point2f>) -> Point2f {
+        scale_point(*self, scale, about.into())
+    }
+}
+
+impl Offset for Rectf {
+    #[inline]
+    fn offset_by(&self, offset: Vector2f) -> Rectf {
+        self.translated_by(offset)
+    }
+}
+
+impl Scale for Rectf {
+    #[inline]
+    fn scaled_by(&self, scale: Vector2f, about: impl Into<Point2f>) -> Rectf {
+        let about = about.into();
+        let p1 = scale_point(Point2f::new(self.left, self.top), scale, about);
+        let p2 = scale_point(Point2f::new(self.right, self.bottom), scale, about);
+        Rectf::from_points(p1, p2)
+    }
+}
+
+impl Offset for RoundedRect {
+    #[inline]
+    fn offset_by(&self, offset: Vector2f) -> RoundedRect {
+        RoundedRect {
+            rect: self.rect.translated_by(offset),
+            ..*self
+        }
+    }
+}
+
+impl Scale for RoundedRect {
+    #[inline]
+    fn scaled_by(&self, scale: Vector2f, about: impl Into<Point2f>) -> RoundedRect {
+        RoundedRect {
+            rect: Scale::<Vector2f>::scaled_by(&self.rect, scale, about),
+            radius_x: (self.radius_x * scale.x).abs(),
+            radius_y: (self.radius_y * scale.y).abs(),
+        }
+    }
+}
+
+impl Offset for Ellipse {
+    #[inline]
+    fn offset_by(&self, offset: Vector2f) -> Ellipse {
+        Ellipse {
+            center: self.center + offset,
+            ..*self
+        }
+    }
+}
+
+impl Scale for Ellipse {
+    #[inline]
+    fn scaled_by(&self, scale: Vector2f, about: impl Into<Point2f>) -> Ellipse {
+        Ellipse {
+            center: scale_point(self.center, scale, about.into()),
+            radius_x: (self.radius_x * scale.x).abs(),
+            radius_y: (self.radius_y * scale.y).abs(),
+        }
+    }
+}
+
+impl Offset for Circle {
+    #[inline]
+    fn offset_by(&self, offset: Vector2f) -> Circle {
+        Circle::new(self.center + offset, self.radius)
+    }
+}
+
+impl Scale<f32> for Circle {
+    #[inline]
+    fn scaled_by(&self, scale: f32, about: impl Into<Point2f>) -> Circle {
+        let center = scale_point(self.center, Vector2f::new(scale, scale), about.into());
+        Circle::new(center, (self.radius * scale).abs())
+    }
+}
+
+impl Offset for Capsule {
+    #[inline]
+    fn offset_by(&self, offset: Vector2f) -> Capsule {
+        Capsule::new(self.p1 + offset, self.p2 + offset, self.radius)
+    }
+}
+
+impl Scale<f32> for Capsule {
+    #[inline]
+    fn scaled_by(&self, scale: f32, about: impl Into<Point2f>) -> Capsule {
+        let (about, factors) = (about.into(), Vector2f::new(scale, scale));
+        Capsule::new(
+            scale_point(self.p1, factors, about),
+            scale_point(self.p2, factors, about),
+            (self.radius * scale).abs(),
+        )
+    }
+}
+
+impl Offset for Triangle {
+    #[inline]
+    fn offset_by(&self, offset: Vector2f) -> Triangle {
+        Triangle {
+            p1: self.p1 + offset,
+            p2: self.p2 + offset,
+            p3: self.p3 + offset,
+        }
+    }
+}
+
+impl Scale for Triangle {
+    #[inline]
+    fn scaled_by(&self, scale: Vector2f, about: impl Into<Point2f>) -> Triangle {
+        let about = about.into();
+        Triangle {
+            p1: scale_point(self.p1, scale, about),
+            p2: scale_point(self.p2, scale, about),
+            p3: scale_point(self.p3, scale, about),
+        }
+    }
+}
+
+impl Offset for Polygon {
+    #[inline]
+    fn offset_by(&self, offset: Vector2f) -> Polygon {
+        Polygon::new(self.points.iter().map(|&p| p + offset).collect::<Vec<_>>())
+    }
+}
+
+impl Scale for Polygon {
+    #[inline]
+    fn scaled_by(&self, scale: Vector2f, about: impl Into<Point2f>) -> Polygon {
+        let about = about.into();
+        let points: Vec<Point2f> = self
+            .points
+            .iter()
+            .map(|&p| scale_point(p, scale, about))
+            .collect();
+        Polygon::new(points)
+    }
+}
+
+impl Offset for Polyline {
+    #[inline]
+    fn offset_by(&self, offset: Vector2f) -> Polyline {
+        Polyline::new(self.points.iter().map(|&p| p + offset).collect::<Vec<_>>())
+    }
+}
+
+impl Scale for Polyline {
+    #[inline]
+    fn scaled_by(&self, scale: Vector2f, about: impl Into<Point2f>) -> Polyline {
+        let about = about.into();
+        let points: Vec<Point2f> = self
+            .points
+            .iter()
+            .map(|&p| scale_point(p, scale, about))
+            .collect();
+        Polyline::new(points)
+    }
+}
+
+impl Offset for Path {
+    #[inline]
+    fn offset_by(&self, offset: Vector2f) -> Path {
+        self.transformed(&Matrix3x2f::translation(offset))
+    }
+}
+
+impl Scale for Path {
+    #[inline]
+    fn scaled_by(&self, scale: Vector2f, about: impl Into<Point2f>) -> Path {
+        self.transformed(&Matrix3x2f::scaling(scale, about))
+    }
+}
+
+impl Offset for Geometry {
+    #[inline]
+    fn offset_by(&self, offset: Vector2f) -> Geometry {
+        match *self {
+            Geometry::Rect(ref rect) => Geometry::Rect(rect.offset_by(offset)),
+            Geometry::RoundedRect(ref rr) => Geometry::RoundedRect(rr.offset_by(offset)),
+            Geometry::Ellipse(ref e) => Geometry::Ellipse(e.offset_by(offset)),
+            Geometry::Path(ref path) => Geometry::Path(path.offset_by(offset)),
+            Geometry::Polygon(ref polygon) => Geometry::Polygon(polygon.offset_by(offset)),
+        }
+    }
+}
+
+impl Scale for Geometry {
+    #[inline]
+    fn scaled_by(&self, scale: Vector2f, about: impl Into<Point2f>) -> Geometry {
+        let about = about.into();
+        match *self {
+            Geometry::Rect(ref r) => Geometry::Rect(Scale::scaled_by(r, scale, about)),
+            Geometry::RoundedRect(ref rr) => {
+                Geometry::RoundedRect(Scale::scaled_by(rr, scale, about))
+            }
+            Geometry::Ellipse(ref e) => Geometry::Ellipse(Scale::scaled_by(e, scale, about)),
+            Geometry::Path(ref path) => Geometry::Path(Scale::scaled_by(path, scale, about)),
+            Geometry::Polygon(ref p) => Geometry::Polygon(Scale::scaled_by(p, scale, about)),
+        }
+    }
+}
+
+impl Offset for GeometryGroup {
+    #[inline]
+    fn offset_by(&self, offset: Vector2f) -> GeometryGroup {
+        GeometryGroup::new(
+            self.fill_rule,
+            self.geometries
+                .iter()
+                .map(|g| g.offset_by(offset))
+                .collect(),
+        )
+    }
+}
+
+impl Scale for GeometryGroup {
+    #[inline]
+    fn scaled_by(&self, scale: Vector2f, about: impl Into<Point2f>) -> GeometryGroup {
+        let about = about.into();
+        GeometryGroup::new(
+            self.fill_rule,
+            self.geometries
+                .iter()
+                .map(|g| Scale::scaled_by(g, scale, about))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circle::Circle;
+    use crate::ellipse::Ellipse;
+    use crate::offset::{Offset, Scale};
+    use crate::path::Path;
+    use crate::point2f::Point2f;
+    use crate::rectf::Rectf;
+    use crate::rounded_rect::RoundedRect;
+    use crate::vector2f::Vector2f;
+
+    #[test]
+    fn offsets_and_scales() {
+        let rect = Rectf::new(0.0, 0.0, 10.0, 20.0);
+        assert_eq!(
+            rect.offset_by(Vector2f::new(5.0, -5.0)),
+            Rectf::new(5.0, -5.0, 15.0, 15.0)
+        );
+        assert_eq!(
+            rect.scaled_by(2.0, rect.center()),
+            Rectf::new(-5.0, -10.0, 15.0, 30.0)
+        );
+
+        // Mirroring keeps the rect normalized
+        assert_eq!(
+            rect.scaled_by(Vector2f::new(-1.0, 0.5), (0.0, 0.0)),
+            Rectf::new(-10.0, 0.0, 0.0, 10.0)
+        );
+
+        let rr = RoundedRect::new(rect, 2.0, 4.0).scaled_by(Vector2f::new(-2.0, 1.0), (0.0, 0.0));
+        assert_eq!((rr.radius_x, rr.radius_y), (4.0, 4.0));
+
+        let e = Ellipse::new((1.0, 1.0), 1.0, 2.0).scaled_by(Vector2f::new(3.0, 0.5), (0.0, 0.0));
+        assert_eq!(e, Ellipse::new((3.0, 0.5), 3.0, 1.0));
+
+        let c = Circle::new((2.0, 2.0), 1.0).scaled_by(-2.0, Point2f::new(1.0, 1.0));
+        assert_eq!(c, Circle::new((-1.0, -1.0), 2.0));
+
+        let path =
+            Path::from(Ellipse::new((0.0, 0.0), 1.0, 1.0)).offset_by(Vector2f::new(3.0, 4.0));
+        let bounds = path.bounds();
+        assert!((bounds.left - 2.0).abs() < 1e-5 && (bounds.bottom - 5.0).abs() < 1e-5);
+        let scaled = path.scaled_by(2.0, (3.0, 4.0)).bounds();
+        assert!((scaled.left - 1.0).abs() < 1e-5 && (scaled.bottom - 6.0).abs() < 1e-5);
+    }
+}
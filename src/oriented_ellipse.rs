@@ -0,0 +1,193 @@
+//! An ellipse which may be rotated, so that it can represent any affine
+//! transformation of an `Ellipse` or `Circle`.
+
+use crate::ellipse::Ellipse;
+use crate::matrix3x2f::Matrix3x2f;
+use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+use crate::vector2f::Vector2f;
+
+use std::f32::consts::FRAC_PI_2;
+
+/// An ellipse whose x-radius lies along the direction `rotation` radians
+/// clockwise from the x axis (on a y-down screen), as with the rotation of
+/// `CenterArc`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[repr(C)]
+pub struct OrientedEllipse {
+    /// The center point of the ellipse.
+    pub center: Point2f,
+    /// The radius along the rotated x axis.
+    pub radius_x: f32,
+    /// The radius along the rotated y axis.
+    pub radius_y: f32,
+    /// The rotation of the x axis, in radians.
+    pub rotation: f32,
+}
+
+impl OrientedEllipse {
+    /// Constructs an ellipse from its components.
+    #[inline]
+    pub fn new(center: impl Into<Point2f>, rx: f32, ry: f32, rotation: f32) -> OrientedEllipse {
+        OrientedEllipse {
+            center: center.into(),
+            radius_x: rx,
+            radius_y: ry,
+            rotation,
+        }
+    }
+
+    /// Constructs the ellipse traced by `center + u*cos(t) + v*sin(t)`.
+    /// The radii `u` and `v` need not be perpendicular, which is how the
+    /// axes of an ellipse come out of a skewing transformation.
+    ///
+    /// The result has its x-radius along the longer principal axis, with a
+    /// rotation in `(-pi/2, pi/2]`, unless `u` and `v` are already
+    /// perpendicular, in which case the x-radius is along `u`.
+    pub fn from_conjugate_radii(
+        center: impl Into<Point2f>,
+        u: impl Into<Vector2f>,
+        v: impl Into<Vector2f>,
+    ) -> OrientedEllipse {
+        let (u, v) = (u.into(), v.into());
+        let dot = u.dot(v);
+        let (mut p1, p2) = if dot == 0.0 {
+            (u, v)
+        } else {
+            // The parameter at which the distance from the center is
+            // greatest, where the derivative of |u*cos(t) + v*sin(t)|^2 is 0
+            let t = 0.5 * (2.0 * dot).atan2(u.len_squared() - v.len_squared());
+            let (sin, cos) = t.sin_cos();
+            (u * cos + v * sin, v * cos - u * sin)
+        };
+        if p1.x < 0.0 || (p1.x == 0.0 && p1.y < 0.0) {
+            p1 = -p1;
+        }
+        OrientedEllipse {
+            center: center.into(),
+            radius_x: p1.len(),
+            radius_y: p2.len(),
+            rotation: p1.y.atan2(p1.x),
+        }
+    }
+
+    /// Determines if either radius of the ellipse is zero, or NaN, which
+    /// leaves it enclosing no area.
+    #[inline]
+    pub fn is_degenerate(&self) -> bool {
+        !(self.radius_x.abs() > 0.0 && self.radius_y.abs() > 0.0)
+    }
+
+    /// Checks if the ellipse contains a point.
+    #[inline]
+    pub fn contains_point(&self, point: impl Into<Point2f>) -> bool {
+        self.unrotated()
+            .contains_point(self.unrotate_point(point.into()))
+    }
+
+    /// The axis-aligned bounding box of the ellipse.
+    #[inline]
+    pub fn bounds(&self) -> Rectf {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (rx, ry) = (self.radius_x, self.radius_y);
+        let half_x = (rx * cos).hypot(ry * sin);
+        let half_y = (rx * sin).hypot(ry * cos);
+        Rectf::from_center_half_extent(self.center, [half_x, half_y])
+    }
+
+    /// The equivalent axis-aligned ellipse, if the rotation is a multiple of
+    /// a right angle within `(-pi/2, pi/2]` or the radii are equal.
+    #[inline]
+    pub fn to_ellipse(&self) -> Option<Ellipse> {
+        let (rx, ry) = (self.radius_x, self.radius_y);
+        if self.rotation == 0.0 || rx.abs() == ry.abs() {
+            Some(Ellipse::new(self.center, rx, ry))
+        } else if self.rotation == FRAC_PI_2 {
+            Some(Ellipse::new(self.center, ry, rx))
+        } else {
+            None
+        }
+    }
+
+    /// The ellipse with the rotation removed, about the same center.
+    #[inline]
+    pub(crate) fn unrotated(&self) -> Ellipse {
+        Ellipse::new(self.center, self.radius_x, self.radius_y)
+    }
+
+    /// Rotates a point about the center by the inverse of the ellipse's
+    /// rotation, into the frame of `unrotated`.
+    #[inline]
+    pub(crate) fn unrotate_point(&self, point: Point2f) -> Point2f {
+        let (sin, cos) = self.rotation.sin_cos();
+        let d = point - self.center;
+        self.center + [d.x * cos + d.y * sin, d.y * cos - d.x * sin]
+    }
+
+    /// The transformation which places `unrotated` onto this ellipse.
+    #[inline]
+    pub(crate) fn rotation_matrix(&self) -> Matrix3x2f {
+        Matrix3x2f::rotation(self.rotation, self.center)
+    }
+}
+
+impl From<Ellipse> for OrientedEllipse {
+    #[inline]
+    fn from(e: Ellipse) -> OrientedEllipse {
+        OrientedEllipse::new(e.center, e.radius_x.abs(), e.radius_y.abs(), 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ellipse::Ellipse;
+    use crate::oriented_ellipse::OrientedEllipse;
+    use crate::rectf::Rectf;
+
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    #[test]
+    fn conjugate_radii() {
+        // Perpendicular radii are taken as they are
+        let e = OrientedEllipse::from_conjugate_radii((1.0, 2.0), [0.0, 3.0], [-2.0, 0.0]);
+        assert_eq!(e, OrientedEllipse::new((1.0, 2.0), 3.0, 2.0, FRAC_PI_2));
+        assert_eq!(e.to_ellipse(), Some(Ellipse::new((1.0, 2.0), 2.0, 3.0)));
+        let e = OrientedEllipse::from_conjugate_radii((0.0, 0.0), [-2.0, 0.0], [0.0, 1.0]);
+        assert_eq!(e.to_ellipse(), Some(Ellipse::new((0.0, 0.0), 2.0, 1.0)));
+
+        // A unit circle sheared by x += y is an ellipse with radii of the
+        // golden ratio and its inverse
+        let e = OrientedEllipse::from_conjugate_radii((0.0, 0.0), [1.0, 0.0], [1.0, 1.0]);
+        let phi = (1.0 + 5f32.sqrt()) / 2.0;
+        assert!((e.radius_x - phi).abs() < 1e-5);
+        assert!((e.radius_y - 1.0 / phi).abs() < 1e-5);
+        assert!((e.rotation - (1.0 / phi).atan()).abs() < 1e-5);
+        assert_eq!(e.to_ellipse(), None);
+        assert!(e.contains_point((1.0, 0.9)));
+        assert!(!e.contains_point((0.0, 0.9)));
+    }
+
+    #[test]
+    fn rotated_bounds() {
+        let e = OrientedEllipse::new((10.0, 0.0), 2.0, 1.0, FRAC_PI_4);
+        let half = (2.5f32).sqrt();
+        let expected = Rectf::from_center_half_extent((10.0, 0.0), [half, half]);
+        let bounds = e.bounds();
+        assert!((bounds.left - expected.left).abs() < 1e-5);
+        assert!((bounds.bottom - expected.bottom).abs() < 1e-5);
+
+        // The x-radius points down and to the right
+        assert!(e.contains_point((11.3, 1.3)));
+        assert!(!e.contains_point((11.3, -1.3)));
+        assert!(OrientedEllipse::new((0.0, 0.0), 2.0, 2.0, 1.0)
+            .to_ellipse()
+            .is_some());
+    }
+}
@@ -0,0 +1,162 @@
+//! A rectangle which has been rotated, scaled or skewed, described by one
+//! corner and the two edges which meet there.
+
+use crate::point2f::Point2f;
+use crate::polygon::{self, Polygon};
+use crate::rectf::Rectf;
+use crate::vector2f::Vector2f;
+
+/// A parallelogram spanned by the edges `x_axis` and `y_axis` from the
+/// corner `origin`. This is what an axis-aligned rectangle becomes under an
+/// arbitrary affine transformation: `origin` is the image of its top left
+/// corner, and the axes are the images of its top and left edges.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[repr(C)]
+pub struct OrientedRect {
+    /// The corner which the axes start from.
+    pub origin: Point2f,
+    /// The edge from `origin` to the second corner.
+    pub x_axis: Vector2f,
+    /// The edge from `origin` to the fourth corner.
+    pub y_axis: Vector2f,
+}
+
+impl OrientedRect {
+    /// Constructs the rectangle from a corner and the two edges leaving it.
+    #[inline]
+    pub fn new(
+        origin: impl Into<Point2f>,
+        x_axis: impl Into<Vector2f>,
+        y_axis: impl Into<Vector2f>,
+    ) -> OrientedRect {
+        OrientedRect {
+            origin: origin.into(),
+            x_axis: x_axis.into(),
+            y_axis: y_axis.into(),
+        }
+    }
+
+    /// The four corners, starting at `origin` and following `x_axis` first,
+    /// in the same order `Path::from(Rectf)` traces an untransformed
+    /// rectangle.
+    #[inline]
+    pub fn corners(&self) -> [Point2f; 4] {
+        [
+            self.origin,
+            self.origin + self.x_axis,
+            self.origin + self.x_axis + self.y_axis,
+            self.origin + self.y_axis,
+        ]
+    }
+
+    /// The point halfway across both axes.
+    #[inline]
+    pub fn center(&self) -> Point2f {
+        self.origin + (self.x_axis + self.y_axis) / 2.0
+    }
+
+    /// The area enclosed by the rectangle, which is never negative.
+    #[inline]
+    pub fn area(&self) -> f32 {
+        self.x_axis.cross(self.y_axis).abs()
+    }
+
+    /// Determines if the axes are parallel, or either is zero, which leaves
+    /// the rectangle enclosing no area.
+    #[inline]
+    pub fn is_degenerate(&self) -> bool {
+        self.x_axis.cross(self.y_axis) == 0.0
+    }
+
+    /// Checks if the rectangle contains a point. Points on the edges are
+    /// contained, and degenerate rectangles contain nothing.
+    #[inline]
+    pub fn contains_point(&self, point: impl Into<Point2f>) -> bool {
+        let det = self.x_axis.cross(self.y_axis);
+        if det == 0.0 {
+            return false;
+        }
+        let d = point.into() - self.origin;
+        let s = d.cross(self.y_axis) / det;
+        let t = self.x_axis.cross(d) / det;
+        (0.0..=1.0).contains(&s) && (0.0..=1.0).contains(&t)
+    }
+
+    /// The axis-aligned bounding box of the corners.
+    #[inline]
+    pub fn bounds(&self) -> Rectf {
+        polygon::point_bounds(&self.corners())
+    }
+
+    /// The equivalent axis-aligned rectangle, if the axes are still aligned
+    /// with the x and y axes (whatever their direction or length).
+    #[inline]
+    pub fn to_rect(&self) -> Option<Rectf> {
+        if self.x_axis.y == 0.0 && self.y_axis.x == 0.0 {
+            Some(Rectf::from_points(
+                self.origin,
+                self.origin + self.x_axis + self.y_axis,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// The corners as a polygon, in the order of `corners`.
+    #[inline]
+    pub fn to_polygon(&self) -> Polygon {
+        Polygon::new(self.corners().to_vec())
+    }
+}
+
+impl From<Rectf> for OrientedRect {
+    /// The rectangle as it is, with `origin` at its top left corner.
+    #[inline]
+    fn from(rect: Rectf) -> OrientedRect {
+        OrientedRect {
+            origin: Point2f::new(rect.left, rect.top),
+            x_axis: Vector2f::new(rect.right - rect.left, 0.0),
+            y_axis: Vector2f::new(0.0, rect.bottom - rect.top),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::oriented_rect::OrientedRect;
+    use crate::point2f::Point2f;
+    use crate::rectf::Rectf;
+
+    #[test]
+    fn parallelogram() {
+        let rect = OrientedRect::new((0.0, 0.0), [4.0, 2.0], [-1.0, 2.0]);
+        assert_eq!(rect.area(), 10.0);
+        assert_eq!(rect.corners()[2], Point2f::new(3.0, 4.0));
+        assert_eq!(rect.center(), Point2f::new(1.5, 2.0));
+        assert_eq!(rect.bounds(), Rectf::new(-1.0, 0.0, 4.0, 4.0));
+        assert_eq!(rect.to_rect(), None);
+
+        assert!(rect.contains_point((1.5, 2.0)));
+        assert!(rect.contains_point((0.0, 0.0)));
+        assert!(!rect.contains_point((3.5, 0.5)));
+        assert!(!rect.contains_point((-0.9, 0.5)));
+        assert!((rect.to_polygon().area() - 10.0).abs() < 1e-5);
+
+        let flat = OrientedRect::new((0.0, 0.0), [4.0, 2.0], [2.0, 1.0]);
+        assert!(flat.is_degenerate());
+        assert!(!flat.contains_point((0.0, 0.0)));
+
+        // Flipped axes still describe the same axis-aligned rectangle
+        let r = Rectf::new(1.0, 2.0, 5.0, 3.0);
+        assert_eq!(OrientedRect::from(r).to_rect(), Some(r));
+        let flipped = OrientedRect::new((5.0, 3.0), [-4.0, 0.0], [0.0, -1.0]);
+        assert_eq!(flipped.to_rect(), Some(r));
+    }
+}
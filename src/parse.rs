@@ -0,0 +1,145 @@
+//! Human-readable text formats for the geometric types.
+//!
+//! Points, vectors and sizes are written as their two components in
+//! parentheses, e.g. `(3, 4)`, and rectangles and thicknesses as their four
+//! components in square brackets, e.g. `[0, 0, 100, 50]`. Parsing accepts the
+//! same format with any amount of whitespace around the components, and the
+//! brackets may be left off.
+
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The reason text could not be parsed as a geometric type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The text opened or closed with the wrong kind of bracket.
+    BadBrackets,
+    /// The text did not have the right number of comma-separated components.
+    WrongComponentCount {
+        /// How many components the type has.
+        expected: usize,
+        /// How many components the text had.
+        found: usize,
+    },
+    /// The component at this index was not a valid number.
+    BadComponent(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::BadBrackets => write!(fmt, "Mismatched brackets"),
+            ParseError::WrongComponentCount { expected, found } => {
+                write!(fmt, "Expected {} components, found {}", expected, found)
+            }
+            ParseError::BadComponent(i) => write!(fmt, "Component {} is not a number", i),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Writes the components separated by commas between the brackets, passing the
+/// formatter's options such as precision on to each component.
+pub(crate) fn write_components<T: fmt::Display>(
+    fmt: &mut fmt::Formatter,
+    brackets: (char, char),
+    components: &[&T],
+) -> fmt::Result {
+    fmt.write_fmt(format_args!("{}", brackets.0))?;
+    for (i, c) in components.iter().enumerate() {
+        if i > 0 {
+            fmt.write_str(", ")?;
+        }
+        c.fmt(fmt)?;
+    }
+    fmt.write_fmt(format_args!("{}", brackets.1))
+}
+
+/// Parses `N` comma-separated components, optionally between the brackets.
+pub(crate) fn parse_components<T: FromStr, const N: usize>(
+    s: &str,
+    brackets: (char, char),
+) -> Result<[T; N], ParseError> {
+    let s = s.trim();
+    let inner = s
+        .strip_prefix(brackets.0)
+        .and_then(|s| s.strip_suffix(brackets.1));
+    let s = match inner {
+        Some(inner) => inner,
+        None if s.starts_with(brackets.0) || s.ends_with(brackets.1) => {
+            return Err(ParseError::BadBrackets)
+        }
+        None => s,
+    };
+
+    let parts: Vec<&str> = s.split(',').collect();
+    let error = ParseError::WrongComponentCount {
+        expected: N,
+        found: parts.len(),
+    };
+    if parts.len() != N {
+        return Err(error);
+    }
+    let components = parts
+        .iter()
+        .enumerate()
+        .map(|(i, c)| c.trim().parse().map_err(|_| ParseError::BadComponent(i)))
+        .collect::<Result<Vec<T>, _>>()?;
+    components.try_into().map_err(|_| error)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::Color;
+    use crate::parse::ParseError;
+    use crate::point2f::Point2f;
+    use crate::recti::Recti;
+    use crate::sizeu::Sizeu;
+    use crate::thicknessf::Thicknessf;
+    use crate::vector2f::Vector2f;
+
+    #[test]
+    fn round_trips() {
+        let p = Point2f::new(3.0, 4.5);
+        assert_eq!(p.to_string(), "(3, 4.5)");
+        assert_eq!(format!("{:.2}", p), "(3.00, 4.50)");
+        assert_eq!(p.to_string().parse(), Ok(p));
+        assert_eq!(" ( 3 ,4.5 ) ".parse(), Ok(p));
+        assert_eq!("3, 4.5".parse(), Ok(p));
+
+        let r = Recti::new(0, 0, 100, 50);
+        assert_eq!(r.to_string(), "[0, 0, 100, 50]");
+        assert_eq!(r.to_string().parse(), Ok(r));
+
+        let t = Thicknessf::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(t.to_string().parse(), Ok(t));
+        let v = Vector2f::new(-1.0, 0.25);
+        assert_eq!(v.to_string().parse(), Ok(v));
+
+        let c = Color::from_u32(0xFF8800, 1.0);
+        assert_eq!(c.to_string(), "#FF8800");
+        assert_eq!(c.to_string().parse::<Color>().unwrap(), c);
+        let translucent = Color::from_u32(0x336699, 0x80 as f32 / 255.0);
+        assert_eq!(translucent.to_string(), "#33669980");
+        assert_eq!(
+            translucent.to_string().parse::<Color>().unwrap(),
+            translucent
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!("(1, 2]".parse::<Point2f>(), Err(ParseError::BadBrackets));
+        assert_eq!(
+            "[1, 2, 3]".parse::<Recti>(),
+            Err(ParseError::WrongComponentCount {
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!("(1, -2)".parse::<Sizeu>(), Err(ParseError::BadComponent(1)));
+    }
+}
@@ -0,0 +1,1928 @@
+//! Paths are a sequence of figures, each one a chain of line, bezier, and
+//! arc segments continuing on from a start point. See Direct2D's path
+//! geometries or SVG's path element for an overview of the concept.
+
+use crate::arc_segment::{ArcSegment, ArcSize, SweepDirection};
+use crate::bezier_segment::BezierSegment;
+use crate::cached_bounds::CachedBounds;
+use crate::ellipse::Ellipse;
+use crate::fill_rule::FillRule;
+use crate::matrix3x2f::Matrix3x2f;
+use crate::morph;
+use crate::oriented_ellipse::OrientedEllipse;
+use crate::oriented_rect::OrientedRect;
+use crate::path_measure::PathMeasure;
+use crate::point2f::Point2f;
+use crate::polygon::{self, Polygon};
+use crate::polyline::Polyline;
+use crate::quad_bezier_segment::QuadBezierSegment;
+use crate::rectf::Rectf;
+use crate::rounded_rect::RoundedRect;
+use crate::sizef::Sizef;
+use crate::stroke::{self, LineCap, LineJoin};
+use crate::tolerance::Tolerance;
+use crate::vector2f::Vector2f;
+use crate::winding::Winding;
+
+use std::f32::consts::PI;
+use std::slice;
+
+#[cfg(all(windows, feature = "d2d"))]
+use winapi::um::d2d1::{
+    ID2D1GeometrySink, D2D1_ARC_SEGMENT, D2D1_BEZIER_SEGMENT, D2D1_FIGURE_BEGIN_FILLED,
+    D2D1_FIGURE_END_CLOSED, D2D1_FIGURE_END_OPEN, D2D1_QUADRATIC_BEZIER_SEGMENT,
+};
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D as d2d;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D::Common as d2d_common;
+
+/// Nodes and weights of 3-point Gauss-Legendre quadrature over [0, 1].
+const GAUSS_LEGENDRE_3: [(f32, f32); 3] = [
+    (0.112_701_67, 5.0 / 18.0),
+    (0.5, 8.0 / 18.0),
+    (0.887_298_33, 5.0 / 18.0),
+];
+
+/// One segment of a figure. Each segment begins implicitly at the end
+/// point of the segment before it (or the start of the figure).
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum PathSegment {
+    /// A straight line to the given point.
+    Line(Point2f),
+    /// A cubic bezier curve.
+    Bezier(BezierSegment),
+    /// A quadratic bezier curve.
+    QuadBezier(QuadBezierSegment),
+    /// An elliptical arc.
+    Arc(ArcSegment),
+}
+
+impl PathSegment {
+    /// The point where this segment ends, and the next one begins.
+    #[inline]
+    pub fn end_point(&self) -> Point2f {
+        match *self {
+            PathSegment::Line(p) => p,
+            PathSegment::Bezier(ref bez) => bez.p3,
+            PathSegment::QuadBezier(ref quad) => quad.p2,
+            PathSegment::Arc(ref arc) => arc.point,
+        }
+    }
+
+    /// The point on the segment at the parameter `t`, given its start point.
+    #[inline]
+    pub fn point_at(&self, start: impl Into<Point2f>, t: f32) -> Point2f {
+        let start = start.into();
+        match *self {
+            PathSegment::Line(p) => start + (p - start) * t,
+            PathSegment::Bezier(ref bez) => bez.point_at(start, t),
+            PathSegment::QuadBezier(ref quad) => quad.point_at(start, t),
+            PathSegment::Arc(ref arc) => arc.point_at(start, t),
+        }
+    }
+
+    /// The derivative of the segment at the parameter `t`, given its start
+    /// point.
+    #[inline]
+    pub fn derivative_at(&self, start: impl Into<Point2f>, t: f32) -> Vector2f {
+        let start = start.into();
+        match *self {
+            PathSegment::Line(p) => p - start,
+            PathSegment::Bezier(ref bez) => bez.derivative_at(start, t),
+            PathSegment::QuadBezier(ref quad) => quad.derivative_at(start, t),
+            PathSegment::Arc(ref arc) => arc.tangent_at(start, t),
+        }
+    }
+
+    /// Extracts the part of the segment between the parameters `t0` and
+    /// `t1`, returning it along with its new start point.
+    pub fn subsegment(
+        &self,
+        start: impl Into<Point2f>,
+        t0: f32,
+        t1: f32,
+    ) -> (Point2f, PathSegment) {
+        let start = start.into();
+        let new_start = self.point_at(start, t0);
+        let segment = match *self {
+            PathSegment::Line(_) => PathSegment::Line(self.point_at(start, t1)),
+            PathSegment::Bezier(ref bez) => {
+                let (head, _) = bez.split_at(start, t1);
+                let t = if t1 > 0.0 { t0 / t1 } else { 0.0 };
+                PathSegment::Bezier(head.split_at(start, t).1)
+            }
+            PathSegment::QuadBezier(ref quad) => {
+                let (head, _) = quad.split_at(start, t1);
+                let t = if t1 > 0.0 { t0 / t1 } else { 0.0 };
+                PathSegment::QuadBezier(head.split_at(start, t).1)
+            }
+            PathSegment::Arc(ref arc) => match arc.to_center_form(start) {
+                Some(mut center) => {
+                    center.start_angle += center.sweep_angle * t0;
+                    center.sweep_angle *= t1 - t0;
+                    PathSegment::Arc(center.to_arc_segment().1)
+                }
+                None => PathSegment::Line(self.point_at(start, t1)),
+            },
+        };
+        (new_start, segment)
+    }
+
+    /// Determines if the segment has zero length, given its start point.
+    #[inline]
+    pub fn is_degenerate(&self, start: impl Into<Point2f>) -> bool {
+        let start = start.into();
+        match *self {
+            PathSegment::Line(p) => p == start,
+            PathSegment::Bezier(ref bez) => bez.is_degenerate(start),
+            PathSegment::QuadBezier(ref quad) => quad.is_degenerate(start),
+            PathSegment::Arc(ref arc) => arc.is_degenerate(start),
+        }
+    }
+
+    /// The tight bounding box of the segment, given its start point.
+    #[inline]
+    pub fn bounds(&self, start: impl Into<Point2f>) -> Rectf {
+        let start = start.into();
+        match *self {
+            PathSegment::Line(p) => Rectf::from_points(start, p),
+            PathSegment::Bezier(ref bez) => bez.bounds(start),
+            PathSegment::QuadBezier(ref quad) => quad.bounds(start),
+            PathSegment::Arc(ref arc) => arc.bounds(start),
+        }
+    }
+
+    /// A conservative bounding box of the segment, given its start point.
+    /// Curves use the bounds of their control points. Arcs have no control
+    /// points, so their tight bounds are used.
+    #[inline]
+    pub fn fast_bounds(&self, start: impl Into<Point2f>) -> Rectf {
+        let start = start.into();
+        match *self {
+            PathSegment::Line(p) => Rectf::from_points(start, p),
+            PathSegment::Bezier(ref bez) => bez.fast_bounds(start),
+            PathSegment::QuadBezier(ref quad) => quad.fast_bounds(start),
+            PathSegment::Arc(ref arc) => arc.bounds(start),
+        }
+    }
+
+    /// The length of the segment given its start point, approximated to
+    /// within `tolerance` for bezier curves.
+    #[inline]
+    pub fn length(&self, start: impl Into<Point2f>, tolerance: f32) -> f32 {
+        let start = start.into();
+        match *self {
+            PathSegment::Line(p) => (p - start).len(),
+            PathSegment::Bezier(ref bez) => bez.arc_length(start, tolerance),
+            PathSegment::QuadBezier(ref quad) => quad.arc_length(start, tolerance),
+            PathSegment::Arc(ref arc) => arc.arc_length(start),
+        }
+    }
+
+    /// Approximates the segment with line segments no further than
+    /// `tolerance` from the true curve. The start point is not included in
+    /// the result, but the end point always is (exactly). Relative
+    /// tolerances are measured against the segment itself.
+    #[inline]
+    pub fn flatten(
+        &self,
+        start: impl Into<Point2f>,
+        tolerance: impl Into<Tolerance>,
+    ) -> Vec<Point2f> {
+        let (start, tolerance) = (start.into(), tolerance.into());
+        match *self {
+            PathSegment::Line(p) => vec![p],
+            PathSegment::Bezier(ref bez) => bez.flatten(start, tolerance),
+            PathSegment::QuadBezier(ref quad) => quad.flatten(start, tolerance),
+            PathSegment::Arc(ref arc) => arc.flatten(start, tolerance),
+        }
+    }
+
+    /// Reverses the direction of the segment, so that it ends at `start`.
+    /// The reversed segment begins at the end point of this one.
+    #[inline]
+    pub fn reversed(&self, start: impl Into<Point2f>) -> PathSegment {
+        let start = start.into();
+        match *self {
+            PathSegment::Line(_) => PathSegment::Line(start),
+            PathSegment::Bezier(ref bez) => {
+                PathSegment::Bezier(BezierSegment::new(bez.p2, bez.p1, start))
+            }
+            PathSegment::QuadBezier(ref quad) => {
+                PathSegment::QuadBezier(QuadBezierSegment::new(quad.p1, start))
+            }
+            PathSegment::Arc(ref arc) => PathSegment::Arc(ArcSegment {
+                point: start,
+                sweep_direction: !arc.sweep_direction,
+                ..*arc
+            }),
+        }
+    }
+
+    /// The segment's contribution to the signed area of a figure, i.e. the
+    /// integral of `(x dy - y dx) / 2` along it.
+    fn area_term(&self, start: Point2f) -> f32 {
+        match *self {
+            PathSegment::Line(p) => start.to_vector().cross(p.to_vector()) / 2.0,
+            PathSegment::Bezier(_) | PathSegment::QuadBezier(_) => {
+                // The integrand is a polynomial of degree 5 at most, which
+                // this quadrature integrates exactly.
+                let sum: f32 = GAUSS_LEGENDRE_3
+                    .iter()
+                    .map(|&(t, w)| {
+                        let p = self.point_at(start, t).to_vector();
+                        w * p.cross(self.derivative_at(start, t))
+                    })
+                    .sum();
+                sum / 2.0
+            }
+            PathSegment::Arc(ref arc) => {
+                let mut start = start;
+                let mut area = 0.0;
+                for bez in arc.to_beziers(start, PI / 4.0) {
+                    area += PathSegment::Bezier(bez).area_term(start);
+                    start = bez.p3;
+                }
+                area
+            }
+        }
+    }
+
+    /// Applies an affine transformation to the segment. The start point must
+    /// be transformed separately.
+    #[inline]
+    pub fn transformed(&self, matrix: &Matrix3x2f) -> PathSegment {
+        match *self {
+            PathSegment::Line(p) => PathSegment::Line(p * *matrix),
+            PathSegment::Bezier(ref bez) => PathSegment::Bezier(bez.transformed(matrix)),
+            PathSegment::QuadBezier(ref quad) => PathSegment::QuadBezier(quad.transformed(matrix)),
+            PathSegment::Arc(ref arc) => PathSegment::Arc(arc.transformed(matrix)),
+        }
+    }
+}
+
+impl From<Point2f> for PathSegment {
+    #[inline]
+    fn from(p: Point2f) -> PathSegment {
+        PathSegment::Line(p)
+    }
+}
+
+impl From<BezierSegment> for PathSegment {
+    #[inline]
+    fn from(seg: BezierSegment) -> PathSegment {
+        PathSegment::Bezier(seg)
+    }
+}
+
+impl From<QuadBezierSegment> for PathSegment {
+    #[inline]
+    fn from(seg: QuadBezierSegment) -> PathSegment {
+        PathSegment::QuadBezier(seg)
+    }
+}
+
+impl From<ArcSegment> for PathSegment {
+    #[inline]
+    fn from(seg: ArcSegment) -> PathSegment {
+        PathSegment::Arc(seg)
+    }
+}
+
+/// A single connected chain of segments within a path.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Figure {
+    /// The point where the first segment begins.
+    pub start: Point2f,
+    /// The segments making up the figure, in order.
+    pub segments: Vec<PathSegment>,
+    /// Whether the figure is closed with an implicit line from the end of the
+    /// last segment back to the start point.
+    pub closed: bool,
+}
+
+impl Figure {
+    /// Constructs an empty, open figure beginning at the given point.
+    #[inline]
+    pub fn new(start: impl Into<Point2f>) -> Figure {
+        Figure {
+            start: start.into(),
+            segments: Vec::new(),
+            closed: false,
+        }
+    }
+
+    /// The point where the last segment ends. Returns the start point for
+    /// figures with no segments.
+    #[inline]
+    pub fn end_point(&self) -> Point2f {
+        self.segments
+            .last()
+            .map(PathSegment::end_point)
+            .unwrap_or(self.start)
+    }
+
+    /// Determines if the figure has no segments.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Determines if the figure has zero length, because it is empty or all
+    /// of its segments are degenerate.
+    #[inline]
+    pub fn is_degenerate(&self) -> bool {
+        self.segments()
+            .all(|(start, segment)| segment.is_degenerate(start))
+    }
+
+    /// Removes the degenerate segments of the figure. They begin and end at
+    /// the same point, so the rest of the figure is unchanged.
+    pub fn sanitized(&self) -> Figure {
+        let segments = self
+            .segments()
+            .filter(|&(start, segment)| !segment.is_degenerate(start))
+            .map(|(_, segment)| *segment)
+            .collect();
+        Figure { segments, ..*self }
+    }
+
+    /// The tight bounding box of the figure, including the extrema of any
+    /// curved segments.
+    #[inline]
+    pub fn bounds(&self) -> Rectf {
+        self.fold_bounds(PathSegment::bounds)
+    }
+
+    /// A conservative bounding box of the figure which includes the control
+    /// points of curved segments. Cheaper to compute than `bounds`.
+    #[inline]
+    pub fn fast_bounds(&self) -> Rectf {
+        self.fold_bounds(PathSegment::fast_bounds)
+    }
+
+    /// The total length of the figure, including the closing line of closed
+    /// figures. Curves are approximated to within `tolerance`.
+    pub fn length(&self, tolerance: f32) -> f32 {
+        let length: f32 = self
+            .segments()
+            .map(|(start, segment)| segment.length(start, tolerance))
+            .sum();
+        if self.closed {
+            length + (self.start - self.end_point()).len()
+        } else {
+            length
+        }
+    }
+
+    /// The signed area enclosed by the figure, which is positive when the
+    /// figure winds clockwise (on screen). Open figures are measured as if
+    /// they were closed by a straight line.
+    pub fn signed_area(&self) -> f32 {
+        let area: f32 = self
+            .segments()
+            .map(|(start, segment)| segment.area_term(start))
+            .sum();
+        area + PathSegment::Line(self.start).area_term(self.end_point())
+    }
+
+    /// The direction the figure winds around its area, or `None` if it
+    /// encloses no area.
+    #[inline]
+    pub fn winding(&self) -> Option<Winding> {
+        Winding::from_signed_area(self.signed_area())
+    }
+
+    /// Reverses the direction of the figure, so that it begins at the end
+    /// point of this one and traces the same segments backwards.
+    pub fn reversed(&self) -> Figure {
+        let mut segments: Vec<_> = self
+            .segments()
+            .map(|(start, segment)| segment.reversed(start))
+            .collect();
+        segments.reverse();
+        Figure {
+            start: self.end_point(),
+            segments,
+            closed: self.closed,
+        }
+    }
+
+    /// Iterates over the segments of the figure along with the point where
+    /// each one begins.
+    #[inline]
+    pub fn segments(&self) -> Segments<'_> {
+        Segments {
+            start: self.start,
+            iter: self.segments.iter(),
+        }
+    }
+
+    /// Approximates the figure with straight lines no further than
+    /// `tolerance` from the true curves. The result begins at the start
+    /// point, and closed figures end with a return to the start point.
+    /// Relative tolerances are measured against the whole figure.
+    pub fn flatten(&self, tolerance: impl Into<Tolerance>) -> Polyline {
+        let tolerance = tolerance.into().resolve(|| self.fast_bounds());
+        let mut points = vec![self.start];
+        for (start, segment) in self.segments() {
+            points.extend(segment.flatten(start, tolerance));
+        }
+        if self.closed && points.last() != Some(&self.start) {
+            points.push(self.start);
+        }
+        Polyline { points }
+    }
+
+    /// Feeds the figure to `sink` as a sequence of drawing commands.
+    pub fn stream(&self, sink: &mut (impl PathSink + ?Sized)) {
+        sink.begin_figure(self.start);
+        for segment in &self.segments {
+            match *segment {
+                PathSegment::Line(p) => sink.add_line(p),
+                PathSegment::Bezier(ref bez) => sink.add_bezier(bez),
+                PathSegment::QuadBezier(ref quad) => sink.add_quadratic_bezier(quad),
+                PathSegment::Arc(ref arc) => sink.add_arc(arc),
+            }
+        }
+        sink.end_figure(self.closed);
+    }
+
+    /// Applies an affine transformation to every point in the figure.
+    #[inline]
+    pub fn transformed(&self, matrix: &Matrix3x2f) -> Figure {
+        Figure {
+            start: self.start * *matrix,
+            segments: self
+                .segments
+                .iter()
+                .map(|s| s.transformed(matrix))
+                .collect(),
+            closed: self.closed,
+        }
+    }
+
+    #[inline]
+    fn fold_bounds(&self, seg_bounds: impl Fn(&PathSegment, Point2f) -> Rectf) -> Rectf {
+        self.segments().fold(
+            Rectf::from_points(self.start, self.start),
+            |acc, (start, seg)| acc.combined_with(seg_bounds(seg, start)),
+        )
+    }
+}
+
+/// Iterator over the segments of a figure paired with their start points.
+/// Created by `Figure::segments`.
+#[derive(Clone, Debug)]
+pub struct Segments<'a> {
+    start: Point2f,
+    iter: slice::Iter<'a, PathSegment>,
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = (Point2f, &'a PathSegment);
+
+    #[inline]
+    fn next(&mut self) -> Option<(Point2f, &'a PathSegment)> {
+        let segment = self.iter.next()?;
+        let start = self.start;
+        self.start = segment.end_point();
+        Some((start, segment))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for Segments<'a> {}
+
+/// A complete shape made up of any number of figures.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Path {
+    /// The figures making up the path.
+    pub figures: Vec<Figure>,
+}
+
+impl Path {
+    /// Constructs an empty path.
+    #[inline]
+    pub fn new() -> Path {
+        Path {
+            figures: Vec::new(),
+        }
+    }
+
+    /// Starts building a path with a `PathBuilder`.
+    #[inline]
+    pub fn builder() -> PathBuilder {
+        PathBuilder::new()
+    }
+
+    /// Determines if the path contains no segments at all.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.figures.iter().all(Figure::is_empty)
+    }
+
+    /// Determines if the path has zero length, because every figure is
+    /// degenerate. Such a path draws nothing, whether filled or stroked.
+    #[inline]
+    pub fn is_degenerate(&self) -> bool {
+        self.figures.iter().all(Figure::is_degenerate)
+    }
+
+    /// Removes degenerate segments and then degenerate figures from the
+    /// path, which leaves it drawing the same outline. Tessellators often
+    /// fail on zero-length segments, so paths from untrusted sources should be
+    /// sanitized first.
+    pub fn sanitized(&self) -> Path {
+        let figures = self
+            .figures
+            .iter()
+            .map(Figure::sanitized)
+            .filter(|figure| !figure.is_empty())
+            .collect::<Vec<_>>();
+        Path::from(figures)
+    }
+
+    /// The tight bounding box of the path, including the extrema of any
+    /// curved segments. A path with no figures has a zero-sized box at the
+    /// origin.
+    #[inline]
+    pub fn bounds(&self) -> Rectf {
+        self.fold_bounds(Figure::bounds)
+    }
+
+    /// A conservative bounding box of the path which includes the control
+    /// points of curved segments. Cheaper to compute than `bounds`, which
+    /// makes it useful for broad-phase culling.
+    #[inline]
+    pub fn fast_bounds(&self) -> Rectf {
+        self.fold_bounds(Figure::fast_bounds)
+    }
+
+    /// Wraps the path so that its bounds are only computed once, for paths
+    /// which are culled or hit tested repeatedly without changing.
+    #[inline]
+    pub fn into_cached(self) -> CachedBounds<Path> {
+        CachedBounds::new(self)
+    }
+
+    /// The total length of all figures in the path. Curves are approximated
+    /// to within `tolerance`.
+    #[inline]
+    pub fn length(&self, tolerance: f32) -> f32 {
+        self.figures.iter().map(|f| f.length(tolerance)).sum()
+    }
+
+    /// Reverses the direction of every figure in the path. The figures
+    /// themselves stay in the same order.
+    #[inline]
+    pub fn reversed(&self) -> Path {
+        Path {
+            figures: self.figures.iter().map(Figure::reversed).collect(),
+        }
+    }
+
+    /// The direction that the figure at `index` winds around its area.
+    /// Returns `None` if there is no such figure or it encloses no area.
+    #[inline]
+    pub fn figure_winding(&self, index: usize) -> Option<Winding> {
+        self.figures.get(index).and_then(Figure::winding)
+    }
+
+    /// Reverses figures as needed so that every figure with an area winds in
+    /// the given direction.
+    #[inline]
+    pub fn with_winding(&self, winding: Winding) -> Path {
+        Path {
+            figures: self
+                .figures
+                .iter()
+                .map(|figure| match figure.winding() {
+                    Some(w) if w != winding => figure.reversed(),
+                    _ => figure.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Iterates over the segments of every figure in the path along with the
+    /// point where each one begins.
+    #[inline]
+    pub fn segments(&self) -> impl Iterator<Item = (Point2f, &PathSegment)> {
+        self.figures.iter().flat_map(Figure::segments)
+    }
+
+    /// Approximates the path with straight lines no further than `tolerance`
+    /// from the true curves, producing one polyline for each figure.
+    /// Relative tolerances are measured against the whole path, so every
+    /// figure is flattened to the same accuracy.
+    #[inline]
+    pub fn flatten(&self, tolerance: impl Into<Tolerance>) -> Vec<Polyline> {
+        let tolerance = tolerance.into().resolve(|| self.fast_bounds());
+        self.figures.iter().map(|f| f.flatten(tolerance)).collect()
+    }
+
+    /// Feeds the path to `sink` as a sequence of drawing commands, one figure
+    /// at a time. With the `d2d` feature enabled, this can write directly
+    /// into an `ID2D1GeometrySink`, and likewise into a `kurbo::BezPath`,
+    /// `cairo::Context`, `skia_safe::Path` or `tiny_skia::PathBuilder` with
+    /// those features.
+    #[inline]
+    pub fn stream(&self, sink: &mut (impl PathSink + ?Sized)) {
+        for figure in &self.figures {
+            figure.stream(sink);
+        }
+    }
+
+    /// The number of times the figures of the path wind around `point`, with
+    /// clockwise windings counted positively. Open figures are treated as if
+    /// they were closed, and curves are flattened to within `tolerance`.
+    pub fn winding_number(&self, point: impl Into<Point2f>, tolerance: f32) -> i32 {
+        let point = point.into();
+        self.figures
+            .iter()
+            .filter(|figure| !figure.is_empty())
+            .map(|figure| polygon::winding_number(&figure.flatten(tolerance).points, point))
+            .sum()
+    }
+
+    /// Determines if the filled area of the path contains `point` under the
+    /// given fill rule. Curves are flattened to within `tolerance`.
+    #[inline]
+    pub fn contains_point(
+        &self,
+        point: impl Into<Point2f>,
+        fill_rule: FillRule,
+        tolerance: f32,
+    ) -> bool {
+        fill_rule.is_inside(self.winding_number(point, tolerance))
+    }
+
+    /// Splits the path into dashes according to `pattern`, which alternates
+    /// between the lengths of dashes and the gaps between them. `offset` is
+    /// the distance into the pattern at which each figure begins, and curves
+    /// are measured to within `tolerance`. Each dash becomes an open figure
+    /// in the result.
+    ///
+    /// As in SVG, a pattern with an odd number of entries is repeated to
+    /// make it even, the pattern restarts at each figure, and a pattern which
+    /// is empty, contains negative values, or sums to zero leaves the path
+    /// undashed. So does a pattern whose entries are all too short to
+    /// advance along the path at `f32` precision. Dashes of zero length
+    /// produce zero-length figures, so that round or square line caps can
+    /// still draw them as dots.
+    pub fn dash(&self, pattern: &[f32], offset: f32, tolerance: f32) -> Path {
+        let mut pattern = pattern.to_vec();
+        if pattern.len() % 2 == 1 {
+            pattern.extend_from_within(..);
+        }
+        let total: f32 = pattern.iter().sum();
+        if !(total > 0.0 && total.is_finite()) || pattern.iter().any(|&d| d < 0.0) {
+            return self.clone();
+        }
+
+        let measure = PathMeasure::new(self, tolerance);
+        let mut dashes = Path::new();
+        for (start, end) in measure.figure_ranges() {
+            // Distances only grow less precise along the path, so a pattern
+            // which cannot advance at the end never would
+            if end > start && pattern.iter().all(|&d| end + d <= end) {
+                return self.clone();
+            }
+
+            // Find where in the pattern the figure begins
+            let mut i = 0;
+            let mut phase = offset.rem_euclid(total);
+            while phase > 0.0 && phase >= pattern[i] {
+                phase -= pattern[i];
+                i = (i + 1) % pattern.len();
+            }
+
+            let mut distance = start;
+            // Entries in a row which did not advance the distance
+            let mut stalled = 0;
+            while distance < end {
+                let next = distance + pattern[i] - phase;
+                if next > distance {
+                    stalled = 0;
+                } else {
+                    stalled += 1;
+                    if stalled > pattern.len() {
+                        return self.clone();
+                    }
+                }
+                if i % 2 == 0 {
+                    if next > distance {
+                        let dash = measure.slice(distance, next.min(end));
+                        dashes.figures.extend(dash.figures);
+                    } else if let Some(p) = measure.point_at_distance(distance) {
+                        dashes.figures.push(Figure {
+                            start: p,
+                            segments: vec![PathSegment::Line(p)],
+                            closed: false,
+                        });
+                    }
+                }
+                distance = next;
+                phase = 0.0;
+                i = (i + 1) % pattern.len();
+            }
+        }
+        dashes
+    }
+
+    /// Computes the outline of the area covered by stroking the path with a
+    /// line of the given `width`, which can then be filled to draw the
+    /// stroke or hit tested to find what the stroke covers. Curves are
+    /// flattened to within `tolerance` first, while round caps and joins are
+    /// produced as exact arcs. `miter_limit` is the longest a mitered corner
+    /// may extend from its vertex, in multiples of half the stroke width,
+    /// before it is beveled instead.
+    ///
+    /// The outline may overlap itself and must be filled with the non-zero
+    /// winding rule. Closed figures produce a pair of loops around their
+    /// inside and outside edges, and open figures produce a single loop with
+    /// a cap on each end.
+    #[inline]
+    pub fn stroke_outline(
+        &self,
+        width: f32,
+        cap: LineCap,
+        join: LineJoin,
+        miter_limit: f32,
+        tolerance: f32,
+    ) -> Path {
+        stroke::stroke_outline(self, width, cap, join, miter_limit, tolerance)
+    }
+
+    /// Offsets the closed figures of the path by `distance`, growing them for
+    /// positive distances and shrinking them for negative ones regardless of
+    /// their winding direction. Open figures are omitted. Curves are
+    /// flattened to within `tolerance`, and the outer corners are joined
+    /// according to `join` and `miter_limit` as in `stroke_outline`.
+    ///
+    /// No attempt is made to remove self-intersections, so shrinking a figure
+    /// by more than its narrowest part produces inverted loops.
+    #[inline]
+    pub fn offset(&self, distance: f32, join: LineJoin, miter_limit: f32, tolerance: f32) -> Path {
+        stroke::offset(self, distance, join, miter_limit, tolerance)
+    }
+
+    /// Interpolates between two paths with the same structure, such as the
+    /// pair returned by `morph::make_compatible`. `0.0` returns this path
+    /// and `1.0` returns `other`. Returns `None` unless both paths have the
+    /// same number of figures, closed the same way, each with the same kinds
+    /// of segments in the same order. Arcs must also agree in their sweep
+    /// direction and arc size.
+    #[inline]
+    pub fn morph_compatible(&self, other: &Path, t: f32) -> Option<Path> {
+        morph::morph(self, other, t)
+    }
+
+    /// Applies an affine transformation to every point in the path. Curves
+    /// and arcs are transformed exactly.
+    #[inline]
+    pub fn transformed(&self, matrix: &Matrix3x2f) -> Path {
+        Path {
+            figures: self.figures.iter().map(|f| f.transformed(matrix)).collect(),
+        }
+    }
+
+    #[inline]
+    fn fold_bounds(&self, figure_bounds: impl Fn(&Figure) -> Rectf) -> Rectf {
+        self.figures
+            .iter()
+            .map(figure_bounds)
+            .fold(None, |acc: Option<Rectf>, b| match acc {
+                Some(acc) => Some(acc.combined_with(b)),
+                None => Some(b),
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl From<Vec<Figure>> for Path {
+    #[inline]
+    fn from(figures: Vec<Figure>) -> Path {
+        Path { figures }
+    }
+}
+
+impl From<Figure> for Path {
+    #[inline]
+    fn from(figure: Figure) -> Path {
+        Path {
+            figures: vec![figure],
+        }
+    }
+}
+
+impl From<Rectf> for Path {
+    /// Traces the rectangle clockwise from its top left corner.
+    #[inline]
+    fn from(rect: Rectf) -> Path {
+        Path::builder()
+            .move_to((rect.left, rect.top))
+            .line_to((rect.right, rect.top))
+            .line_to((rect.right, rect.bottom))
+            .line_to((rect.left, rect.bottom))
+            .close()
+            .build()
+    }
+}
+
+impl From<RoundedRect> for Path {
+    /// Traces the rounded rectangle clockwise from the end of its top left
+    /// corner. Radii larger than half of the rectangle are clamped to fit.
+    fn from(rr: RoundedRect) -> Path {
+        let rect = rr.rect.normalized();
+        let size = rect.size();
+        let rx = rr.radius_x.abs().min(size.width / 2.0);
+        let ry = rr.radius_y.abs().min(size.height / 2.0);
+        if rx == 0.0 || ry == 0.0 {
+            return rect.into();
+        }
+
+        let (l, t, r, b) = (rect.left, rect.top, rect.right, rect.bottom);
+        let cw = SweepDirection::Clockwise;
+        let small = ArcSize::Small;
+        Path::builder()
+            .move_to((l + rx, t))
+            .line_to((r - rx, t))
+            .arc_to((r, t + ry), (rx, ry), 0.0, cw, small)
+            .line_to((r, b - ry))
+            .arc_to((r - rx, b), (rx, ry), 0.0, cw, small)
+            .line_to((l + rx, b))
+            .arc_to((l, b - ry), (rx, ry), 0.0, cw, small)
+            .line_to((l, t + ry))
+            .arc_to((l + rx, t), (rx, ry), 0.0, cw, small)
+            .close()
+            .build()
+    }
+}
+
+impl From<Ellipse> for Path {
+    /// Traces the ellipse clockwise from its rightmost point, as a pair of
+    /// half-ellipse arcs.
+    #[inline]
+    fn from(e: Ellipse) -> Path {
+        let (rx, ry) = (e.radius_x.abs(), e.radius_y.abs());
+        let right = e.center + [rx, 0.0];
+        let left = e.center - [rx, 0.0];
+        let cw = SweepDirection::Clockwise;
+        Path::builder()
+            .move_to(right)
+            .arc_to(left, (rx, ry), 0.0, cw, ArcSize::Small)
+            .arc_to(right, (rx, ry), 0.0, cw, ArcSize::Small)
+            .close()
+            .build()
+    }
+}
+
+impl From<OrientedRect> for Path {
+    /// Traces the corners in the order of `OrientedRect::corners`.
+    #[inline]
+    fn from(rect: OrientedRect) -> Path {
+        let [p1, p2, p3, p4] = rect.corners();
+        Path::builder()
+            .move_to(p1)
+            .line_to(p2)
+            .line_to(p3)
+            .line_to(p4)
+            .close()
+            .build()
+    }
+}
+
+impl From<OrientedEllipse> for Path {
+    /// Traces the unrotated ellipse as `Path::from(Ellipse)` does, then
+    /// rotates it into place.
+    #[inline]
+    fn from(e: OrientedEllipse) -> Path {
+        Path::from(e.unrotated()).transformed(&e.rotation_matrix())
+    }
+}
+
+impl From<Polygon> for Path {
+    /// Converts the polygon into a single closed figure of straight lines.
+    #[inline]
+    fn from(polygon: Polygon) -> Path {
+        let mut builder = Path::builder();
+        let mut points = polygon.points.into_iter();
+        if let Some(first) = points.next() {
+            builder.move_to(first);
+            for p in points {
+                builder.line_to(p);
+            }
+            builder.close();
+        }
+        builder.build()
+    }
+}
+
+impl From<Polyline> for Figure {
+    /// Converts the polyline into an open figure of straight lines. An empty
+    /// polyline becomes an empty figure at the origin.
+    #[inline]
+    fn from(line: Polyline) -> Figure {
+        let mut points = line.points.into_iter();
+        let start = points.next().unwrap_or_default();
+        Figure {
+            start,
+            segments: points.map(PathSegment::Line).collect(),
+            closed: false,
+        }
+    }
+}
+
+impl From<Polyline> for Path {
+    /// Converts the polyline into a single open figure of straight lines. An
+    /// empty polyline becomes an empty path.
+    #[inline]
+    fn from(line: Polyline) -> Path {
+        if line.is_empty() {
+            Path::new()
+        } else {
+            Path::from(Figure::from(line))
+        }
+    }
+}
+
+#[cfg(feature = "kurbo")]
+impl From<Path> for kurbo::BezPath {
+    /// Arcs have no equivalent in kurbo, so they are approximated with cubic
+    /// bezier curves.
+    #[inline]
+    fn from(path: Path) -> kurbo::BezPath {
+        let mut bez = kurbo::BezPath::new();
+        path.stream(&mut bez);
+        bez
+    }
+}
+
+#[cfg(feature = "kurbo")]
+impl From<kurbo::BezPath> for Path {
+    #[inline]
+    fn from(bez: kurbo::BezPath) -> Path {
+        let mut builder = Path::builder();
+        for el in bez.elements() {
+            match *el {
+                kurbo::PathEl::MoveTo(p) => builder.move_to(p),
+                kurbo::PathEl::LineTo(p) => builder.line_to(p),
+                kurbo::PathEl::QuadTo(p1, p2) => builder.quad_to(p1, p2),
+                kurbo::PathEl::CurveTo(p1, p2, p3) => builder.cubic_to(p1, p2, p3),
+                kurbo::PathEl::ClosePath => builder.close(),
+            };
+        }
+        builder.build()
+    }
+}
+
+#[cfg(feature = "cairo")]
+impl Path {
+    /// Appends the figures of the path to the current path of `cr`. Arcs are
+    /// added as cubic bezier curves.
+    #[inline]
+    pub fn append_to_cairo(&self, cr: &cairo::Context) {
+        self.stream(&mut cr.clone());
+    }
+}
+
+#[cfg(feature = "lyon")]
+impl Path {
+    /// Feeds the path into a lyon path builder, such as the one from
+    /// `lyon_path::Path::builder()`. lyon builders have no arcs, so they
+    /// are approximated with cubic bezier curves.
+    pub fn build_lyon<B: lyon_path::builder::PathBuilder>(
+        &self,
+        builder: &mut lyon_path::builder::NoAttributes<B>,
+    ) {
+        for figure in &self.figures {
+            builder.begin(figure.start.into());
+            for (start, segment) in figure.segments() {
+                match *segment {
+                    PathSegment::Line(p) => {
+                        builder.line_to(p.into());
+                    }
+                    PathSegment::Bezier(b) => {
+                        builder.cubic_bezier_to(b.p1.into(), b.p2.into(), b.p3.into());
+                    }
+                    PathSegment::QuadBezier(q) => {
+                        builder.quadratic_bezier_to(q.p1.into(), q.p2.into());
+                    }
+                    PathSegment::Arc(arc) => {
+                        for b in arc.to_beziers(start, PI / 2.0) {
+                            builder.cubic_bezier_to(b.p1.into(), b.p2.into(), b.p3.into());
+                        }
+                    }
+                }
+            }
+            builder.end(figure.closed);
+        }
+    }
+}
+
+#[cfg(feature = "lyon")]
+impl From<Path> for lyon_path::Path {
+    #[inline]
+    fn from(path: Path) -> lyon_path::Path {
+        let mut builder = lyon_path::Path::builder();
+        path.build_lyon(&mut builder);
+        builder.build()
+    }
+}
+
+#[cfg(feature = "lyon")]
+impl From<lyon_path::Path> for Path {
+    #[inline]
+    fn from(path: lyon_path::Path) -> Path {
+        use lyon_path::Event;
+
+        let mut builder = Path::builder();
+        for event in path.iter() {
+            match event {
+                Event::Begin { at } => builder.move_to(at),
+                Event::Line { to, .. } => builder.line_to(to),
+                Event::Quadratic { ctrl, to, .. } => builder.quad_to(ctrl, to),
+                Event::Cubic {
+                    ctrl1, ctrl2, to, ..
+                } => builder.cubic_to(ctrl1, ctrl2, to),
+                Event::End { close: true, .. } => builder.close(),
+                Event::End { close: false, .. } => &mut builder,
+            };
+        }
+        builder.build()
+    }
+}
+
+#[cfg(feature = "skia")]
+impl From<Path> for skia_safe::Path {
+    #[inline]
+    fn from(path: Path) -> skia_safe::Path {
+        let mut sk = skia_safe::Path::new();
+        path.stream(&mut sk);
+        sk
+    }
+}
+
+#[cfg(feature = "tiny-skia")]
+impl Path {
+    /// Converts the path to a tiny-skia path, approximating arcs with cubic
+    /// bezier curves. Returns `None` if the path is empty or degenerate, as
+    /// tiny-skia has no such paths.
+    #[inline]
+    pub fn to_tiny_skia(&self) -> Option<tiny_skia::Path> {
+        let mut builder = tiny_skia::PathBuilder::new();
+        self.stream(&mut builder);
+        builder.finish()
+    }
+}
+
+#[cfg(feature = "raqote")]
+impl From<Path> for raqote::Path {
+    /// Arcs have no exact equivalent in raqote, so they are approximated with
+    /// cubic bezier curves.
+    fn from(path: Path) -> raqote::Path {
+        let mut builder = raqote::PathBuilder::new();
+        for figure in &path.figures {
+            builder.move_to(figure.start.x, figure.start.y);
+            for (start, segment) in figure.segments() {
+                match *segment {
+                    PathSegment::Line(p) => builder.line_to(p.x, p.y),
+                    PathSegment::Bezier(b) => {
+                        builder.cubic_to(b.p1.x, b.p1.y, b.p2.x, b.p2.y, b.p3.x, b.p3.y)
+                    }
+                    PathSegment::QuadBezier(q) => builder.quad_to(q.p1.x, q.p1.y, q.p2.x, q.p2.y),
+                    PathSegment::Arc(arc) => {
+                        for b in arc.to_beziers(start, PI / 2.0) {
+                            builder.cubic_to(b.p1.x, b.p1.y, b.p2.x, b.p2.y, b.p3.x, b.p3.y);
+                        }
+                    }
+                }
+            }
+            if figure.closed {
+                builder.close();
+            }
+        }
+        builder.finish()
+    }
+}
+
+/// Receives the contents of a path as a stream of drawing commands, in the
+/// same order that Direct2D's `ID2D1GeometrySink` expects them: each figure
+/// is introduced by `begin_figure`, followed by its segments, and finished
+/// with `end_figure`.
+pub trait PathSink {
+    /// Begins a new figure at the given point.
+    fn begin_figure(&mut self, start: Point2f);
+    /// Adds a straight line from the current point.
+    fn add_line(&mut self, point: Point2f);
+    /// Adds a cubic bezier curve from the current point.
+    fn add_bezier(&mut self, bezier: &BezierSegment);
+    /// Adds a quadratic bezier curve from the current point.
+    fn add_quadratic_bezier(&mut self, bezier: &QuadBezierSegment);
+    /// Adds an elliptical arc from the current point.
+    fn add_arc(&mut self, arc: &ArcSegment);
+    /// Ends the current figure, optionally closing it back to its start.
+    fn end_figure(&mut self, closed: bool);
+}
+
+#[cfg(all(windows, feature = "d2d"))]
+impl PathSink for ID2D1GeometrySink {
+    #[inline]
+    fn begin_figure(&mut self, start: Point2f) {
+        unsafe { self.BeginFigure(start.into(), D2D1_FIGURE_BEGIN_FILLED) }
+    }
+
+    #[inline]
+    fn add_line(&mut self, point: Point2f) {
+        unsafe { self.AddLine(point.into()) }
+    }
+
+    #[inline]
+    fn add_bezier(&mut self, bezier: &BezierSegment) {
+        let bezier: D2D1_BEZIER_SEGMENT = (*bezier).into();
+        unsafe { self.AddBezier(&bezier) }
+    }
+
+    #[inline]
+    fn add_quadratic_bezier(&mut self, bezier: &QuadBezierSegment) {
+        let bezier: D2D1_QUADRATIC_BEZIER_SEGMENT = (*bezier).into();
+        unsafe { self.AddQuadraticBezier(&bezier) }
+    }
+
+    #[inline]
+    fn add_arc(&mut self, arc: &ArcSegment) {
+        let arc: D2D1_ARC_SEGMENT = (*arc).into();
+        unsafe { self.AddArc(&arc) }
+    }
+
+    #[inline]
+    fn end_figure(&mut self, closed: bool) {
+        let end = if closed {
+            D2D1_FIGURE_END_CLOSED
+        } else {
+            D2D1_FIGURE_END_OPEN
+        };
+        unsafe { self.EndFigure(end) }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl PathSink for d2d::ID2D1GeometrySink {
+    #[inline]
+    fn begin_figure(&mut self, start: Point2f) {
+        unsafe { self.BeginFigure(start.into(), d2d_common::D2D1_FIGURE_BEGIN_FILLED) }
+    }
+
+    #[inline]
+    fn add_line(&mut self, point: Point2f) {
+        unsafe { self.AddLine(point.into()) }
+    }
+
+    #[inline]
+    fn add_bezier(&mut self, bezier: &BezierSegment) {
+        let bezier: d2d_common::D2D1_BEZIER_SEGMENT = (*bezier).into();
+        unsafe { self.AddBezier(&bezier) }
+    }
+
+    #[inline]
+    fn add_quadratic_bezier(&mut self, bezier: &QuadBezierSegment) {
+        let bezier: d2d::D2D1_QUADRATIC_BEZIER_SEGMENT = (*bezier).into();
+        unsafe { self.AddQuadraticBezier(&bezier) }
+    }
+
+    #[inline]
+    fn add_arc(&mut self, arc: &ArcSegment) {
+        let arc: d2d::D2D1_ARC_SEGMENT = (*arc).into();
+        unsafe { self.AddArc(&arc) }
+    }
+
+    #[inline]
+    fn end_figure(&mut self, closed: bool) {
+        let end = if closed {
+            d2d_common::D2D1_FIGURE_END_CLOSED
+        } else {
+            d2d_common::D2D1_FIGURE_END_OPEN
+        };
+        unsafe { self.EndFigure(end) }
+    }
+}
+
+#[cfg(feature = "kurbo")]
+impl PathSink for kurbo::BezPath {
+    #[inline]
+    fn begin_figure(&mut self, start: Point2f) {
+        self.move_to(start);
+    }
+
+    #[inline]
+    fn add_line(&mut self, point: Point2f) {
+        self.line_to(point);
+    }
+
+    #[inline]
+    fn add_bezier(&mut self, bezier: &BezierSegment) {
+        self.curve_to(bezier.p1, bezier.p2, bezier.p3);
+    }
+
+    #[inline]
+    fn add_quadratic_bezier(&mut self, bezier: &QuadBezierSegment) {
+        self.quad_to(bezier.p1, bezier.p2);
+    }
+
+    /// kurbo paths have no arcs, so the arc is added as cubic bezier curves
+    /// of up to a quarter turn each.
+    #[inline]
+    fn add_arc(&mut self, arc: &ArcSegment) {
+        let start = self
+            .current_position()
+            .map(Point2f::from)
+            .unwrap_or_default();
+        for bezier in arc.to_beziers(start, PI / 2.0) {
+            self.add_bezier(&bezier);
+        }
+    }
+
+    #[inline]
+    fn end_figure(&mut self, closed: bool) {
+        if closed {
+            self.close_path();
+        }
+    }
+}
+
+#[cfg(feature = "cairo")]
+impl PathSink for cairo::Context {
+    #[inline]
+    fn begin_figure(&mut self, start: Point2f) {
+        self.move_to(start.x.into(), start.y.into());
+    }
+
+    #[inline]
+    fn add_line(&mut self, point: Point2f) {
+        self.line_to(point.x.into(), point.y.into());
+    }
+
+    #[inline]
+    fn add_bezier(&mut self, bezier: &BezierSegment) {
+        let BezierSegment { p1, p2, p3 } = *bezier;
+        self.curve_to(
+            p1.x.into(),
+            p1.y.into(),
+            p2.x.into(),
+            p2.y.into(),
+            p3.x.into(),
+            p3.y.into(),
+        );
+    }
+
+    /// Cairo has no quadratic curves, so the curve is elevated to the
+    /// identical cubic.
+    #[inline]
+    fn add_quadratic_bezier(&mut self, bezier: &QuadBezierSegment) {
+        let cubic = bezier.to_cubic(cairo_current_point(self));
+        self.add_bezier(&cubic);
+    }
+
+    /// Cairo only draws circular arcs, so the arc is added as cubic bezier
+    /// curves of up to a quarter turn each.
+    #[inline]
+    fn add_arc(&mut self, arc: &ArcSegment) {
+        for bezier in arc.to_beziers(cairo_current_point(self), PI / 2.0) {
+            self.add_bezier(&bezier);
+        }
+    }
+
+    #[inline]
+    fn end_figure(&mut self, closed: bool) {
+        if closed {
+            self.close_path();
+        }
+    }
+}
+
+#[cfg(feature = "cairo")]
+#[inline]
+fn cairo_current_point(cr: &cairo::Context) -> Point2f {
+    let (x, y) = cr.current_point().unwrap_or_default();
+    Point2f::new(x as f32, y as f32)
+}
+
+#[cfg(feature = "skia")]
+impl PathSink for skia_safe::Path {
+    #[inline]
+    fn begin_figure(&mut self, start: Point2f) {
+        self.move_to(start);
+    }
+
+    #[inline]
+    fn add_line(&mut self, point: Point2f) {
+        self.line_to(point);
+    }
+
+    #[inline]
+    fn add_bezier(&mut self, bezier: &BezierSegment) {
+        self.cubic_to(bezier.p1, bezier.p2, bezier.p3);
+    }
+
+    #[inline]
+    fn add_quadratic_bezier(&mut self, bezier: &QuadBezierSegment) {
+        self.quad_to(bezier.p1, bezier.p2);
+    }
+
+    #[inline]
+    fn add_arc(&mut self, arc: &ArcSegment) {
+        let arc_size = match arc.arc_size {
+            ArcSize::Small => skia_safe::path::ArcSize::Small,
+            ArcSize::Large => skia_safe::path::ArcSize::Large,
+        };
+        let sweep = match arc.sweep_direction {
+            SweepDirection::Clockwise => skia_safe::PathDirection::CW,
+            SweepDirection::CounterClockwise => skia_safe::PathDirection::CCW,
+        };
+        let radii = (arc.size.width, arc.size.height);
+        self.arc_to_rotated(radii, arc.rotation_angle, arc_size, sweep, arc.point);
+    }
+
+    #[inline]
+    fn end_figure(&mut self, closed: bool) {
+        if closed {
+            self.close();
+        }
+    }
+}
+
+#[cfg(feature = "tiny-skia")]
+impl PathSink for tiny_skia::PathBuilder {
+    #[inline]
+    fn begin_figure(&mut self, start: Point2f) {
+        self.move_to(start.x, start.y);
+    }
+
+    #[inline]
+    fn add_line(&mut self, point: Point2f) {
+        self.line_to(point.x, point.y);
+    }
+
+    #[inline]
+    fn add_bezier(&mut self, bezier: &BezierSegment) {
+        let BezierSegment { p1, p2, p3 } = *bezier;
+        self.cubic_to(p1.x, p1.y, p2.x, p2.y, p3.x, p3.y);
+    }
+
+    #[inline]
+    fn add_quadratic_bezier(&mut self, bezier: &QuadBezierSegment) {
+        let QuadBezierSegment { p1, p2 } = *bezier;
+        self.quad_to(p1.x, p1.y, p2.x, p2.y);
+    }
+
+    /// tiny-skia has no arcs, so the arc is added as cubic bezier curves of
+    /// up to a quarter turn each.
+    #[inline]
+    fn add_arc(&mut self, arc: &ArcSegment) {
+        let start = self
+            .last_point()
+            .map(|p| Point2f::new(p.x, p.y))
+            .unwrap_or_default();
+        for bezier in arc.to_beziers(start, PI / 2.0) {
+            self.add_bezier(&bezier);
+        }
+    }
+
+    #[inline]
+    fn end_figure(&mut self, closed: bool) {
+        if closed {
+            self.close();
+        }
+    }
+}
+
+/// Incrementally constructs a `Path` using the familiar move/line/curve/close
+/// commands found in most 2D drawing APIs.
+///
+/// Segments added without a preceding `move_to` continue on from the current
+/// point, which is the origin for a new builder. After `close`, the current
+/// point returns to the start of the closed figure.
+#[derive(Clone, Debug, Default)]
+pub struct PathBuilder {
+    figures: Vec<Figure>,
+    current: Option<Figure>,
+    current_point: Point2f,
+}
+
+impl PathBuilder {
+    /// Constructs a builder for an empty path.
+    #[inline]
+    pub fn new() -> PathBuilder {
+        PathBuilder::default()
+    }
+
+    /// The point where the next segment will begin.
+    #[inline]
+    pub fn current_point(&self) -> Point2f {
+        self.current_point
+    }
+
+    /// Ends the current figure (leaving it open) and begins a new one at the
+    /// given point.
+    #[inline]
+    pub fn move_to(&mut self, point: impl Into<Point2f>) -> &mut Self {
+        self.finish_figure();
+        let point = point.into();
+        self.current = Some(Figure::new(point));
+        self.current_point = point;
+        self
+    }
+
+    /// Adds a straight line from the current point to `point`.
+    #[inline]
+    pub fn line_to(&mut self, point: impl Into<Point2f>) -> &mut Self {
+        self.segment(PathSegment::Line(point.into()))
+    }
+
+    /// Adds a cubic bezier curve from the current point with the control
+    /// points `p1` and `p2`, ending at `p3`.
+    #[inline]
+    pub fn cubic_to(
+        &mut self,
+        p1: impl Into<Point2f>,
+        p2: impl Into<Point2f>,
+        p3: impl Into<Point2f>,
+    ) -> &mut Self {
+        self.segment(PathSegment::Bezier(BezierSegment::new(p1, p2, p3)))
+    }
+
+    /// Adds a quadratic bezier curve from the current point with the control
+    /// point `p1`, ending at `p2`.
+    #[inline]
+    pub fn quad_to(&mut self, p1: impl Into<Point2f>, p2: impl Into<Point2f>) -> &mut Self {
+        self.segment(PathSegment::QuadBezier(QuadBezierSegment::new(p1, p2)))
+    }
+
+    /// Adds an elliptical arc from the current point to `point`. The
+    /// parameters match those of `ArcSegment::new`.
+    #[inline]
+    pub fn arc_to(
+        &mut self,
+        point: impl Into<Point2f>,
+        size: impl Into<Sizef>,
+        rotation_angle: f32,
+        sweep_direction: SweepDirection,
+        arc_size: ArcSize,
+    ) -> &mut Self {
+        let arc = ArcSegment::new(point, size, rotation_angle, sweep_direction, arc_size);
+        self.segment(PathSegment::Arc(arc))
+    }
+
+    /// Adds an arbitrary segment starting from the current point.
+    #[inline]
+    pub fn segment(&mut self, segment: impl Into<PathSegment>) -> &mut Self {
+        let segment = segment.into();
+        let start = self.current_point;
+        self.current
+            .get_or_insert_with(|| Figure::new(start))
+            .segments
+            .push(segment);
+        self.current_point = segment.end_point();
+        self
+    }
+
+    /// Closes the current figure, connecting its end back to its start.
+    #[inline]
+    pub fn close(&mut self) -> &mut Self {
+        if let Some(mut figure) = self.current.take() {
+            figure.closed = true;
+            self.current_point = figure.start;
+            self.figures.push(figure);
+        }
+        self
+    }
+
+    /// Finishes the path, leaving the builder empty so it may be reused.
+    #[inline]
+    pub fn build(&mut self) -> Path {
+        self.finish_figure();
+        self.current_point = Point2f::ORIGIN;
+        Path {
+            figures: std::mem::take(&mut self.figures),
+        }
+    }
+
+    #[inline]
+    fn finish_figure(&mut self) {
+        if let Some(figure) = self.current.take() {
+            self.figures.push(figure);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arc_segment::{ArcSegment, ArcSize, SweepDirection};
+    use crate::bezier_segment::BezierSegment;
+    use crate::matrix3x2f::Matrix3x2f;
+    use crate::path::{Path, PathSegment, PathSink};
+    use crate::point2f::Point2f;
+    use crate::quad_bezier_segment::QuadBezierSegment;
+    use crate::rectf::Rectf;
+    use crate::winding::Winding;
+
+    #[test]
+    fn builder() {
+        let path = Path::builder()
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .quad_to((15.0, 5.0), (10.0, 10.0))
+            .close()
+            .line_to((-5.0, 0.0))
+            .move_to((20.0, 20.0))
+            .cubic_to((25.0, 20.0), (30.0, 25.0), (30.0, 30.0))
+            .build();
+
+        assert_eq!(path.figures.len(), 3);
+        assert!(path.figures[0].closed);
+        assert_eq!(path.figures[0].end_point(), Point2f::new(10.0, 10.0));
+
+        // Segments after a close continue from the start of the closed figure
+        assert_eq!(path.figures[1].start, Point2f::new(0.0, 0.0));
+        assert_eq!(
+            path.figures[1].segments,
+            [PathSegment::Line((-5.0, 0.0).into())]
+        );
+        assert!(!path.figures[1].closed);
+
+        assert_eq!(path.figures[2].start, Point2f::new(20.0, 20.0));
+        assert_eq!(path.figures[2].end_point(), Point2f::new(30.0, 30.0));
+    }
+
+    #[test]
+    fn degenerate() {
+        let start = Point2f::new(1.0, 1.0);
+        assert!(PathSegment::Line(start).is_degenerate(start));
+        assert!(BezierSegment::new(start, start, start).is_degenerate(start));
+        assert!(!BezierSegment::new(start, (2.0, 2.0), start).is_degenerate(start));
+        assert!(QuadBezierSegment::new(start, start).is_degenerate(start));
+        let arc = ArcSegment::new(
+            start,
+            (5.0, 5.0),
+            0.0,
+            SweepDirection::Clockwise,
+            ArcSize::Large,
+        );
+        assert!(arc.is_degenerate(start));
+
+        let path = Path::builder()
+            .move_to((0.0, 0.0))
+            .line_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .quad_to((10.0, 0.0), (10.0, 0.0))
+            .close()
+            .move_to((5.0, 5.0))
+            .line_to((5.0, 5.0))
+            .build();
+        assert!(!path.is_degenerate());
+        assert!(path.figures[1].is_degenerate());
+
+        let clean = path.sanitized();
+        assert_eq!(clean.figures.len(), 1);
+        assert_eq!(
+            clean.figures[0].segments,
+            [PathSegment::Line((10.0, 0.0).into())]
+        );
+        assert!(clean.figures[0].closed);
+        assert!(Path::new().is_degenerate());
+        assert!(Path::from(clean.figures[0].sanitized()).sanitized() == clean);
+    }
+
+    #[test]
+    fn measurements() {
+        let path = Path::builder()
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .quad_to((20.0, 5.0), (10.0, 10.0))
+            .line_to((0.0, 10.0))
+            .close()
+            .build();
+
+        // The curve peaks at x = 15, well short of its control point
+        assert_eq!(path.bounds(), Rectf::new(0.0, 0.0, 15.0, 10.0));
+        assert_eq!(path.fast_bounds(), Rectf::new(0.0, 0.0, 20.0, 10.0));
+
+        let length = path.length(1e-3);
+        assert!((length - 44.79).abs() < 1e-2);
+
+        let moved = path.transformed(&Matrix3x2f::translation([5.0, 5.0]));
+        assert_eq!(moved.bounds(), Rectf::new(5.0, 5.0, 20.0, 15.0));
+        assert!((moved.length(1e-3) - length).abs() < 1e-3);
+
+        assert_eq!(Path::new().bounds(), Rectf::default());
+    }
+
+    #[test]
+    fn flatten_and_stream() {
+        let path = Path::builder()
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .cubic_to((15.0, 0.0), (15.0, 10.0), (10.0, 10.0))
+            .close()
+            .move_to((20.0, 0.0))
+            .line_to((30.0, 0.0))
+            .build();
+
+        let starts: Vec<_> = path.segments().map(|(start, _)| start).collect();
+        assert_eq!(
+            starts,
+            [(0.0, 0.0).into(), (10.0, 0.0).into(), (20.0, 0.0).into()]
+        );
+
+        let lines = path.flatten(0.01);
+        assert_eq!(lines.len(), 2);
+        let closed = &lines[0].points;
+        assert!(closed.len() > 4);
+        assert_eq!(closed.first(), closed.last());
+        assert!(closed.contains(&Point2f::new(10.0, 10.0)));
+        assert_eq!(lines[1].points, [(20.0, 0.0).into(), (30.0, 0.0).into()]);
+
+        #[derive(Default)]
+        struct Recorder(Vec<String>);
+        impl PathSink for Recorder {
+            fn begin_figure(&mut self, _: Point2f) {
+                self.0.push("begin".into());
+            }
+            fn add_line(&mut self, _: Point2f) {
+                self.0.push("line".into());
+            }
+            fn add_bezier(&mut self, _: &BezierSegment) {
+                self.0.push("bezier".into());
+            }
+            fn add_quadratic_bezier(&mut self, _: &QuadBezierSegment) {
+                self.0.push("quad".into());
+            }
+            fn add_arc(&mut self, _: &ArcSegment) {
+                self.0.push("arc".into());
+            }
+            fn end_figure(&mut self, closed: bool) {
+                self.0.push(format!("end {}", closed));
+            }
+        }
+
+        let mut recorder = Recorder::default();
+        path.stream(&mut recorder);
+        assert_eq!(
+            recorder.0,
+            [
+                "begin",
+                "line",
+                "bezier",
+                "end true",
+                "begin",
+                "line",
+                "end false"
+            ]
+        );
+    }
+
+    #[test]
+    fn dash() {
+        let line = Path::builder().line_to((10.0, 0.0)).build();
+        let spans = |path: Path| -> Vec<(f32, f32)> {
+            path.figures
+                .iter()
+                .map(|f| (f.start.x, f.end_point().x))
+                .collect()
+        };
+
+        let dashes = spans(line.dash(&[2.0, 1.0], 0.0, 0.01));
+        assert_eq!(dashes, [(0.0, 2.0), (3.0, 5.0), (6.0, 8.0), (9.0, 10.0)]);
+
+        let dashes = spans(line.dash(&[2.0, 1.0], 1.0, 0.01));
+        assert_eq!(dashes, [(0.0, 1.0), (2.0, 4.0), (5.0, 7.0), (8.0, 10.0)]);
+
+        // Odd patterns repeat, so dashes and gaps swap on the second pass
+        let dashes = spans(line.dash(&[3.0], 0.0, 0.01));
+        assert_eq!(dashes, [(0.0, 3.0), (6.0, 9.0)]);
+
+        let dots = spans(line.dash(&[0.0, 5.0], 0.0, 0.01));
+        assert_eq!(dots, [(0.0, 0.0), (5.0, 5.0)]);
+
+        assert_eq!(line.dash(&[], 0.0, 0.01), line);
+    }
+
+    #[test]
+    fn dash_below_precision() {
+        // Steps this small stop changing the distance partway along the line
+        let line = Path::builder().line_to((2.5, 0.0)).build();
+        assert_eq!(line.dash(&[1.1e-7, 1.1e-7], 0.0, 0.01), line);
+
+        // A gap which still advances keeps the vanishing dashes as dots
+        let dots = line.dash(&[1e-9, 1.0], 0.0, 0.01);
+        assert_eq!(dots.figures.len(), 3);
+    }
+
+    #[test]
+    fn winding() {
+        // A clockwise unit square followed by a counterclockwise circle
+        let path = Path::builder()
+            .line_to((10.0, 0.0))
+            .line_to((10.0, 10.0))
+            .line_to((0.0, 10.0))
+            .close()
+            .move_to((30.0, 20.0))
+            .arc_to(
+                (30.0, 0.0),
+                (10.0, 10.0),
+                0.0,
+                SweepDirection::CounterClockwise,
+                ArcSize::Small,
+            )
+            .arc_to(
+                (30.0, 20.0),
+                (10.0, 10.0),
+                0.0,
+                SweepDirection::CounterClockwise,
+                ArcSize::Small,
+            )
+            .close()
+            .move_to((50.0, 50.0))
+            .line_to((60.0, 60.0))
+            .build();
+
+        assert!((path.figures[0].signed_area() - 100.0).abs() < 1e-4);
+        assert!((path.figures[1].signed_area() + 100.0 * std::f32::consts::PI).abs() < 1e-1);
+        assert_eq!(path.figure_winding(0), Some(Winding::Clockwise));
+        assert_eq!(path.figure_winding(1), Some(Winding::CounterClockwise));
+        assert_eq!(path.figure_winding(2), None);
+        assert_eq!(path.figure_winding(3), None);
+
+        let reversed = path.reversed();
+        assert_eq!(reversed.figure_winding(0), Some(Winding::CounterClockwise));
+        assert_eq!(reversed.figures[0].start, Point2f::new(0.0, 10.0));
+        assert_eq!(reversed.figures[0].end_point(), Point2f::new(0.0, 0.0));
+        assert_eq!(reversed.reversed(), path);
+
+        let clockwise = path.with_winding(Winding::Clockwise);
+        assert_eq!(clockwise.figures[0], path.figures[0]);
+        assert_eq!(clockwise.figure_winding(1), Some(Winding::Clockwise));
+        assert_eq!(clockwise.figures[2], path.figures[2]);
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn archived() {
+        let path = Path::builder()
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .quad_to((15.0, 5.0), (10.0, 10.0))
+            .close()
+            .build();
+
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&path).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<Path>, rkyv::rancor::Error>(&bytes).unwrap();
+        assert_eq!(archived.figures.len(), 1);
+        assert_eq!(archived.figures[0].start.x, 0.0);
+        assert!(archived.figures[0].closed);
+
+        let restored = rkyv::deserialize::<Path, rkyv::rancor::Error>(archived).unwrap();
+        assert_eq!(restored, path);
+    }
+
+    #[cfg(feature = "kurbo")]
+    #[test]
+    fn kurbo_round_trip() {
+        let path = Path::builder()
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .quad_to((15.0, 5.0), (10.0, 10.0))
+            .cubic_to((8.0, 12.0), (2.0, 12.0), (0.0, 10.0))
+            .close()
+            .move_to((20.0, 0.0))
+            .line_to((25.0, 5.0))
+            .build();
+
+        let bez = kurbo::BezPath::from(path.clone());
+        assert_eq!(bez.elements().len(), 7);
+        assert_eq!(Path::from(bez), path);
+
+        let circle = Path::from(crate::ellipse::Ellipse::new((0.0, 0.0), 5.0, 5.0));
+        let bez = kurbo::BezPath::from(circle);
+        let bounds = kurbo::Shape::bounding_box(&bez);
+        assert!((bounds.width() - 10.0).abs() < 1e-3);
+        assert!((bounds.height() - 10.0).abs() < 1e-3);
+    }
+
+    #[cfg(feature = "lyon")]
+    #[test]
+    fn lyon_round_trip() {
+        let path = Path::builder()
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .quad_to((15.0, 5.0), (10.0, 10.0))
+            .cubic_to((8.0, 12.0), (2.0, 12.0), (0.0, 10.0))
+            .close()
+            .move_to((20.0, 0.0))
+            .line_to((25.0, 5.0))
+            .build();
+
+        let lyon = lyon_path::Path::from(path.clone());
+        assert_eq!(lyon.iter().count(), 8);
+        assert_eq!(Path::from(lyon), path);
+
+        let arc = ArcSegment::new(
+            (10.0, 0.0),
+            (5.0, 5.0),
+            0.0,
+            SweepDirection::Clockwise,
+            ArcSize::Small,
+        );
+        let (start, back) = ArcSegment::from_lyon(arc.to_lyon((0.0, 0.0)));
+        assert_eq!(start, Point2f::ORIGIN);
+        assert_eq!(back, arc);
+    }
+
+    #[cfg(feature = "cairo")]
+    #[test]
+    fn cairo_path() {
+        let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, 32, 32).unwrap();
+        let cr = cairo::Context::new(&surface).unwrap();
+        cr.set_matrix(Matrix3x2f::translation([2.0, 3.0]).into());
+
+        let path = Path::builder()
+            .move_to((0.0, 0.0))
+            .quad_to((10.0, 0.0), (10.0, 10.0))
+            .arc_to(
+                (0.0, 10.0),
+                (5.0, 5.0),
+                0.0,
+                SweepDirection::Clockwise,
+                ArcSize::Small,
+            )
+            .close()
+            .build();
+        path.append_to_cairo(&cr);
+
+        let (x1, y1, x2, y2) = cr.path_extents().unwrap();
+        let bounds = path.bounds();
+        assert!((x1 as f32 - bounds.left).abs() < 1e-3);
+        assert!((y1 as f32 - bounds.top).abs() < 1e-3);
+        assert!((x2 as f32 - bounds.right).abs() < 1e-3);
+        assert!((y2 as f32 - bounds.bottom).abs() < 1e-3);
+        assert_eq!(
+            Matrix3x2f::from(cr.matrix()),
+            Matrix3x2f::translation([2.0, 3.0])
+        );
+    }
+
+    #[cfg(all(feature = "tiny-skia", feature = "raqote"))]
+    #[test]
+    fn software_renderers() {
+        let path = Path::builder()
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .arc_to(
+                (10.0, 10.0),
+                (5.0, 5.0),
+                0.0,
+                SweepDirection::Clockwise,
+                ArcSize::Small,
+            )
+            .close()
+            .build();
+
+        let tiny = path.to_tiny_skia().unwrap();
+        let bounds = Rectf::from(tiny.bounds());
+        assert!((bounds.right - 15.0).abs() < 1e-3);
+        assert!((bounds.bottom - 10.0).abs() < 1e-3);
+        assert!(Path::new().to_tiny_skia().is_none());
+
+        let raqote = raqote::Path::from(path);
+        assert_eq!(raqote.ops.len(), 5);
+        assert!(raqote.contains_point(0.1, 14.0, 5.0));
+    }
+}
@@ -0,0 +1,286 @@
+//! Measures distances along a path and samples it by arc length.
+
+use crate::path::{Path, PathBuilder, PathSegment};
+use crate::point2f::Point2f;
+use crate::vector2f::Vector2f;
+
+/// Identifies a position within a path by the figure and segment it lies on,
+/// and the curve parameter within that segment.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct PathLocation {
+    /// The index of the figure in the path.
+    pub figure: usize,
+    /// The index of the segment in the figure. The implicit closing line of
+    /// a closed figure has an index equal to the number of segments.
+    pub segment: usize,
+    /// The curve parameter within the segment, in the range [0, 1].
+    pub t: f32,
+}
+
+#[derive(Clone, Debug)]
+struct MeasuredSegment {
+    figure: usize,
+    index: usize,
+    start: Point2f,
+    segment: PathSegment,
+    /// Distance from the start of the path to the start of the segment.
+    offset: f32,
+    /// Cumulative distance from the start of the segment at each evenly
+    /// spaced parameter value, ending with the length of the segment.
+    lengths: Vec<f32>,
+}
+
+impl MeasuredSegment {
+    #[inline]
+    fn length(&self) -> f32 {
+        *self.lengths.last().unwrap()
+    }
+
+    /// Maps a distance from the start of the segment to a curve parameter by
+    /// interpolating between the sampled lengths.
+    fn t_at(&self, distance: f32) -> f32 {
+        let n = self.lengths.len() - 1;
+        let i = self.lengths[1..]
+            .partition_point(|&l| l < distance)
+            .min(n - 1);
+        let (l0, l1) = (self.lengths[i], self.lengths[i + 1]);
+        let frac = if l1 > l0 {
+            ((distance - l0) / (l1 - l0)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        (i as f32 + frac) / n as f32
+    }
+}
+
+/// Precomputes the lengths along a path so that it can be sampled by
+/// distance rather than by curve parameter. This is the building block for
+/// placing text or markers along a path and for trimming a path for
+/// "draw-on" animations.
+///
+/// Curves are measured along their flattened approximations, so distances
+/// are accurate to about the tolerance given at construction. Closing lines
+/// of closed figures are measured like any other segment, and segments of
+/// zero length are skipped entirely.
+#[derive(Clone, Debug)]
+pub struct PathMeasure {
+    segments: Vec<MeasuredSegment>,
+    length: f32,
+}
+
+impl PathMeasure {
+    /// Measures the path, approximating curves to within `tolerance`.
+    pub fn new(path: &Path, tolerance: f32) -> PathMeasure {
+        let mut segments = Vec::new();
+        let mut offset = 0.0;
+
+        for (fi, figure) in path.figures.iter().enumerate() {
+            let closing = if figure.closed && figure.end_point() != figure.start {
+                Some(PathSegment::Line(figure.start))
+            } else {
+                None
+            };
+
+            let all = figure.segments().map(|(start, seg)| (start, *seg));
+            let all = all.chain(closing.map(|seg| (figure.end_point(), seg)));
+            for (si, (start, segment)) in all.enumerate() {
+                let count = segment.flatten(start, tolerance).len();
+                let mut lengths = Vec::with_capacity(count + 1);
+                let mut prev = start;
+                let mut total = 0.0;
+                lengths.push(0.0);
+                for i in 1..=count {
+                    let p = segment.point_at(start, i as f32 / count as f32);
+                    total += (p - prev).len();
+                    lengths.push(total);
+                    prev = p;
+                }
+
+                if total > 0.0 {
+                    segments.push(MeasuredSegment {
+                        figure: fi,
+                        index: si,
+                        start,
+                        segment,
+                        offset,
+                        lengths,
+                    });
+                    offset += total;
+                }
+            }
+        }
+
+        PathMeasure {
+            segments,
+            length: offset,
+        }
+    }
+
+    /// The total length of the path.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.length
+    }
+
+    /// The point at the given distance along the path. Distances outside
+    /// the path are clamped to its ends. Returns `None` if the path has no
+    /// length.
+    #[inline]
+    pub fn point_at_distance(&self, distance: f32) -> Option<Point2f> {
+        let (seg, t) = self.find(distance)?;
+        Some(seg.segment.point_at(seg.start, t))
+    }
+
+    /// The unit tangent vector at the given distance along the path.
+    /// Distances outside the path are clamped to its ends. Returns `None` if
+    /// the path has no length.
+    #[inline]
+    pub fn tangent_at_distance(&self, distance: f32) -> Option<Vector2f> {
+        let (seg, t) = self.find(distance)?;
+        Some(seg.segment.derivative_at(seg.start, t).normalized())
+    }
+
+    /// Locates the segment at the given distance along the path. Distances
+    /// outside the path are clamped to its ends. Returns `None` if the path
+    /// has no length.
+    #[inline]
+    pub fn segment_at_distance(&self, distance: f32) -> Option<PathLocation> {
+        let (seg, t) = self.find(distance)?;
+        Some(PathLocation {
+            figure: seg.figure,
+            segment: seg.index,
+            t,
+        })
+    }
+
+    /// Extracts the part of the path between the distances `d0` and `d1`.
+    /// Each figure the range passes through becomes a separate open figure
+    /// in the result. The range is clamped to the path, and an empty range
+    /// produces an empty path.
+    pub fn slice(&self, d0: f32, d1: f32) -> Path {
+        let d0 = d0.max(0.0);
+        let d1 = d1.min(self.length);
+        let mut builder = PathBuilder::new();
+        if d0 >= d1 {
+            return builder.build();
+        }
+
+        let mut figure = None;
+        for seg in &self.segments {
+            let end = seg.offset + seg.length();
+            if end <= d0 || seg.offset >= d1 {
+                continue;
+            }
+
+            let t0 = seg.t_at(d0 - seg.offset);
+            let t1 = seg.t_at(d1 - seg.offset);
+            let (start, piece) = seg.segment.subsegment(seg.start, t0, t1);
+            if figure != Some(seg.figure) {
+                builder.move_to(start);
+                figure = Some(seg.figure);
+            }
+            builder.segment(piece);
+        }
+        builder.build()
+    }
+
+    /// The distances where each figure begins and ends, ordered by figure.
+    /// Figures without any length are omitted.
+    pub(crate) fn figure_ranges(&self) -> Vec<(f32, f32)> {
+        let mut ranges: Vec<(usize, f32, f32)> = Vec::new();
+        for seg in &self.segments {
+            let end = seg.offset + seg.length();
+            match ranges.last_mut() {
+                Some(last) if last.0 == seg.figure => last.2 = end,
+                _ => ranges.push((seg.figure, seg.offset, end)),
+            }
+        }
+        ranges
+            .into_iter()
+            .map(|(_, start, end)| (start, end))
+            .collect()
+    }
+
+    fn find(&self, distance: f32) -> Option<(&MeasuredSegment, f32)> {
+        if self.segments.is_empty() {
+            return None;
+        }
+
+        let distance = distance.clamp(0.0, self.length);
+        let i = self
+            .segments
+            .partition_point(|s| s.offset + s.length() < distance)
+            .min(self.segments.len() - 1);
+        let seg = &self.segments[i];
+        Some((seg, seg.t_at(distance - seg.offset)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::path::Path;
+    use crate::path_measure::PathMeasure;
+    use crate::vector2f::Vector2f;
+
+    #[test]
+    fn sampling() {
+        let path = Path::builder()
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .line_to((10.0, 10.0))
+            .close()
+            .build();
+        let measure = PathMeasure::new(&path, 0.01);
+        let diagonal = 200.0f32.sqrt();
+        assert!((measure.length() - (20.0 + diagonal)).abs() < 1e-4);
+
+        let p = measure.point_at_distance(15.0).unwrap();
+        assert!(p.is_approx_eq((10.0, 5.0), 1e-5));
+        let t = measure.tangent_at_distance(15.0).unwrap();
+        assert!(t.is_approx_eq(Vector2f::DOWN, 1e-5));
+
+        // The closing line is the third segment of the figure
+        let loc = measure.segment_at_distance(20.0 + diagonal / 2.0).unwrap();
+        assert_eq!((loc.figure, loc.segment), (0, 2));
+        assert!((loc.t - 0.5).abs() < 1e-4);
+
+        // Out of range distances clamp to the ends
+        let end = measure.point_at_distance(1000.0).unwrap();
+        assert!(end.is_approx_eq((0.0, 0.0), 1e-5));
+        assert!(PathMeasure::new(&Path::new(), 0.01)
+            .point_at_distance(0.0)
+            .is_none());
+    }
+
+    #[test]
+    fn slice() {
+        let path = Path::builder()
+            .move_to((0.0, 0.0))
+            .quad_to((5.0, 10.0), (10.0, 0.0))
+            .line_to((20.0, 0.0))
+            .move_to((0.0, 10.0))
+            .line_to((10.0, 10.0))
+            .build();
+        let measure = PathMeasure::new(&path, 0.001);
+        let curve = measure.length() - 20.0;
+
+        let sliced = measure.slice(curve / 2.0, curve + 15.0);
+        assert_eq!(sliced.figures.len(), 2);
+        assert_eq!(sliced.figures[0].segments.len(), 2);
+        assert!(sliced.figures[0].start.is_approx_eq((5.0, 5.0), 1e-2));
+        assert!(sliced.figures[1]
+            .end_point()
+            .is_approx_eq((5.0, 10.0), 1e-3));
+
+        let sliced_length = PathMeasure::new(&sliced, 0.001).length();
+        assert!((sliced_length - (curve / 2.0 + 15.0)).abs() < 1e-2);
+        assert!(measure.slice(5.0, 5.0).is_empty());
+    }
+}
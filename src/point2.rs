@@ -0,0 +1,551 @@
+//! Point on the 2D (x, y) plane, generic over its component type.
+
+use crate::axis::Axis;
+use crate::parse::{self, ParseError};
+use crate::vector2::Vector2;
+
+use num_traits::{AsPrimitive, Float, Num};
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Mathematical point on the 2D (x, y) plane, generic over the type of its
+/// components. Points are offset by `Vector2`s of the same component type,
+/// for any `num_traits::Num` component. `Point2f`, `Point2d`, `Point2i` and
+/// `Point2u` name the common component types.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde-compact",
+    serde(
+        into = "crate::compact::Array<[T; 2]>",
+        from = "crate::compact::Array<[T; 2]>",
+        bound(
+            serialize = "T: Clone + serde::Serialize",
+            deserialize = "T: serde::Deserialize<'de>"
+        )
+    )
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[repr(C)]
+pub struct Point2<T> {
+    /// Horizontal component
+    pub x: T,
+    /// Vertical component
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    /// Construct a point from its components
+    #[inline]
+    pub fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+
+    /// Converts each component with `f`, such as to change to another
+    /// numeric type.
+    #[inline]
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Point2<U> {
+        Point2 {
+            x: f(self.x),
+            y: f(self.y),
+        }
+    }
+
+    /// Constructs a point from its components along `axis` and the other
+    /// axis.
+    #[inline]
+    pub fn from_axes(axis: Axis, along: T, across: T) -> Self {
+        match axis {
+            Axis::X => Point2::new(along, across),
+            Axis::Y => Point2::new(across, along),
+        }
+    }
+
+    /// The component along `axis`.
+    #[inline]
+    pub fn get(&self, axis: Axis) -> T
+    where
+        T: Copy,
+    {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+        }
+    }
+
+    /// Borrows the component along `axis` mutably.
+    #[inline]
+    pub fn get_mut(&mut self, axis: Axis) -> &mut T {
+        match axis {
+            Axis::X => &mut self.x,
+            Axis::Y => &mut self.y,
+        }
+    }
+
+    /// Replaces the component along `axis`.
+    #[inline]
+    pub fn set(&mut self, axis: Axis, value: T) {
+        *self.get_mut(axis) = value;
+    }
+
+    /// The point with its components swapped.
+    #[inline]
+    pub fn yx(self) -> Self {
+        Point2::new(self.y, self.x)
+    }
+
+    /// The point with its x-component replaced by `x`.
+    #[inline]
+    pub fn with_x(self, x: T) -> Self {
+        Point2 { x, ..self }
+    }
+
+    /// The point with its y-component replaced by `y`.
+    #[inline]
+    pub fn with_y(self, y: T) -> Self {
+        Point2 { y, ..self }
+    }
+}
+
+impl<T: Num + Copy> Point2<T> {
+    /// The vector from the origin to this point.
+    #[inline]
+    pub fn to_vector(self) -> Vector2<T> {
+        Vector2::new(self.x, self.y)
+    }
+}
+
+impl<T: Float> Point2<T> {
+    /// Rounds the values in the point to the nearest integer, rounding away
+    /// from zero in the half-way case.
+    #[inline]
+    pub fn rounded(self) -> Self {
+        self.map(T::round)
+    }
+}
+
+impl<T: Copy + 'static> Point2<T> {
+    /// Converts the components with `as`, so floats are truncated towards
+    /// zero and saturate at the limits of integer types. Round the point
+    /// first if another behavior is desired.
+    #[inline]
+    pub fn cast<U: Copy + 'static>(self) -> Point2<U>
+    where
+        T: AsPrimitive<U>,
+    {
+        self.map(T::as_)
+    }
+
+    /// Converts the components to `f32`, as with `cast`.
+    #[inline]
+    pub fn to_f32(self) -> Point2<f32>
+    where
+        T: AsPrimitive<f32>,
+    {
+        self.cast()
+    }
+
+    /// Converts the components to `f64`, as with `cast`.
+    #[inline]
+    pub fn to_f64(self) -> Point2<f64>
+    where
+        T: AsPrimitive<f64>,
+    {
+        self.cast()
+    }
+
+    /// Converts the components to `i32`, as with `cast`.
+    #[inline]
+    pub fn to_i32(self) -> Point2<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast()
+    }
+
+    /// Converts the components to `u32`, as with `cast`. Negative
+    /// components become 0.
+    #[inline]
+    pub fn to_u32(self) -> Point2<u32>
+    where
+        T: AsPrimitive<u32>,
+    {
+        self.cast()
+    }
+}
+
+impl<T: Num + Copy, V: Into<Vector2<T>>> Add<V> for Point2<T> {
+    type Output = Point2<T>;
+
+    #[inline]
+    fn add(self, rhs: V) -> Point2<T> {
+        let rhs = rhs.into();
+        Point2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Num + Copy> Add<Point2<T>> for Vector2<T> {
+    type Output = Point2<T>;
+
+    #[inline]
+    fn add(self, rhs: Point2<T>) -> Point2<T> {
+        rhs + self
+    }
+}
+
+impl<T: Num + Copy> Add<(T, T)> for Vector2<T> {
+    type Output = Point2<T>;
+
+    #[inline]
+    fn add(self, rhs: (T, T)) -> Point2<T> {
+        Point2::from(rhs) + self
+    }
+}
+
+impl<T: Num + Copy> Sub for Point2<T> {
+    type Output = Vector2<T>;
+
+    #[inline]
+    fn sub(self, rhs: Point2<T>) -> Vector2<T> {
+        Vector2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Num + Copy> Sub<(T, T)> for Point2<T> {
+    type Output = Vector2<T>;
+
+    #[inline]
+    fn sub(self, rhs: (T, T)) -> Vector2<T> {
+        self - Point2::from(rhs)
+    }
+}
+
+impl<T: Num + Copy> Sub<Point2<T>> for (T, T) {
+    type Output = Vector2<T>;
+
+    #[inline]
+    fn sub(self, rhs: Point2<T>) -> Vector2<T> {
+        Point2::from(self) - rhs
+    }
+}
+
+impl<T: Num + Copy, V: Into<Vector2<T>>> Sub<V> for Point2<T> {
+    type Output = Point2<T>;
+
+    #[inline]
+    fn sub(self, rhs: V) -> Point2<T> {
+        let rhs = rhs.into();
+        Point2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T, V> AddAssign<V> for Point2<T>
+where
+    Point2<T>: Add<V, Output = Point2<T>> + Copy,
+{
+    #[inline]
+    fn add_assign(&mut self, v: V) {
+        *self = *self + v;
+    }
+}
+
+impl<T, V> SubAssign<V> for Point2<T>
+where
+    Point2<T>: Sub<V, Output = Point2<T>> + Copy,
+{
+    #[inline]
+    fn sub_assign(&mut self, v: V) {
+        *self = *self - v;
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    #[inline]
+    fn from((x, y): (T, T)) -> Point2<T> {
+        Point2::new(x, y)
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    #[inline]
+    fn from(p: Point2<T>) -> (T, T) {
+        (p.x, p.y)
+    }
+}
+
+impl<T> From<[T; 2]> for Point2<T> {
+    #[inline]
+    fn from([x, y]: [T; 2]) -> Point2<T> {
+        Point2::new(x, y)
+    }
+}
+
+impl<T> From<Point2<T>> for [T; 2] {
+    #[inline]
+    fn from(p: Point2<T>) -> [T; 2] {
+        [p.x, p.y]
+    }
+}
+
+/// Mathematical point on the 2D (x, y) plane, with double precision
+/// components for coordinates too large or too finely detailed for `Point2f`.
+pub type Point2d = Point2<f64>;
+
+/// Widens each component exactly.
+impl From<Point2<f32>> for Point2<f64> {
+    #[inline]
+    fn from(p: Point2<f32>) -> Point2<f64> {
+        p.map(f64::from)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        parse::write_components(fmt, ('(', ')'), &[&self.x, &self.y])
+    }
+}
+
+impl<T: FromStr> FromStr for Point2<T> {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Point2<T>, ParseError> {
+        let [x, y] = parse::parse_components(s, ('(', ')'))?;
+        Ok(Point2 { x, y })
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<T, U> From<Point2<T>> for euclid::Point2D<T, U> {
+    #[inline]
+    fn from(p: Point2<T>) -> euclid::Point2D<T, U> {
+        euclid::Point2D::new(p.x, p.y)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<T, U> From<euclid::Point2D<T, U>> for Point2<T> {
+    #[inline]
+    fn from(p: euclid::Point2D<T, U>) -> Point2<T> {
+        Point2 { x: p.x, y: p.y }
+    }
+}
+
+#[cfg(feature = "winit")]
+impl<T> From<Point2<T>> for winit_dpi::PhysicalPosition<T> {
+    #[inline]
+    fn from(p: Point2<T>) -> winit_dpi::PhysicalPosition<T> {
+        winit_dpi::PhysicalPosition::new(p.x, p.y)
+    }
+}
+
+#[cfg(feature = "winit")]
+impl<T> From<winit_dpi::PhysicalPosition<T>> for Point2<T> {
+    #[inline]
+    fn from(p: winit_dpi::PhysicalPosition<T>) -> Point2<T> {
+        Point2 { x: p.x, y: p.y }
+    }
+}
+
+#[cfg(feature = "winit")]
+impl<T> From<Point2<T>> for winit_dpi::LogicalPosition<T> {
+    #[inline]
+    fn from(p: Point2<T>) -> winit_dpi::LogicalPosition<T> {
+        winit_dpi::LogicalPosition::new(p.x, p.y)
+    }
+}
+
+#[cfg(feature = "winit")]
+impl<T> From<winit_dpi::LogicalPosition<T>> for Point2<T> {
+    #[inline]
+    fn from(p: winit_dpi::LogicalPosition<T>) -> Point2<T> {
+        Point2 { x: p.x, y: p.y }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<T> From<Point2<T>> for mint::Point2<T> {
+    #[inline]
+    fn from(p: Point2<T>) -> mint::Point2<T> {
+        mint::Point2 { x: p.x, y: p.y }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<T> From<mint::Point2<T>> for Point2<T> {
+    #[inline]
+    fn from(p: mint::Point2<T>) -> Point2<T> {
+        Point2 { x: p.x, y: p.y }
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar> From<Point2<T>> for nalgebra::Point2<T> {
+    #[inline]
+    fn from(p: Point2<T>) -> nalgebra::Point2<T> {
+        nalgebra::Point2::new(p.x, p.y)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar> From<nalgebra::Point2<T>> for Point2<T> {
+    #[inline]
+    fn from(p: nalgebra::Point2<T>) -> Point2<T> {
+        let [x, y]: [T; 2] = p.coords.into();
+        Point2 { x, y }
+    }
+}
+
+// The struct is `#[repr(C)]` with every field of type `T`, so it has
+// the same layout as `[T; 2]`.
+#[cfg(feature = "encase")]
+impl<T: encase::vector::VectorScalar> encase::vector::AsRefVectorParts<T, 2> for Point2<T> {
+    #[inline]
+    fn as_ref_parts(&self) -> &[T; 2] {
+        unsafe { &*(self as *const Point2<T> as *const [T; 2]) }
+    }
+}
+
+#[cfg(feature = "encase")]
+impl<T: encase::vector::VectorScalar> encase::vector::AsMutVectorParts<T, 2> for Point2<T> {
+    #[inline]
+    fn as_mut_parts(&mut self) -> &mut [T; 2] {
+        unsafe { &mut *(self as *mut Point2<T> as *mut [T; 2]) }
+    }
+}
+
+#[cfg(feature = "encase")]
+impl<T: encase::vector::VectorScalar> encase::vector::FromVectorParts<T, 2> for Point2<T> {
+    #[inline]
+    fn from_parts([x, y]: [T; 2]) -> Point2<T> {
+        Point2 { x, y }
+    }
+}
+
+// Points are laid out as a `vec2` in shader buffers.
+#[cfg(feature = "encase")]
+encase::impl_vector!(2, Point2<T>; (T: encase::vector::VectorScalar));
+
+#[cfg(test)]
+mod tests {
+    use crate::point2::{Point2, Point2d};
+    use crate::point2f::Point2f;
+    use crate::rect::{Rect, Rectd};
+    use crate::rectf::Rectf;
+    use crate::sizef::Sizef;
+    use crate::vector2::Vector2;
+    use crate::vector2f::Vector2f;
+
+    #[test]
+    fn generic() {
+        let p: Point2<f64> = Point2::new(1.5, -2.0);
+        let q: Point2f = p.map(|c| c as f32);
+        assert_eq!(q, Point2f::new(1.5, -2.0));
+        assert_eq!(Point2::new(3i64, 4i64).map(|c| c * 2), Point2::new(6, 8));
+    }
+
+    #[test]
+    fn generic_arithmetic() {
+        // i64 has no alias of its own, but gets the same operations
+        let p = Point2::new(1i64 << 40, 3);
+        let v = Vector2::new(2i64, -4);
+        assert_eq!(p + v - p, v);
+        assert_eq!(v * 3 / 2, Vector2::new(3, -6));
+        assert_eq!(v.dot(v), 20);
+        assert_eq!(v.abs(), Vector2::new(2, 4));
+        assert_eq!(
+            (p + v).to_f64(),
+            Point2::new((1u64 << 40) as f64 + 2.0, -1.0)
+        );
+
+        let r = Rect::from_points(p, p - v);
+        assert_eq!(r, Rect::new(p.x - 2, 3, p.x, 7));
+        assert_eq!(r.center(), Point2::new(p.x - 1, 5));
+        assert!(r.contains_point((p.x, 7)));
+        assert_eq!(Rect::new(4i32, 2, 1, 8).normalized(), Rect::new(1, 2, 4, 8));
+
+        // Casts truncate towards zero, and saturate at the integer limits
+        let v = Vector2::new(-1.5f32, 1e20);
+        assert_eq!(v.to_i32(), Vector2::new(-1, i32::MAX));
+        assert_eq!(v.to_u32(), Vector2::new(0, u32::MAX));
+        assert_eq!(Vector2f::from(3.0).normalized().len(), 1.0);
+    }
+
+    #[test]
+    fn double_precision() {
+        let r = Rectf::new(0.1, 0.2, 0.3, 0.4);
+        assert_eq!(Rectd::from(r).to_f32(), r);
+
+        // A centimeter on the far side of the planet is lost in an f32
+        let far = Point2d::new(40_075_000.0, 0.0);
+        let near = far + [0.01, 0.0];
+        assert!(near.x > far.x);
+        assert_eq!(near.to_f32(), far.to_f32());
+        assert!(Rectd::from_points(far, near).contains_point((40_075_000.005, 0.0)));
+    }
+
+    #[test]
+    fn component_helpers() {
+        let p = Point2f::new(1.0, 2.0);
+        assert_eq!(p.yx(), Point2f::new(2.0, 1.0));
+        assert_eq!(p.with_x(5.0).with_y(6.0), Point2f::new(5.0, 6.0));
+        assert_eq!(
+            Vector2f::new(3.0, 4.0).with_y(0.0).yx(),
+            Vector2f::new(0.0, 3.0)
+        );
+        assert_eq!(Sizef::new(10.0, 20.0).transpose(), Sizef::new(20.0, 10.0));
+
+        // Sliding one edge of a rectangle, e.g. to align it with a sibling
+        let rect = Rectf::new(0.0, 0.0, 10.0, 10.0);
+        assert_eq!(
+            rect.with_left(4.0).with_bottom(2.0),
+            Rectf::new(4.0, 0.0, 10.0, 2.0)
+        );
+        assert_eq!(
+            rect.with_top(-1.0).with_right(3.0),
+            Rectf::new(0.0, -1.0, 3.0, 10.0)
+        );
+    }
+
+    #[cfg(all(feature = "serde", not(feature = "serde-compact")))]
+    #[test]
+    fn serde_fields() {
+        use serde_test::{assert_tokens, Token};
+
+        assert_tokens(
+            &Point2f::new(1.0, 2.0),
+            &[
+                Token::Struct {
+                    name: "Point2",
+                    len: 2,
+                },
+                Token::Str("x"),
+                Token::F32(1.0),
+                Token::Str("y"),
+                Token::F32(2.0),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn json_schema() {
+        let schema = schemars::schema_for!(Point2f);
+        let ty = schema.get("type").and_then(|t| t.as_str());
+        if cfg!(feature = "serde-compact") {
+            assert_eq!(ty, Some("array"));
+        } else {
+            assert_eq!(ty, Some("object"));
+            let properties = schema.get("properties").unwrap();
+            assert!(properties.get("x").is_some() && properties.get("y").is_some());
+        }
+    }
+}
@@ -1,24 +1,16 @@
 //! Mathematical point on the 2D (x, y) plane.
 
-use crate::point2i::Point2i;
-use crate::point2u::Point2u;
-use crate::vector2f::Vector2f;
-
-use std::ops::{Add, Sub};
+use crate::point2::Point2;
+use crate::tolerance::Tolerance;
+use crate::ulps::approx_eq_ulps;
 
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::dcommon::D2D_POINT_2F;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D::Common as d2d_common;
 
 /// Mathematical point on the 2D (x, y) plane.
-#[derive(Copy, Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
-#[repr(C)]
-pub struct Point2f {
-    /// Horizontal component
-    pub x: f32,
-    /// Vertical component
-    pub y: f32,
-}
+pub type Point2f = Point2<f32>;
 
 /// Mathematical origin point on the real number plane.
 pub const ORIGIN: Point2f = Point2f::ORIGIN;
@@ -27,200 +19,154 @@ impl Point2f {
     /// Mathematical origin point on the real number plane.
     pub const ORIGIN: Point2f = Point2f { x: 0.0, y: 0.0 };
 
-    /// Construct a point from the components
-    #[inline]
-    pub fn new(x: f32, y: f32) -> Self {
-        Point2f { x, y }
-    }
-
-    /// Casts the point to an integer point. Will truncate integers; if
-    /// another behavior is desired it should be manually performed on
-    /// the values before calling this function.
-    #[inline]
-    pub fn to_i32(self) -> Point2i {
-        Point2i {
-            x: self.x as i32,
-            y: self.y as i32,
-        }
-    }
-
-    /// Casts the point to an unsigned integer point. Will truncate integers;
-    /// if another behavior is desired it should be manually performed on
-    /// the values before calling this function. Beware this function will
-    /// not produce reasonable values if the current value is negative.
-    #[inline]
-    pub fn to_u32(self) -> Point2u {
-        Point2u {
-            x: self.x as u32,
-            y: self.y as u32,
-        }
-    }
-
-    #[inline]
-    pub fn to_vector(self) -> Vector2f {
-        self - ORIGIN
-    }
-
-    /// Rounds the values in the point to the nearest integer, rounding away
-    /// from zero in the half-way case.
-    ///
-    /// See [f32::round][1]
-    ///
-    /// [1]: https://doc.rust-lang.org/std/primitive.f32.html#method.round
-    #[inline]
-    pub fn rounded(self) -> Point2f {
-        Point2f {
-            x: self.x.round(),
-            y: self.y.round(),
-        }
-    }
-
     /// Determines if the components of two points are less than `epsilon`
     /// distance from each other. Be wary that this does not check the actual
     /// distance, but a component-wise distance check. If you desire a more
     /// precise distance check, consider subtracting one point from the other
     /// and comparing the length(_sq) of the resulting vector.
+    ///
+    /// `epsilon` may be a plain `f32` or a `Tolerance`, whose relative part
+    /// scales with the magnitude of each component.
     #[inline]
-    pub fn is_approx_eq(self, other: impl Into<Point2f>, epsilon: f32) -> bool {
+    pub fn is_approx_eq(self, other: impl Into<Point2f>, epsilon: impl Into<Tolerance>) -> bool {
+        let (other, epsilon) = (other.into(), epsilon.into());
+        return epsilon.approx_eq(self.x, other.x) && epsilon.approx_eq(self.y, other.y);
+    }
+
+    /// Determines if the components of two points are at most `max_ulps`
+    /// representable values apart, which scales the tolerance with the
+    /// magnitude of the coordinates. See `math2d::ulps`.
+    #[inline]
+    pub fn is_approx_eq_ulps(self, other: impl Into<Point2f>, max_ulps: u32) -> bool {
         let other = other.into();
-        return (self.x - other.x).abs() <= epsilon && (self.y - other.y).abs() <= epsilon;
+        approx_eq_ulps(self.x, other.x, max_ulps) && approx_eq_ulps(self.y, other.y, max_ulps)
     }
 }
 
-impl<V> Add<V> for Point2f
-where
-    V: Into<Vector2f>,
-{
-    type Output = Point2f;
-
+#[cfg(all(windows, feature = "d2d"))]
+impl From<Point2f> for D2D_POINT_2F {
     #[inline]
-    fn add(self, rhs: V) -> Point2f {
-        let rhs = rhs.into();
-        Point2f {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
+    fn from(point: Point2f) -> D2D_POINT_2F {
+        D2D_POINT_2F {
+            x: point.x,
+            y: point.y,
         }
     }
 }
 
-impl Sub for Point2f {
-    type Output = Vector2f;
-
+#[cfg(all(windows, feature = "d2d"))]
+impl From<D2D_POINT_2F> for Point2f {
     #[inline]
-    fn sub(self, rhs: Point2f) -> Vector2f {
-        Vector2f {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
+    fn from(point: D2D_POINT_2F) -> Point2f {
+        Point2f {
+            x: point.x,
+            y: point.y,
         }
     }
 }
 
-impl Sub<(f32, f32)> for Point2f {
-    type Output = Vector2f;
-
+#[cfg(all(windows, feature = "windows"))]
+impl From<Point2f> for d2d_common::D2D_POINT_2F {
     #[inline]
-    fn sub(self, rhs: (f32, f32)) -> Vector2f {
-        Vector2f {
-            x: self.x - rhs.0,
-            y: self.y - rhs.1,
+    fn from(point: Point2f) -> d2d_common::D2D_POINT_2F {
+        d2d_common::D2D_POINT_2F {
+            x: point.x,
+            y: point.y,
         }
     }
 }
 
-impl Sub<Point2f> for (f32, f32) {
-    type Output = Vector2f;
-
+#[cfg(all(windows, feature = "windows"))]
+impl From<d2d_common::D2D_POINT_2F> for Point2f {
     #[inline]
-    fn sub(self, rhs: Point2f) -> Vector2f {
-        Vector2f {
-            x: self.0 - rhs.x,
-            y: self.1 - rhs.y,
+    fn from(point: d2d_common::D2D_POINT_2F) -> Point2f {
+        Point2f {
+            x: point.x,
+            y: point.y,
         }
     }
 }
 
-impl<V> Sub<V> for Point2f
-where
-    V: Into<Vector2f>,
-{
-    type Output = Point2f;
-
+#[cfg(feature = "kurbo")]
+impl From<kurbo::Vec2> for Point2f {
     #[inline]
-    fn sub(self, rhs: V) -> Point2f {
-        let rhs = rhs.into();
+    fn from(p: kurbo::Vec2) -> Point2f {
         Point2f {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
+            x: p.x as f32,
+            y: p.y as f32,
         }
     }
 }
 
-impl From<(f32, f32)> for Point2f {
+#[cfg(feature = "kurbo")]
+impl From<Point2f> for kurbo::Point {
     #[inline]
-    fn from((x, y): (f32, f32)) -> Point2f {
-        Point2f { x, y }
+    fn from(p: Point2f) -> kurbo::Point {
+        kurbo::Point::new(p.x.into(), p.y.into())
     }
 }
 
-impl From<[f32; 2]> for Point2f {
+#[cfg(feature = "kurbo")]
+impl From<kurbo::Point> for Point2f {
     #[inline]
-    fn from(p: [f32; 2]) -> Point2f {
-        Point2f { x: p[0], y: p[1] }
+    fn from(p: kurbo::Point) -> Point2f {
+        Point2f {
+            x: p.x as f32,
+            y: p.y as f32,
+        }
     }
 }
 
-impl From<Point2f> for [f32; 2] {
+#[cfg(feature = "glam")]
+impl From<Point2f> for glam::Vec2 {
     #[inline]
-    fn from(p: Point2f) -> [f32; 2] {
-        [p.x, p.y]
+    fn from(p: Point2f) -> glam::Vec2 {
+        glam::Vec2::new(p.x, p.y)
     }
 }
 
-#[cfg(all(windows, feature = "d2d"))]
-impl From<Point2f> for D2D_POINT_2F {
+#[cfg(feature = "glam")]
+impl From<glam::Vec2> for Point2f {
     #[inline]
-    fn from(point: Point2f) -> D2D_POINT_2F {
-        D2D_POINT_2F {
-            x: point.x,
-            y: point.y,
-        }
+    fn from(p: glam::Vec2) -> Point2f {
+        Point2f { x: p.x, y: p.y }
     }
 }
 
-#[cfg(all(windows, feature = "d2d"))]
-impl From<D2D_POINT_2F> for Point2f {
+#[cfg(feature = "skia")]
+impl From<Point2f> for skia_safe::Point {
     #[inline]
-    fn from(point: D2D_POINT_2F) -> Point2f {
-        Point2f {
-            x: point.x,
-            y: point.y,
-        }
+    fn from(p: Point2f) -> skia_safe::Point {
+        skia_safe::Point::new(p.x, p.y)
     }
 }
 
-#[cfg(feature = "mint")]
-impl From<Point2f> for mint::Point2<f32> {
+#[cfg(feature = "skia")]
+impl From<skia_safe::Point> for Point2f {
     #[inline]
-    fn from(p: Point2f) -> mint::Point2<f32> {
-        mint::Point2 { x: p.x, y: p.y }
+    fn from(p: skia_safe::Point) -> Point2f {
+        Point2f { x: p.x, y: p.y }
     }
 }
 
-#[cfg(feature = "mint")]
-impl From<mint::Point2<f32>> for Point2f {
+#[cfg(feature = "web")]
+impl Point2f {
+    /// Constructs a DOM point at this position. This fails with the
+    /// exception thrown by the constructor, e.g. when there is no DOM.
     #[inline]
-    fn from(p: mint::Point2<f32>) -> Point2f {
-        Point2f { x: p.x, y: p.y }
+    pub fn to_dom_point(&self) -> Result<web_sys::DomPoint, web_sys::wasm_bindgen::JsValue> {
+        web_sys::DomPoint::new_with_x_and_y(self.x.into(), self.y.into())
     }
 }
 
-#[cfg(feature = "kurbo")]
-impl From<kurbo::Vec2> for Point2f {
+/// The z and w components of the DOM point are ignored.
+#[cfg(feature = "web")]
+impl<'a> From<&'a web_sys::DomPoint> for Point2f {
     #[inline]
-    fn from(p: kurbo::Vec2) -> Point2f {
+    fn from(p: &'a web_sys::DomPoint) -> Point2f {
         Point2f {
-            x: p.x as f32,
-            y: p.y as f32,
+            x: p.x() as f32,
+            y: p.y() as f32,
         }
     }
 }
@@ -241,3 +187,20 @@ fn pt2f_d2d_bin_compat() {
     assert!(ptr_eq(&pt.y, &d2d.y));
     assert_eq!(size_of_val(&pt), size_of_val(d2d));
 }
+
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn pt2f_windows_bin_compat() {
+    use std::mem::size_of_val;
+
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
+    }
+
+    let pt = Point2f::ORIGIN;
+    let d2d = unsafe { &*((&pt) as *const _ as *const d2d_common::D2D_POINT_2F) };
+
+    assert!(ptr_eq(&pt.x, &d2d.x));
+    assert!(ptr_eq(&pt.y, &d2d.y));
+    assert_eq!(size_of_val(&pt), size_of_val(d2d));
+}
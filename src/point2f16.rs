@@ -0,0 +1,120 @@
+//! Half precision point for compact storage.
+
+use crate::point2::Point2;
+use crate::point2f::Point2f;
+
+use half::f16;
+use half::slice::HalfFloatSliceExt;
+
+/// Point on the 2D (x, y) plane stored as two half precision floats, taking
+/// up half the memory of a `Point2f`. It is meant for storing large meshes
+/// and paths, and should be promoted to a `Point2f` for any math.
+///
+/// Half floats keep 11 significant bits, so values are only exact to about
+/// three decimal digits, and magnitudes above 65504 become infinite.
+pub type Point2f16 = Point2<f16>;
+
+impl Point2f16 {
+    /// Mathematical origin point.
+    pub const ORIGIN: Point2f16 = Point2f16 {
+        x: f16::ZERO,
+        y: f16::ZERO,
+    };
+
+    /// Rounds each component of the point to the nearest half float.
+    #[inline]
+    pub fn from_f32(point: Point2f) -> Point2f16 {
+        Point2f16 {
+            x: f16::from_f32(point.x),
+            y: f16::from_f32(point.y),
+        }
+    }
+
+    /// Promotes every point in `src` into the same position of `dst`. This
+    /// uses the hardware conversion instructions where they are available,
+    /// which is much faster than converting the points one at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two slices have different lengths.
+    #[inline]
+    pub fn convert_to_f32_slice(src: &[Point2f16], dst: &mut [Point2f]) {
+        assert_eq!(src.len(), dst.len(), "slices must have the same length");
+        halves(src).convert_to_f32_slice(floats(dst));
+    }
+
+    /// Rounds every point in `src` into the same position of `dst`, like
+    /// `from_f32` but using the hardware conversion instructions where they
+    /// are available.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two slices have different lengths.
+    #[inline]
+    pub fn convert_from_f32_slice(src: &[Point2f], dst: &mut [Point2f16]) {
+        assert_eq!(src.len(), dst.len(), "slices must have the same length");
+        halves_mut(dst).convert_from_f32_slice(floats_ref(src));
+    }
+}
+
+// Both point types are #[repr(C)] with two fields of the same type, so their
+// slices have the layout of a flat slice twice the length.
+
+#[inline]
+fn halves(points: &[Point2f16]) -> &[f16] {
+    unsafe { std::slice::from_raw_parts(points.as_ptr() as *const f16, points.len() * 2) }
+}
+
+#[inline]
+fn halves_mut(points: &mut [Point2f16]) -> &mut [f16] {
+    unsafe { std::slice::from_raw_parts_mut(points.as_mut_ptr() as *mut f16, points.len() * 2) }
+}
+
+#[inline]
+fn floats_ref(points: &[Point2f]) -> &[f32] {
+    unsafe { std::slice::from_raw_parts(points.as_ptr() as *const f32, points.len() * 2) }
+}
+
+#[inline]
+fn floats(points: &mut [Point2f]) -> &mut [f32] {
+    unsafe { std::slice::from_raw_parts_mut(points.as_mut_ptr() as *mut f32, points.len() * 2) }
+}
+
+/// Promotes the point to single precision. This is exact, and is the same
+/// as `to_f32`.
+impl From<Point2f16> for Point2f {
+    #[inline]
+    fn from(p: Point2f16) -> Point2f {
+        p.to_f32()
+    }
+}
+
+impl From<Point2f> for Point2f16 {
+    #[inline]
+    fn from(p: Point2f) -> Point2f16 {
+        Point2f16::from_f32(p)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn half_conversions() {
+    let p = Point2f::new(1.5, -1024.0);
+    assert_eq!(Point2f16::from(p).to_f32(), p);
+
+    // Rounds to the 11 significant bits a half float holds
+    let rounded = Point2f16::from_f32(Point2f::new(1.0 / 3.0, 70000.0)).to_f32();
+    assert_eq!(rounded.x, 0.33325195);
+    assert_eq!(rounded.y, f32::INFINITY);
+
+    let points: Vec<Point2f> = (0..9)
+        .map(|i| Point2f::new(i as f32, -0.5 * i as f32))
+        .collect();
+    let mut halves = vec![Point2f16::ORIGIN; points.len()];
+    Point2f16::convert_from_f32_slice(&points, &mut halves);
+    assert_eq!(halves[3], Point2f16::from(points[3]));
+
+    let mut promoted = vec![Point2f::ORIGIN; points.len()];
+    Point2f16::convert_to_f32_slice(&halves, &mut promoted);
+    assert_eq!(promoted, points);
+}
@@ -1,183 +1,166 @@
 //! Mathematical point on the 2D (x, y) plane.
 
-use crate::point2f::Point2f;
-use crate::point2u::Point2u;
-use crate::vector2i::Vector2i;
-
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+use crate::point2::Point2;
+use crate::raster::{self, BresenhamLine, WuLine};
 
+#[cfg(all(windows, feature = "d2d"))]
+use winapi::shared::windef::POINTL;
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::dcommon::D2D_POINT_2L;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Foundation as foundation;
 
 /// Mathematical point on the 2D (x, y) plane.
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
-#[repr(C)]
-pub struct Point2i {
-    /// Horizontal component
-    pub x: i32,
-    /// Vertical component
-    pub y: i32,
-}
+pub type Point2i = Point2<i32>;
 
 impl Point2i {
     /// Mathematical origin point
     pub const ORIGIN: Point2i = Point2i { x: 0, y: 0 };
 
-    /// Construct a point from its components
+    /// The four points sharing an edge with this one on the integer grid, in
+    /// the order right, down, left, up.
     #[inline]
-    pub fn new(x: i32, y: i32) -> Point2i {
-        Point2i { x, y }
+    pub fn neighbors4(self) -> [Point2i; 4] {
+        let Point2i { x, y } = self;
+        [
+            Point2i::new(x + 1, y),
+            Point2i::new(x, y + 1),
+            Point2i::new(x - 1, y),
+            Point2i::new(x, y - 1),
+        ]
     }
 
-    /// Convert this value to a floating point
+    /// The eight points sharing an edge or corner with this one on the
+    /// integer grid, clockwise on screen starting from the right.
     #[inline]
-    pub fn to_f32(self) -> Point2f {
-        Point2f {
-            x: self.x as f32,
-            y: self.y as f32,
-        }
+    pub fn neighbors8(self) -> [Point2i; 8] {
+        let Point2i { x, y } = self;
+        [
+            Point2i::new(x + 1, y),
+            Point2i::new(x + 1, y + 1),
+            Point2i::new(x, y + 1),
+            Point2i::new(x - 1, y + 1),
+            Point2i::new(x - 1, y),
+            Point2i::new(x - 1, y - 1),
+            Point2i::new(x, y - 1),
+            Point2i::new(x + 1, y - 1),
+        ]
     }
 
-    /// Convert this value to an unsigned point. It is the caller's duty
-    /// to ensure the values are not negative to avoid casting underflow.
+    /// The number of steps between two points when moving only
+    /// horizontally or vertically, the sum of the differences along each
+    /// axis.
     #[inline]
-    pub fn to_u32(self) -> Point2u {
-        Point2u {
-            x: self.x as u32,
-            y: self.y as u32,
-        }
+    pub fn manhattan_distance(self, other: impl Into<Point2i>) -> i32 {
+        let d = other.into() - self;
+        d.x.abs() + d.y.abs()
     }
-}
-
-impl<V> Add<V> for Point2i
-where
-    V: Into<Vector2i>,
-{
-    type Output = Point2i;
 
+    /// The number of steps between two points when diagonal moves are also
+    /// allowed, the larger of the differences along each axis.
     #[inline]
-    fn add(self, rhs: V) -> Point2i {
-        let rhs = rhs.into();
-        Point2i {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
+    pub fn chebyshev_distance(self, other: impl Into<Point2i>) -> i32 {
+        let d = other.into() - self;
+        d.x.abs().max(d.y.abs())
     }
-}
-
-impl Add<Point2i> for Vector2i {
-    type Output = Point2i;
 
+    /// Iterates over the grid points on the line from this point to `other`
+    /// with Bresenham's algorithm, including both end points.
     #[inline]
-    fn add(self, rhs: Point2i) -> Point2i {
-        rhs + self
+    pub fn line_to(self, other: impl Into<Point2i>) -> BresenhamLine {
+        BresenhamLine::new(self, other)
     }
-}
-
-impl Add<(i32, i32)> for Vector2i {
-    type Output = Point2i;
 
+    /// Iterates over the grid points near the line from this point to
+    /// `other` with Xiaolin Wu's algorithm, along with how much of each the
+    /// line covers, for drawing anti-aliased lines.
     #[inline]
-    fn add(self, rhs: (i32, i32)) -> Point2i {
-        Point2i::from(rhs) + self
+    pub fn wu_line_to(self, other: impl Into<Point2i>) -> WuLine {
+        raster::wu_line(self.to_f32(), other.into().to_f32())
     }
 }
 
-impl Sub for Point2i {
-    type Output = Vector2i;
-
+#[cfg(all(windows, feature = "d2d"))]
+impl From<Point2i> for D2D_POINT_2L {
     #[inline]
-    fn sub(self, rhs: Point2i) -> Vector2i {
-        Vector2i {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
+    fn from(point: Point2i) -> D2D_POINT_2L {
+        D2D_POINT_2L {
+            x: point.x,
+            y: point.y,
         }
     }
 }
 
-impl Sub<(i32, i32)> for Point2i {
-    type Output = Vector2i;
-
+#[cfg(all(windows, feature = "d2d"))]
+impl From<D2D_POINT_2L> for Point2i {
     #[inline]
-    fn sub(self, rhs: (i32, i32)) -> Vector2i {
-        Vector2i {
-            x: self.x - rhs.0,
-            y: self.y - rhs.0,
+    fn from(point: D2D_POINT_2L) -> Point2i {
+        Point2i {
+            x: point.x,
+            y: point.y,
         }
     }
 }
 
-impl<V> Sub<V> for Point2i
-where
-    V: Into<Vector2i>,
-{
-    type Output = Point2i;
-
+#[cfg(all(windows, feature = "windows"))]
+impl From<Point2i> for foundation::POINT {
     #[inline]
-    fn sub(self, rhs: V) -> Point2i {
-        let rhs = rhs.into();
-        Point2i {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
+    fn from(point: Point2i) -> foundation::POINT {
+        foundation::POINT {
+            x: point.x,
+            y: point.y,
         }
     }
 }
 
-impl<V> AddAssign<V> for Point2i
-where
-    Point2i: Add<V, Output = Point2i>,
-{
-    fn add_assign(&mut self, v: V) {
-        *self = *self + v;
-    }
-}
-
-impl<V> SubAssign<V> for Point2i
-where
-    Point2i: Sub<V, Output = Point2i>,
-{
-    fn sub_assign(&mut self, v: V) {
-        *self = *self - v;
-    }
-}
-
-impl From<(i32, i32)> for Point2i {
+#[cfg(all(windows, feature = "windows"))]
+impl From<foundation::POINT> for Point2i {
     #[inline]
-    fn from((x, y): (i32, i32)) -> Point2i {
-        Point2i { x, y }
+    fn from(point: foundation::POINT) -> Point2i {
+        Point2i {
+            x: point.x,
+            y: point.y,
+        }
     }
 }
 
-impl From<[i32; 2]> for Point2i {
+#[cfg(all(windows, feature = "d2d"))]
+impl From<Point2i> for POINTL {
     #[inline]
-    fn from(p: [i32; 2]) -> Point2i {
-        Point2i { x: p[0], y: p[1] }
+    fn from(point: Point2i) -> POINTL {
+        POINTL {
+            x: point.x,
+            y: point.y,
+        }
     }
 }
 
-impl From<Point2i> for (i32, i32) {
+#[cfg(all(windows, feature = "d2d"))]
+impl From<POINTL> for Point2i {
     #[inline]
-    fn from(p: Point2i) -> (i32, i32) {
-        (p.x, p.y)
+    fn from(point: POINTL) -> Point2i {
+        Point2i {
+            x: point.x,
+            y: point.y,
+        }
     }
 }
 
-#[cfg(all(windows, feature = "d2d"))]
-impl From<Point2i> for D2D_POINT_2L {
+#[cfg(all(windows, feature = "windows"))]
+impl From<Point2i> for foundation::POINTL {
     #[inline]
-    fn from(point: Point2i) -> D2D_POINT_2L {
-        D2D_POINT_2L {
+    fn from(point: Point2i) -> foundation::POINTL {
+        foundation::POINTL {
             x: point.x,
             y: point.y,
         }
     }
 }
 
-#[cfg(all(windows, feature = "d2d"))]
-impl From<D2D_POINT_2L> for Point2i {
+#[cfg(all(windows, feature = "windows"))]
+impl From<foundation::POINTL> for Point2i {
     #[inline]
-    fn from(point: D2D_POINT_2L) -> Point2i {
+    fn from(point: foundation::POINTL) -> Point2i {
         Point2i {
             x: point.x,
             y: point.y,
@@ -185,19 +168,22 @@ impl From<D2D_POINT_2L> for Point2i {
     }
 }
 
-#[cfg(feature = "mint")]
-impl From<Point2i> for mint::Point2<i32> {
+#[cfg(feature = "sdl2")]
+impl From<Point2i> for sdl2::rect::Point {
     #[inline]
-    fn from(p: Point2i) -> mint::Point2<i32> {
-        mint::Point2 { x: p.x, y: p.y }
+    fn from(point: Point2i) -> sdl2::rect::Point {
+        sdl2::rect::Point::new(point.x, point.y)
     }
 }
 
-#[cfg(feature = "mint")]
-impl From<mint::Point2<i32>> for Point2i {
+#[cfg(feature = "sdl2")]
+impl From<sdl2::rect::Point> for Point2i {
     #[inline]
-    fn from(p: mint::Point2<i32>) -> Point2i {
-        Point2i { x: p.x, y: p.y }
+    fn from(point: sdl2::rect::Point) -> Point2i {
+        Point2i {
+            x: point.x(),
+            y: point.y(),
+        }
     }
 }
 
@@ -217,3 +203,44 @@ fn pt2i_d2d_bin_compat() {
     assert!(ptr_eq(&pt.y, &d2d.y));
     assert_eq!(size_of_val(&pt), size_of_val(d2d));
 }
+
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn pt2i_windows_bin_compat() {
+    use std::mem::size_of_val;
+
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
+    }
+
+    let pt = Point2i::ORIGIN;
+    let d2d = unsafe { &*((&pt) as *const _ as *const foundation::POINT) };
+
+    assert!(ptr_eq(&pt.x, &d2d.x));
+    assert!(ptr_eq(&pt.y, &d2d.y));
+    assert_eq!(size_of_val(&pt), size_of_val(d2d));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::point2i::Point2i;
+
+    #[test]
+    fn neighbors() {
+        let p = Point2i::new(3, -2);
+        for n in &p.neighbors4() {
+            assert_eq!(p.manhattan_distance(*n), 1);
+        }
+        for n in &p.neighbors8() {
+            assert_eq!(p.chebyshev_distance(*n), 1);
+        }
+        let mut all = p.neighbors8().to_vec();
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), 8);
+        assert!(p.neighbors4().iter().all(|n| all.contains(n)));
+
+        assert_eq!(p.manhattan_distance((-1, 5)), 11);
+        assert_eq!(p.chebyshev_distance((-1, 5)), 7);
+    }
+}
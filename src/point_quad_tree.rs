@@ -0,0 +1,386 @@
+//! A quadtree over a set of points for fast proximity queries.
+
+use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+
+/// The number of points a node holds before it is split into quadrants.
+const NODE_CAPACITY: usize = 8;
+/// Nodes this deep are never split, so that many coincident points cannot
+/// recurse forever.
+const MAX_DEPTH: u32 = 16;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Node {
+    bounds: Rectf,
+    depth: u32,
+    /// Index of the first of four child nodes, ordered top-left, top-right,
+    /// bottom-left, bottom-right.
+    children: Option<usize>,
+    /// Indices of the points held by a leaf node.
+    items: Vec<usize>,
+}
+
+impl Node {
+    #[inline]
+    fn leaf(bounds: Rectf, depth: u32) -> Node {
+        Node {
+            bounds,
+            depth,
+            children: None,
+            items: Vec::new(),
+        }
+    }
+}
+
+/// A point quadtree for answering nearest-neighbour and range queries over
+/// a large set of points, such as snapping a cursor to anchor points.
+///
+/// Points are identified by the index they were given on insertion, which
+/// can be used to look them up again with `point` or to associate them with
+/// data stored elsewhere. The tree grows to fit points inserted outside of
+/// its current bounds.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointQuadTree {
+    points: Vec<Point2f>,
+    nodes: Vec<Node>,
+}
+
+impl PointQuadTree {
+    /// Constructs an empty tree. Its bounds are chosen when the first point
+    /// is inserted.
+    #[inline]
+    pub fn new() -> PointQuadTree {
+        PointQuadTree::default()
+    }
+
+    /// Constructs an empty tree covering `bounds`. Choosing bounds which fit
+    /// all of the points up front avoids rebuilding the tree as it grows.
+    #[inline]
+    pub fn with_bounds(bounds: impl Into<Rectf>) -> PointQuadTree {
+        PointQuadTree {
+            points: Vec::new(),
+            nodes: vec![Node::leaf(bounds.into().normalized(), 0)],
+        }
+    }
+
+    /// The number of points in the tree.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Determines if the tree has no points.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The point with the given index.
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline]
+    pub fn point(&self, index: usize) -> Point2f {
+        self.points[index]
+    }
+
+    /// Every point in the tree, in the order they were inserted.
+    #[inline]
+    pub fn points(&self) -> &[Point2f] {
+        &self.points
+    }
+
+    /// Adds a point to the tree, returning its index. A point with a NaN or
+    /// infinite coordinate keeps its index, but no query will find it.
+    pub fn insert(&mut self, point: impl Into<Point2f>) -> usize {
+        let point = point.into();
+        let index = self.points.len();
+        self.points.push(point);
+        if !is_finite(point) {
+            return index;
+        }
+
+        match self.nodes.first() {
+            None => {
+                let bounds = Rectf::from_center_half_extent(point, [0.5, 0.5]);
+                self.nodes.push(Node::leaf(bounds, 0));
+            }
+            Some(root) if !root.bounds.contains_point(point) => {
+                self.grow_to(point);
+                return index;
+            }
+            _ => (),
+        }
+
+        self.insert_at(0, index);
+        index
+    }
+
+    /// Finds the point closest to `point`, returning its index. Returns
+    /// `None` if the tree is empty.
+    pub fn nearest(&self, point: impl Into<Point2f>) -> Option<usize> {
+        let point = point.into();
+        let mut best = None;
+        let mut best_dist = f32::INFINITY;
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if distance_squared_to_rect(&node.bounds, point) > best_dist {
+                continue;
+            }
+
+            match node.children {
+                Some(first) => {
+                    // Visit the nearest quadrant first so that the others are
+                    // more likely to be pruned.
+                    let mut children = [first, first + 1, first + 2, first + 3];
+                    children.sort_by(|&a, &b| {
+                        let da = distance_squared_to_rect(&self.nodes[a].bounds, point);
+                        let db = distance_squared_to_rect(&self.nodes[b].bounds, point);
+                        db.partial_cmp(&da).unwrap()
+                    });
+                    stack.extend_from_slice(&children);
+                }
+                None => {
+                    for &i in &node.items {
+                        let dist = (self.points[i] - point).len_squared();
+                        if dist < best_dist {
+                            best = Some(i);
+                            best_dist = dist;
+                        }
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Finds the indices of all points no further than `radius` from `point`.
+    /// The results are in no particular order.
+    pub fn within_radius(&self, point: impl Into<Point2f>, radius: f32) -> Vec<usize> {
+        let point = point.into();
+        let radius_sq = radius * radius;
+        let mut results = Vec::new();
+        self.visit(
+            |bounds| distance_squared_to_rect(bounds, point) <= radius_sq,
+            |i, p| {
+                if (p - point).len_squared() <= radius_sq {
+                    results.push(i);
+                }
+            },
+        );
+        results
+    }
+
+    /// Finds the indices of all points inside `rect`, including its edges.
+    /// The results are in no particular order.
+    pub fn in_rect(&self, rect: impl Into<Rectf>) -> Vec<usize> {
+        let rect = rect.into().normalized();
+        let mut results = Vec::new();
+        self.visit(
+            |b| {
+                b.left <= rect.right
+                    && b.right >= rect.left
+                    && b.top <= rect.bottom
+                    && b.bottom >= rect.top
+            },
+            |i, p| {
+                if rect.contains_point(p) {
+                    results.push(i);
+                }
+            },
+        );
+        results
+    }
+
+    /// Visits every point in the nodes accepted by `filter`.
+    fn visit(&self, filter: impl Fn(&Rectf) -> bool, mut f: impl FnMut(usize, Point2f)) {
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if !filter(&node.bounds) {
+                continue;
+            }
+            match node.children {
+                Some(first) => stack.extend(first..first + 4),
+                None => {
+                    for &i in &node.items {
+                        f(i, self.points[i]);
+                    }
+                }
+            }
+        }
+    }
+
+    fn insert_at(&mut self, mut n: usize, index: usize) {
+        let point = self.points[index];
+        while let Some(first) = self.nodes[n].children {
+            n = first + quadrant(&self.nodes[n].bounds, point);
+        }
+
+        let node = &mut self.nodes[n];
+        node.items.push(index);
+        if node.items.len() > NODE_CAPACITY && node.depth < MAX_DEPTH {
+            self.split(n);
+        }
+    }
+
+    fn split(&mut self, n: usize) {
+        let (b, depth) = (self.nodes[n].bounds, self.nodes[n].depth + 1);
+        let c = b.center();
+        let first = self.nodes.len();
+        self.nodes.extend_from_slice(&[
+            Node::leaf(Rectf::new(b.left, b.top, c.x, c.y), depth),
+            Node::leaf(Rectf::new(c.x, b.top, b.right, c.y), depth),
+            Node::leaf(Rectf::new(b.left, c.y, c.x, b.bottom), depth),
+            Node::leaf(Rectf::new(c.x, c.y, b.right, b.bottom), depth),
+        ]);
+
+        let items = std::mem::take(&mut self.nodes[n].items);
+        self.nodes[n].children = Some(first);
+        for index in items {
+            self.insert_at(n, index);
+        }
+    }
+
+    /// Doubles the bounds of the tree towards `point` until it fits, then
+    /// rebuilds the tree with every point.
+    fn grow_to(&mut self, point: Point2f) {
+        let mut b = self.nodes[0].bounds;
+        while !b.contains_point(point) {
+            let size = b.size();
+            let (w, h) = (size.width.max(1.0), size.height.max(1.0));
+            if point.x < b.left {
+                b.left -= w;
+            } else {
+                b.right += w;
+            }
+            if point.y < b.top {
+                b.top -= h;
+            } else {
+                b.bottom += h;
+            }
+        }
+
+        self.nodes.clear();
+        self.nodes.push(Node::leaf(b, 0));
+        for index in 0..self.points.len() {
+            if is_finite(self.points[index]) {
+                self.insert_at(0, index);
+            }
+        }
+    }
+}
+
+/// Whether the point can be placed in the tree. A NaN coordinate is outside
+/// any bounds, and an infinite one would grow the bounds without end.
+#[inline]
+fn is_finite(point: Point2f) -> bool {
+    point.x.is_finite() && point.y.is_finite()
+}
+
+/// The index of the quadrant of `bounds` that `point` falls in.
+#[inline]
+fn quadrant(bounds: &Rectf, point: Point2f) -> usize {
+    let c = bounds.center();
+    (point.x >= c.x) as usize + 2 * (point.y >= c.y) as usize
+}
+
+/// The squared distance from `point` to the nearest point of `rect`, or zero
+/// if it is inside.
+#[inline]
+fn distance_squared_to_rect(rect: &Rectf, point: Point2f) -> f32 {
+    let dx = (rect.left - point.x).max(point.x - rect.right).max(0.0);
+    let dy = (rect.top - point.y).max(point.y - rect.bottom).max(0.0);
+    dx * dx + dy * dy
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::point2f::Point2f;
+    use crate::point_quad_tree::PointQuadTree;
+    use crate::rectf::Rectf;
+
+    #[test]
+    fn queries() {
+        let mut tree = PointQuadTree::new();
+        let mut points = Vec::new();
+        for i in 0..40 {
+            for j in 0..25 {
+                let p = Point2f::new(i as f32 * 2.5, j as f32 * 4.0 - 30.0);
+                assert_eq!(tree.insert(p), points.len());
+                points.push(p);
+            }
+        }
+        assert_eq!(tree.len(), 1000);
+
+        // Compare against brute force
+        let probes = [(-20.0, 5.0), (33.3, -2.2), (97.6, 66.0), (51.0, 12.0)];
+        for &probe in &probes {
+            let probe = Point2f::from(probe);
+            let dist = |p: Point2f| (p - probe).len_squared();
+            let nearest = tree.nearest(probe).unwrap();
+            let best = points.iter().cloned().map(dist).fold(f32::MAX, f32::min);
+            assert_eq!(dist(tree.point(nearest)), best);
+
+            let mut within = tree.within_radius(probe, 6.0);
+            within.sort_unstable();
+            let expected: Vec<_> = (0..points.len())
+                .filter(|&i| dist(points[i]) <= 36.0)
+                .collect();
+            assert_eq!(within, expected);
+        }
+
+        let mut inside = tree.in_rect(Rectf::new(10.0, 10.0, 15.0, 18.0));
+        inside.sort_unstable();
+        let expected: Vec<_> = (0..points.len())
+            .filter(|&i| {
+                let p = points[i];
+                p.x >= 10.0 && p.x <= 15.0 && p.y >= 10.0 && p.y <= 18.0
+            })
+            .collect();
+        assert_eq!(inside.len(), 9);
+        assert_eq!(inside, expected);
+
+        assert_eq!(PointQuadTree::new().nearest((0.0, 0.0)), None);
+    }
+
+    #[test]
+    fn coincident_points() {
+        let mut tree = PointQuadTree::with_bounds(Rectf::new(0.0, 0.0, 10.0, 10.0));
+        for _ in 0..100 {
+            tree.insert((5.0, 5.0));
+        }
+        assert_eq!(tree.within_radius((5.0, 5.0), 0.0).len(), 100);
+        assert_eq!(
+            tree.nearest((6.0, 6.0)).map(|i| tree.point(i)),
+            Some((5.0, 5.0).into())
+        );
+    }
+
+    #[test]
+    fn non_finite_points() {
+        let mut tree = PointQuadTree::new();
+        assert_eq!(tree.insert((f32::NAN, 0.0)), 0);
+        assert_eq!(tree.nearest((0.0, 0.0)), None);
+        tree.insert((0.0, 0.0));
+        assert_eq!(tree.insert((f32::NAN, 0.0)), 2);
+        assert_eq!(tree.insert((0.0, f32::INFINITY)), 3);
+        tree.insert((100.0, 100.0));
+
+        assert_eq!(tree.len(), 5);
+        assert!(tree.point(2).x.is_nan());
+        assert_eq!(tree.nearest((1.0, 1.0)), Some(1));
+        assert_eq!(tree.in_rect(Rectf::INFINITE).len(), 2);
+    }
+}
@@ -0,0 +1,820 @@
+//! A closed shape bounded by straight edges between a list of points.
+
+use crate::arc_segment::ArcSize;
+use crate::area_moments::{self, AreaMoments};
+use crate::cached_bounds::CachedBounds;
+use crate::corner;
+use crate::fill_rule::FillRule;
+use crate::interval::Interval;
+use crate::matrix3x2f::Matrix3x2f;
+use crate::path::Path;
+use crate::point2f::Point2f;
+use crate::predicates::orient2d;
+use crate::raster::{self, PolygonSpans};
+use crate::rectf::Rectf;
+use crate::vector2f::Vector2f;
+use crate::winding::Winding;
+
+#[cfg(feature = "rand")]
+use crate::triangulation;
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+/// A closed shape bounded by straight edges between a list of points. The
+/// last point is implicitly connected back to the first.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Polygon {
+    /// The vertices of the polygon, in order.
+    pub points: Vec<Point2f>,
+}
+
+impl Polygon {
+    /// Constructs a polygon from its vertices.
+    #[inline]
+    pub fn new(points: impl Into<Vec<Point2f>>) -> Polygon {
+        Polygon {
+            points: points.into(),
+        }
+    }
+
+    /// The signed area enclosed by the polygon. The area is positive when the
+    /// vertices wind clockwise on screen and negative when they wind
+    /// counterclockwise. Overlapping parts of self-intersecting polygons
+    /// contribute once for each time they are wound around.
+    #[inline]
+    pub fn area(&self) -> f32 {
+        self.edges()
+            .map(|(a, b)| a.x * b.y - b.x * a.y)
+            .sum::<f32>()
+            / 2.0
+    }
+
+    /// The total length of the polygon's edges, including the edge from the
+    /// last vertex back to the first.
+    #[inline]
+    pub fn perimeter(&self) -> f32 {
+        self.edges().map(|(a, b)| (b - a).len()).sum()
+    }
+
+    /// The center of mass of the area enclosed by the polygon. Polygons with
+    /// no area fall back to the average of their vertices, and a polygon with
+    /// no vertices has its centroid at the origin.
+    pub fn centroid(&self) -> Point2f {
+        if self.points.is_empty() {
+            return Point2f::ORIGIN;
+        }
+
+        // Measure relative to the first vertex to limit rounding error
+        let origin = self.points[0];
+        let (mut area, mut cx, mut cy) = (0.0, 0.0, 0.0);
+        for (a, b) in self.edges() {
+            let (a, b) = (a - origin, b - origin);
+            let cross = a.cross(b);
+            area += cross;
+            cx += (a.x + b.x) * cross;
+            cy += (a.y + b.y) * cross;
+        }
+
+        if area != 0.0 {
+            origin + [cx / (3.0 * area), cy / (3.0 * area)]
+        } else {
+            let n = self.points.len() as f32;
+            let (sx, sy) = self.points.iter().fold((0.0, 0.0), |(sx, sy), &p| {
+                (sx + p.x - origin.x, sy + p.y - origin.y)
+            });
+            origin + [sx / n, sy / n]
+        }
+    }
+
+    /// The second moments of the polygon's area about the x and y axes,
+    /// assuming the polygon is simple.
+    #[inline]
+    pub fn second_moment_of_area(&self) -> AreaMoments {
+        area_moments::polygon_moments(&self.points, Point2f::ORIGIN)
+    }
+
+    /// The second moments of the polygon's area about axes through its
+    /// centroid, assuming the polygon is simple. These are the smallest
+    /// moments about any parallel axes.
+    #[inline]
+    pub fn centroidal_moments(&self) -> AreaMoments {
+        area_moments::polygon_moments(&self.points, self.centroid())
+    }
+
+    /// The direction the polygon winds around its area, or `None` if it
+    /// encloses no area.
+    #[inline]
+    pub fn winding(&self) -> Option<Winding> {
+        Winding::from_signed_area(self.area())
+    }
+
+    /// Determines if the polygon is convex, i.e. every edge turns in the same
+    /// direction and the outline travels around exactly once. Collinear
+    /// vertices are allowed, but polygons with fewer than 3 vertices or no
+    /// area are not considered convex.
+    pub fn is_convex(&self) -> bool {
+        let n = self.points.len();
+        if n < 3 {
+            return false;
+        }
+
+        let mut sign = 0.0;
+        let mut turning = 0.0;
+        for i in 0..n {
+            let a = self.points[i];
+            let b = self.points[(i + 1) % n];
+            let c = self.points[(i + 2) % n];
+            let (d1, d2) = (b - a, c - b);
+            let cross = d1.cross(d2);
+            if cross != 0.0 {
+                if cross * sign < 0.0 {
+                    return false;
+                }
+                sign = cross;
+            }
+            turning += cross.atan2(d1.dot(d2));
+        }
+
+        // A star turns the same way at every vertex but circles more than once
+        sign != 0.0 && (turning.abs() - 2.0 * std::f32::consts::PI).abs() < 1e-3
+    }
+
+    /// Determines if the polygon is simple, meaning that no two edges cross
+    /// or touch other than adjacent edges meeting at their shared vertex.
+    /// Polygons with fewer than 3 vertices are not considered simple. This
+    /// compares every pair of edges, so it takes quadratic time.
+    pub fn is_simple(&self) -> bool {
+        let n = self.points.len();
+        if n < 3 {
+            return false;
+        }
+
+        let edge = |i: usize| (self.points[i], self.points[(i + 1) % n]);
+        for i in 0..n {
+            let (a, b) = edge(i);
+            if a == b {
+                return false;
+            }
+
+            // Adjacent edges may only overlap by doubling back on each other
+            let (_, c) = edge((i + 1) % n);
+            if (b - a).cross(c - b) == 0.0 && (b - a).dot(c - b) < 0.0 {
+                return false;
+            }
+
+            for j in i + 2..n {
+                if i == 0 && j == n - 1 {
+                    continue;
+                }
+                let (c, d) = edge(j);
+                if segments_intersect(a, b, c, d) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Determines if the polygon encloses no area, because it has fewer than
+    /// three vertices which are not in line, once sanitized.
+    #[inline]
+    pub fn is_degenerate(&self) -> bool {
+        self.sanitized().points.is_empty()
+    }
+
+    /// Removes repeated vertices and vertices which lie in line with their
+    /// neighbours, including the tips of zero-width spikes, repeating until
+    /// none remain. The result encloses the same area, and is empty if the
+    /// polygon is degenerate. Collinearity is decided exactly with
+    /// `predicates::orient2d`.
+    pub fn sanitized(&self) -> Polygon {
+        let redundant = |a: Point2f, b: Point2f, c: Point2f| orient2d(a, b, c) == 0.0;
+
+        let mut points: Vec<Point2f> = Vec::with_capacity(self.points.len());
+        for &p in &self.points {
+            while let [.., a, b] = points[..] {
+                if !redundant(a, b, p) {
+                    break;
+                }
+                points.pop();
+            }
+            if points.last() != Some(&p) {
+                points.push(p);
+            }
+        }
+
+        // The join between the last and first vertices is checked once the
+        // rest of the outline has been cleaned up
+        while points.len() >= 3 {
+            let n = points.len();
+            if redundant(points[n - 2], points[n - 1], points[0]) {
+                points.pop();
+            } else if redundant(points[n - 1], points[0], points[1]) {
+                points.remove(0);
+            } else {
+                break;
+            }
+        }
+
+        if points.len() < 3 {
+            points.clear();
+        }
+        Polygon::new(points)
+    }
+
+    /// The range of values of `point.dot(axis)` over the polygon, as used by
+    /// separating axis tests: two convex polygons are disjoint exactly when
+    /// their projections onto the normal of some edge do not overlap. The
+    /// axis does not need to be normalized, but both polygons must be
+    /// projected onto the same one.
+    #[inline]
+    pub fn projected_onto(&self, axis: impl Into<Vector2f>) -> Interval {
+        let axis = axis.into();
+        Interval::covering(self.points.iter().map(|p| p.to_vector().dot(axis)))
+    }
+
+    /// The smallest rectangle containing every vertex of the polygon. A
+    /// polygon with no vertices has a zero-sized box at the origin.
+    #[inline]
+    pub fn bounds(&self) -> Rectf {
+        point_bounds(&self.points)
+    }
+
+    /// Wraps the polygon so that its bounds are only computed once, for
+    /// polygons which are culled or hit tested repeatedly without changing.
+    #[inline]
+    pub fn into_cached(self) -> CachedBounds<Polygon> {
+        CachedBounds::new(self)
+    }
+
+    /// Parallel version of `bounds` for polygons with a very large number
+    /// of vertices.
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn par_bounds(&self) -> Rectf {
+        par_point_bounds(&self.points)
+    }
+
+    /// The number of times the outline of the polygon winds around `point`.
+    /// Clockwise windings count positively.
+    #[inline]
+    pub fn winding_number(&self, point: impl Into<Point2f>) -> i32 {
+        winding_number(&self.points, point.into())
+    }
+
+    /// Determines if the polygon contains `point` under the given fill rule.
+    #[inline]
+    pub fn contains_point(&self, point: impl Into<Point2f>, fill_rule: FillRule) -> bool {
+        fill_rule.is_inside(self.winding_number(point))
+    }
+
+    /// The rows of pixels covered by the polygon under the given fill rule,
+    /// for filling it on an integer grid. See `raster::PolygonSpans`.
+    #[inline]
+    pub fn spans(&self, fill_rule: FillRule) -> PolygonSpans {
+        raster::polygon_spans(&self.points, fill_rule)
+    }
+
+    /// Picks a point inside the polygon at random, with every part of its area
+    /// equally likely. The polygon must be simple. Returns `None` if it
+    /// encloses no area.
+    ///
+    /// Each call triangulates the polygon, so use `samples` when many points
+    /// are needed from the same polygon.
+    #[cfg(feature = "rand")]
+    #[inline]
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Point2f> {
+        self.samples(rng, 1).pop()
+    }
+
+    /// Picks `count` points inside the polygon at random, with every part of
+    /// its area equally likely. The polygon must be simple. Returns no points
+    /// if it encloses no area.
+    #[cfg(feature = "rand")]
+    pub fn samples<R: Rng + ?Sized>(&self, rng: &mut R, count: usize) -> Vec<Point2f> {
+        let triangles = triangulation::ear_clip(&self.points);
+        let mut total = 0.0;
+        let cumulative: Vec<f32> = triangles
+            .iter()
+            .map(|t| {
+                total += t.area();
+                total
+            })
+            .collect();
+        if total <= 0.0 {
+            return Vec::new();
+        }
+
+        // Choose triangles in proportion to their share of the area
+        (0..count)
+            .map(|_| {
+                let target = rng.gen::<f32>() * total;
+                let i = cumulative.partition_point(|&a| a <= target);
+                triangles[i.min(triangles.len() - 1)].sample(rng)
+            })
+            .collect()
+    }
+
+    /// Clips the polygon to the inside of `rect` with the Sutherland-Hodgman
+    /// algorithm. See `clipped_to_convex` for how the result is shaped.
+    #[inline]
+    pub fn clipped_to_rect(&self, rect: impl Into<Rectf>) -> Polygon {
+        let r = rect.into().normalized();
+        let clip = Polygon {
+            points: vec![
+                (r.left, r.top).into(),
+                (r.right, r.top).into(),
+                (r.right, r.bottom).into(),
+                (r.left, r.bottom).into(),
+            ],
+        };
+        self.clipped_to_convex(&clip)
+    }
+
+    /// Clips the polygon to the inside of the convex polygon `convex` with the
+    /// Sutherland-Hodgman algorithm, which may wind either way. The result
+    /// keeps the winding of this polygon and is empty if nothing remains.
+    ///
+    /// This polygon may be concave, but when clipping splits it into several
+    /// pieces they stay joined by zero-width edges along the clip boundary.
+    /// A clip polygon which is not convex gives meaningless results, and one
+    /// with no area clips everything away.
+    pub fn clipped_to_convex(&self, convex: &Polygon) -> Polygon {
+        let area = convex.area();
+        if area == 0.0 {
+            return Polygon::default();
+        }
+        let sign = area.signum();
+
+        let mut output = self.points.clone();
+        for (a, b) in convex.edges() {
+            if output.is_empty() {
+                break;
+            }
+
+            let edge = b - a;
+            let side = |p: Point2f| edge.cross(p - a) * sign;
+            let input = std::mem::take(&mut output);
+            let mut prev = *input.last().unwrap();
+            for &p in &input {
+                let (sp, sq) = (side(prev), side(p));
+                if sq >= 0.0 {
+                    if sp < 0.0 {
+                        output.push(prev + (p - prev) * (sp / (sp - sq)));
+                    }
+                    output.push(p);
+                } else if sp > 0.0 {
+                    output.push(prev + (p - prev) * (sp / (sp - sq)));
+                }
+                prev = p;
+            }
+        }
+
+        Polygon { points: output }
+    }
+
+    /// Rounds off every corner of the polygon with a circular arc tangent to
+    /// both of its edges. Where the edges are too short to fit `radius`, the
+    /// arc shrinks so that it uses at most half of each edge. The result is a
+    /// single closed figure, or an empty path if there are no vertices.
+    pub fn fillet_corners(&self, radius: f32) -> Path {
+        let n = self.points.len();
+        let mut builder = Path::builder();
+        if n == 0 {
+            return builder.build();
+        }
+
+        let fillets: Vec<_> = (0..n)
+            .map(|i| {
+                let prev = self.points[(i + n - 1) % n];
+                let next = self.points[(i + 1) % n];
+                corner::fillet(prev, self.points[i], next, radius)
+            })
+            .collect();
+
+        // Begin where the first corner leaves off, and finish by rounding it
+        builder.move_to(fillets[0].map_or(self.points[0], |f| f.end));
+        for i in (1..n).chain(Some(0)) {
+            match fillets[i] {
+                Some(f) => {
+                    let size = (f.radius, f.radius);
+                    builder
+                        .line_to(f.start)
+                        .arc_to(f.end, size, 0.0, f.sweep, ArcSize::Small);
+                }
+                None if i != 0 => {
+                    builder.line_to(self.points[i]);
+                }
+                None => (),
+            }
+        }
+        builder.close().build()
+    }
+
+    /// Iterates over the edges of the polygon as pairs of points, including
+    /// the edge closing the loop.
+    #[inline]
+    fn edges(&self) -> impl Iterator<Item = (Point2f, Point2f)> + '_ {
+        let next = self.points.iter().cycle().skip(1);
+        self.points.iter().cloned().zip(next.cloned())
+    }
+
+    /// Applies an affine transformation to every vertex.
+    #[inline]
+    pub fn transformed(&self, matrix: &Matrix3x2f) -> Polygon {
+        Polygon {
+            points: self.points.iter().map(|&p| p * *matrix).collect(),
+        }
+    }
+
+    /// Formats the `points` attribute of an SVG `<polygon>` element with
+    /// these vertices.
+    pub fn to_svg_attributes(&self) -> String {
+        format!(r#"points="{}""#, svg_points(&self.points))
+    }
+}
+
+impl From<Vec<Point2f>> for Polygon {
+    #[inline]
+    fn from(points: Vec<Point2f>) -> Polygon {
+        Polygon { points }
+    }
+}
+
+/// Formats points as an SVG `points` list, e.g. `0,0 4,0 4,3`.
+pub(crate) fn svg_points(points: &[Point2f]) -> String {
+    let coords: Vec<String> = points.iter().map(|p| format!("{},{}", p.x, p.y)).collect();
+    coords.join(" ")
+}
+
+/// The smallest rectangle containing all of the points, or a zero-sized box
+/// at the origin if there are none.
+pub(crate) fn point_bounds(points: &[Point2f]) -> Rectf {
+    let first = match points.first() {
+        Some(&p) => p,
+        None => return Rectf::default(),
+    };
+    points
+        .iter()
+        .fold(Rectf::from_points(first, first), |r, p| Rectf {
+            left: r.left.min(p.x),
+            top: r.top.min(p.y),
+            right: r.right.max(p.x),
+            bottom: r.bottom.max(p.y),
+        })
+}
+
+/// Parallel version of `point_bounds`, which bounds separate chunks of the
+/// slice on the rayon thread pool and combines the results.
+#[cfg(feature = "rayon")]
+pub(crate) fn par_point_bounds(points: &[Point2f]) -> Rectf {
+    use rayon::prelude::*;
+
+    points
+        .par_chunks(PAR_CHUNK_LEN)
+        .map(point_bounds)
+        .reduce_with(|a, b| a.combined_with(b))
+        .unwrap_or_default()
+}
+
+/// How many points each rayon task works through at once. Smaller chunks
+/// spend more time on scheduling than on the points themselves.
+#[cfg(feature = "rayon")]
+pub(crate) const PAR_CHUNK_LEN: usize = 4096;
+
+/// Determines if the line segments `ab` and `cd` touch at any point.
+fn segments_intersect(a: Point2f, b: Point2f, c: Point2f, d: Point2f) -> bool {
+    let d1 = orient2d(c, d, a);
+    let d2 = orient2d(c, d, b);
+    let d3 = orient2d(a, b, c);
+    let d4 = orient2d(a, b, d);
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+    {
+        return true;
+    }
+
+    // Collinear points lying within the bounds of the other segment
+    let within = |p: Point2f, q: Point2f, r: Point2f| {
+        r.x >= p.x.min(q.x) && r.x <= p.x.max(q.x) && r.y >= p.y.min(q.y) && r.y <= p.y.max(q.y)
+    };
+    (d1 == 0.0 && within(c, d, a))
+        || (d2 == 0.0 && within(c, d, b))
+        || (d3 == 0.0 && within(a, b, c))
+        || (d4 == 0.0 && within(a, b, d))
+}
+
+/// Winding number of the closed loop through `points` around `point`, with
+/// clockwise windings counted positively.
+pub(crate) fn winding_number(points: &[Point2f], point: Point2f) -> i32 {
+    let n = points.len();
+    let mut winding = 0;
+    for i in 0..n {
+        let (a, b) = (points[i], points[(i + 1) % n]);
+        let side = orient2d(a, b, point);
+        if a.y <= point.y {
+            if b.y > point.y && side > 0.0 {
+                winding += 1;
+            }
+        } else if b.y <= point.y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fill_rule::FillRule;
+    use crate::matrix3x2f::Matrix3x2f;
+    use crate::path::Path;
+    use crate::polygon::Polygon;
+    use crate::rectf::Rectf;
+    use crate::rounded_rect::RoundedRect;
+    use crate::winding::Winding;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn contains_point() {
+        // A pentagram overlaps itself around its center
+        let star = Polygon::new(vec![
+            (0.0, -10.0).into(),
+            (5.9, 8.1).into(),
+            (-9.5, -3.1).into(),
+            (9.5, -3.1).into(),
+            (-5.9, 8.1).into(),
+        ]);
+        assert_eq!(star.winding_number((0.0, 0.0)), 2);
+        assert!(star.contains_point((0.0, 0.0), FillRule::NonZero));
+        assert!(!star.contains_point((0.0, 0.0), FillRule::EvenOdd));
+        assert!(star.contains_point((0.0, -7.0), FillRule::EvenOdd));
+        assert!(!star.contains_point((20.0, 0.0), FillRule::NonZero));
+
+        assert_eq!(star.bounds(), Rectf::new(-9.5, -10.0, 9.5, 8.1));
+    }
+
+    #[test]
+    fn measurements() {
+        let square = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (4.0, 0.0).into(),
+            (4.0, 4.0).into(),
+            (0.0, 4.0).into(),
+        ]);
+        assert_eq!(square.area(), 16.0);
+        assert_eq!(square.perimeter(), 16.0);
+        assert_eq!(square.centroid(), (2.0, 2.0).into());
+        assert_eq!(square.winding(), Some(Winding::Clockwise));
+        assert!(square.is_convex() && square.is_simple());
+
+        let mut reversed = square.clone();
+        reversed.points.reverse();
+        assert_eq!(reversed.area(), -16.0);
+        assert_eq!(reversed.winding(), Some(Winding::CounterClockwise));
+        assert!(reversed.is_convex());
+
+        // An L shape is simple but not convex, and its centroid is off-center
+        let ell = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (2.0, 0.0).into(),
+            (2.0, 1.0).into(),
+            (1.0, 1.0).into(),
+            (1.0, 2.0).into(),
+            (0.0, 2.0).into(),
+        ]);
+        assert_eq!(ell.area(), 3.0);
+        assert!(ell.centroid().is_approx_eq((5.0 / 6.0, 5.0 / 6.0), 1e-6));
+        assert!(ell.is_simple() && !ell.is_convex());
+
+        // Self-intersecting shapes are neither simple nor convex
+        let bowtie = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (2.0, 2.0).into(),
+            (2.0, 0.0).into(),
+            (0.0, 2.0).into(),
+        ]);
+        assert_eq!(bowtie.area(), 0.0);
+        assert_eq!(bowtie.winding(), None);
+        assert!(!bowtie.is_simple() && !bowtie.is_convex());
+        let star = Polygon::new(vec![
+            (0.0, -10.0).into(),
+            (5.9, 8.1).into(),
+            (-9.5, -3.1).into(),
+            (9.5, -3.1).into(),
+            (-5.9, 8.1).into(),
+        ]);
+        assert!(!star.is_simple() && !star.is_convex());
+    }
+
+    #[test]
+    fn moments() {
+        // A 4x2 rectangle with its corner at the origin
+        let mut rect = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (4.0, 0.0).into(),
+            (4.0, 2.0).into(),
+            (0.0, 2.0).into(),
+        ]);
+        let m = rect.second_moment_of_area();
+        assert!((m.ixx - 4.0 * 8.0 / 3.0).abs() < 1e-5);
+        assert!((m.iyy - 2.0 * 64.0 / 3.0).abs() < 1e-4);
+        assert!((m.ixy - 16.0).abs() < 1e-5);
+
+        // About the centroid, bh^3/12 and hb^3/12 with no product of inertia
+        let c = rect.centroidal_moments();
+        assert!((c.ixx - 4.0 * 8.0 / 12.0).abs() < 1e-5);
+        assert!((c.iyy - 2.0 * 64.0 / 12.0).abs() < 1e-5);
+        assert!(c.ixy.abs() < 1e-5);
+        assert_eq!(c.principal_angle(), 0.0);
+
+        // Winding doesn't matter
+        rect.points.reverse();
+        assert_eq!(rect.centroidal_moments(), c);
+
+        // A 45 degree rotation leaves the principal axes unchanged
+        let rotated = rect.transformed(&Matrix3x2f::rotation(PI / 4.0, (2.0, 1.0)));
+        let r = rotated.centroidal_moments();
+        let (major, minor) = r.principal();
+        assert!((major - c.iyy).abs() < 1e-4 && (minor - c.ixx).abs() < 1e-4);
+        assert!((r.principal_angle() - PI / 4.0).abs() < 1e-4);
+        assert!((r.polar() - c.polar()).abs() < 1e-4);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn sampling() {
+        use crate::circle::Circle;
+        use crate::point2f::Point2f;
+        use crate::triangle::Triangle;
+        use rand::{SeedableRng, XorShiftRng};
+
+        let mut rng = XorShiftRng::from_seed([7; 16]);
+        let rect = Rectf::new(0.0, 0.0, 10.0, 5.0);
+        let circle = Circle::new((3.0, 3.0), 2.0);
+        let tri = Triangle::from(((0.0, 0.0), (4.0, 0.0), (0.0, 4.0)));
+        for _ in 0..100 {
+            assert!(rect.contains_point(rect.sample(&mut rng)));
+            assert!(circle.contains_point(circle.sample(&mut rng)));
+            let p = tri.sample(&mut rng);
+            assert!(p.x >= 0.0 && p.y >= 0.0 && p.x + p.y <= 4.0 + 1e-5);
+        }
+
+        // An L shape whose larger arm should get two thirds of the samples
+        let ell = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (4.0, 0.0).into(),
+            (4.0, 2.0).into(),
+            (2.0, 2.0).into(),
+            (2.0, 4.0).into(),
+            (0.0, 4.0).into(),
+        ]);
+        let samples = ell.samples(&mut rng, 3000);
+        assert_eq!(samples.len(), 3000);
+        let inside = |p: &Point2f| ell.bounds().contains_point(*p) && (p.x <= 2.0 || p.y <= 2.0);
+        assert!(samples.iter().all(inside));
+        let top = samples.iter().filter(|p| p.y < 2.0).count() as f32 / 3000.0;
+        assert!((top - 2.0 / 3.0).abs() < 0.05);
+        assert_eq!(Polygon::default().sample(&mut rng), None);
+    }
+
+    #[test]
+    fn clipping() {
+        let diamond = Polygon::new(vec![
+            (0.0, -2.0).into(),
+            (2.0, 0.0).into(),
+            (0.0, 2.0).into(),
+            (-2.0, 0.0).into(),
+        ]);
+
+        // Clipping a diamond to a square leaves an octagon
+        let clipped = diamond.clipped_to_rect(Rectf::new(-1.5, -1.5, 1.5, 1.5));
+        assert_eq!(clipped.points.len(), 8);
+        assert!((clipped.area() - 7.0).abs() < 1e-5);
+        assert_eq!(clipped.winding(), diamond.winding());
+        assert!(clipped.is_convex());
+
+        // The clip polygon may wind either way
+        let mut triangle = Polygon::new(vec![
+            (0.0, -2.0).into(),
+            (2.0, 2.0).into(),
+            (-2.0, 2.0).into(),
+        ]);
+        let a = diamond.clipped_to_convex(&triangle);
+        triangle.points.reverse();
+        let b = diamond.clipped_to_convex(&triangle);
+        assert!((a.area() - b.area()).abs() < 1e-5);
+        assert!(a.area() > 0.0 && a.area() < diamond.area());
+
+        // Entirely inside or outside
+        let big = Rectf::new(-10.0, -10.0, 10.0, 10.0);
+        assert_eq!(diamond.clipped_to_rect(big), diamond);
+        let away = Rectf::new(5.0, 5.0, 6.0, 6.0);
+        assert!(diamond.clipped_to_rect(away).points.is_empty());
+    }
+
+    #[test]
+    fn fillet_corners() {
+        let square = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (10.0, 0.0).into(),
+            (10.0, 10.0).into(),
+            (0.0, 10.0).into(),
+        ]);
+        let rounded = square.fillet_corners(2.0);
+        assert_eq!(rounded.figures.len(), 1);
+        assert_eq!(rounded.figures[0].segments.len(), 8);
+        assert!(rounded.figures[0].closed);
+        assert!(rounded.figures[0].start.is_approx_eq((2.0, 0.0), 1e-6));
+
+        // Matches the equivalent rounded rectangle
+        let expected = Path::from(RoundedRect::new(Rectf::new(0.0, 0.0, 10.0, 10.0), 2.0, 2.0));
+        let area = |p: &Path| p.figures[0].signed_area();
+        assert!((area(&rounded) - area(&expected)).abs() < 1e-3);
+        assert!(rounded.contains_point((5.0, 5.0), FillRule::NonZero, 0.01));
+        assert!(!rounded.contains_point((0.2, 0.2), FillRule::NonZero, 0.01));
+
+        // Oversized radii are limited by the edge lengths
+        let circle = square.fillet_corners(100.0);
+        let b = circle.bounds();
+        assert!((b.right - 10.0).abs() < 1e-4 && (b.bottom - 10.0).abs() < 1e-4);
+        assert!((area(&circle) - 25.0 * std::f32::consts::PI).abs() < 1e-2);
+    }
+
+    #[test]
+    fn sanitize() {
+        // A square with a repeated corner, a vertex midway along an edge and
+        // a zero-width spike
+        let messy = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (5.0, 0.0).into(),
+            (10.0, 0.0).into(),
+            (10.0, 0.0).into(),
+            (10.0, 10.0).into(),
+            (15.0, 10.0).into(),
+            (10.0, 10.0).into(),
+            (0.0, 10.0).into(),
+            (0.0, 0.0).into(),
+        ]);
+        let clean = messy.sanitized();
+        assert_eq!(
+            clean.points,
+            [
+                (0.0, 0.0).into(),
+                (10.0, 0.0).into(),
+                (10.0, 10.0).into(),
+                (0.0, 10.0).into()
+            ]
+        );
+        assert!(!messy.is_degenerate());
+        assert_eq!(clean.area(), messy.area());
+
+        let line = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (1.0, 1.0).into(),
+            (3.0, 3.0).into(),
+        ]);
+        assert!(line.is_degenerate());
+        assert!(line.sanitized().points.is_empty());
+
+        // Tracing a triangle's outline there and back again has no area
+        let folded = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (4.0, 0.0).into(),
+            (0.0, 4.0).into(),
+            (4.0, 0.0).into(),
+        ]);
+        assert!(folded.is_degenerate());
+    }
+
+    #[test]
+    fn svg_attributes() {
+        let triangle = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (4.0, 0.0).into(),
+            (4.0, 2.5).into(),
+        ]);
+        assert_eq!(triangle.to_svg_attributes(), r#"points="0,0 4,0 4,2.5""#);
+
+        let rect = Rectf::new(10.0, 5.0, 0.0, 0.0);
+        assert_eq!(
+            rect.to_svg_attributes(),
+            r#"x="0" y="0" width="10" height="5""#
+        );
+        let rounded = RoundedRect::new(rect, 2.0, 1.0);
+        assert_eq!(
+            rounded.to_svg_attributes(),
+            r#"x="0" y="0" width="10" height="5" rx="2" ry="1""#
+        );
+        assert_eq!(
+            Matrix3x2f::translation([10.0, -20.0]).to_svg_attributes(),
+            r#"transform="matrix(1 0 0 1 10 -20)""#
+        );
+    }
+}
@@ -0,0 +1,598 @@
+//! An open chain of points connected by straight lines.
+
+use crate::corner;
+use crate::matrix3x2f::Matrix3x2f;
+use crate::point2f::Point2f;
+use crate::polygon::{self, Polygon};
+use crate::rectf::Rectf;
+use crate::tolerance::Tolerance;
+
+/// An open chain of points connected by straight lines. Unlike a polygon,
+/// the last point is not implicitly connected back to the first.
+///
+/// Polylines convert into open single-figure paths with `Path::from`, and
+/// any path can be approximated by polylines with `Path::flatten`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Polyline {
+    /// The points along the line, in order.
+    pub points: Vec<Point2f>,
+}
+
+impl Polyline {
+    /// Constructs a polyline through the given points.
+    #[inline]
+    pub fn new(points: impl Into<Vec<Point2f>>) -> Polyline {
+        Polyline {
+            points: points.into(),
+        }
+    }
+
+    /// Determines if the polyline has no points.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The total length of the line segments.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.points.windows(2).map(|w| (w[1] - w[0]).len()).sum()
+    }
+
+    /// The point at the given distance along the line. Distances outside the
+    /// line are clamped to its ends. Returns `None` if there are no points.
+    pub fn point_at_distance(&self, distance: f32) -> Option<Point2f> {
+        let first = *self.points.first()?;
+        if distance <= 0.0 {
+            return Some(first);
+        }
+
+        let mut remaining = distance;
+        for w in self.points.windows(2) {
+            let len = (w[1] - w[0]).len();
+            if remaining < len {
+                return Some(w[0] + (w[1] - w[0]) * (remaining / len));
+            }
+            remaining -= len;
+        }
+        self.points.last().cloned()
+    }
+
+    /// The smallest rectangle containing every point of the line. A polyline
+    /// with no points has a zero-sized box at the origin.
+    #[inline]
+    pub fn bounds(&self) -> Rectf {
+        polygon::point_bounds(&self.points)
+    }
+
+    /// Parallel version of `bounds` for lines with a very large number of
+    /// points.
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn par_bounds(&self) -> Rectf {
+        polygon::par_point_bounds(&self.points)
+    }
+
+    /// Applies an affine transformation to every point.
+    #[inline]
+    pub fn transformed(&self, matrix: &Matrix3x2f) -> Polyline {
+        Polyline {
+            points: self.points.iter().map(|&p| p * *matrix).collect(),
+        }
+    }
+
+    /// Places points along the line at every multiple of `spacing` from the
+    /// start, keeping the original end point. The last interval is shorter
+    /// than `spacing` unless the length divides evenly; use
+    /// `resample_by_distance` to make every interval the same length
+    /// instead. A non-positive spacing returns the line unchanged.
+    pub fn resampled(&self, spacing: f32) -> Polyline {
+        let last = match self.points.last() {
+            Some(&p) if spacing > 0.0 => p,
+            _ => return self.clone(),
+        };
+
+        // Each sample is placed at a multiple of the spacing rather than by
+        // repeatedly adding it, which would stop advancing once the spacing
+        // fell below the precision of the running total
+        let spacing = f64::from(spacing);
+        let mut points = vec![self.points[0]];
+        let mut k = 1u64;
+        // Distance along the line to the start of the current segment
+        let mut walked = 0.0;
+        for w in self.points.windows(2) {
+            let len = f64::from((w[1] - w[0]).len());
+            loop {
+                let along = k as f64 * spacing - walked;
+                if along >= len {
+                    break;
+                }
+                points.push(w[0] + (w[1] - w[0]) * (along / len) as f32);
+                k += 1;
+            }
+            walked += len;
+        }
+
+        // Avoid a tiny final interval from rounding
+        if points.len() > 1 && k as f64 * spacing - walked > spacing * 0.999 {
+            points.pop();
+        }
+        points.push(last);
+        Polyline { points }
+    }
+
+    /// Places points evenly along the line, keeping both end points. The
+    /// spacing is adjusted to the nearest length which divides the line into
+    /// equal intervals, so unlike `resampled` there is no short interval at
+    /// the end. A non-positive spacing, or a line with no length, returns the
+    /// line unchanged.
+    pub fn resample_by_distance(&self, spacing: f32) -> Polyline {
+        let length = self.length();
+        if spacing <= 0.0 || length <= 0.0 {
+            return self.clone();
+        }
+        let intervals = (length / spacing).round().max(1.0) as usize;
+        self.resample_to_count(intervals + 1)
+    }
+
+    /// Places exactly `count` points evenly along the line, keeping both end
+    /// points when `count` is at least 2. A count of 1 keeps only the start
+    /// point, every point of a line with no length is its start, and a line
+    /// with no points stays empty.
+    pub fn resample_to_count(&self, count: usize) -> Polyline {
+        let (first, last) = match (self.points.first(), self.points.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return self.clone(),
+        };
+        let length = self.length();
+        if count < 2 || length <= 0.0 {
+            return Polyline::new(vec![first; count]);
+        }
+
+        let mut points = Vec::with_capacity(count);
+        points.push(first);
+        let mut segments = self.points.windows(2);
+        let mut current = segments.next();
+        // Distance along the line to the start of the current segment
+        let mut walked = 0.0;
+        for i in 1..count - 1 {
+            let target = length * i as f32 / (count - 1) as f32;
+            while let Some(w) = current {
+                let len = (w[1] - w[0]).len();
+                if len > 0.0 && target - walked <= len {
+                    points.push(w[0] + (w[1] - w[0]) * ((target - walked) / len));
+                    break;
+                }
+                walked += len;
+                current = segments.next();
+            }
+            // Rounding in the running total can overshoot the last segment
+            if current.is_none() {
+                points.push(last);
+            }
+        }
+        points.push(last);
+        Polyline { points }
+    }
+
+    /// Smooths the line with Chaikin's corner cutting algorithm. Each
+    /// iteration replaces every interior point with two points a quarter of
+    /// the way along its neighbouring edges, converging on a quadratic
+    /// B-spline. The end points are kept in place.
+    pub fn chaikin_smooth(&self, iterations: u32) -> Polyline {
+        let mut points = self.points.clone();
+        for _ in 0..iterations {
+            if points.len() < 3 {
+                break;
+            }
+
+            let mut smoothed = Vec::with_capacity(points.len() * 2);
+            smoothed.push(points[0]);
+            for w in points.windows(3) {
+                let (a, b) = corner::cut(w[0], w[1], w[2], 0.25, 0.25);
+                smoothed.push(a);
+                smoothed.push(b);
+            }
+            smoothed.push(points[points.len() - 1]);
+            points = smoothed;
+        }
+        Polyline { points }
+    }
+
+    /// Simplifies the line with the Ramer-Douglas-Peucker algorithm, removing
+    /// points until every original point lies within `tolerance` of the
+    /// result. The first and last points are always kept, and relative
+    /// tolerances are measured against the bounds of the line.
+    ///
+    /// The algorithm is quadratic in the worst case, so very dense input is
+    /// best passed through `radial_filtered` first.
+    pub fn simplified(&self, tolerance: impl Into<Tolerance>) -> Polyline {
+        let n = self.points.len();
+        if n < 3 {
+            return self.clone();
+        }
+        let tolerance = tolerance.into().resolve(|| self.bounds());
+
+        let mut keep = vec![false; n];
+        keep[0] = true;
+        keep[n - 1] = true;
+        let mut stack = vec![(0, n - 1)];
+        while let Some((first, last)) = stack.pop() {
+            let (a, b) = (self.points[first], self.points[last]);
+            let mut max_dist = 0.0;
+            let mut index = first;
+            for i in first + 1..last {
+                let dist = segment_distance_squared(self.points[i], a, b);
+                if dist > max_dist {
+                    max_dist = dist;
+                    index = i;
+                }
+            }
+
+            if max_dist > tolerance * tolerance {
+                keep[index] = true;
+                stack.push((first, index));
+                stack.push((index, last));
+            }
+        }
+
+        let points = self
+            .points
+            .iter()
+            .zip(keep)
+            .filter(|&(_, keep)| keep)
+            .map(|(&p, _)| p)
+            .collect();
+        Polyline { points }
+    }
+
+    /// Parallel version of `simplified`, which produces the same result.
+    /// Every pending split of the line is searched at the same time, and
+    /// the farthest point of a long run is itself searched in parallel.
+    #[cfg(feature = "rayon")]
+    pub fn par_simplified(&self, tolerance: impl Into<Tolerance>) -> Polyline {
+        use rayon::prelude::*;
+
+        let n = self.points.len();
+        if n < 3 {
+            return self.clone();
+        }
+        let tolerance = tolerance.into().resolve(|| self.par_bounds());
+
+        let mut keep = vec![false; n];
+        keep[0] = true;
+        keep[n - 1] = true;
+        let mut runs = vec![(0, n - 1)];
+        while !runs.is_empty() {
+            let splits: Vec<_> = runs
+                .par_iter()
+                .filter_map(|&(first, last)| {
+                    let (index, dist) = par_farthest(&self.points, first, last);
+                    if dist > tolerance * tolerance {
+                        Some((first, index, last))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            runs.clear();
+            for (first, index, last) in splits {
+                keep[index] = true;
+                runs.push((first, index));
+                runs.push((index, last));
+            }
+        }
+
+        let points = self
+            .points
+            .iter()
+            .zip(keep)
+            .filter(|&(_, keep)| keep)
+            .map(|(&p, _)| p)
+            .collect();
+        Polyline { points }
+    }
+
+    /// Removes points which lie within `tolerance` of the last point kept.
+    /// This is a cheap linear pass which thins out clusters of nearly
+    /// identical points, such as those produced by slow pointer movement.
+    /// The first and last points are always kept, and relative tolerances
+    /// are measured against the bounds of the line.
+    pub fn radial_filtered(&self, tolerance: impl Into<Tolerance>) -> Polyline {
+        let (&first, &last) = match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) if self.points.len() > 2 => (first, last),
+            _ => return self.clone(),
+        };
+        let tolerance = tolerance.into().resolve(|| self.bounds());
+
+        let mut points = vec![first];
+        let mut prev = first;
+        for &p in &self.points[1..self.points.len() - 1] {
+            if (p - prev).len_squared() > tolerance * tolerance {
+                points.push(p);
+                prev = p;
+            }
+        }
+        points.push(last);
+        Polyline { points }
+    }
+
+    /// Formats the `points` attribute of an SVG `<polyline>` element
+    /// through these points.
+    pub fn to_svg_attributes(&self) -> String {
+        format!(r#"points="{}""#, polygon::svg_points(&self.points))
+    }
+}
+
+/// Finds the point strictly between `first` and `last` which is farthest
+/// from the segment joining them, along with its squared distance. Ties go
+/// to the earlier point, like the scan in `simplified`.
+#[cfg(feature = "rayon")]
+fn par_farthest(points: &[Point2f], first: usize, last: usize) -> (usize, f32) {
+    use rayon::prelude::*;
+
+    let (a, b) = (points[first], points[last]);
+    let distance = |i: usize| (i, segment_distance_squared(points[i], a, b));
+    let farthest = |best: (usize, f32), next: (usize, f32)| {
+        if next.1 > best.1 {
+            next
+        } else {
+            best
+        }
+    };
+
+    let interior = first + 1..last;
+    if interior.len() >= polygon::PAR_CHUNK_LEN {
+        interior
+            .into_par_iter()
+            .map(distance)
+            .reduce(|| (first, 0.0), farthest)
+    } else {
+        interior.map(distance).fold((first, 0.0), farthest)
+    }
+}
+
+/// The squared distance from `p` to the line segment between `a` and `b`.
+fn segment_distance_squared(p: Point2f, a: Point2f, b: Point2f) -> f32 {
+    let ab = b - a;
+    let len_sq = ab.len_squared();
+    let t = if len_sq > 0.0 {
+        ((p - a).dot(ab) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (p - (a + ab * t)).len_squared()
+}
+
+impl From<Vec<Point2f>> for Polyline {
+    #[inline]
+    fn from(points: Vec<Point2f>) -> Polyline {
+        Polyline { points }
+    }
+}
+
+impl From<Polyline> for Vec<Point2f> {
+    #[inline]
+    fn from(line: Polyline) -> Vec<Point2f> {
+        line.points
+    }
+}
+
+impl From<Polygon> for Polyline {
+    /// Opens the polygon into a line which repeats its first vertex at the
+    /// end, so that it traces every edge.
+    #[inline]
+    fn from(polygon: Polygon) -> Polyline {
+        let mut points = polygon.points;
+        if let Some(&first) = points.first() {
+            points.push(first);
+        }
+        Polyline { points }
+    }
+}
+
+impl From<Polyline> for Polygon {
+    /// Closes the line into a polygon. A final point repeating the first is
+    /// dropped, since the polygon already connects back to its start.
+    #[inline]
+    fn from(line: Polyline) -> Polygon {
+        let mut points = line.points;
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        Polygon { points }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::path::Path;
+    use crate::point2f::Point2f;
+    use crate::polygon::Polygon;
+    use crate::polyline::Polyline;
+    use crate::rectf::Rectf;
+
+    #[test]
+    fn measurements() {
+        let line = Polyline::new(vec![
+            (0.0, 0.0).into(),
+            (3.0, 4.0).into(),
+            (3.0, 10.0).into(),
+        ]);
+        assert_eq!(line.length(), 11.0);
+        assert_eq!(line.bounds(), Rectf::new(0.0, 0.0, 3.0, 10.0));
+        assert!(line
+            .point_at_distance(2.5)
+            .unwrap()
+            .is_approx_eq((1.5, 2.0), 1e-6));
+        assert_eq!(line.point_at_distance(8.0), Some((3.0, 7.0).into()));
+        assert_eq!(line.point_at_distance(-1.0), Some((0.0, 0.0).into()));
+        assert_eq!(line.point_at_distance(20.0), Some((3.0, 10.0).into()));
+        assert_eq!(Polyline::default().point_at_distance(1.0), None);
+
+        let resampled = line.resampled(2.0);
+        assert_eq!(resampled.points.len(), 7);
+        assert_eq!(resampled.points[3], (3.0, 5.0).into());
+        assert_eq!(*resampled.points.last().unwrap(), Point2f::new(3.0, 10.0));
+        // Samples are evenly spaced along the line, cutting across corners
+        assert!(resampled.points[1].is_approx_eq((1.2, 1.6), 1e-6));
+        assert_eq!(resampled.points[4], (3.0, 7.0).into());
+        assert_eq!(line.resampled(5.5).points.len(), 3);
+    }
+
+    #[test]
+    fn even_resampling() {
+        let line = Polyline::new(vec![
+            (0.0, 0.0).into(),
+            (3.0, 4.0).into(),
+            (3.0, 10.0).into(),
+        ]);
+
+        // 11 / 2 rounds to 6 intervals, each 11 / 6 long
+        let even = line.resample_by_distance(2.0);
+        assert_eq!(even.points.len(), 7);
+        assert_eq!(even.points[0], Point2f::new(0.0, 0.0));
+        assert_eq!(*even.points.last().unwrap(), Point2f::new(3.0, 10.0));
+        // The fifth point is 4 * 11 / 6 along, past the corner at 5
+        assert!(even.points[4].is_approx_eq((3.0, 4.0 + 44.0 / 6.0 - 5.0), 1e-5));
+        assert_eq!(line.resample_by_distance(0.0), line);
+
+        let counted = line.resample_to_count(12);
+        assert_eq!(counted.points.len(), 12);
+        assert!(counted.points[5].is_approx_eq((3.0, 4.0), 1e-5));
+        assert!(counted.points[8].is_approx_eq((3.0, 7.0), 1e-5));
+        assert_eq!(counted.points[11], Point2f::new(3.0, 10.0));
+        assert_eq!(
+            line.resample_to_count(2).points,
+            [line.points[0], line.points[2]]
+        );
+
+        // Too few points to reach the end
+        assert!(line.resample_to_count(0).is_empty());
+        assert_eq!(line.resample_to_count(1).points, [Point2f::new(0.0, 0.0)]);
+
+        // Lines with no length collapse onto their start
+        let p = Point2f::new(2.0, 3.0);
+        let zero = Polyline::new(vec![p, p]);
+        assert_eq!(zero.resample_to_count(3).points, [p, p, p]);
+        assert_eq!(zero.resample_by_distance(1.0), zero);
+        let single = Polyline::new(vec![p]);
+        assert_eq!(single.resample_to_count(2).points, [p, p]);
+        assert_eq!(single.resample_by_distance(1.0), single);
+        assert!(Polyline::default().resample_to_count(5).is_empty());
+        assert!(Polyline::default().resample_by_distance(1.0).is_empty());
+    }
+
+    #[test]
+    fn fine_resampling() {
+        // Adding up a million spacings in f32 drifts by several samples
+        let line = Polyline::new(vec![Point2f::new(0.0, 0.0), Point2f::new(1000.0, 0.0)]);
+        let resampled = line.resampled(0.001);
+        assert_eq!(resampled.points.len(), 1_000_001);
+        assert!((resampled.points[500_000].x - 500.0).abs() < 1e-3);
+        assert_eq!(resampled.points[1_000_000], Point2f::new(1000.0, 0.0));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_operations() {
+        // A long wobbly spiral gives both the parallel search and the split
+        // queue plenty of work
+        let points: Vec<Point2f> = (0..20_000)
+            .map(|i| {
+                let t = i as f32 * 0.01;
+                let r = 10.0 + t * 3.0 + (i % 7) as f32 * 0.05;
+                Point2f::new(r * t.cos(), r * t.sin())
+            })
+            .collect();
+        let line = Polyline::new(points.clone());
+
+        for &tolerance in &[0.01, 0.5, 4.0] {
+            assert_eq!(line.par_simplified(tolerance), line.simplified(tolerance));
+        }
+        assert_eq!(line.par_bounds(), line.bounds());
+        assert_eq!(Rectf::par_bounding(&points), line.bounds());
+        assert_eq!(Rectf::par_bounding(&[]), Rectf::default());
+    }
+
+    #[test]
+    fn simplification() {
+        // A noisy zigzag along a straight line collapses to its end points
+        let points: Vec<Point2f> = (0..=100)
+            .map(|i| (i as f32, if i % 2 == 0 { 0.05 } else { -0.05 }).into())
+            .collect();
+        let noisy = Polyline::new(points);
+        let simple = noisy.simplified(0.1);
+        assert_eq!(simple.points, vec![noisy.points[0], noisy.points[100]]);
+
+        // Real corners are kept
+        let corner = Polyline::new(vec![
+            (0.0, 0.0).into(),
+            (5.0, 0.01).into(),
+            (10.0, 0.0).into(),
+            (10.0, 10.0).into(),
+        ]);
+        let simple = corner.simplified(0.1);
+        assert_eq!(simple.points.len(), 3);
+        assert_eq!(simple.points[1], (10.0, 0.0).into());
+        assert_eq!(corner.simplified(0.001), corner);
+
+        let filtered = noisy.radial_filtered(1.5);
+        assert_eq!(filtered.points.len(), 51);
+        assert_eq!(filtered.points.last(), noisy.points.last());
+    }
+
+    #[test]
+    fn chaikin_smooth() {
+        let corner = Polyline::new(vec![
+            (0.0, 0.0).into(),
+            (4.0, 0.0).into(),
+            (4.0, 4.0).into(),
+        ]);
+        let smooth = corner.chaikin_smooth(1);
+        assert_eq!(
+            smooth.points,
+            vec![
+                (0.0, 0.0).into(),
+                (3.0, 0.0).into(),
+                (4.0, 1.0).into(),
+                (4.0, 4.0).into(),
+            ]
+        );
+
+        let smoother = corner.chaikin_smooth(4);
+        assert_eq!(smoother.points.len(), 18);
+        assert_eq!(smoother.points[0], corner.points[0]);
+        assert_eq!(smoother.points.last(), corner.points.last());
+        assert!(smoother.length() < smooth.length());
+        assert_eq!(corner.chaikin_smooth(0), corner);
+    }
+
+    #[test]
+    fn conversions() {
+        let square = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (1.0, 0.0).into(),
+            (1.0, 1.0).into(),
+            (0.0, 1.0).into(),
+        ]);
+        let line = Polyline::from(square.clone());
+        assert_eq!(line.points.len(), 5);
+        assert_eq!(line.length(), 4.0);
+        assert_eq!(Polygon::from(line.clone()), square);
+
+        let path = Path::from(line.clone());
+        assert_eq!(path.figures.len(), 1);
+        assert!(!path.figures[0].closed);
+        assert_eq!(path.flatten(0.1), vec![line]);
+        assert!(Path::from(Polyline::default()).is_empty());
+    }
+}
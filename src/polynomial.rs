@@ -0,0 +1,189 @@
+//! Real roots of quadratic and cubic polynomials.
+//!
+//! Finding the extrema of a bezier curve, or where it crosses a line, comes
+//! down to solving for the roots of a polynomial in the curve parameter.
+//! The textbook formulas lose most of their precision when the roots are
+//! far apart or the leading coefficient is tiny, so these solvers use forms
+//! which avoid cancellation and degrade to the lower degree solution when
+//! the leading term vanishes.
+//!
+//! Each solver returns the distinct real roots sorted in ascending order.
+//! The `_clamped` variants only return roots in the range `[0, 1]` of curve
+//! parameters.
+
+use std::f64::consts::PI;
+
+/// How far outside `[0, 1]` a root may be and still be clamped onto it, to
+/// allow for roots at the ends which rounding has pushed slightly outside.
+const CLAMP_EPSILON: f32 = 1e-5;
+
+/// Real roots of `a*t^2 + b*t + c`, sorted ascending. Falls back to the
+/// linear solution when the quadratic term is negligible next to the
+/// others, and returns no roots when every coefficient is.
+pub fn solve_quadratic(a: f32, b: f32, c: f32) -> Vec<f32> {
+    let scale = a.abs().max(b.abs()).max(c.abs());
+    if scale == 0.0 {
+        return vec![];
+    }
+    let (a, b, c) = (a / scale, b / scale, c / scale);
+
+    if a.abs() < 1e-6 {
+        if b.abs() < 1e-6 {
+            return vec![];
+        }
+        return vec![-c / b];
+    }
+
+    let disc = b * b - 4.0 * a * c;
+    if disc < 0.0 {
+        vec![]
+    } else if disc == 0.0 {
+        vec![-b / (2.0 * a)]
+    } else {
+        // Computing one root from the other avoids subtracting nearly equal
+        // values when b*b dominates 4*a*c
+        let q = -0.5 * (b + b.signum() * disc.sqrt());
+        let (r1, r2) = (q / a, c / q);
+        if r1 < r2 {
+            vec![r1, r2]
+        } else {
+            vec![r2, r1]
+        }
+    }
+}
+
+/// Real roots of `a*t^3 + b*t^2 + c*t + d`, sorted ascending. Falls back to
+/// `solve_quadratic` when the cubic term is negligible next to the others.
+///
+/// The roots are found in double precision with Cardano's formula when there
+/// is one real root and the trigonometric method when there are three, then
+/// refined with a step of Newton's method. Repeated roots are returned once.
+pub fn solve_cubic(a: f32, b: f32, c: f32, d: f32) -> Vec<f32> {
+    let scale = a.abs().max(b.abs()).max(c.abs()).max(d.abs());
+    if scale == 0.0 {
+        return vec![];
+    }
+    if (a / scale).abs() < 1e-6 {
+        return solve_quadratic(b, c, d);
+    }
+
+    // t^3 + b*t^2 + c*t + d, substituted with t = x - b/3 to remove the
+    // square term, leaving x^3 + p*x + q
+    let (b, c, d) = (
+        f64::from(b) / f64::from(a),
+        f64::from(c) / f64::from(a),
+        f64::from(d) / f64::from(a),
+    );
+    let shift = -b / 3.0;
+    let p = c - b * b / 3.0;
+    let q = 2.0 * b * b * b / 27.0 - b * c / 3.0 + d;
+    let disc = q * q / 4.0 + p * p * p / 27.0;
+
+    let roots: Vec<f64> = if disc > 0.0 {
+        let sqrt_disc = disc.sqrt();
+        vec![(-q / 2.0 + sqrt_disc).cbrt() + (-q / 2.0 - sqrt_disc).cbrt()]
+    } else if p == 0.0 {
+        vec![0.0]
+    } else {
+        // A non-positive discriminant implies p < 0
+        let r = 2.0 * (-p / 3.0).sqrt();
+        let phi = (3.0 * q / (p * r)).clamp(-1.0, 1.0).acos() / 3.0;
+        (0..3)
+            .map(|k| r * (phi - 2.0 * PI * f64::from(k) / 3.0).cos())
+            .collect()
+    };
+
+    let mut result: Vec<f32> = roots
+        .into_iter()
+        .map(|x| {
+            let t = x + shift;
+            let f = ((t + b) * t + c) * t + d;
+            let df = (3.0 * t + 2.0 * b) * t + c;
+            let t = if df != 0.0 { t - f / df } else { t };
+            t as f32
+        })
+        .collect();
+    result.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    result.dedup_by(|x, y| (*x - *y).abs() <= 1e-6 * x.abs().max(y.abs()).max(1.0));
+    result
+}
+
+/// Keeps the roots in `[0, 1]`, clamping those just outside onto the range.
+fn clamped(mut roots: Vec<f32>) -> Vec<f32> {
+    roots.retain(|t| (-CLAMP_EPSILON..=1.0 + CLAMP_EPSILON).contains(t));
+    for t in &mut roots {
+        *t = t.clamp(0.0, 1.0);
+    }
+    roots.dedup();
+    roots
+}
+
+/// The roots of `solve_quadratic` in the range `[0, 1]`. Roots which are
+/// outside the range by no more than rounding error are clamped onto it.
+#[inline]
+pub fn solve_quadratic_clamped(a: f32, b: f32, c: f32) -> Vec<f32> {
+    clamped(solve_quadratic(a, b, c))
+}
+
+/// The roots of `solve_cubic` in the range `[0, 1]`. Roots which are
+/// outside the range by no more than rounding error are clamped onto it.
+#[inline]
+pub fn solve_cubic_clamped(a: f32, b: f32, c: f32, d: f32) -> Vec<f32> {
+    clamped(solve_cubic(a, b, c, d))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::polynomial::{
+        solve_cubic, solve_cubic_clamped, solve_quadratic, solve_quadratic_clamped,
+    };
+
+    fn assert_roots(actual: Vec<f32>, expected: &[f32]) {
+        assert_eq!(
+            actual.len(),
+            expected.len(),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+        for (a, e) in actual.iter().zip(expected) {
+            assert!(
+                (a - e).abs() <= 1e-5 * e.abs().max(1.0),
+                "{:?} != {:?}",
+                actual,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn quadratics() {
+        assert_roots(solve_quadratic(1.0, -3.0, 2.0), &[1.0, 2.0]);
+        assert_roots(solve_quadratic(1.0, 2.0, 1.0), &[-1.0]);
+        assert_roots(solve_quadratic(1.0, 0.0, 1.0), &[]);
+        assert_roots(solve_quadratic(0.0, 2.0, -1.0), &[0.5]);
+        assert_roots(solve_quadratic(0.0, 0.0, 0.0), &[]);
+
+        // Widely separated roots, where the textbook formula cancels
+        assert_roots(solve_quadratic(1.0, -1e4, 1.0), &[1e-4, 1e4]);
+        assert_roots(solve_quadratic_clamped(1.0, -1.0, 0.0), &[0.0, 1.0]);
+        assert_roots(solve_quadratic_clamped(1.0, -3.0, 2.0), &[1.0]);
+    }
+
+    #[test]
+    fn cubics() {
+        // (t - 1)(t - 2)(t - 3)
+        assert_roots(solve_cubic(1.0, -6.0, 11.0, -6.0), &[1.0, 2.0, 3.0]);
+        assert_roots(solve_cubic(2.0, -12.0, 22.0, -12.0), &[1.0, 2.0, 3.0]);
+        // t^3 + t + 2 has only t = -1
+        assert_roots(solve_cubic(1.0, 0.0, 1.0, 2.0), &[-1.0]);
+        // (t - 1)^2 (t + 2) and t^3
+        assert_roots(solve_cubic(1.0, 0.0, -3.0, 2.0), &[-2.0, 1.0]);
+        assert_roots(solve_cubic(1.0, 0.0, 0.0, 0.0), &[0.0]);
+        // Degrades to a quadratic
+        assert_roots(solve_cubic(0.0, 1.0, -3.0, 2.0), &[1.0, 2.0]);
+
+        // (t - 0.25)(t - 0.5)(t - 1.5)
+        assert_roots(solve_cubic_clamped(1.0, -2.25, 1.25, -0.1875), &[0.25, 0.5]);
+    }
+}
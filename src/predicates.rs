@@ -0,0 +1,228 @@
+//! Robust geometric predicates in the style of Shewchuk's "Adaptive
+//! Precision Floating-Point Arithmetic and Fast Robust Geometric
+//! Predicates" (1997).
+//!
+//! Each predicate first evaluates its determinant in ordinary double
+//! precision along with a bound on the rounding error. Only when the bound
+//! cannot rule out the wrong sign, as happens for nearly collinear or nearly
+//! cocircular points, is the determinant recomputed exactly with expansion
+//! arithmetic. The sign of the result is therefore always correct, while its
+//! magnitude is only approximate.
+//!
+//! The results are exact for any finite inputs which do not overflow or
+//! underflow during the computation, which covers every `f32` coordinate.
+
+use crate::point2::Point2d;
+
+/// Rounding error of a single double precision operation.
+const EPSILON: f64 = f64::EPSILON / 2.0;
+
+/// Bound on the relative error of the fast `orient2d` determinant.
+const ORIENT_ERROR_BOUND: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+
+/// Bound on the relative error of the fast `in_circle` determinant.
+const IN_CIRCLE_ERROR_BOUND: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+/// Twice the signed area of the triangle `abc`, which is positive when the
+/// points turn clockwise on a y-down screen, negative when they turn
+/// counterclockwise, and zero when they are collinear. This is the same as
+/// `(b - a).cross(c - a)`, but never reports the wrong side for points which
+/// are nearly in line.
+pub fn orient2d(a: impl Into<Point2d>, b: impl Into<Point2d>, c: impl Into<Point2d>) -> f64 {
+    let (a, b, c) = (a.into(), b.into(), c.into());
+
+    let left = (a.x - c.x) * (b.y - c.y);
+    let right = (a.y - c.y) * (b.x - c.x);
+    let det = left - right;
+    if det.abs() >= ORIENT_ERROR_BOUND * (left.abs() + right.abs()) {
+        return det;
+    }
+
+    estimate(&orient2d_terms(a, b, c))
+}
+
+/// Determines where `d` lies relative to the circle through `a`, `b` and
+/// `c`. When `orient2d(a, b, c)` is positive the result is positive if `d`
+/// is inside the circle, negative if it is outside and zero if it is on the
+/// circle; the signs are swapped for the opposite winding.
+pub fn in_circle(
+    a: impl Into<Point2d>,
+    b: impl Into<Point2d>,
+    c: impl Into<Point2d>,
+    d: impl Into<Point2d>,
+) -> f64 {
+    let (a, b, c, d) = (a.into(), b.into(), c.into(), d.into());
+
+    let (adx, ady) = (a.x - d.x, a.y - d.y);
+    let (bdx, bdy) = (b.x - d.x, b.y - d.y);
+    let (cdx, cdy) = (c.x - d.x, c.y - d.y);
+    let (bdxcdy, cdxbdy) = (bdx * cdy, cdx * bdy);
+    let (cdxady, adxcdy) = (cdx * ady, adx * cdy);
+    let (adxbdy, bdxady) = (adx * bdy, bdx * ady);
+    let alift = adx * adx + ady * ady;
+    let blift = bdx * bdx + bdy * bdy;
+    let clift = cdx * cdx + cdy * cdy;
+
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+    if det.abs() > IN_CIRCLE_ERROR_BOUND * permanent {
+        return det;
+    }
+
+    // Expanding the 4x4 lifted determinant along its lifted column avoids the
+    // rounding of the translation above
+    let mut terms = Vec::with_capacity(4 * 4 * 12 * 2);
+    let lifted = [
+        (a, orient2d_terms(b, c, d), 1.0),
+        (b, orient2d_terms(a, c, d), -1.0),
+        (c, orient2d_terms(a, b, d), 1.0),
+        (d, orient2d_terms(a, b, c), -1.0),
+    ];
+    for (p, orient, sign) in lifted.iter() {
+        let (xx, xx_err) = two_product(p.x, p.x);
+        let (yy, yy_err) = two_product(p.y, p.y);
+        for &lift in &[xx, xx_err, yy, yy_err] {
+            for &o in orient {
+                let (product, err) = two_product(lift * sign, o);
+                terms.push(product);
+                terms.push(err);
+            }
+        }
+    }
+    estimate(&terms)
+}
+
+/// Doubles whose exact sum is the orientation determinant of `abc`.
+fn orient2d_terms(a: Point2d, b: Point2d, c: Point2d) -> Vec<f64> {
+    let products = [
+        (a.x, b.y),
+        (-a.y, b.x),
+        (b.x, c.y),
+        (-b.y, c.x),
+        (c.x, a.y),
+        (-c.y, a.x),
+    ];
+    let mut terms = Vec::with_capacity(12);
+    for &(u, v) in &products {
+        let (product, err) = two_product(u, v);
+        terms.push(product);
+        terms.push(err);
+    }
+    terms
+}
+
+/// Sums the terms exactly and rounds the result, which always has the sign
+/// of the exact sum.
+fn estimate(terms: &[f64]) -> f64 {
+    // The expansion is kept nonoverlapping and in increasing order of
+    // magnitude, so its sign is the sign of its last component
+    let mut expansion: Vec<f64> = Vec::with_capacity(terms.len());
+    for &term in terms {
+        grow_expansion(&mut expansion, term);
+    }
+    expansion.iter().sum()
+}
+
+/// Adds a double to an expansion in place, removing any zero components.
+fn grow_expansion(expansion: &mut Vec<f64>, value: f64) {
+    let mut q = value;
+    let mut len = 0;
+    for i in 0..expansion.len() {
+        let (sum, err) = two_sum(q, expansion[i]);
+        q = sum;
+        if err != 0.0 {
+            expansion[len] = err;
+            len += 1;
+        }
+    }
+    expansion.truncate(len);
+    if q != 0.0 {
+        expansion.push(q);
+    }
+}
+
+/// The rounded sum of `a` and `b` along with its rounding error.
+#[inline]
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let bv = x - a;
+    let av = x - bv;
+    (x, (a - av) + (b - bv))
+}
+
+/// The rounded product of `a` and `b` along with its rounding error.
+#[inline]
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    let (ahi, alo) = split(a);
+    let (bhi, blo) = split(b);
+    let err = x - ahi * bhi - alo * bhi - ahi * blo;
+    (x, alo * blo - err)
+}
+
+/// Splits a double into two halves of 26 significant bits each, so that
+/// their products are exact.
+#[inline]
+fn split(a: f64) -> (f64, f64) {
+    const SPLITTER: f64 = 134_217_729.0; // 2^27 + 1
+    let c = SPLITTER * a;
+    let hi = c - (c - a);
+    (hi, a - hi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point2f::Point2f;
+
+    #[test]
+    fn orientation() {
+        let (a, b) = (Point2f::new(0.0, 0.0), Point2f::new(1.0, 0.0));
+        assert!(orient2d(a, b, (0.0, 1.0)) > 0.0);
+        assert!(orient2d(a, b, (0.0, -1.0)) < 0.0);
+        assert_eq!(orient2d(a, b, (7.0, 0.0)), 0.0);
+
+        // Points along the line through (0.5, 0.5) and (12, 12) which the
+        // fast determinant misjudges, from the examples in Kettner et al.,
+        // "Classroom Examples of Robustness Problems in Geometric
+        // Computations"
+        let (q, r) = (Point2d::new(12.0, 12.0), Point2d::new(24.0, 24.0));
+        let mut p = Point2d::new(0.5, 0.5);
+        let step = f64::EPSILON / 2.0;
+        let sign = |v: f64| (v > 0.0) as i32 - (v < 0.0) as i32;
+        for i in 0..64i32 {
+            for j in 0..64 {
+                let pij = Point2d::new(p.x + i as f64 * step, p.y + j as f64 * step);
+                assert_eq!(sign(orient2d(pij, q, r)), (j - i).signum());
+            }
+        }
+        p.x += 1.0;
+        assert!(orient2d(p, q, r) < 0.0);
+    }
+
+    #[test]
+    fn circles() {
+        let (a, b, c) = (
+            Point2f::new(0.0, 0.0),
+            Point2f::new(1.0, 0.0),
+            Point2f::new(0.0, 1.0),
+        );
+        assert!(orient2d(a, b, c) > 0.0);
+        assert!(in_circle(a, b, c, (0.25, 0.25)) > 0.0);
+        assert!(in_circle(a, b, c, (2.0, 2.0)) < 0.0);
+        assert_eq!(in_circle(a, b, c, (1.0, 1.0)), 0.0);
+        assert!(in_circle(a, c, b, (0.25, 0.25)) < 0.0);
+
+        // Far from the origin, where the fast determinant cancels to zero for
+        // the cocircular corners of a square, a point one ULP further out is
+        // still outside
+        let offset = 1.0e6f32;
+        let shifted = |x: f32, y: f32| Point2f::new(x + offset, y + offset);
+        let (a, b, c) = (shifted(0.0, 0.0), shifted(8.0, 0.0), shifted(0.0, 8.0));
+        assert_eq!(in_circle(a, b, c, shifted(8.0, 8.0)), 0.0);
+        let outside = Point2f::new(f32::from_bits((8.0 + offset).to_bits() + 1), 8.0 + offset);
+        assert!(in_circle(a, b, c, outside) < 0.0);
+    }
+}
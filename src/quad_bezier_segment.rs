@@ -1,14 +1,28 @@
 //! Quadratic version of the BezierSegment, uses 1 fewer control point than
 //! the cubic variant.
 
+use crate::bezier_segment::BezierSegment;
+use crate::interval::Interval;
+use crate::matrix3x2f::Matrix3x2f;
 use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+use crate::tolerance::Tolerance;
+use crate::vector2f::Vector2f;
 
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::d2d1::D2D1_QUADRATIC_BEZIER_SEGMENT;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D as d2d;
 
 /// Contains the control point and end point for a quadratic Bezier segment.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(C)]
 pub struct QuadBezierSegment {
     /// The control point of the quadratic Bezier segment.
@@ -26,6 +40,159 @@ impl QuadBezierSegment {
             p2: p2.into(),
         }
     }
+
+    /// Evaluates the point on the curve at the parameter `t`, where `0.0` is
+    /// the start point and `1.0` is the end point `p2`. The start point must
+    /// be provided as it is implicit in the segment.
+    #[inline]
+    pub fn point_at(&self, start: impl Into<Point2f>, t: f32) -> Point2f {
+        let p0 = start.into().to_vector();
+        let (p1, p2) = (self.p1.to_vector(), self.p2.to_vector());
+        let ti = 1.0 - t;
+        ((ti * ti) * p0 + (2.0 * ti * t) * p1 + (t * t) * p2).to_point()
+    }
+
+    /// The first derivative (tangent vector) of the curve at the parameter `t`.
+    #[inline]
+    pub fn derivative_at(&self, start: impl Into<Point2f>, t: f32) -> Vector2f {
+        let p0 = start.into();
+        (2.0 * (1.0 - t)) * (self.p1 - p0) + (2.0 * t) * (self.p2 - self.p1)
+    }
+
+    /// The second derivative of the curve, which is constant for a
+    /// quadratic segment.
+    #[inline]
+    pub fn second_derivative(&self, start: impl Into<Point2f>) -> Vector2f {
+        let p0 = start.into().to_vector();
+        2.0 * (self.p2.to_vector() - 2.0 * self.p1.to_vector() + p0)
+    }
+
+    /// The signed curvature of the curve at the parameter `t`, i.e. the
+    /// reciprocal of the radius of the osculating circle. Positive values
+    /// turn clockwise in the y-down coordinate system. Quadratic segments
+    /// never inflect, so the sign is the same along the entire curve.
+    #[inline]
+    pub fn curvature_at(&self, start: impl Into<Point2f>, t: f32) -> f32 {
+        let start = start.into();
+        let d1 = self.derivative_at(start, t);
+        let d2 = self.second_derivative(start);
+        let len = d1.len();
+        d1.cross(d2) / (len * len * len)
+    }
+
+    /// Determines if the curve has zero length, with every control point at
+    /// the start point.
+    #[inline]
+    pub fn is_degenerate(&self, start: impl Into<Point2f>) -> bool {
+        let start = start.into();
+        self.p1 == start && self.p2 == start
+    }
+
+    /// The tight axis-aligned bounding box of the curve. Each axis has at
+    /// most one extremum, where its derivative is zero.
+    pub fn bounds(&self, start: impl Into<Point2f>) -> Rectf {
+        let start = start.into();
+
+        // B'(t) / 2 = (p0 - 2p1 + p2) * t + (p1 - p0)
+        let extent = |p0: f32, p1: f32, p2: f32| {
+            let range = Interval::between(p0, p2);
+            let t = (p0 - p1) / (p0 - 2.0 * p1 + p2);
+            if t > 0.0 && t < 1.0 {
+                let mt = 1.0 - t;
+                range.including(mt * mt * p0 + 2.0 * mt * t * p1 + t * t * p2)
+            } else {
+                range
+            }
+        };
+        Rectf::from_intervals(
+            extent(start.x, self.p1.x, self.p2.x),
+            extent(start.y, self.p1.y, self.p2.y),
+        )
+    }
+
+    /// The bounding box of the control polygon. This is cheaper to compute
+    /// than `bounds` and always contains the curve, but may be larger.
+    #[inline]
+    pub fn fast_bounds(&self, start: impl Into<Point2f>) -> Rectf {
+        Rectf::from_points(start, self.p2).combined_with(Rectf::from_points(self.p1, self.p1))
+    }
+
+    /// Approximates the length of the curve to within `tolerance`.
+    #[inline]
+    pub fn arc_length(&self, start: impl Into<Point2f>, tolerance: f32) -> f32 {
+        let start = start.into();
+        self.to_cubic(start).arc_length(start, tolerance)
+    }
+
+    /// Approximates the curve with line segments no further than `tolerance`
+    /// from the true curve. The start point is not included in the result,
+    /// but the end point always is (exactly). Relative tolerances are
+    /// measured against the size of the control polygon. Tolerances finer
+    /// than `f32` can resolve at that size, including zero and negative ones,
+    /// are raised to `f32::EPSILON` times the size.
+    pub fn flatten(
+        &self,
+        start: impl Into<Point2f>,
+        tolerance: impl Into<Tolerance>,
+    ) -> Vec<Point2f> {
+        let start = start.into();
+        let tolerance = tolerance
+            .into()
+            .resolve_flattening(&self.fast_bounds(start));
+        let dd = (start.to_vector() - 2.0 * self.p1.to_vector() + self.p2.to_vector()).len();
+        let count = if tolerance > 0.0 {
+            (0.25 * dd / tolerance).sqrt().ceil().max(1.0) as usize
+        } else {
+            1
+        };
+
+        let mut points: Vec<_> = (1..count)
+            .map(|i| self.point_at(start, i as f32 / count as f32))
+            .collect();
+        points.push(self.p2);
+        points
+    }
+
+    /// Splits the segment at the parameter `t` using de Casteljau's
+    /// algorithm. The first half starts at `start` and the second half starts
+    /// at the end point of the first.
+    #[inline]
+    pub fn split_at(
+        &self,
+        start: impl Into<Point2f>,
+        t: f32,
+    ) -> (QuadBezierSegment, QuadBezierSegment) {
+        let start = start.into();
+        let p01 = start + (self.p1 - start) * t;
+        let p12 = self.p1 + (self.p2 - self.p1) * t;
+        let mid = p01 + (p12 - p01) * t;
+        (
+            QuadBezierSegment::new(p01, mid),
+            QuadBezierSegment::new(p12, self.p2),
+        )
+    }
+
+    /// Elevates the segment to the identical cubic bezier curve.
+    #[inline]
+    pub fn to_cubic(&self, start: impl Into<Point2f>) -> BezierSegment {
+        let start = start.into();
+        BezierSegment {
+            p1: start + (self.p1 - start) * (2.0 / 3.0),
+            p2: self.p2 + (self.p1 - self.p2) * (2.0 / 3.0),
+            p3: self.p2,
+        }
+    }
+
+    /// Applies an affine transformation to the segment. Bezier curves are
+    /// affine invariant, so transforming the control points is exact. The
+    /// start point must be transformed separately.
+    #[inline]
+    pub fn transformed(&self, matrix: &Matrix3x2f) -> QuadBezierSegment {
+        QuadBezierSegment {
+            p1: self.p1 * *matrix,
+            p2: self.p2 * *matrix,
+        }
+    }
 }
 
 impl<P1, P2> From<(P1, P2)> for QuadBezierSegment
@@ -42,6 +209,47 @@ where
     }
 }
 
+#[cfg(feature = "kurbo")]
+impl QuadBezierSegment {
+    /// Converts the segment into a kurbo curve beginning at `start`.
+    #[inline]
+    pub fn to_kurbo(&self, start: impl Into<Point2f>) -> kurbo::QuadBez {
+        kurbo::QuadBez::new(start.into(), self.p1, self.p2)
+    }
+
+    /// Splits a kurbo curve into its start point and the segment that
+    /// continues from it.
+    #[inline]
+    pub fn from_kurbo(bez: kurbo::QuadBez) -> (Point2f, QuadBezierSegment) {
+        (bez.p0.into(), QuadBezierSegment::new(bez.p1, bez.p2))
+    }
+}
+
+#[cfg(feature = "lyon")]
+impl QuadBezierSegment {
+    /// Converts the segment into a lyon curve beginning at `start`.
+    #[inline]
+    pub fn to_lyon(
+        &self,
+        start: impl Into<Point2f>,
+    ) -> lyon_path::geom::QuadraticBezierSegment<f32> {
+        lyon_path::geom::QuadraticBezierSegment {
+            from: start.into().into(),
+            ctrl: self.p1.into(),
+            to: self.p2.into(),
+        }
+    }
+
+    /// Splits a lyon curve into its start point and the segment that
+    /// continues from it.
+    #[inline]
+    pub fn from_lyon(
+        bez: lyon_path::geom::QuadraticBezierSegment<f32>,
+    ) -> (Point2f, QuadBezierSegment) {
+        (bez.from.into(), QuadBezierSegment::new(bez.ctrl, bez.to))
+    }
+}
+
 #[cfg(all(windows, feature = "d2d"))]
 impl From<QuadBezierSegment> for D2D1_QUADRATIC_BEZIER_SEGMENT {
     #[inline]
@@ -64,6 +272,28 @@ impl From<D2D1_QUADRATIC_BEZIER_SEGMENT> for QuadBezierSegment {
     }
 }
 
+#[cfg(all(windows, feature = "windows"))]
+impl From<QuadBezierSegment> for d2d::D2D1_QUADRATIC_BEZIER_SEGMENT {
+    #[inline]
+    fn from(seg: QuadBezierSegment) -> d2d::D2D1_QUADRATIC_BEZIER_SEGMENT {
+        d2d::D2D1_QUADRATIC_BEZIER_SEGMENT {
+            point1: seg.p1.into(),
+            point2: seg.p2.into(),
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<d2d::D2D1_QUADRATIC_BEZIER_SEGMENT> for QuadBezierSegment {
+    #[inline]
+    fn from(seg: d2d::D2D1_QUADRATIC_BEZIER_SEGMENT) -> QuadBezierSegment {
+        QuadBezierSegment {
+            p1: seg.point1.into(),
+            p2: seg.point2.into(),
+        }
+    }
+}
+
 #[cfg(all(test, windows, feature = "d2d"))]
 #[test]
 fn qbezier_d2d_bin_compat() {
@@ -82,3 +312,53 @@ fn qbezier_d2d_bin_compat() {
     assert!(ptr_eq(&bez.p2.y, &d2d.point2.y));
     assert_eq!(size_of_val(&bez), size_of_val(d2d));
 }
+
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn qbezier_windows_bin_compat() {
+    use std::mem::size_of_val;
+
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
+    }
+
+    let bez = QuadBezierSegment::new((0.0, 0.0), (1.0, 0.0));
+    let d2d = unsafe { &*((&bez) as *const _ as *const d2d::D2D1_QUADRATIC_BEZIER_SEGMENT) };
+
+    assert!(ptr_eq(&bez.p1.x, &d2d.point1.x));
+    assert!(ptr_eq(&bez.p1.y, &d2d.point1.y));
+    assert!(ptr_eq(&bez.p2.x, &d2d.point2.x));
+    assert!(ptr_eq(&bez.p2.y, &d2d.point2.y));
+    assert_eq!(size_of_val(&bez), size_of_val(d2d));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::quad_bezier_segment::QuadBezierSegment;
+
+    #[test]
+    fn flatten_without_tolerance() {
+        let arch = QuadBezierSegment::new((10.0, 10.0), (10.0, 0.0));
+        let finest = arch.flatten((0.0, 10.0), 0.0);
+        assert!(finest.len() > 100);
+        assert_eq!(arch.flatten((0.0, 10.0), -1.0), finest);
+        assert_eq!(*finest.last().unwrap(), arch.p2);
+    }
+
+    #[test]
+    fn curvature() {
+        // The parabola (2t - 1, 2t(1 - t)) has its vertex at t = 0.5, where
+        // the curvature is |x'y'' - y'x''| / |B'|^3 = 8 / 8. It bends upwards
+        // there, which is counterclockwise in y-down coordinates.
+        let up = QuadBezierSegment::new((0.0, 1.0), (1.0, 0.0));
+        assert!((up.curvature_at((-1.0, 0.0), 0.5) + 1.0).abs() < 1e-6);
+        let k0 = up.curvature_at((-1.0, 0.0), 0.0);
+        assert!((k0 + 1.0 / 8f32.sqrt()).abs() < 1e-6, "{}", k0);
+
+        let down = QuadBezierSegment::new((0.0, -1.0), (1.0, 0.0));
+        assert!((down.curvature_at((-1.0, 0.0), 0.5) - 1.0).abs() < 1e-6);
+
+        let line = QuadBezierSegment::new((1.0, 1.0), (2.0, 2.0));
+        assert_eq!(line.curvature_at((0.0, 0.0), 0.3), 0.0);
+    }
+}
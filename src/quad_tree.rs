@@ -0,0 +1,307 @@
+//! A quadtree of rectangles for culling and hit testing.
+
+use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+
+/// The number of items a node holds before it is split into quadrants.
+const NODE_CAPACITY: usize = 8;
+/// Nodes this deep are never split.
+const MAX_DEPTH: u32 = 12;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Node {
+    bounds: Rectf,
+    depth: u32,
+    /// Index of the first of four child nodes, ordered top-left, top-right,
+    /// bottom-left, bottom-right.
+    children: Option<usize>,
+    /// Keys of the items stored in this node. Items live in the deepest node
+    /// which contains them entirely, so interior nodes hold items which
+    /// straddle their quadrants.
+    items: Vec<usize>,
+}
+
+impl Node {
+    #[inline]
+    fn leaf(bounds: Rectf, depth: u32) -> Node {
+        Node {
+            bounds,
+            depth,
+            children: None,
+            items: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Entry<T> {
+    rect: Rectf,
+    value: T,
+    node: usize,
+}
+
+/// A spatial index of values by their bounding rectangles, for finding the
+/// UI elements under the cursor or the scene items visible in a viewport
+/// without testing every one of them.
+///
+/// Each value is identified by the key returned when it was inserted. Keys
+/// of removed values may be reused by later insertions. The tree covers the
+/// bounds given at construction; items outside of them are still found by
+/// queries, but are not subdivided and so are tested on every query.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuadTree<T> {
+    nodes: Vec<Node>,
+    entries: Vec<Option<Entry<T>>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+impl<T> QuadTree<T> {
+    /// Constructs an empty tree covering `bounds`.
+    #[inline]
+    pub fn new(bounds: impl Into<Rectf>) -> QuadTree<T> {
+        QuadTree {
+            nodes: vec![Node::leaf(bounds.into().normalized(), 0)],
+            entries: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// The area covered by the tree.
+    #[inline]
+    pub fn bounds(&self) -> Rectf {
+        self.nodes[0].bounds
+    }
+
+    /// The number of values in the tree.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Determines if the tree has no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds a value covering `rect` to the tree, returning its key.
+    pub fn insert(&mut self, rect: impl Into<Rectf>, value: T) -> usize {
+        let rect = rect.into().normalized();
+        let entry = Some(Entry {
+            rect,
+            value,
+            node: 0,
+        });
+        let key = match self.free.pop() {
+            Some(key) => {
+                self.entries[key] = entry;
+                key
+            }
+            None => {
+                self.entries.push(entry);
+                self.entries.len() - 1
+            }
+        };
+
+        self.len += 1;
+        self.insert_at(0, key);
+        key
+    }
+
+    /// Removes the value with the given key, returning it and its rectangle.
+    /// Returns `None` if there is no such value.
+    pub fn remove(&mut self, key: usize) -> Option<(Rectf, T)> {
+        let entry = self.entries.get_mut(key)?.take()?;
+        let items = &mut self.nodes[entry.node].items;
+        let pos = items.iter().position(|&k| k == key).unwrap();
+        items.swap_remove(pos);
+        self.free.push(key);
+        self.len -= 1;
+        Some((entry.rect, entry.value))
+    }
+
+    /// The rectangle and value with the given key.
+    #[inline]
+    pub fn get(&self, key: usize) -> Option<(Rectf, &T)> {
+        match self.entries.get(key) {
+            Some(Some(entry)) => Some((entry.rect, &entry.value)),
+            _ => None,
+        }
+    }
+
+    /// The value with the given key, for modification. The rectangle cannot
+    /// be changed in place; remove and reinsert the value to move it.
+    #[inline]
+    pub fn get_mut(&mut self, key: usize) -> Option<&mut T> {
+        match self.entries.get_mut(key) {
+            Some(Some(entry)) => Some(&mut entry.value),
+            _ => None,
+        }
+    }
+
+    /// Iterates over the key, rectangle and value of every item in the tree,
+    /// ordered by key.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (usize, Rectf, &T)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(key, e)| e.as_ref().map(|e| (key, e.rect, &e.value)))
+    }
+
+    /// Finds every item whose rectangle overlaps `rect`, including those
+    /// which only touch its edges. The results are in no particular order.
+    pub fn query_rect(&self, rect: impl Into<Rectf>) -> Vec<(usize, &T)> {
+        let rect = rect.into().normalized();
+        self.query(|r| touches(r, &rect))
+    }
+
+    /// Finds every item whose rectangle contains `point`, including those
+    /// with the point on an edge. The results are in no particular order.
+    pub fn query_point(&self, point: impl Into<Point2f>) -> Vec<(usize, &T)> {
+        let point = point.into();
+        self.query(|r| r.contains_point(point))
+    }
+
+    /// Collects the items accepted by `filter`, skipping the nodes whose
+    /// bounds it rejects. Items outside the root are always tested.
+    fn query(&self, filter: impl Fn(&Rectf) -> bool) -> Vec<(usize, &T)> {
+        let mut results = Vec::new();
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if n != 0 && !filter(&node.bounds) {
+                continue;
+            }
+
+            for &key in &node.items {
+                let entry = self.entries[key].as_ref().unwrap();
+                if filter(&entry.rect) {
+                    results.push((key, &entry.value));
+                }
+            }
+            if let Some(first) = node.children {
+                stack.extend(first..first + 4);
+            }
+        }
+        results
+    }
+
+    fn insert_at(&mut self, mut n: usize, key: usize) {
+        let rect = self.entries[key].as_ref().unwrap().rect;
+        while let Some(first) = self.nodes[n].children {
+            match (first..first + 4).find(|&c| contains(&self.nodes[c].bounds, &rect)) {
+                Some(child) => n = child,
+                None => break,
+            }
+        }
+
+        self.entries[key].as_mut().unwrap().node = n;
+        let node = &mut self.nodes[n];
+        node.items.push(key);
+        if node.children.is_none() && node.items.len() > NODE_CAPACITY && node.depth < MAX_DEPTH {
+            self.split(n);
+        }
+    }
+
+    fn split(&mut self, n: usize) {
+        let (b, depth) = (self.nodes[n].bounds, self.nodes[n].depth + 1);
+        let c = b.center();
+        let first = self.nodes.len();
+        self.nodes.extend_from_slice(&[
+            Node::leaf(Rectf::new(b.left, b.top, c.x, c.y), depth),
+            Node::leaf(Rectf::new(c.x, b.top, b.right, c.y), depth),
+            Node::leaf(Rectf::new(b.left, c.y, c.x, b.bottom), depth),
+            Node::leaf(Rectf::new(c.x, c.y, b.right, b.bottom), depth),
+        ]);
+
+        let items = std::mem::take(&mut self.nodes[n].items);
+        self.nodes[n].children = Some(first);
+        for key in items {
+            self.insert_at(n, key);
+        }
+    }
+}
+
+/// Determines if `inner` lies entirely within `outer`.
+#[inline]
+fn contains(outer: &Rectf, inner: &Rectf) -> bool {
+    inner.left >= outer.left
+        && inner.top >= outer.top
+        && inner.right <= outer.right
+        && inner.bottom <= outer.bottom
+}
+
+/// Determines if two rectangles overlap or share an edge.
+#[inline]
+fn touches(a: &Rectf, b: &Rectf) -> bool {
+    a.left <= b.right && a.right >= b.left && a.top <= b.bottom && a.bottom >= b.top
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::quad_tree::QuadTree;
+    use crate::rectf::Rectf;
+
+    #[test]
+    fn queries() {
+        let mut tree = QuadTree::new(Rectf::new(0.0, 0.0, 100.0, 100.0));
+        let mut rects = Vec::new();
+        for i in 0..20 {
+            for j in 0..20 {
+                let (x, y) = (i as f32 * 5.0, j as f32 * 5.0);
+                let rect = Rectf::new(x, y, x + 3.0 + (i % 3) as f32, y + 2.0);
+                assert_eq!(tree.insert(rect, (i, j)), rects.len());
+                rects.push(rect);
+            }
+        }
+        // A large item straddling every quadrant, and one outside the bounds
+        let big = tree.insert(Rectf::new(10.0, 10.0, 90.0, 90.0), (100, 100));
+        let outside = tree.insert(Rectf::new(150.0, 150.0, 160.0, 160.0), (200, 200));
+        assert_eq!(tree.len(), 402);
+
+        let mut hits = tree.query_point((51.0, 51.0));
+        hits.sort_unstable();
+        assert_eq!(hits, vec![(210, &(10, 10)), (big, &(100, 100))]);
+        assert_eq!(
+            tree.query_point((155.0, 155.0)),
+            vec![(outside, &(200, 200))]
+        );
+        assert!(tree.query_point((4.0, 4.0)).is_empty());
+
+        let view = Rectf::new(20.0, 20.0, 40.0, 30.0);
+        let mut visible: Vec<_> = tree.query_rect(view).into_iter().map(|(k, _)| k).collect();
+        visible.sort_unstable();
+        let mut expected: Vec<_> = tree
+            .iter()
+            .filter(|&(_, r, _)| {
+                r.left <= 40.0 && r.right >= 20.0 && r.top <= 30.0 && r.bottom >= 20.0
+            })
+            .map(|(k, _, _)| k)
+            .collect();
+        expected.sort_unstable();
+        assert_eq!(visible, expected);
+        assert!(visible.contains(&big));
+
+        // Removed items are no longer found, and their keys are reused
+        assert_eq!(
+            tree.remove(big),
+            Some((Rectf::new(10.0, 10.0, 90.0, 90.0), (100, 100)))
+        );
+        assert_eq!(tree.remove(big), None);
+        assert_eq!(tree.query_point((51.0, 51.0)).len(), 1);
+        assert_eq!(tree.insert(Rectf::new(0.0, 0.0, 1.0, 1.0), (300, 300)), big);
+        *tree.get_mut(big).unwrap() = (301, 301);
+        assert_eq!(tree.query_point((0.5, 0.5)).len(), 2);
+        assert_eq!(
+            tree.get(big),
+            Some((Rectf::new(0.0, 0.0, 1.0, 1.0), &(301, 301)))
+        );
+        assert_eq!(tree.iter().count(), tree.len());
+    }
+}
@@ -0,0 +1,782 @@
+//! Rasterization of shapes onto the integer grid, for grid-based tools and
+//! simple software rendering.
+//!
+//! Pixels are addressed by the integer coordinates of their centers, so the
+//! pixel `(x, y)` covers the area from `x - 0.5` to `x + 0.5` horizontally.
+
+use crate::fill_rule::FillRule;
+use crate::point2f::Point2f;
+use crate::point2i::Point2i;
+
+use std::ops::Range;
+
+/// Iterates over the pixels of a line between two integer points with
+/// Bresenham's algorithm. Both end points are included, and consecutive
+/// pixels are always 8-connected. Created by `Point2i::line_to`.
+#[derive(Clone, Debug)]
+pub struct BresenhamLine {
+    current: Point2i,
+    end: Point2i,
+    // The differences between points of the grid may not fit in an i32
+    dx: i64,
+    dy: i64,
+    step: Point2i,
+    error: i64,
+    done: bool,
+}
+
+impl BresenhamLine {
+    /// Traces the line from `start` to `end`.
+    #[inline]
+    pub fn new(start: impl Into<Point2i>, end: impl Into<Point2i>) -> BresenhamLine {
+        let (start, end) = (start.into(), end.into());
+        let (x, y) = (
+            i64::from(end.x) - i64::from(start.x),
+            i64::from(end.y) - i64::from(start.y),
+        );
+        let (dx, dy) = (x.abs(), -y.abs());
+        BresenhamLine {
+            current: start,
+            end,
+            dx,
+            dy,
+            step: Point2i::new(x.signum() as i32, y.signum() as i32),
+            error: dx + dy,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for BresenhamLine {
+    type Item = Point2i;
+
+    #[inline]
+    fn next(&mut self) -> Option<Point2i> {
+        if self.done {
+            return None;
+        }
+
+        let point = self.current;
+        if point == self.end {
+            self.done = true;
+            return Some(point);
+        }
+
+        let e2 = 2 * self.error;
+        if e2 >= self.dy {
+            self.error += self.dy;
+            self.current.x += self.step.x;
+        }
+        if e2 <= self.dx {
+            self.error += self.dx;
+            self.current.y += self.step.y;
+        }
+        Some(point)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.done {
+            0
+        } else {
+            let rest_x = i64::from(self.end.x) - i64::from(self.current.x);
+            let rest_y = i64::from(self.end.y) - i64::from(self.current.y);
+            rest_x.abs().max(rest_y.abs()) as usize + 1
+        };
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for BresenhamLine {}
+
+/// Iterates over the pixels of an anti-aliased line with Xiaolin Wu's
+/// algorithm, yielding each pixel along with the fraction of it the line
+/// covers. Pixels are visited in order from one end of the line to the
+/// other, two at a time across the line's width, and pixels with no
+/// coverage are skipped. Created by `wu_line` or `Point2i::wu_line_to`.
+#[derive(Clone, Debug)]
+pub struct WuLine {
+    steep: bool,
+    gradient: f32,
+    /// The minor axis coordinate of the line at `x`.
+    intery: f32,
+    x: i32,
+    x_end: i32,
+    /// The coverage of the pixels at each end of the line.
+    caps: [[(i32, i32, f32); 2]; 2],
+    /// How many of the pixels at the start have been yielded, or 2 once the
+    /// interior has been reached.
+    start_index: usize,
+    end_index: usize,
+    /// The second pixel at the current position in the interior.
+    pending: Option<(i32, i32, f32)>,
+}
+
+/// Traces an anti-aliased line between two points with Xiaolin Wu's
+/// algorithm. The end points need not lie on pixel centers.
+pub fn wu_line(start: impl Into<Point2f>, end: impl Into<Point2f>) -> WuLine {
+    let (mut p0, mut p1) = (start.into(), end.into());
+    let steep = (p1.y - p0.y).abs() > (p1.x - p0.x).abs();
+    if steep {
+        p0 = Point2f::new(p0.y, p0.x);
+        p1 = Point2f::new(p1.y, p1.x);
+    }
+    if p0.x > p1.x {
+        std::mem::swap(&mut p0, &mut p1);
+    }
+
+    let (dx, dy) = (p1.x - p0.x, p1.y - p0.y);
+    let gradient = if dx == 0.0 { 1.0 } else { dy / dx };
+    let fpart = |v: f32| v - v.floor();
+
+    // Each end covers its pixel in proportion to how far the line reaches
+    // into it along the major axis.
+    let cap = |p: Point2f, gap: f32| {
+        let x = (p.x + 0.5).floor();
+        let y = p.y + gradient * (x - p.x);
+        let frac = fpart(y);
+        let (xi, yi) = (x as i32, y.floor() as i32);
+        (
+            xi,
+            y,
+            [(xi, yi, (1.0 - frac) * gap), (xi, yi + 1, frac * gap)],
+        )
+    };
+    let (x0, y0, first) = cap(p0, 1.0 - fpart(p0.x + 0.5));
+    let (x1, _, last) = cap(p1, fpart(p1.x + 0.5));
+
+    WuLine {
+        steep,
+        gradient,
+        intery: y0 + gradient,
+        x: x0 + 1,
+        x_end: x1,
+        caps: [first, last],
+        start_index: 0,
+        end_index: 0,
+        pending: None,
+    }
+}
+
+impl WuLine {
+    #[inline]
+    fn emit(&self, (x, y, coverage): (i32, i32, f32)) -> (Point2i, f32) {
+        if self.steep {
+            (Point2i::new(y, x), coverage)
+        } else {
+            (Point2i::new(x, y), coverage)
+        }
+    }
+
+    fn next_raw(&mut self) -> Option<(i32, i32, f32)> {
+        if self.start_index < 2 {
+            self.start_index += 1;
+            return Some(self.caps[0][self.start_index - 1]);
+        }
+        if let Some(pixel) = self.pending.take() {
+            return Some(pixel);
+        }
+        if self.x < self.x_end {
+            let (x, y) = (self.x, self.intery.floor());
+            let frac = self.intery - y;
+            self.pending = Some((x, y as i32 + 1, frac));
+            self.x += 1;
+            self.intery += self.gradient;
+            return Some((x, y as i32, 1.0 - frac));
+        }
+        if self.end_index < 2 {
+            self.end_index += 1;
+            return Some(self.caps[1][self.end_index - 1]);
+        }
+        None
+    }
+}
+
+impl Iterator for WuLine {
+    type Item = (Point2i, f32);
+
+    #[inline]
+    fn next(&mut self) -> Option<(Point2i, f32)> {
+        loop {
+            let pixel = self.next_raw()?;
+            if pixel.2 > 0.0 {
+                return Some(self.emit(pixel));
+            }
+        }
+    }
+}
+
+/// Iterates over the outline pixels of a circle with the midpoint circle
+/// algorithm. Each pixel is visited once, in no particular order. Created by
+/// `circle_outline`.
+#[derive(Clone, Debug)]
+pub struct CircleOutline {
+    center: Point2i,
+    /// The current point in the octant from the top of the circle to the
+    /// diagonal, relative to the center.
+    x: i32,
+    y: i32,
+    decision: i32,
+    reflections: Reflections,
+}
+
+/// Traces the outline of a circle with the given integer radius. A radius
+/// of zero gives just the center pixel, and negative radii give nothing.
+#[inline]
+pub fn circle_outline(center: impl Into<Point2i>, radius: i32) -> CircleOutline {
+    CircleOutline {
+        center: center.into(),
+        x: 0,
+        y: radius,
+        decision: 1 - radius,
+        reflections: Reflections::default(),
+    }
+}
+
+impl Iterator for CircleOutline {
+    type Item = Point2i;
+
+    fn next(&mut self) -> Option<Point2i> {
+        loop {
+            if let Some(point) = self.reflections.next() {
+                return Some(point);
+            }
+            if self.x > self.y {
+                return None;
+            }
+
+            let (x, y) = (self.x, self.y);
+            self.reflections.fill(self.center, x, y);
+            self.reflections.fill(self.center, y, x);
+
+            self.x += 1;
+            if self.decision < 0 {
+                self.decision += 2 * self.x + 1;
+            } else {
+                self.y -= 1;
+                self.decision += 2 * (self.x - self.y) + 1;
+            }
+        }
+    }
+}
+
+/// Iterates over the outline pixels of an axis-aligned ellipse with the
+/// midpoint ellipse algorithm. Each pixel is visited once, in no particular
+/// order. Created by `ellipse_outline`.
+#[derive(Clone, Debug)]
+pub struct EllipseOutline {
+    center: Point2i,
+    radius_x: i64,
+    rx2: i64,
+    ry2: i64,
+    /// The current point in the quadrant from the top of the ellipse to its
+    /// right end, relative to the center.
+    x: i64,
+    y: i64,
+    /// The partial derivatives of the ellipse equation at the current point,
+    /// which tell whether the outline is steeper than a diagonal.
+    px: i64,
+    py: i64,
+    /// Four times the ellipse equation at the next candidate midpoint.
+    decision: i64,
+    steep: bool,
+    reflections: Reflections,
+}
+
+/// Traces the outline of an axis-aligned ellipse with the given integer
+/// radii. Radii of zero give a line or just the center pixel, and negative
+/// radii give nothing.
+pub fn ellipse_outline(center: impl Into<Point2i>, radius_x: i32, radius_y: i32) -> EllipseOutline {
+    let (rx, ry) = (i64::from(radius_x), i64::from(radius_y));
+    let (rx2, ry2) = (rx * rx, ry * ry);
+    EllipseOutline {
+        center: center.into(),
+        radius_x: rx,
+        rx2,
+        ry2,
+        x: 0,
+        y: if radius_x < 0 { -1 } else { ry },
+        px: 0,
+        py: 2 * rx2 * ry,
+        decision: 4 * ry2 - 4 * rx2 * ry + rx2,
+        steep: false,
+        reflections: Reflections::default(),
+    }
+}
+
+impl Iterator for EllipseOutline {
+    type Item = Point2i;
+
+    fn next(&mut self) -> Option<Point2i> {
+        loop {
+            if let Some(point) = self.reflections.next() {
+                return Some(point);
+            }
+            if self.y < 0 {
+                return None;
+            }
+
+            if !self.steep && self.px >= self.py {
+                // Past the point where the outline is diagonal, step along y
+                // instead, starting from the midpoint beside the current one
+                let (x, y) = (2 * self.x + 1, self.y - 1);
+                self.decision = self.ry2 * x * x + 4 * self.rx2 * y * y - 4 * self.rx2 * self.ry2;
+                self.steep = true;
+            }
+
+            self.reflections
+                .fill(self.center, self.x as i32, self.y as i32);
+
+            if !self.steep {
+                self.x += 1;
+                self.px += 2 * self.ry2;
+                if self.decision < 0 {
+                    self.decision += 4 * (self.ry2 + self.px);
+                } else {
+                    self.y -= 1;
+                    self.py -= 2 * self.rx2;
+                    self.decision += 4 * (self.ry2 + self.px - self.py);
+                }
+            } else if self.y == 0 {
+                // Very flat ellipses reach their last row before their end,
+                // so finish the row to avoid leaving a gap at the tips
+                if self.x < self.radius_x {
+                    self.x += 1;
+                } else {
+                    self.y = -1;
+                }
+            } else {
+                self.y -= 1;
+                self.py -= 2 * self.rx2;
+                if self.decision > 0 {
+                    self.decision += 4 * (self.rx2 - self.py);
+                } else {
+                    self.x += 1;
+                    self.px += 2 * self.ry2;
+                    self.decision += 4 * (self.rx2 - self.py + self.px);
+                }
+            }
+        }
+    }
+}
+
+/// The distinct reflections of a point across the axes through a center,
+/// waiting to be yielded.
+#[derive(Copy, Clone, Debug, Default)]
+struct Reflections {
+    points: [Point2i; 8],
+    len: usize,
+    index: usize,
+}
+
+impl Reflections {
+    /// Queues the reflections of `(x, y)`, skipping any which are the same
+    /// pixel as one already queued.
+    #[inline]
+    fn fill(&mut self, center: Point2i, x: i32, y: i32) {
+        if self.index == self.len {
+            self.len = 0;
+            self.index = 0;
+        }
+        for &(sx, sy) in &[(1, 1), (-1, 1), (1, -1), (-1, -1)] {
+            let point = Point2i::new(center.x + sx * x, center.y + sy * y);
+            if !self.points[..self.len].contains(&point) {
+                self.points[self.len] = point;
+                self.len += 1;
+            }
+        }
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Point2i> {
+        if self.index < self.len {
+            self.index += 1;
+            Some(self.points[self.index - 1])
+        } else {
+            None
+        }
+    }
+}
+
+/// Iterates over the rows of a filled ellipse or circle, from top to bottom,
+/// as the `y` coordinate of the row and the range of `x` coordinates covered
+/// in it. Created by `ellipse_spans` or `circle_spans`.
+#[derive(Clone, Debug)]
+pub struct EllipseSpans {
+    center: Point2i,
+    /// The radii of the ellipse through the outermost pixel centers covered.
+    a: f64,
+    b: f64,
+    y: i32,
+    y_end: i32,
+}
+
+/// The rows of the pixels covered by a filled circle with the given integer
+/// radius, which are those whose centers lie within half a pixel past the
+/// radius. A radius of zero gives just the center pixel, and negative radii
+/// give nothing.
+#[inline]
+pub fn circle_spans(center: impl Into<Point2i>, radius: i32) -> EllipseSpans {
+    ellipse_spans(center, radius, radius)
+}
+
+/// The rows of the pixels covered by a filled axis-aligned ellipse with the
+/// given integer radii, which are those whose centers lie within half a pixel
+/// past the radii. Radii of zero give a line or just the center pixel, and
+/// negative radii give nothing.
+#[inline]
+pub fn ellipse_spans(center: impl Into<Point2i>, radius_x: i32, radius_y: i32) -> EllipseSpans {
+    let radius_y = if radius_x < 0 { -1 } else { radius_y };
+    EllipseSpans {
+        center: center.into(),
+        a: f64::from(radius_x) + 0.5,
+        b: f64::from(radius_y) + 0.5,
+        y: -radius_y,
+        y_end: radius_y,
+    }
+}
+
+impl Iterator for EllipseSpans {
+    type Item = (i32, Range<i32>);
+
+    #[inline]
+    fn next(&mut self) -> Option<(i32, Range<i32>)> {
+        if self.y > self.y_end {
+            return None;
+        }
+
+        let y = f64::from(self.y);
+        let a2 = self.a * self.a;
+        let half_width = (a2 - y * y * a2 / (self.b * self.b)).sqrt().floor() as i32;
+        let row = self.center.y + self.y;
+        self.y += 1;
+        let x = self.center.x;
+        Some((row, x - half_width..x + half_width + 1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.y_end - self.y + 1).max(0) as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for EllipseSpans {}
+
+/// Iterates over the rows of a filled polygon from top to bottom, as the
+/// `y` coordinate of a row and a range of `x` coordinates covered in it.
+/// Pixels are covered when their centers are inside the polygon, and rows
+/// with gaps produce more than one span. Created by `polygon_spans`,
+/// `Polygon::spans` or `Triangle::spans`.
+#[derive(Clone, Debug)]
+pub struct PolygonSpans {
+    fill_rule: FillRule,
+    /// The edges not yet reached, sorted by where they start.
+    edges: Vec<SpanEdge>,
+    next_edge: usize,
+    /// The edges crossing the current row.
+    active: Vec<SpanEdge>,
+    y: i32,
+    y_end: i32,
+    /// The rest of the spans in the current row, in reverse order.
+    row: Vec<Range<i32>>,
+    crossings: Vec<(f32, i32)>,
+}
+
+/// An edge running down the screen from `top` to `bottom`, counting
+/// `direction` toward the winding number of the points to its side.
+#[derive(Copy, Clone, Debug)]
+struct SpanEdge {
+    top: Point2f,
+    bottom: Point2f,
+    direction: i32,
+}
+
+/// The rows of the pixels covered by the closed polygon through `points`
+/// under the given fill rule.
+pub fn polygon_spans(points: &[Point2f], fill_rule: FillRule) -> PolygonSpans {
+    let n = points.len();
+    let mut edges: Vec<SpanEdge> = (0..n)
+        .filter_map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            if a.y < b.y {
+                Some(SpanEdge {
+                    top: a,
+                    bottom: b,
+                    direction: 1,
+                })
+            } else if a.y > b.y {
+                Some(SpanEdge {
+                    top: b,
+                    bottom: a,
+                    direction: -1,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+    edges.sort_by(|a, b| a.top.y.partial_cmp(&b.top.y).unwrap());
+
+    // Rows are covered from the first pixel center at or below the top of
+    // the polygon to the last one above its bottom
+    let top = edges.first().map_or(0.0, |e| e.top.y.ceil());
+    let bottom = edges.iter().map(|e| e.bottom.y).fold(top, f32::max).ceil();
+
+    PolygonSpans {
+        fill_rule,
+        edges,
+        next_edge: 0,
+        active: Vec::new(),
+        y: top as i32,
+        y_end: bottom as i32,
+        row: Vec::new(),
+        crossings: Vec::new(),
+    }
+}
+
+impl PolygonSpans {
+    /// Fills `row` with the spans of the current row.
+    fn scan_row(&mut self) {
+        let y = self.y as f32;
+        while self.next_edge < self.edges.len() && self.edges[self.next_edge].top.y <= y {
+            self.active.push(self.edges[self.next_edge]);
+            self.next_edge += 1;
+        }
+        self.active.retain(|e| e.bottom.y > y);
+
+        self.crossings.clear();
+        self.crossings
+            .extend(self.active.iter().filter(|e| e.top.y <= y).map(|e| {
+                let t = (y - e.top.y) / (e.bottom.y - e.top.y);
+                (e.top.x + (e.bottom.x - e.top.x) * t, e.direction)
+            }));
+        self.crossings
+            .sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let mut winding = 0;
+        let mut start = 0.0;
+        for &(x, direction) in &self.crossings {
+            let was_inside = self.fill_rule.is_inside(winding);
+            winding += direction;
+            match (was_inside, self.fill_rule.is_inside(winding)) {
+                (false, true) => start = x,
+                (true, false) => {
+                    let span = start.ceil() as i32..x.ceil() as i32;
+                    match self.row.last_mut() {
+                        Some(last) if last.end >= span.start => last.end = span.end,
+                        _ if span.start < span.end => self.row.push(span),
+                        _ => (),
+                    }
+                }
+                _ => (),
+            }
+        }
+        self.row.reverse();
+    }
+}
+
+impl Iterator for PolygonSpans {
+    type Item = (i32, Range<i32>);
+
+    fn next(&mut self) -> Option<(i32, Range<i32>)> {
+        loop {
+            if let Some(span) = self.row.pop() {
+                return Some((self.y - 1, span));
+            }
+            if self.y >= self.y_end {
+                return None;
+            }
+            self.scan_row();
+            self.y += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fill_rule::FillRule;
+    use crate::point2i::Point2i;
+    use crate::polygon::Polygon;
+    use crate::raster::{
+        circle_outline, circle_spans, ellipse_outline, ellipse_spans, wu_line, BresenhamLine,
+    };
+    use crate::triangle::Triangle;
+
+    #[test]
+    fn bresenham() {
+        let line: Vec<_> = Point2i::new(0, 0).line_to((5, 2)).collect();
+        let expected: Vec<Point2i> = vec![
+            (0, 0).into(),
+            (1, 0).into(),
+            (2, 1).into(),
+            (3, 1).into(),
+            (4, 2).into(),
+            (5, 2).into(),
+        ];
+        assert_eq!(line, expected);
+
+        // Lines are symmetric in every octant and always 8-connected
+        let reversed: Vec<_> = Point2i::new(5, 2).line_to((0, 0)).collect();
+        assert_eq!(reversed.len(), 6);
+        assert_eq!(reversed[0], (5, 2).into());
+        let steep = Point2i::new(3, -4).line_to((-1, 7));
+        assert_eq!(steep.len(), 12);
+        let steep: Vec<_> = steep.collect();
+        for w in steep.windows(2) {
+            let d = w[1] - w[0];
+            assert!(d.x.abs() <= 1 && d.y.abs() <= 1);
+        }
+        assert_eq!(*steep.last().unwrap(), (-1, 7).into());
+        assert_eq!(Point2i::new(2, 2).line_to((2, 2)).count(), 1);
+
+        // End points further apart than i32 can measure
+        let long = BresenhamLine::new((i32::MIN / 2, 0), (i32::MAX / 2, 1));
+        assert_eq!(long.len(), 1usize << 31);
+        let start: Vec<_> = long.take(3).collect();
+        assert_eq!(start[2], (i32::MIN / 2 + 2, 0).into());
+        let wide: Vec<_> = BresenhamLine::new((i32::MIN, i32::MAX), (i32::MAX, i32::MIN))
+            .take(2)
+            .collect();
+        assert_eq!(wide[1], (i32::MIN + 1, i32::MAX - 1).into());
+    }
+
+    #[test]
+    fn wu() {
+        // A horizontal line through pixel centers fully covers one row
+        let flat: Vec<_> = Point2i::new(0, 3).wu_line_to((4, 3)).collect();
+        assert_eq!(flat.len(), 5);
+        for (i, &(p, coverage)) in flat.iter().enumerate() {
+            assert_eq!(p, (i as i32, 3).into());
+            let expected = if i == 0 || i == 4 { 0.5 } else { 1.0 };
+            assert!((coverage - expected).abs() < 1e-6);
+        }
+
+        // A line halfway between rows splits its coverage between them
+        let split: Vec<_> = wu_line((0.0, 3.5), (4.0, 3.5)).collect();
+        let middle: Vec<_> = split.iter().filter(|(p, _)| p.x == 2).collect();
+        assert_eq!(middle.len(), 2);
+        assert!(middle.iter().all(|&&(_, c)| (c - 0.5).abs() < 1e-6));
+
+        // Steep lines cover one pixel's worth per row
+        let steep: Vec<_> = wu_line((1.0, 0.0), (2.0, 6.0)).collect();
+        for y in 1..6 {
+            let row: f32 = steep
+                .iter()
+                .filter(|(p, _)| p.y == y)
+                .map(|&(_, c)| c)
+                .sum();
+            assert!((row - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn circles() {
+        let outline: Vec<_> = circle_outline((10, -4), 5).collect();
+        for (i, p) in outline.iter().enumerate() {
+            assert!(!outline[i + 1..].contains(p));
+            let d = *p - Point2i::new(10, -4);
+            assert!((d.x * d.x + d.y * d.y - 25).abs() <= 5);
+        }
+        // Every outline pixel touches another, so the outline is closed
+        for p in &outline {
+            let neighbors = outline
+                .iter()
+                .filter(|q| *q != p && (q.x - p.x).abs() <= 1 && (q.y - p.y).abs() <= 1)
+                .count();
+            assert!(neighbors >= 2);
+        }
+        assert_eq!(
+            circle_outline((1, 1), 0).collect::<Vec<_>>(),
+            vec![(1, 1).into()]
+        );
+        assert_eq!(circle_outline((1, 1), -1).count(), 0);
+
+        let spans: Vec<_> = circle_spans((0, 0), 5).collect();
+        assert_eq!(spans.len(), 11);
+        assert_eq!(spans[0], (-5, -2..3));
+        assert_eq!(spans[5], (0, -5..6));
+        let area: i32 = spans.iter().map(|(_, r)| r.len() as i32).sum();
+        assert!((area as f32 - std::f32::consts::PI * 5.5 * 5.5).abs() < 8.0);
+        // The outline lies within the filled area
+        for p in &outline {
+            let (_, ref range) = spans[(p.y + 4 + 5) as usize];
+            assert!(range.contains(&(p.x - 10)));
+        }
+    }
+
+    #[test]
+    fn ellipses() {
+        let outline: Vec<_> = ellipse_outline((0, 0), 8, 3).collect();
+        for (i, p) in outline.iter().enumerate() {
+            assert!(!outline[i + 1..].contains(p));
+            let neighbors = outline
+                .iter()
+                .filter(|q| *q != p && (q.x - p.x).abs() <= 1 && (q.y - p.y).abs() <= 1)
+                .count();
+            assert!(neighbors >= 2);
+        }
+        for &end in &[(8, 0), (-8, 0), (0, 3), (0, -3)] {
+            assert!(outline.contains(&end.into()));
+        }
+
+        // Degenerate ellipses are lines
+        assert_eq!(ellipse_outline((0, 0), 3, 0).count(), 7);
+        assert_eq!(ellipse_outline((0, 0), 0, 2).count(), 5);
+        assert_eq!(ellipse_outline((0, 0), -1, 2).count(), 0);
+
+        let spans: Vec<_> = ellipse_spans((2, 2), 8, 3).collect();
+        assert_eq!(spans.len(), 7);
+        assert_eq!(spans[3], (2, -6..11));
+        assert_eq!(
+            ellipse_spans((0, 0), 3, 0).collect::<Vec<_>>(),
+            vec![(0, -3..4)]
+        );
+        assert_eq!(ellipse_spans((0, 0), -3, 2).count(), 0);
+    }
+
+    #[test]
+    fn polygon_spans() {
+        // Pixel centers on the top and left edges are covered, those on the
+        // bottom and right edges are not, so adjacent shapes never overlap
+        let square = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (4.0, 0.0).into(),
+            (4.0, 2.0).into(),
+            (0.0, 2.0).into(),
+        ]);
+        let spans: Vec<_> = square.spans(FillRule::NonZero).collect();
+        assert_eq!(spans, vec![(0, 0..4), (1, 0..4)]);
+
+        let tri = Triangle::from(((0.5, 0.5), (8.5, 0.5), (0.5, 8.5)));
+        let spans: Vec<_> = tri.spans().collect();
+        assert_eq!(spans.len(), 7);
+        for (i, (y, range)) in spans.into_iter().enumerate() {
+            assert_eq!(y, i as i32 + 1);
+            assert_eq!(range, 1..8 - i as i32);
+        }
+
+        // Overlapping loops differ between fill rules
+        let star = Polygon::new(vec![
+            (0.0, -10.0).into(),
+            (6.0, 8.0).into(),
+            (-9.5, -3.0).into(),
+            (9.5, -3.0).into(),
+            (-6.0, 8.0).into(),
+        ]);
+        let row = |rule| -> Vec<_> {
+            star.spans(rule)
+                .filter(|&(y, _)| y == 0)
+                .map(|(_, r)| r)
+                .collect()
+        };
+        assert_eq!(row(FillRule::NonZero).len(), 1);
+        assert_eq!(row(FillRule::EvenOdd).len(), 2);
+        let covered: usize = star.spans(FillRule::EvenOdd).map(|(_, r)| r.len()).sum();
+        let expected = (-10..8)
+            .flat_map(|y| (-10..10).map(move |x| (x, y)))
+            .filter(|&(x, y)| star.contains_point((x as f32, y as f32), FillRule::EvenOdd))
+            .count();
+        assert_eq!(covered, expected);
+    }
+}
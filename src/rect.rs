@@ -0,0 +1,516 @@
+//! Axis-aligned rectangle, generic over its component type.
+
+use crate::parse::{self, ParseError};
+use crate::point2::Point2;
+use crate::size::Size;
+use crate::vector2::Vector2;
+
+use num_traits::{AsPrimitive, Float, Num};
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+/// Represents a rectangle defined by the coordinates of the upper-left corner
+/// (left, top) and the coordinates of the lower-right corner (right, bottom),
+/// generic over the type of its components. The geometry is defined for any
+/// `num_traits::Num` component, and `Rectf`, `Rectd`, `Recti` and `Rectu`
+/// name the common component types.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde-compact",
+    serde(
+        into = "crate::compact::Array<[T; 4]>",
+        from = "crate::compact::Array<[T; 4]>",
+        bound(
+            serialize = "T: Clone + serde::Serialize",
+            deserialize = "T: serde::Deserialize<'de>"
+        )
+    )
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[repr(C)]
+pub struct Rect<T> {
+    /// The x-coordinate of the upper-left corner of the rectangle.
+    pub left: T,
+    /// The y-coordinate of the upper-left corner of the rectangle.
+    pub top: T,
+    /// The x-coordinate of the lower-right corner of the rectangle.
+    pub right: T,
+    /// The y-coordinate of the lower-right corner of the rectangle.
+    pub bottom: T,
+}
+
+/// Represents a corner of the rectangle
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum RectCorner {
+    /// The (left, top) coordinate pair
+    TopLeft,
+    /// The (right, top) coordinate pair
+    TopRight,
+    /// The (left, bottom) coordinate pair
+    BottomLeft,
+    /// The (right, bottom) coordinate pair
+    BottomRight,
+}
+
+impl<T> Rect<T> {
+    /// Constructs the rectangle from components.
+    #[inline]
+    pub fn new(left: T, top: T, right: T, bottom: T) -> Self {
+        Rect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Converts each component with `f`, such as to change to another
+    /// numeric type.
+    #[inline]
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Rect<U> {
+        Rect {
+            left: f(self.left),
+            top: f(self.top),
+            right: f(self.right),
+            bottom: f(self.bottom),
+        }
+    }
+
+    /// The rectangle with its left edge moved to `left`.
+    #[inline]
+    pub fn with_left(self, left: T) -> Self {
+        Rect { left, ..self }
+    }
+
+    /// The rectangle with its top edge moved to `top`.
+    #[inline]
+    pub fn with_top(self, top: T) -> Self {
+        Rect { top, ..self }
+    }
+
+    /// The rectangle with its right edge moved to `right`.
+    #[inline]
+    pub fn with_right(self, right: T) -> Self {
+        Rect { right, ..self }
+    }
+
+    /// The rectangle with its bottom edge moved to `bottom`.
+    #[inline]
+    pub fn with_bottom(self, bottom: T) -> Self {
+        Rect { bottom, ..self }
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> Rect<T> {
+    /// The distance from the left edge to the right.
+    #[inline]
+    pub fn width(&self) -> T {
+        self.right - self.left
+    }
+
+    /// The distance from the top edge to the bottom.
+    #[inline]
+    pub fn height(&self) -> T {
+        self.bottom - self.top
+    }
+}
+
+impl<T: Num + Copy + PartialOrd> Rect<T> {
+    /// Constructs a rectangle that will encompass all of the axis-aligned
+    /// space between the two provided points.
+    #[inline]
+    pub fn from_points(p1: impl Into<Point2<T>>, p2: impl Into<Point2<T>>) -> Self {
+        let p1 = p1.into();
+        let p2 = p2.into();
+        Rect {
+            left: min(p1.x, p2.x),
+            top: min(p1.y, p2.y),
+            right: max(p1.x, p2.x),
+            bottom: max(p1.y, p2.y),
+        }
+    }
+
+    /// Constructs a rectangle given its desired center point and desired
+    /// width and height.
+    #[inline]
+    pub fn from_center_size(center: impl Into<Point2<T>>, size: impl Into<Size<T>>) -> Self {
+        let size = size.into();
+        Rect::from_center_half_extent(center, size.to_vector() / two::<T>())
+    }
+
+    /// Constructs a rectangle given its desired center and the desired
+    /// distance from the center to the corners.
+    #[inline]
+    pub fn from_center_half_extent(
+        center: impl Into<Point2<T>>,
+        half_extents: impl Into<Vector2<T>>,
+    ) -> Self {
+        let center = center.into();
+        let half_extents = half_extents.into();
+        Rect {
+            left: center.x - half_extents.x,
+            top: center.y - half_extents.y,
+            right: center.x + half_extents.x,
+            bottom: center.y + half_extents.y,
+        }
+    }
+
+    /// Determines if the rectangle encloses no area, because its width or
+    /// height is zero or negative (or NaN). Rectangles which are not
+    /// normalized are degenerate.
+    #[inline]
+    pub fn is_degenerate(&self) -> bool {
+        !(self.right > self.left && self.bottom > self.top)
+    }
+
+    /// Gets the width and height of this rectangle.
+    #[inline]
+    pub fn size(&self) -> Size<T> {
+        Size::new(self.width(), self.height())
+    }
+
+    /// Gets the center point of this rectangle.
+    #[inline]
+    pub fn center(&self) -> Point2<T> {
+        Point2::new(
+            (self.left + self.right) / two(),
+            (self.top + self.bottom) / two(),
+        )
+    }
+
+    /// Gets the half-extent of the rectangle i.e. the vector from the
+    /// center to the most-positive corner.
+    #[inline]
+    pub fn half_extent(&self) -> Vector2<T> {
+        self.size().to_vector() / two::<T>()
+    }
+
+    /// Get the point of the specified corner.
+    #[inline]
+    pub fn corner(&self, corner: RectCorner) -> Point2<T> {
+        match corner {
+            RectCorner::TopLeft => Point2::new(self.left, self.top),
+            RectCorner::TopRight => Point2::new(self.right, self.top),
+            RectCorner::BottomLeft => Point2::new(self.left, self.bottom),
+            RectCorner::BottomRight => Point2::new(self.right, self.bottom),
+        }
+    }
+
+    /// Determines if the specified point is located inside the rectangle.
+    /// Points on the edges are inside.
+    #[inline]
+    pub fn contains_point(&self, point: impl Into<Point2<T>>) -> bool {
+        let point = point.into();
+        point.x >= self.left
+            && point.y >= self.top
+            && point.x <= self.right
+            && point.y <= self.bottom
+    }
+
+    /// Determines if two rects overlap at all. Rects which only share an
+    /// edge do not overlap.
+    #[inline]
+    pub fn overlaps(&self, other: &Rect<T>) -> bool {
+        let a = self.normalized();
+        let b = other.normalized();
+
+        a.left < b.right && a.right > b.left && a.top < b.bottom && a.bottom > b.top
+    }
+
+    /// Normalizes the rectangle to enforce the invariants
+    /// `left < right` and `top < bottom`.
+    #[inline]
+    pub fn normalized(self) -> Self {
+        Rect {
+            left: min(self.left, self.right),
+            top: min(self.top, self.bottom),
+            right: max(self.left, self.right),
+            bottom: max(self.top, self.bottom),
+        }
+    }
+
+    /// Translates the rectangle by the given vector.
+    #[inline]
+    pub fn translated_by(self, translation: impl Into<Vector2<T>>) -> Self {
+        self + translation.into()
+    }
+}
+
+impl<T: Float> Rect<T> {
+    /// Rounds the components to the nearest integers, rounding
+    /// half-way values away from zero.
+    #[inline]
+    pub fn rounded(self) -> Self {
+        self.map(T::round)
+    }
+}
+
+impl<T: Copy + 'static> Rect<T> {
+    /// Converts the components with `as`, so floats are truncated towards
+    /// zero and saturate at the limits of integer types. Round the
+    /// rectangle first if another behavior is desired.
+    #[inline]
+    pub fn cast<U: Copy + 'static>(self) -> Rect<U>
+    where
+        T: AsPrimitive<U>,
+    {
+        self.map(T::as_)
+    }
+
+    /// Converts the components to `f32`, as with `cast`.
+    #[inline]
+    pub fn to_f32(self) -> Rect<f32>
+    where
+        T: AsPrimitive<f32>,
+    {
+        self.cast()
+    }
+
+    /// Converts the components to `f64`, as with `cast`.
+    #[inline]
+    pub fn to_f64(self) -> Rect<f64>
+    where
+        T: AsPrimitive<f64>,
+    {
+        self.cast()
+    }
+
+    /// Converts the components to `i32`, as with `cast`.
+    #[inline]
+    pub fn to_i32(self) -> Rect<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast()
+    }
+
+    /// Converts the components to `u32`, as with `cast`. Negative
+    /// components become 0.
+    #[inline]
+    pub fn to_u32(self) -> Rect<u32>
+    where
+        T: AsPrimitive<u32>,
+    {
+        self.cast()
+    }
+}
+
+// Like `f32::min` and `f32::max`, these ignore a NaN argument, where the
+// comparison operators alone would depend on the argument order.
+
+#[inline]
+fn min<T: PartialOrd>(a: T, b: T) -> T {
+    #[allow(clippy::eq_op)]
+    if b < a || a != a {
+        b
+    } else {
+        a
+    }
+}
+
+#[inline]
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    #[allow(clippy::eq_op)]
+    if b > a || a != a {
+        b
+    } else {
+        a
+    }
+}
+
+#[inline]
+fn two<T: Num>() -> T {
+    T::one() + T::one()
+}
+
+impl<T: Num + Copy> Add<Vector2<T>> for Rect<T> {
+    type Output = Rect<T>;
+
+    #[inline]
+    fn add(self, rhs: Vector2<T>) -> Rect<T> {
+        Rect {
+            left: self.left + rhs.x,
+            top: self.top + rhs.y,
+            right: self.right + rhs.x,
+            bottom: self.bottom + rhs.y,
+        }
+    }
+}
+
+impl<T: Num + Copy> Sub<Vector2<T>> for Rect<T> {
+    type Output = Rect<T>;
+
+    #[inline]
+    fn sub(self, rhs: Vector2<T>) -> Rect<T> {
+        Rect {
+            left: self.left - rhs.x,
+            top: self.top - rhs.y,
+            right: self.right - rhs.x,
+            bottom: self.bottom - rhs.y,
+        }
+    }
+}
+
+impl<T: Num + Copy + PartialOrd> From<(Point2<T>, Point2<T>)> for Rect<T> {
+    #[inline]
+    fn from((p1, p2): (Point2<T>, Point2<T>)) -> Rect<T> {
+        Rect::from_points(p1, p2)
+    }
+}
+
+impl<T: Num + Copy + PartialOrd> From<(Point2<T>, Size<T>)> for Rect<T> {
+    #[inline]
+    fn from((center, size): (Point2<T>, Size<T>)) -> Rect<T> {
+        Rect::from_center_size(center, size)
+    }
+}
+
+impl<T: Num + Copy + PartialOrd> From<(Point2<T>, Vector2<T>)> for Rect<T> {
+    #[inline]
+    fn from((center, half_extent): (Point2<T>, Vector2<T>)) -> Rect<T> {
+        Rect::from_center_half_extent(center, half_extent)
+    }
+}
+
+impl<T> From<[T; 4]> for Rect<T> {
+    #[inline]
+    fn from([left, top, right, bottom]: [T; 4]) -> Rect<T> {
+        Rect::new(left, top, right, bottom)
+    }
+}
+
+/// A rectangle with double precision components.
+pub type Rectd = Rect<f64>;
+
+/// Widens each component exactly.
+impl From<Rect<f32>> for Rect<f64> {
+    #[inline]
+    fn from(r: Rect<f32>) -> Rect<f64> {
+        r.map(f64::from)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Rect<T> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let components = [&self.left, &self.top, &self.right, &self.bottom];
+        parse::write_components(fmt, ('[', ']'), &components)
+    }
+}
+
+impl<T: FromStr> FromStr for Rect<T> {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Rect<T>, ParseError> {
+        let [left, top, right, bottom] = parse::parse_components(s, ('[', ']'))?;
+        Ok(Rect {
+            left,
+            top,
+            right,
+            bottom,
+        })
+    }
+}
+
+/// `Box2D` stores the same two corners as `Rect`, so the conversion is
+/// exact in both directions.
+#[cfg(feature = "euclid")]
+impl<T, U> From<Rect<T>> for euclid::Box2D<T, U> {
+    #[inline]
+    fn from(r: Rect<T>) -> euclid::Box2D<T, U> {
+        euclid::Box2D::new(
+            euclid::Point2D::new(r.left, r.top),
+            euclid::Point2D::new(r.right, r.bottom),
+        )
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<T, U> From<euclid::Box2D<T, U>> for Rect<T> {
+    #[inline]
+    fn from(b: euclid::Box2D<T, U>) -> Rect<T> {
+        Rect {
+            left: b.min.x,
+            top: b.min.y,
+            right: b.max.x,
+            bottom: b.max.y,
+        }
+    }
+}
+
+/// euclid's `Rect` is an origin and a size, so converting to it costs a
+/// subtraction and may round for floating point components.
+#[cfg(feature = "euclid")]
+impl<T: Copy + Sub<Output = T>, U> From<Rect<T>> for euclid::Rect<T, U> {
+    #[inline]
+    fn from(r: Rect<T>) -> euclid::Rect<T, U> {
+        euclid::Rect::new(
+            euclid::Point2D::new(r.left, r.top),
+            euclid::Size2D::new(r.width(), r.height()),
+        )
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<T: Copy + std::ops::Add<Output = T>, U> From<euclid::Rect<T, U>> for Rect<T> {
+    #[inline]
+    fn from(r: euclid::Rect<T, U>) -> Rect<T> {
+        Rect {
+            left: r.origin.x,
+            top: r.origin.y,
+            right: r.origin.x + r.size.width,
+            bottom: r.origin.y + r.size.height,
+        }
+    }
+}
+
+// The struct is `#[repr(C)]` with every field of type `T`, so it has
+// the same layout as `[T; 4]`.
+#[cfg(feature = "encase")]
+impl<T: encase::vector::VectorScalar> encase::vector::AsRefVectorParts<T, 4> for Rect<T> {
+    #[inline]
+    fn as_ref_parts(&self) -> &[T; 4] {
+        unsafe { &*(self as *const Rect<T> as *const [T; 4]) }
+    }
+}
+
+#[cfg(feature = "encase")]
+impl<T: encase::vector::VectorScalar> encase::vector::AsMutVectorParts<T, 4> for Rect<T> {
+    #[inline]
+    fn as_mut_parts(&mut self) -> &mut [T; 4] {
+        unsafe { &mut *(self as *mut Rect<T> as *mut [T; 4]) }
+    }
+}
+
+#[cfg(feature = "encase")]
+impl<T: encase::vector::VectorScalar> encase::vector::FromVectorParts<T, 4> for Rect<T> {
+    #[inline]
+    fn from_parts([left, top, right, bottom]: [T; 4]) -> Rect<T> {
+        Rect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+}
+
+// Rectangles are laid out as a `vec4` of (left, top, right, bottom) in
+// shader buffers.
+#[cfg(feature = "encase")]
+encase::impl_vector!(4, Rect<T>; (T: encase::vector::VectorScalar));
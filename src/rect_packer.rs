@@ -0,0 +1,291 @@
+//! Allocation of rectangular regions within a fixed area, for texture atlases.
+
+use crate::recti::Recti;
+use crate::sizeu::Sizeu;
+
+use std::convert::TryFrom;
+
+/// Packs rectangles of requested sizes into a fixed size atlas, such as the
+/// glyphs or sprites sharing one texture, with the guillotine algorithm.
+///
+/// Each allocation is placed in the free rectangle it fits most tightly,
+/// and the rest of that rectangle is cut in two along the shorter leftover
+/// side. Deallocated regions go back to the free list, merging with free
+/// neighbors which share an entire edge with them, and freeing everything
+/// restores the whole atlas.
+///
+/// Allocations are identified by the key returned when they were made. Keys
+/// of deallocated regions may be reused by later allocations. Regions are
+/// half-open, so an allocation of size `(w, h)` at `(x, y)` is
+/// `Recti::new(x, y, x + w, y + h)`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RectPacker {
+    size: Sizeu,
+    free_rects: Vec<Recti>,
+    allocations: Vec<Option<Recti>>,
+    free_keys: Vec<usize>,
+    len: usize,
+    allocated_area: u64,
+}
+
+impl RectPacker {
+    /// Constructs an empty packer for an atlas of the given size.
+    #[inline]
+    pub fn new(size: impl Into<Sizeu>) -> RectPacker {
+        let size = size.into();
+        let mut packer = RectPacker {
+            size,
+            free_rects: Vec::new(),
+            allocations: Vec::new(),
+            free_keys: Vec::new(),
+            len: 0,
+            allocated_area: 0,
+        };
+        packer.clear();
+        packer
+    }
+
+    /// The size of the atlas.
+    #[inline]
+    pub fn size(&self) -> Sizeu {
+        self.size
+    }
+
+    /// The number of regions allocated.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Determines if nothing is allocated.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The total area of the allocated regions.
+    #[inline]
+    pub fn allocated_area(&self) -> u64 {
+        self.allocated_area
+    }
+
+    /// The fraction of the atlas which is allocated, between 0 and 1.
+    #[inline]
+    pub fn occupancy(&self) -> f32 {
+        let total = u64::from(self.size.width) * u64::from(self.size.height);
+        if total == 0 {
+            0.0
+        } else {
+            (self.allocated_area as f64 / total as f64) as f32
+        }
+    }
+
+    /// Allocates a region of the given size, returning its key and where it
+    /// was placed. Returns `None` if the size is empty or there is no free
+    /// space it fits in.
+    pub fn allocate(&mut self, size: impl Into<Sizeu>) -> Option<(usize, Recti)> {
+        let size = size.into();
+        if size.width == 0
+            || size.height == 0
+            || size.width > self.size.width
+            || size.height > self.size.height
+        {
+            return None;
+        }
+        let w = i32::try_from(size.width).ok()?;
+        let h = i32::try_from(size.height).ok()?;
+
+        // Best area fit, breaking ties by the shorter leftover side
+        let (index, _) = self
+            .free_rects
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.width() >= w && r.height() >= h)
+            .min_by_key(|(_, r)| {
+                let leftover = (r.width() - w).min(r.height() - h);
+                (r.area(), leftover)
+            })?;
+
+        let free = self.free_rects.swap_remove(index);
+        let rect = Recti::new(free.left, free.top, free.left + w, free.top + h);
+        let (right, bottom) = if free.width() - w < free.height() - h {
+            (
+                Recti::new(rect.right, free.top, free.right, rect.bottom),
+                Recti::new(free.left, rect.bottom, free.right, free.bottom),
+            )
+        } else {
+            (
+                Recti::new(rect.right, free.top, free.right, free.bottom),
+                Recti::new(free.left, rect.bottom, rect.right, free.bottom),
+            )
+        };
+        for split in [right, bottom].iter() {
+            if split.area() > 0 {
+                self.free_rects.push(*split);
+            }
+        }
+
+        let key = match self.free_keys.pop() {
+            Some(key) => {
+                self.allocations[key] = Some(rect);
+                key
+            }
+            None => {
+                self.allocations.push(Some(rect));
+                self.allocations.len() - 1
+            }
+        };
+        self.len += 1;
+        self.allocated_area += rect.area() as u64;
+        Some((key, rect))
+    }
+
+    /// The region allocated with the given key.
+    #[inline]
+    pub fn get(&self, key: usize) -> Option<Recti> {
+        self.allocations.get(key).cloned().flatten()
+    }
+
+    /// Iterates over the keys and regions of every allocation.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (usize, Recti)> + '_ {
+        self.allocations
+            .iter()
+            .enumerate()
+            .filter_map(|(key, rect)| rect.map(|rect| (key, rect)))
+    }
+
+    /// Frees the region allocated with the given key, returning it. Returns
+    /// `None` if there is no such allocation.
+    pub fn deallocate(&mut self, key: usize) -> Option<Recti> {
+        let rect = self.allocations.get_mut(key)?.take()?;
+        self.free_keys.push(key);
+        self.len -= 1;
+        self.allocated_area -= rect.area() as u64;
+        if self.len == 0 {
+            self.clear();
+            return Some(rect);
+        }
+
+        // Merging one pair can line the result up with another, so keep
+        // going until nothing merges
+        let mut merged = rect;
+        while let Some(i) = self
+            .free_rects
+            .iter()
+            .position(|free| merge_adjacent(&merged, free).is_some())
+        {
+            let free = self.free_rects.swap_remove(i);
+            merged = merge_adjacent(&merged, &free).unwrap();
+        }
+        self.free_rects.push(merged);
+        Some(rect)
+    }
+
+    /// Frees every allocation.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.free_rects.clear();
+        self.allocations.clear();
+        self.free_keys.clear();
+        self.len = 0;
+        self.allocated_area = 0;
+        if self.size.width > 0 && self.size.height > 0 {
+            // Regions are `Recti`s, so only the first i32::MAX pixels of an
+            // atlas larger than that can be handed out
+            let w = i32::try_from(self.size.width).unwrap_or(i32::MAX);
+            let h = i32::try_from(self.size.height).unwrap_or(i32::MAX);
+            self.free_rects.push(Recti::new(0, 0, w, h));
+        }
+    }
+}
+
+/// The union of two rectangles sharing an entire edge.
+#[inline]
+fn merge_adjacent(a: &Recti, b: &Recti) -> Option<Recti> {
+    let same_rows = a.top == b.top && a.bottom == b.bottom;
+    let same_columns = a.left == b.left && a.right == b.right;
+    if same_rows && (a.right == b.left || b.right == a.left) {
+        Some(Recti::new(
+            a.left.min(b.left),
+            a.top,
+            a.right.max(b.right),
+            a.bottom,
+        ))
+    } else if same_columns && (a.bottom == b.top || b.bottom == a.top) {
+        Some(Recti::new(
+            a.left,
+            a.top.min(b.top),
+            a.right,
+            a.bottom.max(b.bottom),
+        ))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rect_packer::RectPacker;
+    use crate::recti::Recti;
+
+    fn overlap(a: &Recti, b: &Recti) -> bool {
+        a.left < b.right && b.left < a.right && a.top < b.bottom && b.top < a.bottom
+    }
+
+    #[test]
+    fn packing() {
+        let mut packer = RectPacker::new((64, 64));
+        let mut rects = Vec::new();
+        for i in 0..24 {
+            let size = (4 + (i * 7) % 13, 4 + (i * 5) % 11);
+            let (key, rect) = packer.allocate(size).unwrap();
+            assert_eq!(packer.get(key), Some(rect));
+            assert_eq!((rect.width() as u32, rect.height() as u32), size);
+            assert!(rect.left >= 0 && rect.top >= 0 && rect.right <= 64 && rect.bottom <= 64);
+            rects.push(rect);
+        }
+        for (i, a) in rects.iter().enumerate() {
+            for b in &rects[i + 1..] {
+                assert!(!overlap(a, b));
+            }
+        }
+        let area: i64 = rects.iter().map(|r| r.area()).sum();
+        assert_eq!(packer.allocated_area(), area as u64);
+        assert_eq!(packer.len(), 24);
+        assert!(packer.allocate((65, 1)).is_none());
+        assert!(packer.allocate((0, 1)).is_none());
+
+        // Freed space is reused
+        let freed = packer.deallocate(5).unwrap();
+        let size = (freed.width() as u32, freed.height() as u32);
+        assert_eq!(packer.allocate(size), Some((5, freed)));
+
+        // Freeing everything merges the free space back into the whole atlas
+        let keys: Vec<_> = packer.iter().map(|(key, _)| key).collect();
+        for key in keys {
+            assert!(packer.deallocate(key).is_some());
+        }
+        assert!(packer.deallocate(0).is_none());
+        assert!(packer.is_empty());
+        assert_eq!(packer.occupancy(), 0.0);
+        let (_, whole) = packer.allocate((64, 64)).unwrap();
+        assert_eq!(whole, Recti::new(0, 0, 64, 64));
+        assert_eq!(packer.occupancy(), 1.0);
+    }
+
+    #[test]
+    fn oversized() {
+        // Sizes beyond i32::MAX must not wrap around to negative extents
+        let mut packer = RectPacker::new((64, 64));
+        assert!(packer.allocate((u32::MAX, 1)).is_none());
+        assert!(packer.allocate((1 << 31, 1)).is_none());
+        assert!(packer.allocate((1, u32::MAX)).is_none());
+        assert!(packer.is_empty());
+
+        let mut huge = RectPacker::new((u32::MAX, 1));
+        assert!(huge.allocate((u32::MAX, 1)).is_none());
+        assert!(huge.allocate((1 << 30, 1)).is_some());
+    }
+}
@@ -1,47 +1,26 @@
 //! Axis-aligned rectangle defined by the lines of its 4 edges.
 
-use crate::point2f::Point2f;
-use crate::recti::Recti;
-use crate::rectu::Rectu;
-use crate::sizef::Sizef;
+use crate::axis::Axis;
+use crate::dpi::Dpi;
+use crate::interval::Interval;
+use crate::rect::Rect;
 use crate::thicknessf::Thicknessf;
-use crate::vector2f::Vector2f;
+use crate::ulps::approx_eq_ulps;
 
 use std::f32::{INFINITY, NEG_INFINITY};
-use std::ops::{Add, Sub};
 
+#[cfg(any(feature = "rand", feature = "rayon"))]
+use crate::point2f::Point2f;
+#[cfg(feature = "rand")]
+use rand::Rng;
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::dcommon::D2D_RECT_F;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D::Common as d2d_common;
 
 /// Represents a rectangle defined by the coordinates of the upper-left corner
 /// (left, top) and the coordinates of the lower-right corner (right, bottom).
-#[derive(Copy, Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
-#[repr(C)]
-pub struct Rectf {
-    /// The x-coordinate of the left edge of the rectangle.
-    pub left: f32,
-    /// The y-coordinate of the top edge of the rectangle.
-    pub top: f32,
-    /// The x-coordinate of the right edge of the rectangle.
-    pub right: f32,
-    /// The y-coordinate of the bottom edge of the rectangle.
-    pub bottom: f32,
-}
-
-/// Represents a corner of the rectangle
-#[derive(Copy, Clone, Debug)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
-pub enum RectCorner {
-    /// The (left, top) coordinate pair
-    TopLeft,
-    /// The (right, top) coordinate pair
-    TopRight,
-    /// The (left, bottom) coordinate pair
-    BottomLeft,
-    /// The (right, bottom) coordinate pair
-    BottomRight,
-}
+pub type Rectf = Rect<f32>;
 
 impl Rectf {
     /// A rect that holds the entire real space
@@ -52,174 +31,92 @@ impl Rectf {
         bottom: INFINITY,
     };
 
-    /// Constructs the rectangle from components.
-    #[inline]
-    pub fn new(left: f32, top: f32, right: f32, bottom: f32) -> Rectf {
-        Rectf {
-            left,
-            top,
-            right,
-            bottom,
-        }
-    }
-
-    /// Constructs a rectangle that will encompass all of the axis-aligned
-    /// space between the two provided points.
-    #[inline]
-    pub fn from_points(p1: impl Into<Point2f>, p2: impl Into<Point2f>) -> Rectf {
-        let p1 = p1.into();
-        let p2 = p2.into();
-        Rectf {
-            left: p1.x.min(p2.x),
-            top: p1.y.min(p2.y),
-            right: p1.x.max(p2.x),
-            bottom: p1.y.max(p2.y),
-        }
-    }
-
-    /// Constructs a rectangle given its desired center point and desired
-    /// width and height.
+    /// Snaps the edges so that a stroke of `stroke_width` DIPs centered on
+    /// them covers whole device pixels at `dpi`, and a one pixel border
+    /// renders crisply instead of blurring across two rows of pixels. Edges
+    /// land on pixel boundaries for strokes an even number of pixels wide, and
+    /// half a pixel in, on pixel centers, for odd widths. The rounding is done
+    /// in device pixels, so it holds at any DPI.
+    ///
+    /// Each edge moves by at most half a pixel. The stroke width should also
+    /// be a whole number of pixels, and strokes thinner than a pixel are
+    /// treated as one pixel wide.
     #[inline]
-    pub fn from_center_size(center: impl Into<Point2f>, size: impl Into<Sizef>) -> Rectf {
-        let center = center.into();
-        let size = size.into();
+    pub fn snapped_for_stroke(&self, stroke_width: f32, dpi: impl Into<Dpi>) -> Rectf {
+        let scale = dpi.into().scale();
+        let snap = |edge: f32, scale: f32| {
+            let pixels = (stroke_width * scale).round().max(1.0);
+            let offset = if pixels % 2.0 == 1.0 { 0.5 } else { 0.0 };
+            ((edge * scale - offset).round() + offset) / scale
+        };
         Rectf {
-            left: center.x - size.width / 2.0,
-            top: center.y - size.height / 2.0,
-            right: center.x + size.width / 2.0,
-            bottom: center.y + size.height / 2.0,
+            left: snap(self.left, scale.x),
+            top: snap(self.top, scale.y),
+            right: snap(self.right, scale.x),
+            bottom: snap(self.bottom, scale.y),
         }
     }
 
-    /// Constructs a rectangle given its desired center and the desired
-    /// distance from the center to the corners.
+    /// Constructs the rectangle spanning a horizontal and a vertical
+    /// interval.
     #[inline]
-    pub fn from_center_half_extent(
-        center: impl Into<Point2f>,
-        half_extents: impl Into<Vector2f>,
-    ) -> Rectf {
-        let center = center.into();
-        let half_extents = half_extents.into();
+    pub fn from_intervals(x: Interval, y: Interval) -> Rectf {
         Rectf {
-            left: center.x - half_extents.x,
-            top: center.y - half_extents.y,
-            right: center.x + half_extents.x,
-            bottom: center.y + half_extents.y,
+            left: x.min,
+            top: y.min,
+            right: x.max,
+            bottom: y.max,
         }
     }
 
-    /// Converts this rectangle's components to signed integers. Truncates
-    /// values, perform manual rounding if you would like a different behavior.
+    /// The range of the rectangle along the x axis, from left to right.
     #[inline]
-    pub fn to_i32(&self) -> Recti {
-        Recti {
-            left: self.left as i32,
-            top: self.top as i32,
-            right: self.right as i32,
-            bottom: self.bottom as i32,
-        }
+    pub fn x_interval(&self) -> Interval {
+        Interval::new(self.left, self.right)
     }
 
-    /// Converts the components of the rectangle to unsigned integers. Beware
-    /// this conversion if the components could be negative, you will experience
-    /// unsigned casting underflow.
+    /// The range of the rectangle along the y axis, from top to bottom.
     #[inline]
-    pub fn to_u32(&self) -> Rectu {
-        Rectu {
-            left: self.left as u32,
-            top: self.top as u32,
-            right: self.right as u32,
-            bottom: self.bottom as u32,
-        }
+    pub fn y_interval(&self) -> Interval {
+        Interval::new(self.top, self.bottom)
     }
 
-    /// Rounds the components to the nearest integers, rounding
-    /// half-way values away from zero.
+    /// Constructs the rectangle spanning `along` on `axis` and `across` on
+    /// the other axis.
     #[inline]
-    pub fn rounded(&self) -> Rectf {
-        Rectf {
-            left: self.left.round(),
-            top: self.top.round(),
-            right: self.right.round(),
-            bottom: self.bottom.round(),
+    pub fn from_extents(axis: Axis, along: Interval, across: Interval) -> Rectf {
+        match axis {
+            Axis::X => Rectf::from_intervals(along, across),
+            Axis::Y => Rectf::from_intervals(across, along),
         }
     }
 
-    /// Gets the width and height of this rectangle.
-    #[inline]
-    pub fn size(&self) -> Sizef {
-        (self.right - self.bottom, self.bottom - self.top).into()
-    }
-
-    /// Gets the center point of this rectangle.
+    /// The range of the rectangle along `axis`, as with `x_interval` and
+    /// `y_interval`.
     #[inline]
-    pub fn center(&self) -> Point2f {
-        Point2f {
-            x: (self.left + self.right) / 2.0,
-            y: (self.top + self.bottom) / 2.0,
+    pub fn extent(&self, axis: Axis) -> Interval {
+        match axis {
+            Axis::X => self.x_interval(),
+            Axis::Y => self.y_interval(),
         }
     }
 
-    /// Gets the half-extent of the rectangle i.e. the vector from the
-    /// center to the most-positive corner.
+    /// Replaces the left and right edges for `Axis::X`, or the top and
+    /// bottom edges for `Axis::Y`.
     #[inline]
-    pub fn half_extent(&self) -> Vector2f {
-        let size = self.size();
-        [size.width / 2.0, size.height / 2.0].into()
+    pub fn set_extent(&mut self, axis: Axis, extent: Interval) {
+        *self = Rectf::from_extents(axis, extent, self.extent(axis.other()));
     }
 
-    /// Get the point of the specified corner.
+    /// Determines if the edges of two rects are each at most `max_ulps`
+    /// representable values apart, which scales the tolerance with the
+    /// magnitude of the coordinates. See `math2d::ulps`.
     #[inline]
-    pub fn corner(&self, corner: RectCorner) -> Point2f {
-        match corner {
-            RectCorner::TopLeft => (self.left, self.top).into(),
-            RectCorner::TopRight => (self.right, self.top).into(),
-            RectCorner::BottomLeft => (self.left, self.bottom).into(),
-            RectCorner::BottomRight => (self.right, self.bottom).into(),
-        }
-    }
-
-    /// Determines if the specified point is located inside the rectangle.
-    #[inline]
-    pub fn contains_point(&self, point: impl Into<Point2f>) -> bool {
-        let point = point.into();
-        return point.x >= self.left
-            && point.y >= self.top
-            && point.x <= self.right
-            && point.y <= self.bottom;
-    }
-
-    /// Determines if two rects overlap at all
-    #[inline]
-    pub fn overlaps(&self, other: &Rectf) -> bool {
-        let a = self.normalized();
-        let b = other.normalized();
-
-        a.left < b.right && a.right > b.left && a.top < b.bottom && a.bottom > b.top
-    }
-
-    /// Normalizes the rectangle to enforce the invariants
-    /// `left < right` and `top < bottom`.
-    #[inline]
-    pub fn normalized(self) -> Self {
-        Rectf {
-            left: self.left.min(self.right),
-            top: self.top.min(self.bottom),
-            right: self.left.max(self.right),
-            bottom: self.top.max(self.bottom),
-        }
-    }
-
-    /// Translates the rectangle by the given vector.
-    #[inline]
-    pub fn translated_by(self, translation: impl Into<Vector2f>) -> Self {
-        let trans = translation.into();
-        Rectf {
-            left: self.left + trans.x,
-            top: self.top + trans.y,
-            right: self.right + trans.x,
-            bottom: self.bottom + trans.y,
-        }
+    pub fn is_approx_eq_ulps(&self, other: &Rectf, max_ulps: u32) -> bool {
+        approx_eq_ulps(self.left, other.left, max_ulps)
+            && approx_eq_ulps(self.top, other.top, max_ulps)
+            && approx_eq_ulps(self.right, other.right, max_ulps)
+            && approx_eq_ulps(self.bottom, other.bottom, max_ulps)
     }
 
     /// Expands the rectangle by the given margin.
@@ -253,68 +150,177 @@ impl Rectf {
         let r1 = self.normalized();
         let r2 = other.into().normalized();
 
-        let left = r1.left.min(r2.left);
-        let top = r1.top.min(r2.top);
-        let right = r1.right.max(r2.right);
-        let bottom = r1.bottom.max(r2.bottom);
+        Rectf::from_intervals(
+            r1.x_interval().union(&r2.x_interval()),
+            r1.y_interval().union(&r2.y_interval()),
+        )
+    }
+
+    /// Picks a point inside the rectangle at random, with every part of its
+    /// area equally likely.
+    #[cfg(feature = "rand")]
+    #[inline]
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point2f {
+        Point2f {
+            x: self.left + (self.right - self.left) * rng.gen::<f32>(),
+            y: self.top + (self.bottom - self.top) * rng.gen::<f32>(),
+        }
+    }
+
+    /// Formats the `x`, `y`, `width` and `height` attributes of an SVG
+    /// `<rect>` element covering this rectangle. The rectangle is normalized
+    /// first, as SVG does not allow a negative width or height.
+    pub fn to_svg_attributes(&self) -> String {
+        let r = self.normalized();
+        format!(
+            r#"x="{}" y="{}" width="{}" height="{}""#,
+            r.left,
+            r.top,
+            r.width(),
+            r.height()
+        )
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl Rectf {
+    /// The smallest rectangle containing every point in the slice, computed
+    /// on the rayon thread pool. An empty slice gives a zero-sized box at the
+    /// origin.
+    #[inline]
+    pub fn par_bounding(points: &[Point2f]) -> Rectf {
+        crate::polygon::par_point_bounds(points)
+    }
+}
+
+#[cfg(feature = "cairo")]
+impl Rectf {
+    /// Appends the rectangle to the current path of `cr` as a closed
+    /// sub-path.
+    #[inline]
+    pub fn append_to_cairo(&self, cr: &cairo::Context) {
+        cr.rectangle(
+            self.left.into(),
+            self.top.into(),
+            (self.right - self.left).into(),
+            (self.bottom - self.top).into(),
+        );
+    }
+}
 
+#[cfg(feature = "tiny-skia")]
+impl Rectf {
+    /// Converts the rectangle to a tiny-skia rectangle, which must be
+    /// normalized, finite, and have a positive width and height. Returns
+    /// `None` for any other rectangle.
+    #[inline]
+    pub fn to_tiny_skia(&self) -> Option<tiny_skia::Rect> {
+        tiny_skia::Rect::from_ltrb(self.left, self.top, self.right, self.bottom)
+    }
+}
+
+#[cfg(feature = "tiny-skia")]
+impl From<tiny_skia::Rect> for Rectf {
+    #[inline]
+    fn from(rect: tiny_skia::Rect) -> Rectf {
         Rectf {
-            left,
-            top,
-            right,
-            bottom,
+            left: rect.left(),
+            top: rect.top(),
+            right: rect.right(),
+            bottom: rect.bottom(),
         }
     }
 }
 
-impl Add<Vector2f> for Rectf {
-    type Output = Rectf;
+#[cfg(feature = "web")]
+impl Rectf {
+    /// Constructs a DOM rectangle covering the same area. This fails with
+    /// the exception thrown by the constructor, e.g. when there is no DOM.
+    #[inline]
+    pub fn to_dom_rect(&self) -> Result<web_sys::DomRect, web_sys::wasm_bindgen::JsValue> {
+        web_sys::DomRect::new_with_x_and_y_and_width_and_height(
+            self.left.into(),
+            self.top.into(),
+            self.width().into(),
+            self.height().into(),
+        )
+    }
+}
 
+/// DOM rectangles may have a negative width or height, which carries over
+/// as a rectangle that is not normalized.
+#[cfg(feature = "web")]
+impl<'a> From<&'a web_sys::DomRect> for Rectf {
     #[inline]
-    fn add(self, rhs: Vector2f) -> Rectf {
-        self.translated_by(rhs)
+    fn from(rect: &'a web_sys::DomRect) -> Rectf {
+        Rectf {
+            left: rect.x() as f32,
+            top: rect.y() as f32,
+            right: (rect.x() + rect.width()) as f32,
+            bottom: (rect.y() + rect.height()) as f32,
+        }
     }
 }
 
-impl Sub<Vector2f> for Rectf {
-    type Output = Rectf;
+/// The rectangle as its (left, top) and (right, bottom) corners, the usual
+/// min/max pair for bounding boxes in glam.
+#[cfg(feature = "kurbo")]
+impl From<Rectf> for kurbo::Rect {
+    #[inline]
+    fn from(r: Rectf) -> kurbo::Rect {
+        kurbo::Rect::new(r.left.into(), r.top.into(), r.right.into(), r.bottom.into())
+    }
+}
 
+#[cfg(feature = "kurbo")]
+impl From<kurbo::Rect> for Rectf {
     #[inline]
-    fn sub(self, rhs: Vector2f) -> Rectf {
-        self.translated_by(-rhs)
+    fn from(r: kurbo::Rect) -> Rectf {
+        Rectf {
+            left: r.x0 as f32,
+            top: r.y0 as f32,
+            right: r.x1 as f32,
+            bottom: r.y1 as f32,
+        }
     }
 }
 
-impl From<(Point2f, Point2f)> for Rectf {
+#[cfg(feature = "glam")]
+impl From<Rectf> for (glam::Vec2, glam::Vec2) {
     #[inline]
-    fn from((p1, p2): (Point2f, Point2f)) -> Rectf {
-        Rectf::from_points(p1, p2)
+    fn from(rect: Rectf) -> (glam::Vec2, glam::Vec2) {
+        (
+            glam::Vec2::new(rect.left, rect.top),
+            glam::Vec2::new(rect.right, rect.bottom),
+        )
     }
 }
 
-impl From<(Point2f, Sizef)> for Rectf {
+#[cfg(feature = "glam")]
+impl From<(glam::Vec2, glam::Vec2)> for Rectf {
     #[inline]
-    fn from((center, size): (Point2f, Sizef)) -> Rectf {
-        Rectf::from_center_size(center, size)
+    fn from((p1, p2): (glam::Vec2, glam::Vec2)) -> Rectf {
+        Rectf::from_points(p1, p2)
     }
 }
 
-impl From<(Point2f, Vector2f)> for Rectf {
+#[cfg(feature = "skia")]
+impl From<Rectf> for skia_safe::Rect {
     #[inline]
-    fn from((center, half_extent): (Point2f, Vector2f)) -> Rectf {
-        Rectf::from_center_half_extent(center, half_extent)
+    fn from(rect: Rectf) -> skia_safe::Rect {
+        skia_safe::Rect::new(rect.left, rect.top, rect.right, rect.bottom)
     }
 }
 
-impl From<[f32; 4]> for Rectf {
+#[cfg(feature = "skia")]
+impl From<skia_safe::Rect> for Rectf {
     #[inline]
-    fn from(p: [f32; 4]) -> Rectf {
-        let (left, top, right, bottom) = (p[0], p[1], p[2], p[3]);
+    fn from(rect: skia_safe::Rect) -> Rectf {
         Rectf {
-            left,
-            top,
-            right,
-            bottom,
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
         }
     }
 }
@@ -345,6 +351,32 @@ impl From<D2D_RECT_F> for Rectf {
     }
 }
 
+#[cfg(all(windows, feature = "windows"))]
+impl From<Rectf> for d2d_common::D2D_RECT_F {
+    #[inline]
+    fn from(rect: Rectf) -> d2d_common::D2D_RECT_F {
+        d2d_common::D2D_RECT_F {
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<d2d_common::D2D_RECT_F> for Rectf {
+    #[inline]
+    fn from(rect: d2d_common::D2D_RECT_F) -> Rectf {
+        Rectf {
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
+        }
+    }
+}
+
 #[cfg(all(test, windows, feature = "d2d"))]
 #[test]
 fn rectf_d2d_bin_compat() {
@@ -363,3 +395,22 @@ fn rectf_d2d_bin_compat() {
     assert!(ptr_eq(&rect.bottom, &d2d.bottom));
     assert_eq!(size_of_val(&rect), size_of_val(d2d));
 }
+
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn rectf_windows_bin_compat() {
+    use std::mem::size_of_val;
+
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
+    }
+
+    let rect = Rectf::new(0.0, 0.0, 0.0, 0.0);
+    let d2d = unsafe { &*((&rect) as *const _ as *const d2d_common::D2D_RECT_F) };
+
+    assert!(ptr_eq(&rect.left, &d2d.left));
+    assert!(ptr_eq(&rect.top, &d2d.top));
+    assert!(ptr_eq(&rect.right, &d2d.right));
+    assert!(ptr_eq(&rect.bottom, &d2d.bottom));
+    assert_eq!(size_of_val(&rect), size_of_val(d2d));
+}
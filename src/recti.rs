@@ -1,29 +1,29 @@
 //! Axis-aligned rectangle defined by the lines of its 4 edges.
 
 use crate::point2i::Point2i;
+use crate::rect::Rect;
 use crate::rectf::Rectf;
-use crate::rectu::Rectu;
 
+#[cfg(all(windows, feature = "d2d"))]
+use winapi::shared::windef::RECTL;
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::dcommon::D2D_RECT_L;
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::wincodec::WICRect;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Foundation as foundation;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Imaging as imaging;
 
 /// Represents a rectangle defined by the coordinates of the upper-left corner
 /// (left, top) and the coordinates of the lower-right corner (right, bottom).
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
-#[repr(C)]
-pub struct Recti {
-    /// The x-coordinate of the upper-left corner of the rectangle.
-    pub left: i32,
-    /// The y-coordinate of the upper-left corner of the rectangle.
-    pub top: i32,
-    /// The x-coordinate of the lower-right corner of the rectangle.
-    pub right: i32,
-    /// The y-coordinate of the lower-right corner of the rectangle.
-    pub bottom: i32,
-}
+///
+/// The Win32 `RECT` and `RECTL` conversions copy the edges unchanged, so
+/// `width()` and `height()` agree with GDI. GDI treats the right and bottom
+/// edges as exclusive when deciding which pixels a rectangle covers, while
+/// `contains_point`, `rows` and `columns` include them; use `contains_pixel`
+/// for the GDI behaviour.
+pub type Recti = Rect<i32>;
 
 impl Recti {
     /// An inversely empty rectangle that contains no points
@@ -34,17 +34,6 @@ impl Recti {
         bottom: std::i32::MIN,
     };
 
-    /// Constructs the rectangle from components.
-    #[inline]
-    pub fn new(left: i32, top: i32, right: i32, bottom: i32) -> Recti {
-        Recti {
-            left,
-            top,
-            right,
-            bottom,
-        }
-    }
-
     #[inline]
     pub fn point(point: impl Into<Point2i>) -> Self {
         let point = point.into();
@@ -56,38 +45,13 @@ impl Recti {
         }
     }
 
-    /// Converts the rectangle to floating point values.
-    #[inline]
-    pub fn to_f32(&self) -> Rectf {
-        Rectf {
-            left: self.left as f32,
-            top: self.top as f32,
-            right: self.right as f32,
-            bottom: self.bottom as f32,
-        }
-    }
-
-    /// Converts the components of the rectangle to unsigned integers. Beware
-    /// this conversion if the components could be negative, you will experience
-    /// unsigned casting underflow.
+    /// Determines if the pixel at the specified point is covered by the
+    /// rectangle under the GDI convention, where the right and bottom edges
+    /// are exclusive. This is the test `PtInRect` performs.
     #[inline]
-    pub fn to_u32(&self) -> Rectu {
-        Rectu {
-            left: self.left as u32,
-            top: self.top as u32,
-            right: self.right as u32,
-            bottom: self.bottom as u32,
-        }
-    }
-
-    /// Determines if the specified point is located inside the rectangle.
-    #[inline]
-    pub fn contains_point(&self, point: impl Into<Point2i>) -> bool {
+    pub fn contains_pixel(&self, point: impl Into<Point2i>) -> bool {
         let point = point.into();
-        return point.x >= self.left
-            && point.y >= self.top
-            && point.x <= self.right
-            && point.y <= self.bottom;
+        point.x >= self.left && point.y >= self.top && point.x < self.right && point.y < self.bottom
     }
 
     #[inline]
@@ -99,18 +63,6 @@ impl Recti {
             || point.y == self.bottom;
     }
 
-    /// Normalizes the rectangle to enforce the invariants
-    /// `left < right` and `top < bottom`.
-    #[inline]
-    pub fn normalized(self) -> Self {
-        Recti {
-            left: self.left.min(self.right),
-            top: self.top.min(self.bottom),
-            right: self.left.max(self.top),
-            bottom: self.top.max(self.bottom),
-        }
-    }
-
     /// Constructs a rectangle that contains both rectangles. Normalizes
     /// both arguments before performing the operation.
     #[inline]
@@ -130,16 +82,6 @@ impl Recti {
         }
     }
 
-    #[inline]
-    pub fn width(&self) -> i32 {
-        self.right - self.left
-    }
-
-    #[inline]
-    pub fn height(&self) -> i32 {
-        self.bottom - self.top
-    }
-
     #[inline]
     pub fn area(&self) -> i64 {
         let width = self.width() as i64;
@@ -164,6 +106,13 @@ impl Recti {
     }
 }
 
+impl From<Recti> for Rectf {
+    #[inline]
+    fn from(rect: Recti) -> Rectf {
+        rect.to_f32()
+    }
+}
+
 impl From<Point2i> for Recti {
     #[inline]
     fn from(point: Point2i) -> Recti {
@@ -223,6 +172,158 @@ impl From<Recti> for WICRect {
     }
 }
 
+#[cfg(all(windows, feature = "windows"))]
+impl From<Recti> for foundation::RECT {
+    #[inline]
+    fn from(rect: Recti) -> foundation::RECT {
+        foundation::RECT {
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<foundation::RECT> for Recti {
+    #[inline]
+    fn from(rect: foundation::RECT) -> Recti {
+        Recti {
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<imaging::WICRect> for Recti {
+    #[inline]
+    fn from(rect: imaging::WICRect) -> Recti {
+        Recti {
+            left: rect.X,
+            top: rect.Y,
+            right: rect.X + rect.Width,
+            bottom: rect.Y + rect.Height,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<Recti> for imaging::WICRect {
+    #[inline]
+    fn from(rect: Recti) -> imaging::WICRect {
+        imaging::WICRect {
+            X: rect.left,
+            Y: rect.top,
+            Width: rect.width(),
+            Height: rect.height(),
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "d2d"))]
+impl From<Recti> for RECTL {
+    #[inline]
+    fn from(rect: Recti) -> RECTL {
+        RECTL {
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "d2d"))]
+impl From<RECTL> for Recti {
+    #[inline]
+    fn from(rect: RECTL) -> Recti {
+        Recti {
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<Recti> for foundation::RECTL {
+    #[inline]
+    fn from(rect: Recti) -> foundation::RECTL {
+        foundation::RECTL {
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<foundation::RECTL> for Recti {
+    #[inline]
+    fn from(rect: foundation::RECTL) -> Recti {
+        Recti {
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl Recti {
+    /// Parallel version of `points`, which splits the rectangle up by rows.
+    /// The points are not produced in any particular order.
+    #[inline]
+    pub fn par_points(self) -> impl rayon::iter::ParallelIterator<Item = Point2i> {
+        use rayon::prelude::*;
+
+        (self.top..=self.bottom)
+            .into_par_iter()
+            .flat_map_iter(move |row| self.columns().map(move |col| (col, row).into()))
+    }
+}
+
+#[cfg(feature = "sdl2")]
+impl Recti {
+    /// Converts the rectangle to an SDL rectangle, which stores a position
+    /// and a size. SDL cannot represent an empty rectangle, so this returns
+    /// `None` for rectangles without a positive width and height.
+    #[inline]
+    pub fn to_sdl2(&self) -> Option<sdl2::rect::Rect> {
+        if self.right <= self.left || self.bottom <= self.top {
+            return None;
+        }
+        Some(sdl2::rect::Rect::new(
+            self.left,
+            self.top,
+            self.width() as u32,
+            self.height() as u32,
+        ))
+    }
+}
+
+/// SDL's right and bottom edges lie one past the last covered pixel, like
+/// GDI's, so `width()` and `height()` carry over unchanged.
+#[cfg(feature = "sdl2")]
+impl From<sdl2::rect::Rect> for Recti {
+    #[inline]
+    fn from(rect: sdl2::rect::Rect) -> Recti {
+        Recti {
+            left: rect.left(),
+            top: rect.top(),
+            right: rect.right(),
+            bottom: rect.bottom(),
+        }
+    }
+}
+
 #[cfg(all(test, windows, feature = "d2d"))]
 #[test]
 fn recti_d2d_bin_compat() {
@@ -241,3 +342,56 @@ fn recti_d2d_bin_compat() {
     assert!(ptr_eq(&rect.bottom, &d2d.bottom));
     assert_eq!(size_of_val(&rect), size_of_val(d2d));
 }
+
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn recti_windows_bin_compat() {
+    use std::mem::size_of_val;
+
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
+    }
+
+    let rect = Recti::new(0, 0, 0, 0);
+    let d2d = unsafe { &*((&rect) as *const _ as *const foundation::RECT) };
+
+    assert!(ptr_eq(&rect.left, &d2d.left));
+    assert!(ptr_eq(&rect.top, &d2d.top));
+    assert!(ptr_eq(&rect.right, &d2d.right));
+    assert!(ptr_eq(&rect.bottom, &d2d.bottom));
+    assert_eq!(size_of_val(&rect), size_of_val(d2d));
+}
+
+#[cfg(all(test, feature = "sdl2"))]
+#[test]
+fn sdl2_conversions() {
+    use crate::color::Color;
+    use crate::point2i::Point2i;
+
+    let rect = Recti::new(-2, 3, 8, 7);
+    let sdl = rect.to_sdl2().unwrap();
+    assert_eq!(
+        (sdl.x(), sdl.y(), sdl.width(), sdl.height()),
+        (-2, 3, 10, 4)
+    );
+    assert_eq!(Recti::from(sdl), rect);
+    assert_eq!(Recti::new(1, 1, 1, 5).to_sdl2(), None);
+
+    let point = Point2i::new(4, -9);
+    assert_eq!(Point2i::from(sdl2::rect::Point::from(point)), point);
+
+    let color = sdl2::pixels::Color::from(Color::new(1.0, 0.5, 0.0, 2.0));
+    assert_eq!(color, sdl2::pixels::Color::RGBA(255, 128, 0, 255));
+    assert_eq!(Color::from(color).r, 1.0);
+}
+
+#[cfg(all(test, feature = "rayon"))]
+#[test]
+fn par_points() {
+    use rayon::iter::ParallelIterator;
+
+    let rect = Recti::new(-3, 2, 40, 90);
+    let mut points: Vec<Point2i> = rect.par_points().collect();
+    points.sort_by_key(|p| (p.y, p.x));
+    assert_eq!(points, rect.points().collect::<Vec<_>>());
+}
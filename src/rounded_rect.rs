@@ -2,10 +2,13 @@
 
 use crate::ellipse::Ellipse;
 use crate::point2f::Point2f;
-use crate::rectf::{RectCorner, Rectf};
+use crate::rect::RectCorner;
+use crate::rectf::Rectf;
 
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::d2d1::D2D1_ROUNDED_RECT;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D as d2d;
 
 /// Represents a rectangle with rounded corners described by ellipses that
 /// touch the internal edges of the rectangle at the tangent points.
@@ -14,7 +17,13 @@ use winapi::um::d2d1::D2D1_ROUNDED_RECT;
 ///      alt="Diagram visualizing the meaning of the structure components"
 ///      style="max-width: 350px" />
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(C)]
 pub struct RoundedRect {
     /// The overall rectangle containing this rounded rectangle
@@ -36,6 +45,13 @@ impl RoundedRect {
         }
     }
 
+    /// Determines if the rectangle of the rounded rectangle is degenerate.
+    /// Zero radii only make the corners square.
+    #[inline]
+    pub fn is_degenerate(&self) -> bool {
+        self.rect.is_degenerate()
+    }
+
     /// Gets the ellipse that resides in the given corner of the rectangle
     #[inline]
     pub fn corner_ellipse(&self, corner: RectCorner) -> Ellipse {
@@ -84,6 +100,27 @@ impl RoundedRect {
     pub fn contains_point_crude(&self, point: impl Into<Point2f>) -> bool {
         self.rect.contains_point(point)
     }
+
+    /// Formats the attributes of an SVG `<rect>` element with rounded
+    /// corners matching this rectangle.
+    pub fn to_svg_attributes(&self) -> String {
+        format!(
+            r#"{} rx="{}" ry="{}""#,
+            self.rect.to_svg_attributes(),
+            self.radius_x.abs(),
+            self.radius_y.abs()
+        )
+    }
+}
+
+#[cfg(feature = "cairo")]
+impl RoundedRect {
+    /// Appends the rounded rectangle to the current path of `cr` as a closed
+    /// sub-path, with the corners drawn as cubic bezier curves.
+    #[inline]
+    pub fn append_to_cairo(&self, cr: &cairo::Context) {
+        crate::path::Path::from(*self).append_to_cairo(cr);
+    }
 }
 
 impl<R> From<(R, f32, f32)> for RoundedRect
@@ -96,6 +133,65 @@ where
     }
 }
 
+/// kurbo's corners are circular, so the smaller of the two radii is used for
+/// every corner. The result fits inside the original shape.
+#[cfg(feature = "kurbo")]
+impl From<RoundedRect> for kurbo::RoundedRect {
+    #[inline]
+    fn from(rr: RoundedRect) -> kurbo::RoundedRect {
+        let radius = rr.radius_x.min(rr.radius_y);
+        kurbo::RoundedRect::from_rect(rr.rect.into(), f64::from(radius))
+    }
+}
+
+/// kurbo allows a different radius in each corner, while corners here all
+/// share one ellipse, so the smallest of the four radii is used.
+#[cfg(feature = "kurbo")]
+impl From<kurbo::RoundedRect> for RoundedRect {
+    #[inline]
+    fn from(rr: kurbo::RoundedRect) -> RoundedRect {
+        let r = rr.radii();
+        let radius = r
+            .top_left
+            .min(r.top_right)
+            .min(r.bottom_right)
+            .min(r.bottom_left) as f32;
+        RoundedRect::new(rr.rect(), radius, radius)
+    }
+}
+
+#[cfg(feature = "skia")]
+impl From<RoundedRect> for skia_safe::RRect {
+    #[inline]
+    fn from(rr: RoundedRect) -> skia_safe::RRect {
+        skia_safe::RRect::new_rect_xy(skia_safe::Rect::from(rr.rect), rr.radius_x, rr.radius_y)
+    }
+}
+
+/// Skia allows different radii in each corner, while corners here all share
+/// one ellipse, so the smallest x and y radii of the four corners are used.
+#[cfg(feature = "skia")]
+impl From<skia_safe::RRect> for RoundedRect {
+    #[inline]
+    fn from(rr: skia_safe::RRect) -> RoundedRect {
+        use skia_safe::rrect::Corner;
+
+        let corners = [
+            Corner::UpperLeft,
+            Corner::UpperRight,
+            Corner::LowerRight,
+            Corner::LowerLeft,
+        ];
+        let (rx, ry) = corners
+            .iter()
+            .fold((f32::INFINITY, f32::INFINITY), |(rx, ry), &c| {
+                let radii = rr.radii(c);
+                (rx.min(radii.x), ry.min(radii.y))
+            });
+        RoundedRect::new(*rr.rect(), rx, ry)
+    }
+}
+
 #[cfg(all(windows, feature = "d2d"))]
 impl From<RoundedRect> for D2D1_ROUNDED_RECT {
     #[inline]
@@ -120,6 +216,30 @@ impl From<D2D1_ROUNDED_RECT> for RoundedRect {
     }
 }
 
+#[cfg(all(windows, feature = "windows"))]
+impl From<RoundedRect> for d2d::D2D1_ROUNDED_RECT {
+    #[inline]
+    fn from(rect: RoundedRect) -> d2d::D2D1_ROUNDED_RECT {
+        d2d::D2D1_ROUNDED_RECT {
+            rect: rect.rect.into(),
+            radiusX: rect.radius_x,
+            radiusY: rect.radius_y,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<d2d::D2D1_ROUNDED_RECT> for RoundedRect {
+    #[inline]
+    fn from(rect: d2d::D2D1_ROUNDED_RECT) -> RoundedRect {
+        RoundedRect {
+            rect: rect.rect.into(),
+            radius_x: rect.radiusX,
+            radius_y: rect.radiusY,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::rounded_rect::RoundedRect;
@@ -0,0 +1,303 @@
+//! Signed distance functions for shapes, and combinators for building
+//! compound shapes out of them.
+
+use crate::capsule::Capsule;
+use crate::circle::Circle;
+use crate::ellipse::Ellipse;
+use crate::point2f::Point2f;
+use crate::polygon::{self, Polygon};
+use crate::rectf::Rectf;
+use crate::rounded_rect::RoundedRect;
+use crate::triangle::Triangle;
+use crate::vector2f::Vector2f;
+
+/// A shape described by its signed distance function: the distance from a
+/// point to the nearest edge of the shape, negative for points inside it.
+///
+/// Thresholding the distance gives soft or enlarged hit zones (e.g.
+/// `signed_distance(p) < 4.0` accepts clicks up to 4 units outside), its
+/// absolute value gives outlines, and its gradient can drive anti-aliasing
+/// or custom shading. Shapes combine with `union`, `intersection` and
+/// `subtract`, whose results are exact outside and bounds elsewhere, which
+/// is still enough for hit testing and outlines.
+pub trait Sdf {
+    /// The signed distance from `point` to the edge of the shape.
+    fn signed_distance(&self, point: Point2f) -> f32;
+
+    /// The area covered by either shape.
+    #[inline]
+    fn union<S: Sdf>(self, other: S) -> Union<Self, S>
+    where
+        Self: Sized,
+    {
+        Union(self, other)
+    }
+
+    /// The area covered by both shapes.
+    #[inline]
+    fn intersection<S: Sdf>(self, other: S) -> Intersection<Self, S>
+    where
+        Self: Sized,
+    {
+        Intersection(self, other)
+    }
+
+    /// The area of this shape which is not covered by `other`.
+    #[inline]
+    fn subtract<S: Sdf>(self, other: S) -> Subtraction<Self, S>
+    where
+        Self: Sized,
+    {
+        Subtraction(self, other)
+    }
+}
+
+/// The union of two shapes, created by `Sdf::union`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Union<A, B>(pub A, pub B);
+
+/// The intersection of two shapes, created by `Sdf::intersection`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Intersection<A, B>(pub A, pub B);
+
+/// The first shape with the second cut out of it, created by
+/// `Sdf::subtract`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Subtraction<A, B>(pub A, pub B);
+
+impl<A: Sdf, B: Sdf> Sdf for Union<A, B> {
+    #[inline]
+    fn signed_distance(&self, point: Point2f) -> f32 {
+        self.0
+            .signed_distance(point)
+            .min(self.1.signed_distance(point))
+    }
+}
+
+impl<A: Sdf, B: Sdf> Sdf for Intersection<A, B> {
+    #[inline]
+    fn signed_distance(&self, point: Point2f) -> f32 {
+        self.0
+            .signed_distance(point)
+            .max(self.1.signed_distance(point))
+    }
+}
+
+impl<A: Sdf, B: Sdf> Sdf for Subtraction<A, B> {
+    #[inline]
+    fn signed_distance(&self, point: Point2f) -> f32 {
+        self.0
+            .signed_distance(point)
+            .max(-self.1.signed_distance(point))
+    }
+}
+
+impl<T: Sdf + ?Sized> Sdf for &T {
+    #[inline]
+    fn signed_distance(&self, point: Point2f) -> f32 {
+        (**self).signed_distance(point)
+    }
+}
+
+impl<T: Sdf + ?Sized> Sdf for Box<T> {
+    #[inline]
+    fn signed_distance(&self, point: Point2f) -> f32 {
+        (**self).signed_distance(point)
+    }
+}
+
+/// The signed distance to an origin-centered box with the given half size,
+/// shrunk by `radius` and then rounded by it.
+#[inline]
+fn box_distance(p: Vector2f, half_extent: Vector2f, radius: f32) -> f32 {
+    let q = Vector2f::new(p.x.abs(), p.y.abs()) - half_extent + [radius, radius];
+    let outside = Vector2f::new(q.x.max(0.0), q.y.max(0.0)).len();
+    outside + q.x.max(q.y).min(0.0) - radius
+}
+
+impl Sdf for Circle {
+    #[inline]
+    fn signed_distance(&self, point: Point2f) -> f32 {
+        (point - self.center).len() - self.radius
+    }
+}
+
+impl Sdf for Rectf {
+    #[inline]
+    fn signed_distance(&self, point: Point2f) -> f32 {
+        let rect = self.normalized();
+        box_distance(point - rect.center(), rect.half_extent(), 0.0)
+    }
+}
+
+impl Sdf for RoundedRect {
+    /// Rounded rectangles with elliptical corners are measured as if their
+    /// corners were circles with the smaller of the two radii.
+    #[inline]
+    fn signed_distance(&self, point: Point2f) -> f32 {
+        let rect = self.rect.normalized();
+        let half = rect.half_extent();
+        let radius = self
+            .radius_x
+            .abs()
+            .min(self.radius_y.abs())
+            .min(half.x)
+            .min(half.y);
+        box_distance(point - rect.center(), half, radius)
+    }
+}
+
+impl Sdf for Capsule {
+    #[inline]
+    fn signed_distance(&self, point: Point2f) -> f32 {
+        (point - self.closest_center_point(point)).len() - self.radius
+    }
+}
+
+impl Sdf for Ellipse {
+    /// Ellipses have no closed form distance, so this uses a first order
+    /// approximation which is exact on the edge and at the center, and
+    /// accurate nearby. Its sign is always correct. An ellipse with a zero
+    /// radius is the line segment (or point) it collapses to, and measures the
+    /// exact distance to that.
+    #[inline]
+    fn signed_distance(&self, point: Point2f) -> f32 {
+        let (rx, ry) = (self.radius_x.abs(), self.radius_y.abs());
+        if rx == 0.0 || ry == 0.0 {
+            let half = Vector2f::new(rx, ry);
+            let segment = Capsule::new(self.center - half, self.center + half, 0.0);
+            return segment.signed_distance(point);
+        }
+        let p = point - self.center;
+        let k0 = Vector2f::new(p.x / rx, p.y / ry).len();
+        let k1 = Vector2f::new(p.x / (rx * rx), p.y / (ry * ry)).len();
+        if k1 == 0.0 {
+            -rx.min(ry)
+        } else {
+            k0 * (k0 - 1.0) / k1
+        }
+    }
+}
+
+impl Sdf for Triangle {
+    #[inline]
+    fn signed_distance(&self, point: Point2f) -> f32 {
+        points_distance(&[self.p1, self.p2, self.p3], point)
+    }
+}
+
+impl Sdf for Polygon {
+    /// Points are inside the polygon according to the even-odd fill rule.
+    #[inline]
+    fn signed_distance(&self, point: Point2f) -> f32 {
+        points_distance(&self.points, point)
+    }
+}
+
+/// The signed distance to the closed polygon through `points`.
+fn points_distance(points: &[Point2f], point: Point2f) -> f32 {
+    let dist_sq = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(&a, &b)| {
+            let capsule = Capsule::new(a, b, 0.0);
+            (point - capsule.closest_center_point(point)).len_squared()
+        })
+        .fold(f32::INFINITY, f32::min);
+
+    let inside = polygon::winding_number(points, point) % 2 != 0;
+    if inside {
+        -dist_sq.sqrt()
+    } else {
+        dist_sq.sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::capsule::Capsule;
+    use crate::circle::Circle;
+    use crate::ellipse::Ellipse;
+    use crate::polygon::Polygon;
+    use crate::rectf::Rectf;
+    use crate::rounded_rect::RoundedRect;
+    use crate::sdf::Sdf;
+
+    fn approx(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn shapes() {
+        let circle = Circle::new((0.0, 0.0), 2.0);
+        assert!(approx(circle.signed_distance((5.0, 0.0).into()), 3.0));
+        assert!(approx(circle.signed_distance((0.0, 0.0).into()), -2.0));
+
+        let rect = Rectf::new(0.0, 0.0, 4.0, 2.0);
+        assert!(approx(rect.signed_distance((2.0, 1.0).into()), -1.0));
+        assert!(approx(rect.signed_distance((7.0, 6.0).into()), 5.0));
+        assert!(approx(rect.signed_distance((2.0, -3.0).into()), 3.0));
+
+        let rr = RoundedRect::new(rect, 1.0, 1.0);
+        assert!(approx(rr.signed_distance((-1.0, 1.0).into()), 1.0));
+        let corner = 2.0f32.sqrt() - 1.0;
+        assert!(approx(rr.signed_distance((0.0, 0.0).into()), corner));
+
+        let capsule = Capsule::new((0.0, 0.0), (10.0, 0.0), 1.0);
+        assert!(approx(capsule.signed_distance((5.0, 3.0).into()), 2.0));
+        assert!(approx(capsule.signed_distance((-3.0, 0.0).into()), 2.0));
+
+        // Exact on the axes, right signed everywhere
+        let ellipse = Ellipse::new((0.0, 0.0), 4.0, 2.0);
+        assert!(approx(ellipse.signed_distance((4.0, 0.0).into()), 0.0));
+        assert!(approx(ellipse.signed_distance((0.0, 2.0).into()), 0.0));
+        assert!(ellipse.signed_distance((1.0, 1.0).into()) < 0.0);
+        assert!(approx(ellipse.signed_distance((0.0, 0.0).into()), -2.0));
+        let outside = ellipse.signed_distance((6.0, 0.0).into());
+        assert!(outside > 1.5 && outside <= 2.0);
+
+        // Degenerate ellipses measure to the segment or point they collapse to
+        let point = Ellipse::new((0.0, 0.0), 0.0, 0.0);
+        assert!(approx(point.signed_distance((1.0, 0.0).into()), 1.0));
+        let flat = Ellipse::new((0.0, 0.0), 3.0, 0.0);
+        assert!(approx(flat.signed_distance((1.0, 2.0).into()), 2.0));
+        assert!(approx(flat.signed_distance((7.0, 0.0).into()), 4.0));
+        let tall = Ellipse::new((1.0, 1.0), 0.0, -2.0);
+        assert!(approx(tall.signed_distance((1.0, 1.0).into()), 0.0));
+        assert!(approx(tall.signed_distance((4.0, 2.0).into()), 3.0));
+
+        let square = Polygon::new(vec![
+            (0.0, 0.0).into(),
+            (4.0, 0.0).into(),
+            (4.0, 4.0).into(),
+            (0.0, 4.0).into(),
+        ]);
+        assert!(approx(square.signed_distance((1.0, 2.0).into()), -1.0));
+        assert!(approx(square.signed_distance((7.0, 8.0).into()), 5.0));
+    }
+
+    #[test]
+    fn combinators() {
+        let a = Circle::new((0.0, 0.0), 2.0);
+        let b = Circle::new((3.0, 0.0), 2.0);
+
+        let union = a.union(b);
+        assert!(union.signed_distance((-1.0, 0.0).into()) < 0.0);
+        assert!(union.signed_distance((4.0, 0.0).into()) < 0.0);
+
+        let lens = a.intersection(b);
+        assert!(lens.signed_distance((1.5, 0.0).into()) < 0.0);
+        assert!(lens.signed_distance((-1.0, 0.0).into()) > 0.0);
+
+        let bite = a.subtract(&b);
+        assert!(bite.signed_distance((-1.0, 0.0).into()) < 0.0);
+        assert!(bite.signed_distance((1.5, 0.0).into()) > 0.0);
+
+        let shapes: Vec<Box<dyn Sdf>> = vec![Box::new(a), Box::new(Rectf::new(5.0, 5.0, 6.0, 6.0))];
+        let nearest = shapes
+            .iter()
+            .map(|s| s.signed_distance((5.5, 4.0).into()))
+            .fold(f32::INFINITY, f32::min);
+        assert!(approx(nearest, 1.0));
+    }
+}
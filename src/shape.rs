@@ -0,0 +1,396 @@
+//! A common interface for hit testing, measuring, and transforming every
+//! kind of shape, and an enum to hold any of them.
+
+use crate::bounded::Bounded;
+use crate::circle::Circle;
+use crate::ellipse::Ellipse;
+use crate::geometry_group::{Geometry, GeometryGroup};
+use crate::hit_test::HitTest;
+use crate::matrix3x2f::Matrix3x2f;
+use crate::oriented_ellipse::OrientedEllipse;
+use crate::oriented_rect::OrientedRect;
+use crate::path::Path;
+use crate::polygon::Polygon;
+use crate::rectf::Rectf;
+use crate::rounded_rect::RoundedRect;
+use crate::transformable::Transformable;
+use crate::triangle::Triangle;
+
+/// The tolerance used to flatten curves when a shape must be hit tested
+/// without one being specified. This matches Direct2D's default flattening
+/// tolerance of a quarter of a DIP.
+pub const DEFAULT_FLATTENING_TOLERANCE: f32 = 0.25;
+
+/// Operations common to every closed shape, so that hit testing and culling
+/// code can be written once for all of them. The trait is object safe, so
+/// shapes may also be used as `dyn Shape2D`. Bounding boxes come from the
+/// `Bounded` supertrait, which open curves and segments implement as well,
+/// and hit testing comes from the `HitTest` supertrait.
+///
+/// Several shapes have inherent methods with different signatures under the
+/// same names. Those take precedence in method call syntax, so use
+/// `Shape2D::transformed(&shape, matrix)` to call the trait method
+/// explicitly.
+pub trait Shape2D: Bounded + HitTest {
+    /// Applies an affine transformation to the shape, producing what
+    /// `Transformable` does. Rectangles and ellipses which are still
+    /// axis-aligned afterwards (e.g. under scaling and translation) are
+    /// stored as `Rect` and `Ellipse` rather than their oriented forms.
+    fn transformed(&self, matrix: &Matrix3x2f) -> AnyShape;
+
+    /// Converts the shape into an equivalent path.
+    fn to_path(&self) -> Path;
+}
+
+/// Any one of the crate's shape types, for heterogeneous collections.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum AnyShape {
+    /// An axis-aligned rectangle.
+    Rect(Rectf),
+    /// An axis-aligned rectangle with rounded corners.
+    RoundedRect(RoundedRect),
+    /// An axis-aligned ellipse.
+    Ellipse(Ellipse),
+    /// A rectangle which has been rotated or skewed.
+    OrientedRect(OrientedRect),
+    /// An ellipse which has been rotated.
+    OrientedEllipse(OrientedEllipse),
+    /// A triangle.
+    Triangle(Triangle),
+    /// A polygon with straight edges.
+    Polygon(Polygon),
+    /// An arbitrary path.
+    Path(Path),
+    /// Several geometries filled together.
+    Group(GeometryGroup),
+}
+
+impl AnyShape {
+    /// Borrows the contained shape as a trait object.
+    #[inline]
+    pub fn as_shape(&self) -> &dyn Shape2D {
+        match *self {
+            AnyShape::Rect(ref s) => s,
+            AnyShape::RoundedRect(ref s) => s,
+            AnyShape::Ellipse(ref s) => s,
+            AnyShape::OrientedRect(ref s) => s,
+            AnyShape::OrientedEllipse(ref s) => s,
+            AnyShape::Triangle(ref s) => s,
+            AnyShape::Polygon(ref s) => s,
+            AnyShape::Path(ref s) => s,
+            AnyShape::Group(ref s) => s,
+        }
+    }
+}
+
+impl Shape2D for AnyShape {
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> AnyShape {
+        self.as_shape().transformed(matrix)
+    }
+
+    #[inline]
+    fn to_path(&self) -> Path {
+        self.as_shape().to_path()
+    }
+}
+
+impl From<Rectf> for AnyShape {
+    #[inline]
+    fn from(s: Rectf) -> AnyShape {
+        AnyShape::Rect(s)
+    }
+}
+
+impl From<RoundedRect> for AnyShape {
+    #[inline]
+    fn from(s: RoundedRect) -> AnyShape {
+        AnyShape::RoundedRect(s)
+    }
+}
+
+impl From<Ellipse> for AnyShape {
+    #[inline]
+    fn from(s: Ellipse) -> AnyShape {
+        AnyShape::Ellipse(s)
+    }
+}
+
+impl From<Circle> for AnyShape {
+    /// Circles are stored as ellipses with equal radii.
+    #[inline]
+    fn from(s: Circle) -> AnyShape {
+        AnyShape::Ellipse(s.into())
+    }
+}
+
+impl From<OrientedRect> for AnyShape {
+    /// Rectangles which are still axis-aligned are stored as `Rect`.
+    #[inline]
+    fn from(s: OrientedRect) -> AnyShape {
+        match s.to_rect() {
+            Some(rect) => AnyShape::Rect(rect),
+            None => AnyShape::OrientedRect(s),
+        }
+    }
+}
+
+impl From<OrientedEllipse> for AnyShape {
+    /// Ellipses which are still axis-aligned are stored as `Ellipse`.
+    #[inline]
+    fn from(s: OrientedEllipse) -> AnyShape {
+        match s.to_ellipse() {
+            Some(e) => AnyShape::Ellipse(e),
+            None => AnyShape::OrientedEllipse(s),
+        }
+    }
+}
+
+impl From<Triangle> for AnyShape {
+    #[inline]
+    fn from(s: Triangle) -> AnyShape {
+        AnyShape::Triangle(s)
+    }
+}
+
+impl From<Polygon> for AnyShape {
+    #[inline]
+    fn from(s: Polygon) -> AnyShape {
+        AnyShape::Polygon(s)
+    }
+}
+
+impl From<Path> for AnyShape {
+    #[inline]
+    fn from(s: Path) -> AnyShape {
+        AnyShape::Path(s)
+    }
+}
+
+impl From<GeometryGroup> for AnyShape {
+    #[inline]
+    fn from(s: GeometryGroup) -> AnyShape {
+        AnyShape::Group(s)
+    }
+}
+
+impl From<Geometry> for AnyShape {
+    #[inline]
+    fn from(g: Geometry) -> AnyShape {
+        match g {
+            Geometry::Rect(s) => AnyShape::Rect(s),
+            Geometry::RoundedRect(s) => AnyShape::RoundedRect(s),
+            Geometry::Ellipse(s) => AnyShape::Ellipse(s),
+            Geometry::Path(s) => AnyShape::Path(s),
+            Geometry::Polygon(s) => AnyShape::Polygon(s),
+        }
+    }
+}
+
+impl Shape2D for Rectf {
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> AnyShape {
+        Transformable::transformed(self, matrix).into()
+    }
+
+    #[inline]
+    fn to_path(&self) -> Path {
+        Path::from(*self)
+    }
+}
+
+impl Shape2D for RoundedRect {
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> AnyShape {
+        Transformable::transformed(self, matrix).into()
+    }
+
+    #[inline]
+    fn to_path(&self) -> Path {
+        Path::from(*self)
+    }
+}
+
+impl Shape2D for Ellipse {
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> AnyShape {
+        Transformable::transformed(self, matrix).into()
+    }
+
+    #[inline]
+    fn to_path(&self) -> Path {
+        Path::from(*self)
+    }
+}
+
+impl Shape2D for Circle {
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> AnyShape {
+        Transformable::transformed(self, matrix).into()
+    }
+
+    #[inline]
+    fn to_path(&self) -> Path {
+        Path::from(Ellipse::from(*self))
+    }
+}
+
+impl Shape2D for OrientedRect {
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> AnyShape {
+        Transformable::transformed(self, matrix).into()
+    }
+
+    #[inline]
+    fn to_path(&self) -> Path {
+        Path::from(*self)
+    }
+}
+
+impl Shape2D for OrientedEllipse {
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> AnyShape {
+        Transformable::transformed(self, matrix).into()
+    }
+
+    #[inline]
+    fn to_path(&self) -> Path {
+        Path::from(*self)
+    }
+}
+
+impl Shape2D for Triangle {
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> AnyShape {
+        AnyShape::Triangle(Transformable::transformed(self, matrix))
+    }
+
+    #[inline]
+    fn to_path(&self) -> Path {
+        Path::from(Polygon::new(vec![self.p1, self.p2, self.p3]))
+    }
+}
+
+impl Shape2D for Polygon {
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> AnyShape {
+        AnyShape::Polygon(Polygon::transformed(self, matrix))
+    }
+
+    #[inline]
+    fn to_path(&self) -> Path {
+        Path::from(self.clone())
+    }
+}
+
+impl Shape2D for Path {
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> AnyShape {
+        AnyShape::Path(Path::transformed(self, matrix))
+    }
+
+    #[inline]
+    fn to_path(&self) -> Path {
+        self.clone()
+    }
+}
+
+impl Shape2D for Geometry {
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> AnyShape {
+        Geometry::transformed(self, matrix).into()
+    }
+
+    #[inline]
+    fn to_path(&self) -> Path {
+        match *self {
+            Geometry::Rect(ref s) => s.to_path(),
+            Geometry::RoundedRect(ref s) => s.to_path(),
+            Geometry::Ellipse(ref s) => s.to_path(),
+            Geometry::Path(ref s) => s.clone(),
+            Geometry::Polygon(ref s) => s.to_path(),
+        }
+    }
+}
+
+impl Shape2D for GeometryGroup {
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> AnyShape {
+        AnyShape::Group(GeometryGroup::transformed(self, matrix))
+    }
+
+    /// Combines the figures of every geometry into a single path. The group's
+    /// fill rule is not part of a path, so it must be applied separately when
+    /// filling the result.
+    #[inline]
+    fn to_path(&self) -> Path {
+        let figures = self
+            .geometries
+            .iter()
+            .flat_map(|g| g.to_path().figures)
+            .collect::<Vec<_>>();
+        Path::from(figures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bounded::Bounded;
+    use crate::ellipse::Ellipse;
+    use crate::hit_test::HitTest;
+    use crate::matrix3x2f::Matrix3x2f;
+    use crate::point2f::Point2f;
+    use crate::rectf::Rectf;
+    use crate::shape::{AnyShape, Shape2D};
+    use crate::triangle::Triangle;
+
+    #[test]
+    fn heterogeneous_hit_testing() {
+        let shapes: Vec<AnyShape> = vec![
+            Rectf::new(0.0, 0.0, 10.0, 10.0).into(),
+            Ellipse::new((20.0, 5.0), 5.0, 5.0).into(),
+            Triangle::from(((30.0, 0.0), (40.0, 0.0), (30.0, 10.0))).into(),
+        ];
+
+        let hits = |p: Point2f| shapes.iter().filter(|s| s.hit(p)).count();
+        assert_eq!(hits(Point2f::new(5.0, 5.0)), 1);
+        assert_eq!(hits(Point2f::new(20.0, 1.0)), 1);
+        assert_eq!(hits(Point2f::new(32.0, 2.0)), 1);
+        assert_eq!(hits(Point2f::new(38.0, 8.0)), 0);
+
+        // Scaling keeps shapes in their own types. Rotation orients the
+        // rectangle, but a rotated circle is still an axis-aligned ellipse
+        let scale = Matrix3x2f::scaling([2.0, 2.0], (0.0, 0.0));
+        let rotate = Matrix3x2f::rotation(0.5, (0.0, 0.0));
+        let inside = [(5.0, 5.0), (20.0, 1.0), (32.0, 2.0)].map(Point2f::from);
+        for (shape, &inside) in shapes.iter().zip(&inside) {
+            let scaled = shape.transformed(&scale);
+            let center = shape.bounds().center() * scale;
+            assert_eq!(
+                std::mem::discriminant(&scaled),
+                std::mem::discriminant(shape)
+            );
+            assert!(scaled.hit(center));
+
+            let rotated = shape.as_shape().transformed(&rotate);
+            match *shape {
+                AnyShape::Rect(_) => assert!(matches!(rotated, AnyShape::OrientedRect(_))),
+                _ => assert_eq!(
+                    std::mem::discriminant(&rotated),
+                    std::mem::discriminant(shape)
+                ),
+            }
+            assert!(rotated.hit(inside * rotate));
+        }
+
+        let path = shapes[1].to_path();
+        assert!((path.bounds().left - 15.0).abs() < 1e-4);
+    }
+}
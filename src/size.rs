@@ -0,0 +1,292 @@
+//! Width and height of a rectangle, generic over its component type.
+
+use crate::axis::Axis;
+use crate::parse::{self, ParseError};
+use crate::vector2::Vector2;
+
+use num_traits::AsPrimitive;
+use std::fmt;
+use std::str::FromStr;
+
+/// Stores the width and height of a rectangle, generic over the type of its
+/// components. `Sizef`, `Sized64`, `Sizei` and `Sizeu` name the common
+/// component types.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde-compact",
+    serde(
+        into = "crate::compact::Array<[T; 2]>",
+        from = "crate::compact::Array<[T; 2]>",
+        bound(
+            serialize = "T: Clone + serde::Serialize",
+            deserialize = "T: serde::Deserialize<'de>"
+        )
+    )
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[repr(C)]
+pub struct Size<T> {
+    /// Horizontal component.
+    pub width: T,
+    /// Vertical component.
+    pub height: T,
+}
+
+impl<T> Size<T> {
+    /// Constructs a size from the components.
+    #[inline]
+    pub fn new(width: T, height: T) -> Self {
+        Size { width, height }
+    }
+
+    /// Converts each component with `f`, such as to change to another
+    /// numeric type.
+    #[inline]
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Size<U> {
+        Size {
+            width: f(self.width),
+            height: f(self.height),
+        }
+    }
+
+    /// Constructs a size from its extents along `axis` and the other axis.
+    #[inline]
+    pub fn from_axes(axis: Axis, along: T, across: T) -> Self {
+        match axis {
+            Axis::X => Size::new(along, across),
+            Axis::Y => Size::new(across, along),
+        }
+    }
+
+    /// The width for `Axis::X` or the height for `Axis::Y`.
+    #[inline]
+    pub fn along(&self, axis: Axis) -> T
+    where
+        T: Copy,
+    {
+        match axis {
+            Axis::X => self.width,
+            Axis::Y => self.height,
+        }
+    }
+
+    /// Borrows the width or height mutably, as with `along`.
+    #[inline]
+    pub fn along_mut(&mut self, axis: Axis) -> &mut T {
+        match axis {
+            Axis::X => &mut self.width,
+            Axis::Y => &mut self.height,
+        }
+    }
+
+    /// Replaces the width or height, as with `along`.
+    #[inline]
+    pub fn set_along(&mut self, axis: Axis, value: T) {
+        *self.along_mut(axis) = value;
+    }
+
+    /// The size with its width and height swapped, such as for content
+    /// rotated by a quarter turn.
+    #[inline]
+    pub fn transpose(self) -> Self {
+        Size::new(self.height, self.width)
+    }
+}
+
+impl<T> Size<T> {
+    /// Converts this size to a vector with the width as x and the height
+    /// as y.
+    #[inline]
+    pub fn to_vector(self) -> Vector2<T> {
+        Vector2::new(self.width, self.height)
+    }
+}
+
+impl<T: Copy + 'static> Size<T> {
+    /// Converts the components with `as`, so floats are truncated towards
+    /// zero and saturate at the limits of integer types.
+    #[inline]
+    pub fn cast<U: Copy + 'static>(self) -> Size<U>
+    where
+        T: AsPrimitive<U>,
+    {
+        self.map(T::as_)
+    }
+
+    /// Converts the components to `f32`, as with `cast`.
+    #[inline]
+    pub fn to_f32(self) -> Size<f32>
+    where
+        T: AsPrimitive<f32>,
+    {
+        self.cast()
+    }
+
+    /// Converts the components to `f64`, as with `cast`.
+    #[inline]
+    pub fn to_f64(self) -> Size<f64>
+    where
+        T: AsPrimitive<f64>,
+    {
+        self.cast()
+    }
+
+    /// Converts the components to `i32`, as with `cast`.
+    #[inline]
+    pub fn to_i32(self) -> Size<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast()
+    }
+
+    /// Converts the components to `u32`, as with `cast`. Negative
+    /// components become 0.
+    #[inline]
+    pub fn to_u32(self) -> Size<u32>
+    where
+        T: AsPrimitive<u32>,
+    {
+        self.cast()
+    }
+}
+
+/// Both the width and the height are set to the scalar.
+impl<T: Copy> From<T> for Size<T> {
+    #[inline]
+    fn from(size: T) -> Size<T> {
+        Size::new(size, size)
+    }
+}
+
+impl<T> From<(T, T)> for Size<T> {
+    #[inline]
+    fn from((width, height): (T, T)) -> Size<T> {
+        Size::new(width, height)
+    }
+}
+
+/// Stores an ordered pair of double precision floating-point values,
+/// typically the width and height of a rectangle.
+pub type Sized64 = Size<f64>;
+
+/// Widens each component exactly.
+impl From<Size<f32>> for Size<f64> {
+    #[inline]
+    fn from(s: Size<f32>) -> Size<f64> {
+        s.map(f64::from)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Size<T> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        parse::write_components(fmt, ('(', ')'), &[&self.width, &self.height])
+    }
+}
+
+impl<T: FromStr> FromStr for Size<T> {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Size<T>, ParseError> {
+        let [width, height] = parse::parse_components(s, ('(', ')'))?;
+        Ok(Size { width, height })
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<T, U> From<Size<T>> for euclid::Size2D<T, U> {
+    #[inline]
+    fn from(s: Size<T>) -> euclid::Size2D<T, U> {
+        euclid::Size2D::new(s.width, s.height)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<T, U> From<euclid::Size2D<T, U>> for Size<T> {
+    #[inline]
+    fn from(s: euclid::Size2D<T, U>) -> Size<T> {
+        Size {
+            width: s.width,
+            height: s.height,
+        }
+    }
+}
+
+/// Physical sizes are in whole device pixels, so the natural counterpart is
+/// `Sizeu`. Use `Dpi::physical_size_to_dips` to convert to DIPs instead.
+#[cfg(feature = "winit")]
+impl<T> From<Size<T>> for winit_dpi::PhysicalSize<T> {
+    #[inline]
+    fn from(s: Size<T>) -> winit_dpi::PhysicalSize<T> {
+        winit_dpi::PhysicalSize::new(s.width, s.height)
+    }
+}
+
+#[cfg(feature = "winit")]
+impl<T> From<winit_dpi::PhysicalSize<T>> for Size<T> {
+    #[inline]
+    fn from(s: winit_dpi::PhysicalSize<T>) -> Size<T> {
+        Size {
+            width: s.width,
+            height: s.height,
+        }
+    }
+}
+
+/// Logical sizes are measured in the same units as DIPs.
+#[cfg(feature = "winit")]
+impl<T> From<Size<T>> for winit_dpi::LogicalSize<T> {
+    #[inline]
+    fn from(s: Size<T>) -> winit_dpi::LogicalSize<T> {
+        winit_dpi::LogicalSize::new(s.width, s.height)
+    }
+}
+
+#[cfg(feature = "winit")]
+impl<T> From<winit_dpi::LogicalSize<T>> for Size<T> {
+    #[inline]
+    fn from(s: winit_dpi::LogicalSize<T>) -> Size<T> {
+        Size {
+            width: s.width,
+            height: s.height,
+        }
+    }
+}
+
+// The struct is `#[repr(C)]` with every field of type `T`, so it has
+// the same layout as `[T; 2]`.
+#[cfg(feature = "encase")]
+impl<T: encase::vector::VectorScalar> encase::vector::AsRefVectorParts<T, 2> for Size<T> {
+    #[inline]
+    fn as_ref_parts(&self) -> &[T; 2] {
+        unsafe { &*(self as *const Size<T> as *const [T; 2]) }
+    }
+}
+
+#[cfg(feature = "encase")]
+impl<T: encase::vector::VectorScalar> encase::vector::AsMutVectorParts<T, 2> for Size<T> {
+    #[inline]
+    fn as_mut_parts(&mut self) -> &mut [T; 2] {
+        unsafe { &mut *(self as *mut Size<T> as *mut [T; 2]) }
+    }
+}
+
+#[cfg(feature = "encase")]
+impl<T: encase::vector::VectorScalar> encase::vector::FromVectorParts<T, 2> for Size<T> {
+    #[inline]
+    fn from_parts([width, height]: [T; 2]) -> Size<T> {
+        Size { width, height }
+    }
+}
+
+// Sizes are laid out as a `vec2` of (width, height) in shader buffers.
+#[cfg(feature = "encase")]
+encase::impl_vector!(2, Size<T>; (T: encase::vector::VectorScalar));
@@ -1,55 +1,35 @@
 //! Floating point size descriptor.
 
-use crate::vector2f::Vector2f;
+use crate::size::Size;
 
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::dcommon::D2D_SIZE_F;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D::Common as d2d_common;
 
 /// Stores an ordered pair of floating-point values, typically the width
 /// and height of a rectangle.
-#[derive(Copy, Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
-#[repr(C)]
-pub struct Sizef {
-    /// Horizontal component.
-    pub width: f32,
-    /// Vertical component.
-    pub height: f32,
-}
+pub type Sizef = Size<f32>;
 
-impl Sizef {
-    /// Constructs a size from the components.
+#[cfg(feature = "kurbo")]
+impl From<Sizef> for kurbo::Size {
     #[inline]
-    pub fn new(width: f32, height: f32) -> Sizef {
-        Sizef { width, height }
-    }
-
-    #[inline]
-    pub fn to_vector(self) -> Vector2f {
-        Vector2f {
-            x: self.width,
-            y: self.height,
-        }
+    fn from(s: Sizef) -> kurbo::Size {
+        kurbo::Size::new(s.width.into(), s.height.into())
     }
 }
 
-impl From<f32> for Sizef {
+#[cfg(feature = "kurbo")]
+impl From<kurbo::Size> for Sizef {
     #[inline]
-    fn from(size: f32) -> Sizef {
+    fn from(s: kurbo::Size) -> Sizef {
         Sizef {
-            width: size,
-            height: size,
+            width: s.width as f32,
+            height: s.height as f32,
         }
     }
 }
 
-impl From<(f32, f32)> for Sizef {
-    #[inline]
-    fn from((width, height): (f32, f32)) -> Sizef {
-        Sizef { width, height }
-    }
-}
-
 #[cfg(all(windows, feature = "d2d"))]
 impl From<Sizef> for D2D_SIZE_F {
     #[inline]
@@ -72,6 +52,28 @@ impl From<D2D_SIZE_F> for Sizef {
     }
 }
 
+#[cfg(all(windows, feature = "windows"))]
+impl From<Sizef> for d2d_common::D2D_SIZE_F {
+    #[inline]
+    fn from(point: Sizef) -> d2d_common::D2D_SIZE_F {
+        d2d_common::D2D_SIZE_F {
+            width: point.width,
+            height: point.height,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<d2d_common::D2D_SIZE_F> for Sizef {
+    #[inline]
+    fn from(point: d2d_common::D2D_SIZE_F) -> Sizef {
+        Sizef {
+            width: point.width,
+            height: point.height,
+        }
+    }
+}
+
 #[cfg(all(test, windows, feature = "d2d"))]
 #[test]
 fn sizef_d2d_bin_compat() {
@@ -88,3 +90,20 @@ fn sizef_d2d_bin_compat() {
     assert!(ptr_eq(&sz.height, &d2d.height));
     assert_eq!(size_of_val(&sz), size_of_val(d2d));
 }
+
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn sizef_windows_bin_compat() {
+    use std::mem::size_of_val;
+
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
+    }
+
+    let sz = Sizef::new(0.0, 0.0);
+    let d2d = unsafe { &*((&sz) as *const _ as *const d2d_common::D2D_SIZE_F) };
+
+    assert!(ptr_eq(&sz.width, &d2d.width));
+    assert!(ptr_eq(&sz.height, &d2d.height));
+    assert_eq!(size_of_val(&sz), size_of_val(d2d));
+}
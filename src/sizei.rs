@@ -0,0 +1,90 @@
+//! Signed integer size descriptor e.g. the extent of a window.
+
+use crate::size::Size;
+
+#[cfg(all(windows, feature = "d2d"))]
+use winapi::shared::windef::SIZE;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Foundation as foundation;
+
+/// Stores an ordered pair of signed integer values, typically the width
+/// and height of a window or a GDI extent.
+pub type Sizei = Size<i32>;
+
+#[cfg(all(windows, feature = "d2d"))]
+impl From<Sizei> for SIZE {
+    #[inline]
+    fn from(size: Sizei) -> SIZE {
+        SIZE {
+            cx: size.width,
+            cy: size.height,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "d2d"))]
+impl From<SIZE> for Sizei {
+    #[inline]
+    fn from(size: SIZE) -> Sizei {
+        Sizei {
+            width: size.cx,
+            height: size.cy,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<Sizei> for foundation::SIZE {
+    #[inline]
+    fn from(size: Sizei) -> foundation::SIZE {
+        foundation::SIZE {
+            cx: size.width,
+            cy: size.height,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<foundation::SIZE> for Sizei {
+    #[inline]
+    fn from(size: foundation::SIZE) -> Sizei {
+        Sizei {
+            width: size.cx,
+            height: size.cy,
+        }
+    }
+}
+
+#[cfg(all(test, windows, feature = "d2d"))]
+#[test]
+fn sizei_gdi_bin_compat() {
+    use std::mem::size_of_val;
+
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
+    }
+
+    let size = Sizei::new(0, 0);
+    let gdi = unsafe { &*((&size) as *const _ as *const SIZE) };
+
+    assert!(ptr_eq(&size.width, &gdi.cx));
+    assert!(ptr_eq(&size.height, &gdi.cy));
+    assert_eq!(size_of_val(&size), size_of_val(gdi));
+}
+
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn sizei_windows_bin_compat() {
+    use std::mem::size_of_val;
+
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
+    }
+
+    let size = Sizei::new(0, 0);
+    let gdi = unsafe { &*((&size) as *const _ as *const foundation::SIZE) };
+
+    assert!(ptr_eq(&size.width, &gdi.cx));
+    assert!(ptr_eq(&size.height, &gdi.cy));
+    assert_eq!(size_of_val(&size), size_of_val(gdi));
+}
@@ -1,60 +1,53 @@
 //! Unsigned integer size descriptor e.g. the size of a bitmap.
 
+use crate::size::Size;
+
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::dcommon::D2D_SIZE_U;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D::Common as d2d_common;
 
 /// Stores an ordered pair of unsigned integer values, typically the width
 /// and height of a rectangle.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
-#[repr(C)]
-pub struct Sizeu {
-    /// Horizontal component.
-    pub width: u32,
-    /// Vertical component.
-    pub height: u32,
-}
+pub type Sizeu = Size<u32>;
 
-impl Sizeu {
-    /// Constructs a size from the components.
+#[cfg(all(windows, feature = "d2d"))]
+impl From<Sizeu> for D2D_SIZE_U {
     #[inline]
-    pub fn new(width: u32, height: u32) -> Sizeu {
-        Sizeu { width, height }
+    fn from(point: Sizeu) -> D2D_SIZE_U {
+        D2D_SIZE_U {
+            width: point.width,
+            height: point.height,
+        }
     }
 }
 
-impl From<u32> for Sizeu {
+#[cfg(all(windows, feature = "d2d"))]
+impl From<D2D_SIZE_U> for Sizeu {
     #[inline]
-    fn from(size: u32) -> Sizeu {
+    fn from(point: D2D_SIZE_U) -> Sizeu {
         Sizeu {
-            width: size,
-            height: size,
+            width: point.width,
+            height: point.height,
         }
     }
 }
 
-impl From<(u32, u32)> for Sizeu {
-    #[inline]
-    fn from((width, height): (u32, u32)) -> Sizeu {
-        Sizeu { width, height }
-    }
-}
-
-#[cfg(all(windows, feature = "d2d"))]
-impl From<Sizeu> for D2D_SIZE_U {
+#[cfg(all(windows, feature = "windows"))]
+impl From<Sizeu> for d2d_common::D2D_SIZE_U {
     #[inline]
-    fn from(point: Sizeu) -> D2D_SIZE_U {
-        D2D_SIZE_U {
+    fn from(point: Sizeu) -> d2d_common::D2D_SIZE_U {
+        d2d_common::D2D_SIZE_U {
             width: point.width,
             height: point.height,
         }
     }
 }
 
-#[cfg(all(windows, feature = "d2d"))]
-impl From<D2D_SIZE_U> for Sizeu {
+#[cfg(all(windows, feature = "windows"))]
+impl From<d2d_common::D2D_SIZE_U> for Sizeu {
     #[inline]
-    fn from(point: D2D_SIZE_U) -> Sizeu {
+    fn from(point: d2d_common::D2D_SIZE_U) -> Sizeu {
         Sizeu {
             width: point.width,
             height: point.height,
@@ -78,3 +71,20 @@ fn sizeu_d2d_bin_compat() {
     assert!(ptr_eq(&sz.height, &d2d.height));
     assert_eq!(size_of_val(&sz), size_of_val(d2d));
 }
+
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn sizeu_windows_bin_compat() {
+    use std::mem::size_of_val;
+
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
+    }
+
+    let sz = Sizeu::new(0, 0);
+    let d2d = unsafe { &*((&sz) as *const _ as *const d2d_common::D2D_SIZE_U) };
+
+    assert!(ptr_eq(&sz.width, &d2d.width));
+    assert!(ptr_eq(&sz.height, &d2d.height));
+    assert_eq!(size_of_val(&sz), size_of_val(d2d));
+}
@@ -0,0 +1,158 @@
+//! Interpolating splines which pass through their given points, converted
+//! into chains of cubic bezier segments so they can be drawn as part of a
+//! path.
+
+use crate::bezier_segment::BezierSegment;
+use crate::point2f::Point2f;
+use crate::vector2f::Vector2f;
+
+/// Cardinal (Catmull-Rom) splines, where the tangent at each point is
+/// derived from the positions of its neighbors.
+#[derive(Copy, Clone, Debug)]
+pub struct CatmullRom;
+
+impl CatmullRom {
+    /// Converts an open Catmull-Rom spline through `points` into a chain of
+    /// bezier segments starting at `points[0]`.
+    ///
+    /// `tension` scales the tangents by `1.0 - tension`: `0.0` produces a
+    /// standard Catmull-Rom spline and `1.0` produces straight lines between
+    /// the points. The end points use a one-sided tangent towards their only
+    /// neighbor. Fewer than 2 points produces an empty chain.
+    pub fn to_beziers(points: &[Point2f], tension: f32) -> Vec<BezierSegment> {
+        let n = points.len();
+        if n < 2 {
+            return Vec::new();
+        }
+
+        let scale = (1.0 - tension) / 2.0;
+        let tangent = |i: usize| {
+            let prev = points[i.saturating_sub(1)];
+            let next = points[(i + 1).min(n - 1)];
+            (next - prev) * scale
+        };
+
+        (0..n - 1)
+            .map(|i| Hermite::segment(points[i], tangent(i), points[i + 1], tangent(i + 1)))
+            .collect()
+    }
+
+    /// Converts a closed Catmull-Rom spline through `points` into a chain of
+    /// bezier segments starting and ending at `points[0]`. The tangents at
+    /// the first and last points wrap around to their neighbors on the other
+    /// end. See `to_beziers` for the meaning of `tension`.
+    pub fn to_beziers_closed(points: &[Point2f], tension: f32) -> Vec<BezierSegment> {
+        let n = points.len();
+        if n < 2 {
+            return Vec::new();
+        }
+
+        let scale = (1.0 - tension) / 2.0;
+        let tangent = |i: usize| (points[(i + 1) % n] - points[(i + n - 1) % n]) * scale;
+
+        (0..n)
+            .map(|i| {
+                let j = (i + 1) % n;
+                Hermite::segment(points[i], tangent(i), points[j], tangent(j))
+            })
+            .collect()
+    }
+}
+
+/// A point on a Hermite spline along with the tangent of the curve as it
+/// passes through the point.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct HermitePoint {
+    /// The position the curve passes through.
+    pub point: Point2f,
+    /// The derivative of the curve at this point.
+    pub tangent: Vector2f,
+}
+
+impl HermitePoint {
+    /// Constructs the hermite point from its components.
+    #[inline]
+    pub fn new(point: impl Into<Point2f>, tangent: impl Into<Vector2f>) -> HermitePoint {
+        HermitePoint {
+            point: point.into(),
+            tangent: tangent.into(),
+        }
+    }
+}
+
+/// Cubic Hermite splines, where the tangent at each point is given
+/// explicitly.
+#[derive(Copy, Clone, Debug)]
+pub struct Hermite;
+
+impl Hermite {
+    /// Converts a single hermite curve between two points into the
+    /// equivalent bezier segment, which starts at `p0`.
+    #[inline]
+    pub fn segment(
+        p0: impl Into<Point2f>,
+        m0: impl Into<Vector2f>,
+        p1: impl Into<Point2f>,
+        m1: impl Into<Vector2f>,
+    ) -> BezierSegment {
+        let (p0, m0): (Point2f, Vector2f) = (p0.into(), m0.into());
+        let (p1, m1): (Point2f, Vector2f) = (p1.into(), m1.into());
+        BezierSegment {
+            p1: p0 + m0 / 3.0,
+            p2: p1 - m1 / 3.0,
+            p3: p1,
+        }
+    }
+
+    /// Converts a hermite spline into a chain of bezier segments starting at
+    /// `points[0].point`. Fewer than 2 points produces an empty chain.
+    pub fn to_beziers(points: &[HermitePoint]) -> Vec<BezierSegment> {
+        points
+            .windows(2)
+            .map(|w| Hermite::segment(w[0].point, w[0].tangent, w[1].point, w[1].tangent))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::point2f::Point2f;
+    use crate::spline::CatmullRom;
+
+    #[test]
+    fn catmull_rom_interpolates() {
+        let points = [
+            Point2f::new(0.0, 0.0),
+            Point2f::new(1.0, 2.0),
+            Point2f::new(3.0, 1.0),
+            Point2f::new(4.0, 4.0),
+        ];
+
+        let open = CatmullRom::to_beziers(&points, 0.0);
+        assert_eq!(open.len(), 3);
+        let mut start = points[0];
+        for (seg, &p) in open.iter().zip(&points[1..]) {
+            assert_eq!(seg.p3, p);
+            assert!(seg.point_at(start, 1.0).is_approx_eq(p, 1e-5));
+            start = seg.p3;
+        }
+
+        // Tangents must be continuous across every joint
+        for w in open.windows(2) {
+            let incoming = w[0].p3 - w[0].p2;
+            let outgoing = w[1].p1 - w[0].p3;
+            assert!(incoming.is_approx_eq(outgoing, 1e-5));
+        }
+
+        let closed = CatmullRom::to_beziers_closed(&points, 0.5);
+        assert_eq!(closed.len(), 4);
+        assert_eq!(closed[3].p3, points[0]);
+    }
+}
@@ -0,0 +1,265 @@
+//! [proptest][1] strategies for generating the types in this library.
+//!
+//! Most strategies are built from a strategy for the components, so the same
+//! function can generate points with any values, only finite values, or
+//! values in a range:
+//!
+//! ```
+//! use math2d::strategy;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(rect in strategy::normalized_rect(-1e6f32..1e6))| {
+//!     prop_assert!(rect.contains_point(rect.center()));
+//! });
+//! ```
+//!
+//! [1]: https://docs.rs/proptest
+
+use crate::bezier_segment::BezierSegment;
+use crate::capsule::Capsule;
+use crate::circle::Circle;
+use crate::color::Color;
+use crate::ellipse::Ellipse;
+use crate::matrix3x2f::Matrix3x2f;
+use crate::point2::Point2;
+use crate::point2f::Point2f;
+use crate::quad_bezier_segment::QuadBezierSegment;
+use crate::rect::Rect;
+use crate::rounded_rect::RoundedRect;
+use crate::size::Size;
+use crate::thicknessf::Thicknessf;
+use crate::triangle::Triangle;
+use crate::vector2::Vector2;
+
+use proptest::strategy::Strategy;
+
+/// Any `f32` except infinities and NaN.
+#[inline]
+pub fn finite_f32() -> impl Strategy<Value = f32> + Clone {
+    use proptest::num::f32::{NEGATIVE, NORMAL, POSITIVE, SUBNORMAL, ZERO};
+    POSITIVE | NEGATIVE | NORMAL | SUBNORMAL | ZERO
+}
+
+/// Any `f64` except infinities and NaN.
+#[inline]
+pub fn finite_f64() -> impl Strategy<Value = f64> + Clone {
+    use proptest::num::f64::{NEGATIVE, NORMAL, POSITIVE, SUBNORMAL, ZERO};
+    POSITIVE | NEGATIVE | NORMAL | SUBNORMAL | ZERO
+}
+
+/// Any `f32` between 0 and 1, including both.
+#[inline]
+pub fn unit_f32() -> impl Strategy<Value = f32> + Clone {
+    0.0f32..=1.0
+}
+
+/// Points with each coordinate generated by `coord`.
+#[inline]
+pub fn point2<S>(coord: S) -> impl Strategy<Value = Point2<S::Value>> + Clone
+where
+    S: Strategy + Clone,
+{
+    (coord.clone(), coord).prop_map(|(x, y)| Point2 { x, y })
+}
+
+/// Vectors with each component generated by `component`.
+#[inline]
+pub fn vector2<S>(component: S) -> impl Strategy<Value = Vector2<S::Value>> + Clone
+where
+    S: Strategy + Clone,
+{
+    (component.clone(), component).prop_map(|(x, y)| Vector2 { x, y })
+}
+
+/// Sizes with the width and height generated by `extent`.
+#[inline]
+pub fn size<S>(extent: S) -> impl Strategy<Value = Size<S::Value>> + Clone
+where
+    S: Strategy + Clone,
+{
+    (extent.clone(), extent).prop_map(|(width, height)| Size { width, height })
+}
+
+/// Rectangles with each edge generated by `coord`. The edges may be in either
+/// order; see `normalized_rect` for rectangles with `left <= right` and
+/// `top <= bottom`.
+#[inline]
+pub fn rect<S>(coord: S) -> impl Strategy<Value = Rect<S::Value>> + Clone
+where
+    S: Strategy + Clone,
+{
+    (coord.clone(), coord.clone(), coord.clone(), coord)
+        .prop_map(|(left, top, right, bottom)| Rect::new(left, top, right, bottom))
+}
+
+/// Rectangles with each edge generated by `coord`, ordered so that
+/// `left <= right` and `top <= bottom`.
+#[inline]
+pub fn normalized_rect<S>(coord: S) -> impl Strategy<Value = Rect<S::Value>> + Clone
+where
+    S: Strategy + Clone,
+    S::Value: PartialOrd + Copy,
+{
+    let ordered = |a, b| if b < a { (b, a) } else { (a, b) };
+    rect(coord).prop_map(move |r| {
+        let (left, right) = ordered(r.left, r.right);
+        let (top, bottom) = ordered(r.top, r.bottom);
+        Rect::new(left, top, right, bottom)
+    })
+}
+
+/// Thicknesses with each side generated by `side`.
+#[inline]
+pub fn thicknessf<S>(side: S) -> impl Strategy<Value = Thicknessf>
+where
+    S: Strategy<Value = f32> + Clone,
+{
+    (side.clone(), side.clone(), side.clone(), side)
+        .prop_map(|(left, top, right, bottom)| Thicknessf::new(left, top, right, bottom))
+}
+
+/// Colors with every channel, including alpha, between 0 and 1.
+#[inline]
+pub fn color() -> impl Strategy<Value = Color> {
+    (unit_f32(), unit_f32(), unit_f32(), unit_f32()).prop_map(|(r, g, b, a)| Color::new(r, g, b, a))
+}
+
+/// Matrices with each element generated by `element`. Nothing stops the
+/// matrix from being singular.
+#[inline]
+pub fn matrix3x2f<S>(element: S) -> impl Strategy<Value = Matrix3x2f>
+where
+    S: Strategy<Value = f32> + Clone,
+{
+    let pair = (element.clone(), element);
+    (pair.clone(), pair.clone(), pair).prop_map(|((a, b), (c, d), (x, y))| Matrix3x2f {
+        a,
+        b,
+        c,
+        d,
+        x,
+        y,
+    })
+}
+
+/// Matrices built from a scale, rotation and translation, which are always
+/// invertible. The scale factors are between `1/max_scale` and `max_scale` in
+/// magnitude with either sign, and the translation comes from `coord`.
+#[inline]
+pub fn invertible_matrix3x2f<S>(max_scale: f32, coord: S) -> impl Strategy<Value = Matrix3x2f>
+where
+    S: Strategy<Value = f32> + Clone,
+{
+    let factor =
+        (1.0 / max_scale..=max_scale, proptest::bool::ANY)
+            .prop_map(|(s, flip)| if flip { -s } else { s });
+    (
+        (factor.clone(), factor),
+        -std::f32::consts::PI..std::f32::consts::PI,
+        (coord.clone(), coord),
+    )
+        .prop_map(|((sx, sy), rotation, (x, y))| Matrix3x2f::compose([sx, sy], rotation, [x, y]))
+}
+
+/// Circles with the center coordinates from `coord` and the radius from
+/// `radius`.
+#[inline]
+pub fn circle<S, R>(coord: S, radius: R) -> impl Strategy<Value = Circle>
+where
+    S: Strategy<Value = f32> + Clone,
+    R: Strategy<Value = f32>,
+{
+    (point2(coord), radius).prop_map(|(center, radius)| Circle::new(center, radius))
+}
+
+/// Ellipses with the center coordinates from `coord` and each radius from
+/// `radius`.
+#[inline]
+pub fn ellipse<S, R>(coord: S, radius: R) -> impl Strategy<Value = Ellipse>
+where
+    S: Strategy<Value = f32> + Clone,
+    R: Strategy<Value = f32> + Clone,
+{
+    (point2(coord), radius.clone(), radius)
+        .prop_map(|(center, rx, ry)| Ellipse::new(center, rx, ry))
+}
+
+/// Rounded rectangles within `normalized_rect(coord)`, with each corner
+/// radius from `radius`.
+#[inline]
+pub fn rounded_rect<S, R>(coord: S, radius: R) -> impl Strategy<Value = RoundedRect>
+where
+    S: Strategy<Value = f32> + Clone,
+    R: Strategy<Value = f32> + Clone,
+{
+    (normalized_rect(coord), radius.clone(), radius)
+        .prop_map(|(rect, rx, ry)| RoundedRect::new(rect, rx, ry))
+}
+
+/// Capsules between two points from `point2(coord)` with their radius from
+/// `radius`.
+#[inline]
+pub fn capsule<S, R>(coord: S, radius: R) -> impl Strategy<Value = Capsule>
+where
+    S: Strategy<Value = f32> + Clone,
+    R: Strategy<Value = f32>,
+{
+    let p = point2(coord);
+    (p.clone(), p, radius).prop_map(|(p1, p2, radius)| Capsule::new(p1, p2, radius))
+}
+
+/// Triangles with each vertex from `point2(coord)`. They may be degenerate
+/// and wound either way.
+#[inline]
+pub fn triangle<S>(coord: S) -> impl Strategy<Value = Triangle>
+where
+    S: Strategy<Value = f32> + Clone,
+{
+    let p = point2(coord);
+    (p.clone(), p.clone(), p).prop_map(|(p1, p2, p3)| Triangle { p1, p2, p3 })
+}
+
+/// Cubic Bézier segments with each point from `point2(coord)`.
+#[inline]
+pub fn bezier_segment<S>(coord: S) -> impl Strategy<Value = BezierSegment>
+where
+    S: Strategy<Value = f32> + Clone,
+{
+    let p = point2(coord);
+    (p.clone(), p.clone(), p).prop_map(|(p1, p2, p3)| BezierSegment { p1, p2, p3 })
+}
+
+/// Quadratic Bézier segments with each point from `point2(coord)`.
+#[inline]
+pub fn quad_bezier_segment<S>(coord: S) -> impl Strategy<Value = QuadBezierSegment>
+where
+    S: Strategy<Value = f32> + Clone,
+{
+    let p = point2(coord);
+    (p.clone(), p).prop_map(|(p1, p2)| QuadBezierSegment { p1, p2 })
+}
+
+/// Points with their coordinates anywhere in the finite range of `f32`.
+#[inline]
+pub fn finite_point2f() -> impl Strategy<Value = Point2f> {
+    point2(finite_f32())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn generated_values(
+            p in strategy::finite_point2f(),
+            r in strategy::normalized_rect(-1e3f32..1e3),
+            m in strategy::invertible_matrix3x2f(10.0, -1e3f32..1e3),
+        ) {
+            prop_assert!(p.x.is_finite() && p.y.is_finite());
+            prop_assert!(r.left <= r.right && r.top <= r.bottom);
+            prop_assert!(m.try_inverse().is_some());
+        }
+    }
+}
@@ -0,0 +1,636 @@
+//! Stroke styles, and the conversion of stroked paths into the filled
+//! outlines they cover.
+
+use crate::arc_segment::{ArcSize, SweepDirection};
+use crate::path::{Path, PathBuilder};
+use crate::point2f::Point2f;
+use crate::vector2f::Vector2f;
+
+#[cfg(all(windows, feature = "d2d"))]
+use winapi::um::d2d1::{D2D1_CAP_STYLE, D2D1_LINE_JOIN};
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D as d2d;
+
+/// Describes the shape at the ends of an open stroked line.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum LineCap {
+    /// The line ends exactly at its end point.
+    Butt,
+    /// The line extends past its end point by half of the stroke width.
+    Square,
+    /// The line ends with a semicircle with a diameter of the stroke width.
+    Round,
+}
+
+impl Default for LineCap {
+    #[inline]
+    fn default() -> Self {
+        LineCap::Butt
+    }
+}
+
+impl LineCap {
+    /// Continues an outline from the left edge of a line of the given
+    /// `width` which ends at `end` travelling in `direction`, around the cap
+    /// to the right edge. Left is the side of the normal `(d.y, -d.x)`, which
+    /// is on the left as seen on screen with y pointing down.
+    #[inline]
+    pub fn append_to(
+        self,
+        builder: &mut PathBuilder,
+        end: impl Into<Point2f>,
+        direction: impl Into<Vector2f>,
+        width: f32,
+    ) {
+        let d = direction.into().normalized();
+        cap_to(builder, self, end.into(), d, width / 2.0);
+    }
+
+    /// The area the cap adds past the end of a line of the given `width`, as
+    /// a closed figure. Butt caps add nothing, so they produce an empty path.
+    pub fn outline(
+        self,
+        end: impl Into<Point2f>,
+        direction: impl Into<Vector2f>,
+        width: f32,
+    ) -> Path {
+        let mut builder = PathBuilder::new();
+        let h = width / 2.0;
+        if self != LineCap::Butt && h > 0.0 {
+            let (p, d) = (end.into(), direction.into().normalized());
+            builder.move_to(p + normal(d) * h);
+            cap_to(&mut builder, self, p, d, h);
+            builder.close();
+        }
+        builder.build()
+    }
+
+    /// Converts the cap to the equivalent Direct2D cap style, e.g. for
+    /// `D2D1_STROKE_STYLE_PROPERTIES`.
+    #[cfg(all(windows, feature = "d2d"))]
+    #[inline]
+    pub fn to_d2d(self) -> D2D1_CAP_STYLE {
+        use winapi::um::d2d1::*;
+        match self {
+            LineCap::Butt => D2D1_CAP_STYLE_FLAT,
+            LineCap::Square => D2D1_CAP_STYLE_SQUARE,
+            LineCap::Round => D2D1_CAP_STYLE_ROUND,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<LineCap> for d2d::D2D1_CAP_STYLE {
+    #[inline]
+    fn from(cap: LineCap) -> d2d::D2D1_CAP_STYLE {
+        match cap {
+            LineCap::Butt => d2d::D2D1_CAP_STYLE_FLAT,
+            LineCap::Square => d2d::D2D1_CAP_STYLE_SQUARE,
+            LineCap::Round => d2d::D2D1_CAP_STYLE_ROUND,
+        }
+    }
+}
+
+/// Describes the shape at the corners where segments of a stroked line meet.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum LineJoin {
+    /// The outer edges are extended until they meet in a sharp point. Corners
+    /// where the point would extend beyond the miter limit are beveled.
+    Miter,
+    /// The corner is cut off by a straight line between the outer edges.
+    Bevel,
+    /// The corner is rounded off with a circular arc.
+    Round,
+}
+
+impl Default for LineJoin {
+    #[inline]
+    fn default() -> Self {
+        LineJoin::Miter
+    }
+}
+
+impl LineJoin {
+    /// Continues an outline around the outside of a corner at `vertex`, where
+    /// a line of the given `width` turns from `incoming` to `outgoing`. The
+    /// outline must be at the outer edge of the incoming segment, and ends at
+    /// the outer edge of the outgoing one. The outer side is the left side of
+    /// clockwise turns and the right side of counterclockwise ones, as seen
+    /// on screen. Nothing is added if the line does not turn.
+    #[inline]
+    pub fn append_to(
+        self,
+        builder: &mut PathBuilder,
+        vertex: impl Into<Point2f>,
+        incoming: impl Into<Vector2f>,
+        outgoing: impl Into<Vector2f>,
+        width: f32,
+        miter_limit: f32,
+    ) {
+        let (d0, d1) = (incoming.into().normalized(), outgoing.into().normalized());
+        if !is_straight(d0, d1) {
+            let h = outer_side(d0, d1, width / 2.0);
+            outer_join(builder, self, miter_limit, vertex.into(), d0, d1, h);
+        }
+    }
+
+    /// The wedge which fills the gap between the rectangles of the two
+    /// segments on the outside of the corner, as a closed figure: a triangle
+    /// for a bevel, with a fourth point for a miter, or a circular sector.
+    /// Lines which do not turn produce an empty path.
+    pub fn outline(
+        self,
+        vertex: impl Into<Point2f>,
+        incoming: impl Into<Vector2f>,
+        outgoing: impl Into<Vector2f>,
+        width: f32,
+        miter_limit: f32,
+    ) -> Path {
+        let mut builder = PathBuilder::new();
+        let (d0, d1) = (incoming.into().normalized(), outgoing.into().normalized());
+        if width > 0.0 && !is_straight(d0, d1) {
+            let (p, h) = (vertex.into(), outer_side(d0, d1, width / 2.0));
+            builder.move_to(p).line_to(p + normal(d0) * h);
+            outer_join(&mut builder, self, miter_limit, p, d0, d1, h);
+            builder.close();
+        }
+        builder.build()
+    }
+
+    /// Converts the join to the equivalent Direct2D line join. `Miter` joins
+    /// fall back to a bevel past the miter limit, which is Direct2D's
+    /// `D2D1_LINE_JOIN_MITER_OR_BEVEL`.
+    #[cfg(all(windows, feature = "d2d"))]
+    #[inline]
+    pub fn to_d2d(self) -> D2D1_LINE_JOIN {
+        use winapi::um::d2d1::*;
+        match self {
+            LineJoin::Miter => D2D1_LINE_JOIN_MITER_OR_BEVEL,
+            LineJoin::Bevel => D2D1_LINE_JOIN_BEVEL,
+            LineJoin::Round => D2D1_LINE_JOIN_ROUND,
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<LineJoin> for d2d::D2D1_LINE_JOIN {
+    #[inline]
+    fn from(join: LineJoin) -> d2d::D2D1_LINE_JOIN {
+        match join {
+            LineJoin::Miter => d2d::D2D1_LINE_JOIN_MITER_OR_BEVEL,
+            LineJoin::Bevel => d2d::D2D1_LINE_JOIN_BEVEL,
+            LineJoin::Round => d2d::D2D1_LINE_JOIN_ROUND,
+        }
+    }
+}
+
+/// Directions closer together than this are treated as parallel.
+const PARALLEL_EPSILON: f32 = 1e-6;
+
+struct Stroker {
+    builder: PathBuilder,
+    join: LineJoin,
+    miter_limit: f32,
+}
+
+impl Stroker {
+    /// Outlines one side of a flattened figure, offset by `h` along the left
+    /// normal of each edge (negative values offset to the right). `pivot`
+    /// connects inner corners through the vertex itself rather than through
+    /// the intersection of the offset edges, which keeps the outline covering
+    /// every edge when it is filled with the non-zero rule.
+    fn side(&mut self, points: &[Point2f], closed: bool, h: f32, pivot: bool) {
+        let n = points.len();
+        let dir = |i: usize| (points[(i + 1) % n] - points[i]).normalized();
+
+        if closed {
+            // Start partway along the first edge so every corner gets a join
+            let mid = points[0] + (points[1 % n] - points[0]) / 2.0;
+            self.builder.move_to(mid + normal(dir(0)) * h);
+            for i in 1..=n {
+                let k = i % n;
+                self.join(points[k], dir(i - 1), dir(k), h, pivot);
+            }
+            self.builder.close();
+        } else {
+            for (i, w) in points.windows(3).enumerate() {
+                self.join(w[1], dir(i), dir(i + 1), h, pivot);
+            }
+            self.builder.line_to(points[n - 1] + normal(dir(n - 2)) * h);
+        }
+    }
+
+    fn join(&mut self, p: Point2f, d0: Vector2f, d1: Vector2f, h: f32, pivot: bool) {
+        let (n0, n1) = (normal(d0) * h, normal(d1) * h);
+        let (a, b) = (p + n0, p + n1);
+        if is_straight(d0, d1) {
+            self.builder.line_to(a);
+            return;
+        }
+
+        if !is_outer(d0, d1, h) {
+            let dot = d0.dot(d1);
+            if pivot || 1.0 + dot <= PARALLEL_EPSILON {
+                self.builder.line_to(a).line_to(p).line_to(b);
+            } else {
+                // The offset edges intersect at p + miter
+                self.builder.line_to(p + (n0 + n1) / (1.0 + dot));
+            }
+            return;
+        }
+
+        self.builder.line_to(a);
+        outer_join(&mut self.builder, self.join, self.miter_limit, p, d0, d1, h);
+    }
+
+    /// Caps a line with no length, which has no direction of its own.
+    fn dot(&mut self, p: Point2f, h: f32, cap: LineCap) {
+        match cap {
+            LineCap::Butt => {}
+            LineCap::Square => {
+                self.builder
+                    .move_to(p + Vector2f::new(-h, -h))
+                    .line_to(p + Vector2f::new(h, -h))
+                    .line_to(p + Vector2f::new(h, h))
+                    .line_to(p + Vector2f::new(-h, h))
+                    .close();
+            }
+            LineCap::Round => {
+                let cw = SweepDirection::Clockwise;
+                self.builder
+                    .move_to(p + Vector2f::new(h, 0.0))
+                    .arc_to(p - Vector2f::new(h, 0.0), (h, h), 0.0, cw, ArcSize::Small)
+                    .arc_to(p + Vector2f::new(h, 0.0), (h, h), 0.0, cw, ArcSize::Small)
+                    .close();
+            }
+        }
+    }
+}
+
+/// Continues from `p + normal(d0) * h` around the outside of the corner at
+/// `p` to `p + normal(d1) * h`.
+fn outer_join(
+    builder: &mut PathBuilder,
+    join: LineJoin,
+    miter_limit: f32,
+    p: Point2f,
+    d0: Vector2f,
+    d1: Vector2f,
+    h: f32,
+) {
+    let (n0, n1) = (normal(d0) * h, normal(d1) * h);
+    let b = p + n1;
+    let dot = d0.dot(d1);
+    match join {
+        LineJoin::Miter => {
+            // The miter length is 1 / sin(theta / 2) half widths
+            let near_reversal = 1.0 + dot <= PARALLEL_EPSILON;
+            let ratio_squared = 2.0 / (1.0 + dot);
+            if !near_reversal && ratio_squared <= miter_limit * miter_limit {
+                builder.line_to(p + (n0 + n1) / (1.0 + dot));
+            }
+            builder.line_to(b);
+        }
+        LineJoin::Bevel => {
+            builder.line_to(b);
+        }
+        LineJoin::Round => {
+            let r = h.abs();
+            let clockwise = n0.cross(d0 - d1) > 0.0;
+            builder.arc_to(
+                b,
+                (r, r),
+                0.0,
+                SweepDirection::from(clockwise),
+                ArcSize::Small,
+            );
+        }
+    }
+}
+
+/// Caps the end of a line travelling in the direction `d`, from the left
+/// offset of `p` (the current point) around to the right offset.
+fn cap_to(builder: &mut PathBuilder, cap: LineCap, p: Point2f, d: Vector2f, h: f32) {
+    let n = normal(d) * h;
+    let right = p - n;
+    match cap {
+        LineCap::Butt => {
+            builder.line_to(right);
+        }
+        LineCap::Square => {
+            let along = d * h;
+            builder
+                .line_to(p + n + along)
+                .line_to(right + along)
+                .line_to(right);
+        }
+        LineCap::Round => {
+            let clockwise = n.cross(d) > 0.0;
+            builder
+                .arc_to(
+                    p + d * h,
+                    (h, h),
+                    0.0,
+                    SweepDirection::from(clockwise),
+                    ArcSize::Small,
+                )
+                .arc_to(
+                    right,
+                    (h, h),
+                    0.0,
+                    SweepDirection::from(clockwise),
+                    ArcSize::Small,
+                );
+        }
+    }
+}
+
+/// Determines if a line continues straight on from `d0` to `d1`.
+#[inline]
+fn is_straight(d0: Vector2f, d1: Vector2f) -> bool {
+    d0.cross(d1).abs() <= PARALLEL_EPSILON && d0.dot(d1) > 0.0
+}
+
+/// Determines if the offset `h` is on the outside of the corner. Lines which
+/// reverse direction are outside on both sides.
+#[inline]
+fn is_outer(d0: Vector2f, d1: Vector2f, h: f32) -> bool {
+    let cross = d0.cross(d1);
+    cross * h > 0.0 || (cross.abs() <= PARALLEL_EPSILON && d0.dot(d1) < 0.0)
+}
+
+/// The half width `h` signed to offset to the outside of the corner.
+#[inline]
+fn outer_side(d0: Vector2f, d1: Vector2f, h: f32) -> f32 {
+    if is_outer(d0, d1, h) {
+        h
+    } else {
+        -h
+    }
+}
+
+/// The left normal of a direction in the y-down coordinate system.
+#[inline]
+fn normal(d: Vector2f) -> Vector2f {
+    Vector2f::new(d.y, -d.x)
+}
+
+/// Flattens each figure of the path into a list of distinct points, along
+/// with whether the figure is closed. The closing point of closed figures is
+/// not repeated.
+fn flattened_figures(path: &Path, tolerance: f32) -> Vec<(Vec<Point2f>, bool)> {
+    path.figures
+        .iter()
+        .map(|figure| {
+            let mut points = figure.flatten(tolerance).points;
+            points.dedup_by(|a, b| (*a - *b).len_squared() <= PARALLEL_EPSILON * PARALLEL_EPSILON);
+            if figure.closed && points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+            (points, figure.closed)
+        })
+        .collect()
+}
+
+pub(crate) fn stroke_outline(
+    path: &Path,
+    width: f32,
+    cap: LineCap,
+    join: LineJoin,
+    miter_limit: f32,
+    tolerance: f32,
+) -> Path {
+    let mut stroker = Stroker {
+        builder: PathBuilder::new(),
+        join,
+        miter_limit,
+    };
+    let h = width / 2.0;
+    if h.is_nan() || h <= 0.0 {
+        return stroker.builder.build();
+    }
+
+    for (mut points, closed) in flattened_figures(path, tolerance) {
+        match points.len() {
+            0 => {}
+            1 => stroker.dot(points[0], h, cap),
+            _ if closed => {
+                stroker.side(&points, true, h, true);
+                points.reverse();
+                stroker.side(&points, true, h, true);
+            }
+            n => {
+                let start_dir = (points[1] - points[0]).normalized();
+                let end_dir = (points[n - 1] - points[n - 2]).normalized();
+
+                stroker.builder.move_to(points[0] + normal(start_dir) * h);
+                stroker.side(&points, false, h, true);
+                cap_to(&mut stroker.builder, cap, points[n - 1], end_dir, h);
+                points.reverse();
+                stroker.side(&points, false, h, true);
+                cap_to(&mut stroker.builder, cap, points[n - 1], -start_dir, h);
+                stroker.builder.close();
+            }
+        }
+    }
+    stroker.builder.build()
+}
+
+pub(crate) fn offset(
+    path: &Path,
+    distance: f32,
+    join: LineJoin,
+    miter_limit: f32,
+    tolerance: f32,
+) -> Path {
+    let mut stroker = Stroker {
+        builder: PathBuilder::new(),
+        join,
+        miter_limit,
+    };
+
+    for (points, closed) in flattened_figures(path, tolerance) {
+        if !closed || points.len() < 3 {
+            continue;
+        }
+
+        // The left side is the outside of clockwise figures
+        let n = points.len();
+        let area: f32 = (0..n)
+            .map(|i| points[i].to_vector().cross(points[(i + 1) % n].to_vector()))
+            .sum();
+        let h = if area > 0.0 { distance } else { -distance };
+        stroker.side(&points, true, h, false);
+    }
+    stroker.builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hit_test::HitTest;
+    use crate::path::{Path, PathBuilder, PathSegment};
+    use crate::rectf::Rectf;
+    use crate::stroke::{LineCap, LineJoin};
+
+    fn assert_bounds(path: &Path, expected: Rectf) {
+        let b = path.bounds();
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+        assert!(
+            close(b.left, expected.left)
+                && close(b.top, expected.top)
+                && close(b.right, expected.right)
+                && close(b.bottom, expected.bottom),
+            "{:?} != {:?}",
+            b,
+            expected
+        );
+    }
+
+    #[test]
+    fn caps() {
+        let line = Path::builder().line_to((10.0, 0.0)).build();
+        let stroke = |cap| line.stroke_outline(2.0, cap, LineJoin::Miter, 4.0, 0.01);
+
+        assert_bounds(&stroke(LineCap::Butt), Rectf::new(0.0, -1.0, 10.0, 1.0));
+        assert_bounds(&stroke(LineCap::Square), Rectf::new(-1.0, -1.0, 11.0, 1.0));
+        assert_bounds(&stroke(LineCap::Round), Rectf::new(-1.0, -1.0, 11.0, 1.0));
+
+        let dot = Path::builder()
+            .move_to((5.0, 5.0))
+            .line_to((5.0, 5.0))
+            .build();
+        let circle = dot.stroke_outline(2.0, LineCap::Round, LineJoin::Miter, 4.0, 0.01);
+        assert_bounds(&circle, Rectf::new(4.0, 4.0, 6.0, 6.0));
+        assert!(dot
+            .stroke_outline(2.0, LineCap::Butt, LineJoin::Miter, 4.0, 0.01)
+            .is_empty());
+    }
+
+    #[test]
+    fn joins() {
+        let corner = Path::builder()
+            .line_to((10.0, 0.0))
+            .line_to((10.0, 10.0))
+            .build();
+        let has_point = |path: &Path, x: f32, y: f32| {
+            path.segments().any(|(_, seg)| match *seg {
+                PathSegment::Line(p) => p.is_approx_eq((x, y), 1e-4),
+                _ => false,
+            })
+        };
+
+        let mitered = corner.stroke_outline(2.0, LineCap::Butt, LineJoin::Miter, 4.0, 0.01);
+        assert!(has_point(&mitered, 11.0, -1.0));
+        assert_bounds(&mitered, Rectf::new(0.0, -1.0, 11.0, 10.0));
+
+        // A right angle needs a miter limit of at least sqrt(2)
+        let limited = corner.stroke_outline(2.0, LineCap::Butt, LineJoin::Miter, 1.4, 0.01);
+        assert!(!has_point(&limited, 11.0, -1.0));
+
+        let beveled = corner.stroke_outline(2.0, LineCap::Butt, LineJoin::Bevel, 4.0, 0.01);
+        assert!(!has_point(&beveled, 11.0, -1.0));
+
+        let rounded = corner.stroke_outline(2.0, LineCap::Butt, LineJoin::Round, 4.0, 0.01);
+        assert!(rounded
+            .segments()
+            .any(|(_, seg)| matches!(*seg, PathSegment::Arc(_))));
+    }
+
+    #[test]
+    fn offset() {
+        let clockwise = Path::builder()
+            .line_to((10.0, 0.0))
+            .line_to((10.0, 10.0))
+            .line_to((0.0, 10.0))
+            .close()
+            .build();
+        let offset = |d, join| clockwise.offset(d, join, 4.0, 0.01);
+        assert_bounds(
+            &offset(1.0, LineJoin::Miter),
+            Rectf::new(-1.0, -1.0, 11.0, 11.0),
+        );
+        assert_bounds(
+            &offset(1.0, LineJoin::Round),
+            Rectf::new(-1.0, -1.0, 11.0, 11.0),
+        );
+        assert_bounds(
+            &offset(-1.0, LineJoin::Miter),
+            Rectf::new(1.0, 1.0, 9.0, 9.0),
+        );
+
+        // Positive offsets grow the shape regardless of its winding
+        let counter_clockwise = Path::builder()
+            .line_to((0.0, 10.0))
+            .line_to((10.0, 10.0))
+            .line_to((10.0, 0.0))
+            .close()
+            .build();
+        let grown = counter_clockwise.offset(1.0, LineJoin::Bevel, 4.0, 0.01);
+        assert_bounds(&grown, Rectf::new(-1.0, -1.0, 11.0, 11.0));
+    }
+
+    #[test]
+    fn cap_and_join_pieces() {
+        let cap = |cap: LineCap| cap.outline((10.0, 0.0), [2.0, 0.0], 2.0);
+        assert!(cap(LineCap::Butt).is_empty());
+        assert_bounds(&cap(LineCap::Square), Rectf::new(10.0, -1.0, 11.0, 1.0));
+        assert_bounds(&cap(LineCap::Round), Rectf::new(10.0, -1.0, 11.0, 1.0));
+
+        // Turning clockwise on screen puts the outside of the corner on the
+        // left of the line
+        let join = |join: LineJoin, outgoing: [f32; 2]| {
+            join.outline((10.0, 0.0), [1.0, 0.0], outgoing, 2.0, 4.0)
+        };
+        let mitered = join(LineJoin::Miter, [0.0, 1.0]);
+        assert_bounds(&mitered, Rectf::new(10.0, -1.0, 11.0, 0.0));
+        assert!(mitered.hit((10.9, -0.9).into()));
+        assert!(!join(LineJoin::Bevel, [0.0, 1.0]).hit((10.9, -0.9).into()));
+        assert_bounds(
+            &join(LineJoin::Round, [0.0, -1.0]),
+            Rectf::new(10.0, 0.0, 11.0, 1.0),
+        );
+        assert!(join(LineJoin::Miter, [3.0, 0.0]).is_empty());
+
+        // A custom stroker for a single segment
+        let mut builder = PathBuilder::new();
+        builder.move_to((0.0, -1.0)).line_to((10.0, -1.0));
+        LineCap::Round.append_to(&mut builder, (10.0, 0.0), [1.0, 0.0], 2.0);
+        builder.line_to((0.0, 1.0));
+        LineCap::Square.append_to(&mut builder, (0.0, 0.0), [-1.0, 0.0], 2.0);
+        assert_bounds(&builder.close().build(), Rectf::new(-1.0, -1.0, 11.0, 1.0));
+    }
+}
+
+#[cfg(all(test, windows, feature = "d2d"))]
+#[test]
+fn winapi_stroke_styles() {
+    use winapi::um::d2d1::*;
+
+    assert_eq!(LineCap::Butt.to_d2d(), D2D1_CAP_STYLE_FLAT);
+    assert_eq!(LineCap::Round.to_d2d(), D2D1_CAP_STYLE_ROUND);
+    assert_eq!(LineJoin::Miter.to_d2d(), D2D1_LINE_JOIN_MITER_OR_BEVEL);
+    assert_eq!(LineJoin::Bevel.to_d2d(), D2D1_LINE_JOIN_BEVEL);
+}
+
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn windows_stroke_styles() {
+    let square: d2d::D2D1_CAP_STYLE = LineCap::Square.into();
+    let round: d2d::D2D1_LINE_JOIN = LineJoin::Round.into();
+    assert_eq!(square, d2d::D2D1_CAP_STYLE_SQUARE);
+    assert_eq!(round, d2d::D2D1_LINE_JOIN_ROUND);
+}
@@ -0,0 +1,155 @@
+//! Deterministic input generators for benchmarks and tests.
+//!
+//! These are the generators behind math2d's own benchmarks, exposed so that
+//! downstream crates can measure their code against the same inputs. The
+//! values come from a small seeded generator rather than `rand`, so a seed
+//! produces the same inputs on every platform and every run, which keeps
+//! benchmark results comparable between builds.
+//!
+//! ```
+//! use math2d::test_util::Generator;
+//!
+//! let mut gen = Generator::new(7);
+//! let points = gen.points(1000);
+//! assert_eq!(points, Generator::new(7).points(1000));
+//! ```
+
+use crate::bezier_segment::BezierSegment;
+use crate::matrix3x2f::Matrix3x2f;
+use crate::point2f::Point2f;
+use crate::polygon::Polygon;
+use crate::rectf::Rectf;
+
+use std::f32::consts::PI;
+
+/// Half the width of the square the generated coordinates fall in, centered
+/// on the origin.
+pub const EXTENT: f32 = 1000.0;
+
+/// Seeded source of benchmark inputs. Every method advances the same
+/// sequence, so the inputs depend on the order they are generated in as well
+/// as the seed.
+#[derive(Clone, Debug)]
+pub struct Generator {
+    state: u64,
+}
+
+impl Generator {
+    /// Starts a sequence from the given seed. Any seed is fine, including 0.
+    #[inline]
+    pub fn new(seed: u64) -> Generator {
+        // xorshift gets stuck at 0, so mix the seed into a nonzero state
+        Generator {
+            state: seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1,
+        }
+    }
+
+    /// The next value in `[0, 1)`.
+    #[inline]
+    pub fn unit(&mut self) -> f32 {
+        // xorshift64*, keeping the 24 high bits an f32 can represent exactly
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        let bits = self.state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 40;
+        bits as f32 / (1u32 << 24) as f32
+    }
+
+    /// The next value in `[min, max)`.
+    #[inline]
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + self.unit() * (max - min)
+    }
+
+    /// A point anywhere within `EXTENT` of the origin on each axis.
+    #[inline]
+    pub fn point(&mut self) -> Point2f {
+        Point2f::new(self.range(-EXTENT, EXTENT), self.range(-EXTENT, EXTENT))
+    }
+
+    /// `count` points from `point`.
+    pub fn points(&mut self, count: usize) -> Vec<Point2f> {
+        (0..count).map(|_| self.point()).collect()
+    }
+
+    /// `count` normalized rectangles with their top left corner from `point`
+    /// and sides up to a tenth of the extent long, so that some of them
+    /// overlap and some do not.
+    pub fn rects(&mut self, count: usize) -> Vec<Rectf> {
+        (0..count)
+            .map(|_| {
+                let p = self.point();
+                let w = self.range(0.0, EXTENT / 10.0);
+                let h = self.range(0.0, EXTENT / 10.0);
+                Rectf::new(p.x, p.y, p.x + w, p.y + h)
+            })
+            .collect()
+    }
+
+    /// `count` invertible affine matrices combining a rotation, a nonuniform
+    /// scale and a translation, so that every component is nonzero and none
+    /// of the special-cased matrix paths apply.
+    pub fn matrices(&mut self, count: usize) -> Vec<Matrix3x2f> {
+        (0..count)
+            .map(|_| {
+                let angle = self.range(0.1, 2.0 * PI - 0.1);
+                let scale = [self.range(0.5, 2.0), self.range(0.5, 2.0)];
+                let rotation = Matrix3x2f::rotation(angle, Point2f::ORIGIN);
+                let scaling = Matrix3x2f::scaling(scale, Point2f::ORIGIN);
+                rotation * scaling * Matrix3x2f::translation(self.point().to_vector())
+            })
+            .collect()
+    }
+
+    /// `count` cubic beziers, each paired with the point it starts from. The
+    /// control points are anywhere from `point`, so the curves range from
+    /// nearly straight to looping.
+    pub fn beziers(&mut self, count: usize) -> Vec<(Point2f, BezierSegment)> {
+        (0..count)
+            .map(|_| {
+                let start = self.point();
+                (
+                    start,
+                    BezierSegment::new(self.point(), self.point(), self.point()),
+                )
+            })
+            .collect()
+    }
+
+    /// A simple polygon with `vertices` vertices, made by walking once around
+    /// a circle at a random distance from the center for each vertex. The
+    /// result is star shaped but mostly concave, which is the expensive case
+    /// for triangulation.
+    pub fn polygon(&mut self, vertices: usize) -> Polygon {
+        let points: Vec<Point2f> = (0..vertices)
+            .map(|i| {
+                let angle = i as f32 / vertices as f32 * 2.0 * PI;
+                let radius = self.range(EXTENT / 4.0, EXTENT);
+                Point2f::new(radius * angle.cos(), radius * angle.sin())
+            })
+            .collect();
+        Polygon::new(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic_inputs() {
+        let mut gen = Generator::new(0);
+        let units: Vec<f32> = (0..1000).map(|_| gen.unit()).collect();
+        assert!(units.iter().all(|&u| (0.0..1.0).contains(&u)));
+        assert_ne!(units[0], units[1]);
+
+        let rects = Generator::new(3).rects(100);
+        assert_eq!(rects, Generator::new(3).rects(100));
+        assert!(rects.iter().all(|r| r.left <= r.right && r.top <= r.bottom));
+        assert_ne!(rects, Generator::new(4).rects(100));
+
+        let polygon = Generator::new(5).polygon(64);
+        assert_eq!(polygon.points.len(), 64);
+        assert!(polygon.area() > 0.0);
+    }
+}
@@ -0,0 +1,171 @@
+//! Positioning measured text within a layout box.
+//!
+//! Text engines such as DirectWrite measure a block of text and report its
+//! size and the offset of its first baseline, but leave placing it to the
+//! caller. These types turn those metrics and an alignment into the
+//! rectangle the text occupies and the origin its baseline is drawn from.
+
+use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+use crate::sizef::Sizef;
+
+/// The horizontal alignment of text within its container. The variants
+/// match DirectWrite's `DWRITE_TEXT_ALIGNMENT`, for left-to-right text.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum TextAlignment {
+    /// The text is aligned to the left edge of the container.
+    Leading,
+    /// The text is aligned to the right edge of the container.
+    Trailing,
+    /// The text is centered horizontally in the container.
+    Center,
+}
+
+impl Default for TextAlignment {
+    #[inline]
+    fn default() -> Self {
+        TextAlignment::Leading
+    }
+}
+
+/// The vertical alignment of text within its container. The variants match
+/// DirectWrite's `DWRITE_PARAGRAPH_ALIGNMENT`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ParagraphAlignment {
+    /// The text is aligned to the top of the container.
+    Near,
+    /// The text is aligned to the bottom of the container.
+    Far,
+    /// The text is centered vertically in the container.
+    Center,
+}
+
+impl Default for ParagraphAlignment {
+    #[inline]
+    fn default() -> Self {
+        ParagraphAlignment::Near
+    }
+}
+
+/// The measured extent of a block of text, as reported by the text engine.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct TextMetrics {
+    /// The width of the text.
+    pub width: f32,
+    /// The height of the text, from the top of the first line to the bottom
+    /// of the last.
+    pub height: f32,
+    /// The distance from the top of the text down to the baseline of its
+    /// first line.
+    pub baseline: f32,
+}
+
+impl TextMetrics {
+    /// Constructs the metrics from their components.
+    #[inline]
+    pub fn new(width: f32, height: f32, baseline: f32) -> TextMetrics {
+        TextMetrics {
+            width,
+            height,
+            baseline,
+        }
+    }
+
+    /// The size of the text.
+    #[inline]
+    pub fn size(&self) -> Sizef {
+        Sizef::new(self.width, self.height)
+    }
+
+    /// The rectangle the text occupies when aligned within `container`. Text
+    /// larger than the container overflows it on the side opposite the
+    /// alignment, or on both sides when centered.
+    #[inline]
+    pub fn aligned_in(
+        &self,
+        container: &Rectf,
+        alignment: TextAlignment,
+        paragraph: ParagraphAlignment,
+    ) -> Rectf {
+        let left = match alignment {
+            TextAlignment::Leading => container.left,
+            TextAlignment::Trailing => container.right - self.width,
+            TextAlignment::Center => (container.left + container.right - self.width) / 2.0,
+        };
+        let top = match paragraph {
+            ParagraphAlignment::Near => container.top,
+            ParagraphAlignment::Far => container.bottom - self.height,
+            ParagraphAlignment::Center => (container.top + container.bottom - self.height) / 2.0,
+        };
+        Rectf::new(left, top, left + self.width, top + self.height)
+    }
+
+    /// The point at the left end of the first baseline of text which
+    /// occupies `rect`, such as one returned by `aligned_in`.
+    #[inline]
+    pub fn baseline_origin(&self, rect: &Rectf) -> Point2f {
+        Point2f::new(rect.left, rect.top + self.baseline)
+    }
+
+    /// The point at the left end of the first baseline when the text is
+    /// aligned within `container`.
+    #[inline]
+    pub fn baseline_in(
+        &self,
+        container: &Rectf,
+        alignment: TextAlignment,
+        paragraph: ParagraphAlignment,
+    ) -> Point2f {
+        self.baseline_origin(&self.aligned_in(container, alignment, paragraph))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::point2f::Point2f;
+    use crate::rectf::Rectf;
+    use crate::text::{ParagraphAlignment, TextAlignment, TextMetrics};
+
+    #[test]
+    fn aligned_text() {
+        let button = Rectf::new(10.0, 20.0, 110.0, 60.0);
+        let label = TextMetrics::new(40.0, 16.0, 12.0);
+
+        let centered = label.aligned_in(&button, TextAlignment::Center, ParagraphAlignment::Center);
+        assert_eq!(centered, Rectf::new(40.0, 32.0, 80.0, 48.0));
+        assert_eq!(label.baseline_origin(&centered), Point2f::new(40.0, 44.0));
+
+        let corner = label.baseline_in(&button, TextAlignment::Trailing, ParagraphAlignment::Far);
+        assert_eq!(corner, Point2f::new(70.0, 56.0));
+        assert_eq!(
+            label.aligned_in(&button, Default::default(), Default::default()),
+            Rectf::new(10.0, 20.0, 50.0, 36.0)
+        );
+
+        // Text too wide for its container spills out of both sides
+        let long = TextMetrics::new(140.0, 16.0, 12.0);
+        let spilled = long.aligned_in(&button, TextAlignment::Center, ParagraphAlignment::Near);
+        assert_eq!((spilled.left, spilled.right), (-10.0, 130.0));
+    }
+}
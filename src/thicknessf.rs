@@ -1,10 +1,27 @@
 //! Represents a margin around an axis-aligned rectangle.
 
+use crate::parse::{self, ParseError};
 use crate::vector2f::Vector2f;
 
+use std::fmt;
+use std::str::FromStr;
+
 /// Represents a margin around an axis-aligned rectangle.
 #[derive(Copy, Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde-compact",
+    serde(
+        into = "crate::compact::Array<[f32; 4]>",
+        from = "crate::compact::Array<[f32; 4]>"
+    )
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(C)]
 pub struct Thicknessf {
     /// Left x component
@@ -30,6 +47,29 @@ impl Thicknessf {
     }
 }
 
+impl fmt::Display for Thicknessf {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let components = [&self.left, &self.top, &self.right, &self.bottom];
+        parse::write_components(fmt, ('[', ']'), &components)
+    }
+}
+
+impl FromStr for Thicknessf {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Thicknessf, ParseError> {
+        let [left, top, right, bottom] = parse::parse_components(s, ('[', ']'))?;
+        Ok(Thicknessf {
+            left,
+            top,
+            right,
+            bottom,
+        })
+    }
+}
+
 impl From<Vector2f> for Thicknessf {
     #[inline]
     fn from(vec: Vector2f) -> Thicknessf {
@@ -0,0 +1,222 @@
+//! Keyframe animation of values over time.
+
+use crate::easing::Easing;
+use crate::lerp::Lerp;
+
+/// A value an animation passes through at a point in time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Keyframe<T> {
+    /// When the animation reaches the value.
+    pub time: f32,
+    /// The value of the animation at `time`.
+    pub value: T,
+    /// The curve the animation follows from this keyframe to the next.
+    pub easing: Easing,
+}
+
+/// What a timeline does when sampled outside the times of its keyframes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum LoopMode {
+    /// Holds the first value before the start and the last value after the
+    /// end.
+    Once,
+    /// Starts over from the first keyframe after reaching the last one.
+    Repeat,
+    /// Plays backward after reaching the last keyframe, then forward again
+    /// after reaching the first.
+    PingPong,
+}
+
+impl Default for LoopMode {
+    #[inline]
+    fn default() -> Self {
+        LoopMode::Once
+    }
+}
+
+/// An animation of a value through a sequence of keyframes, blending
+/// between each pair of keyframes along the curve of the earlier one.
+///
+/// Works with any value which implements `Lerp`, which includes points,
+/// vectors, colors and matrices.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct Timeline<T> {
+    /// The keyframes, sorted by time.
+    keyframes: Vec<Keyframe<T>>,
+    loop_mode: LoopMode,
+}
+
+impl<T: Lerp + Clone> Timeline<T> {
+    /// Constructs a timeline with no keyframes which plays once.
+    #[inline]
+    pub fn new() -> Timeline<T> {
+        Timeline::with_loop_mode(LoopMode::Once)
+    }
+
+    /// Constructs a timeline with no keyframes and the given loop mode.
+    #[inline]
+    pub fn with_loop_mode(loop_mode: LoopMode) -> Timeline<T> {
+        Timeline {
+            keyframes: Vec::new(),
+            loop_mode,
+        }
+    }
+
+    /// Adds a keyframe, keeping the keyframes in order of time. A keyframe at
+    /// the same time as an existing one goes after it, so that the value
+    /// jumps at that time.
+    #[inline]
+    pub fn add(&mut self, time: f32, value: T, easing: Easing) -> &mut Self {
+        let index = self.keyframes.partition_point(|k| k.time <= time);
+        self.keyframes.insert(
+            index,
+            Keyframe {
+                time,
+                value,
+                easing,
+            },
+        );
+        self
+    }
+
+    /// The keyframes, in order of time.
+    #[inline]
+    pub fn keyframes(&self) -> &[Keyframe<T>] {
+        &self.keyframes
+    }
+
+    /// What the timeline does outside the times of its keyframes.
+    #[inline]
+    pub fn loop_mode(&self) -> LoopMode {
+        self.loop_mode
+    }
+
+    /// Changes what the timeline does outside the times of its keyframes.
+    #[inline]
+    pub fn set_loop_mode(&mut self, loop_mode: LoopMode) {
+        self.loop_mode = loop_mode;
+    }
+
+    /// The time of the first keyframe, or zero if there are none.
+    #[inline]
+    pub fn start_time(&self) -> f32 {
+        self.keyframes.first().map_or(0.0, |k| k.time)
+    }
+
+    /// The time of the last keyframe, or zero if there are none.
+    #[inline]
+    pub fn end_time(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |k| k.time)
+    }
+
+    /// The time from the first keyframe to the last.
+    #[inline]
+    pub fn duration(&self) -> f32 {
+        self.end_time() - self.start_time()
+    }
+
+    /// The value of the animation at `time`. Returns `None` if there are no
+    /// keyframes.
+    pub fn sample(&self, time: f32) -> Option<T> {
+        let first = self.keyframes.first()?;
+        let (start, duration) = (self.start_time(), self.duration());
+        if duration <= 0.0 {
+            return self.keyframes.last().map(|k| k.value.clone());
+        }
+
+        let elapsed = time - start;
+        let local = match self.loop_mode {
+            LoopMode::Once => elapsed.clamp(0.0, duration),
+            LoopMode::Repeat => elapsed.rem_euclid(duration),
+            LoopMode::PingPong => {
+                let t = elapsed.rem_euclid(2.0 * duration);
+                if t > duration {
+                    2.0 * duration - t
+                } else {
+                    t
+                }
+            }
+        };
+        let time = start + local;
+
+        let next = self.keyframes.partition_point(|k| k.time <= time);
+        if next == 0 {
+            return Some(first.value.clone());
+        }
+        if next == self.keyframes.len() {
+            return Some(self.keyframes[next - 1].value.clone());
+        }
+        let (a, b) = (&self.keyframes[next - 1], &self.keyframes[next]);
+        let t = a.easing.apply((time - a.time) / (b.time - a.time));
+        Some(a.value.lerp(&b.value, t))
+    }
+}
+
+impl<T: Lerp + Clone> Default for Timeline<T> {
+    #[inline]
+    fn default() -> Self {
+        Timeline::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::Color;
+    use crate::easing::Easing;
+    use crate::point2f::Point2f;
+    use crate::timeline::{LoopMode, Timeline};
+
+    #[test]
+    fn sampling() {
+        let mut timeline = Timeline::new();
+        timeline
+            .add(1.0, Point2f::new(0.0, 0.0), Easing::Linear)
+            .add(3.0, Point2f::new(10.0, 0.0), Easing::Hold)
+            .add(5.0, Point2f::new(10.0, 10.0), Easing::Linear);
+        assert_eq!(timeline.duration(), 4.0);
+
+        assert_eq!(timeline.sample(0.0), Some((0.0, 0.0).into()));
+        assert_eq!(timeline.sample(2.0), Some((5.0, 0.0).into()));
+        assert_eq!(timeline.sample(4.9), Some((10.0, 0.0).into()));
+        assert_eq!(timeline.sample(5.0), Some((10.0, 10.0).into()));
+        assert_eq!(timeline.sample(9.0), Some((10.0, 10.0).into()));
+
+        timeline.set_loop_mode(LoopMode::Repeat);
+        assert_eq!(timeline.sample(6.0), Some((5.0, 0.0).into()));
+        timeline.set_loop_mode(LoopMode::PingPong);
+        assert_eq!(timeline.sample(8.0), Some((5.0, 0.0).into()));
+        assert_eq!(timeline.sample(0.0), Some((5.0, 0.0).into()));
+
+        let empty: Timeline<Color> = Timeline::new();
+        assert_eq!(empty.sample(1.0), None);
+
+        // Keyframes at the same time make the value jump
+        let mut jump = Timeline::new();
+        jump.add(0.0, 0.0f32, Easing::Linear)
+            .add(1.0, 1.0, Easing::Linear)
+            .add(1.0, 5.0, Easing::Linear)
+            .add(2.0, 6.0, Easing::Linear);
+        assert_eq!(jump.sample(0.5), Some(0.5));
+        assert_eq!(jump.sample(1.5), Some(5.5));
+    }
+}
@@ -0,0 +1,192 @@
+//! Tolerances for approximate comparisons and curve approximation.
+//!
+//! A fixed epsilon like `1e-5` is far too loose for a drawing measured in
+//! millimeters and far too strict for one measured in kilometers, where
+//! neighbouring `f32` values are already further apart than that. A
+//! `Tolerance` combines an absolute floor with a tolerance relative to the
+//! size of the values involved, plus a separate tolerance for angles.
+//!
+//! Every API which accepts a `Tolerance` takes `impl Into<Tolerance>`, and
+//! a plain `f32` converts into a purely absolute tolerance, so existing code
+//! which passes a number keeps its meaning.
+
+use crate::angle;
+use crate::rectf::Rectf;
+
+/// How far apart two values may be while still being considered equal.
+///
+/// For comparisons the relative part scales with the magnitude of the values
+/// being compared. For flattening and simplification it scales with the size
+/// of the shape, measured as the larger side of its bounding box, so that a
+/// curve is approximated equally well at any zoom level. The angular part
+/// is in radians, and is used wherever two directions are compared.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Tolerance {
+    /// The smallest distance which is always tolerated.
+    pub absolute: f32,
+    /// The tolerated distance as a fraction of the magnitude of the values.
+    pub relative: f32,
+    /// The tolerated difference between two angles, in radians.
+    pub angular: f32,
+}
+
+impl Tolerance {
+    /// The defaults for comparing coordinates: an absolute `1e-5`, which
+    /// suits values near unit size, and a relative `1e-6`, a handful of ULPs,
+    /// which takes over for values larger than 10.
+    pub const DEFAULT: Tolerance = Tolerance {
+        absolute: 1e-5,
+        relative: 1e-6,
+        angular: 1e-5,
+    };
+
+    /// The default tolerance for flattening curves, a quarter of a unit as
+    /// in `DEFAULT_FLATTENING_TOLERANCE`.
+    pub const FLATTENING: Tolerance = Tolerance {
+        absolute: crate::shape::DEFAULT_FLATTENING_TOLERANCE,
+        relative: 0.0,
+        angular: 0.0,
+    };
+
+    /// Constructs a tolerance from its components.
+    #[inline]
+    pub const fn new(absolute: f32, relative: f32, angular: f32) -> Tolerance {
+        Tolerance {
+            absolute,
+            relative,
+            angular,
+        }
+    }
+
+    /// A tolerance of exactly `absolute`, whatever the size of the values.
+    /// This is what a plain `f32` converts into.
+    #[inline]
+    pub const fn absolute(absolute: f32) -> Tolerance {
+        Tolerance::new(absolute, 0.0, 0.0)
+    }
+
+    /// A tolerance which is purely a fraction of the size of the values.
+    #[inline]
+    pub const fn relative(relative: f32) -> Tolerance {
+        Tolerance::new(0.0, relative, 0.0)
+    }
+
+    /// Replaces the relative part of the tolerance.
+    #[inline]
+    pub const fn with_relative(self, relative: f32) -> Tolerance {
+        Tolerance { relative, ..self }
+    }
+
+    /// Replaces the angular part of the tolerance.
+    #[inline]
+    pub const fn with_angular(self, angular: f32) -> Tolerance {
+        Tolerance { angular, ..self }
+    }
+
+    /// The tolerated distance for values of the given magnitude or shapes of
+    /// the given size.
+    #[inline]
+    pub fn distance(&self, size: f32) -> f32 {
+        self.absolute.max(self.relative * size.abs())
+    }
+
+    /// The tolerated distance for a shape with the given bounds.
+    #[inline]
+    pub fn distance_in(&self, bounds: &Rectf) -> f32 {
+        let size = bounds.size();
+        self.distance(size.width.abs().max(size.height.abs()))
+    }
+
+    /// Resolves the tolerance for a shape, only computing its bounds when the
+    /// relative part needs them.
+    #[inline]
+    pub(crate) fn resolve(&self, bounds: impl FnOnce() -> Rectf) -> f32 {
+        if self.relative == 0.0 {
+            self.absolute
+        } else {
+            self.distance_in(&bounds())
+        }
+    }
+
+    /// Resolves the tolerance for flattening a curve with the given bounds.
+    /// Neighbouring `f32` coordinates are about `f32::EPSILON` times the
+    /// size of the curve apart, so finer tolerances, including zero and
+    /// negative ones, are raised to that.
+    #[inline]
+    pub(crate) fn resolve_flattening(&self, bounds: &Rectf) -> f32 {
+        let size = bounds.size();
+        let size = size.width.abs().max(size.height.abs());
+        self.distance(size).max(f32::EPSILON * size)
+    }
+
+    /// Determines if two values are within the tolerance of each other,
+    /// relative to the larger of their magnitudes. NaN is never equal to
+    /// anything.
+    #[inline]
+    pub fn approx_eq(&self, a: f32, b: f32) -> bool {
+        (a - b).abs() <= self.distance(a.abs().max(b.abs()))
+    }
+
+    /// Determines if two angles in radians are within the angular tolerance
+    /// of each other, treating angles a whole turn apart as equal.
+    #[inline]
+    pub fn angles_eq(&self, a: f32, b: f32) -> bool {
+        angle::shortest_delta(a, b).abs() <= self.angular
+    }
+}
+
+impl Default for Tolerance {
+    #[inline]
+    fn default() -> Tolerance {
+        Tolerance::DEFAULT
+    }
+}
+
+impl From<f32> for Tolerance {
+    #[inline]
+    fn from(absolute: f32) -> Tolerance {
+        Tolerance::absolute(absolute)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier_segment::BezierSegment;
+    use crate::point2f::Point2f;
+    use crate::tolerance::Tolerance;
+
+    #[test]
+    fn scaled_comparisons() {
+        let tol = Tolerance::DEFAULT;
+        assert!(tol.approx_eq(1.0, 1.0 + 5e-6));
+        assert!(!tol.approx_eq(1.0, 1.0 + 5e-5));
+
+        // Neighbouring floats near a million are 0.0625 apart
+        assert!(!Tolerance::from(1e-5).approx_eq(1.0e6, 1.0e6 + 0.0625));
+        assert!(tol.approx_eq(1.0e6, 1.0e6 + 0.0625));
+        assert!(!tol.approx_eq(f32::NAN, f32::NAN));
+
+        assert!(tol.angles_eq(std::f32::consts::PI, -std::f32::consts::PI));
+        assert!(!tol.angles_eq(0.0, 1e-3));
+        assert!(Point2f::new(1.0e6, 2.0).is_approx_eq((1.0e6 + 0.0625, 2.0), tol));
+    }
+
+    #[test]
+    fn relative_flattening() {
+        // The same curve at two scales flattens into the same number of
+        // points under a relative tolerance
+        let bez = BezierSegment::new((0.0, 10.0), (10.0, 10.0), (10.0, 0.0));
+        let big = BezierSegment::new((0.0, 1e4), (1e4, 1e4), (1e4, 0.0));
+        let tol = Tolerance::relative(1e-3);
+        let small_count = bez.flatten((0.0, 0.0), tol).len();
+        assert_eq!(big.flatten((0.0, 0.0), tol).len(), small_count);
+        assert!(big.flatten((0.0, 0.0), 0.01).len() > small_count);
+    }
+}
@@ -0,0 +1,151 @@
+//! A stack of nested transforms, for traversing scene graphs.
+
+use crate::matrix3x2f::Matrix3x2f;
+
+use std::ops::{Deref, DerefMut};
+
+/// Tracks the combined transform of nested scene nodes. Each pushed
+/// transform is local to the ones beneath it, so the current transform
+/// applies the innermost transform first and the outermost last.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransformStack {
+    base: Matrix3x2f,
+    /// Each local transform pushed, with the combined transform it makes.
+    entries: Vec<(Matrix3x2f, Matrix3x2f)>,
+}
+
+impl TransformStack {
+    /// Constructs an empty stack whose current transform is the identity.
+    #[inline]
+    pub fn new() -> TransformStack {
+        TransformStack::with_base(Matrix3x2f::IDENTITY)
+    }
+
+    /// Constructs an empty stack with the given transform at the bottom,
+    /// such as the transform from the root of the scene to device pixels.
+    #[inline]
+    pub fn with_base(base: Matrix3x2f) -> TransformStack {
+        TransformStack {
+            base,
+            entries: Vec::new(),
+        }
+    }
+
+    /// The number of transforms pushed on top of the base.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// The combined transform from the innermost pushed space to the
+    /// space of the base.
+    #[inline]
+    pub fn current(&self) -> Matrix3x2f {
+        match self.entries.last() {
+            Some(&(_, combined)) => combined,
+            None => self.base,
+        }
+    }
+
+    /// Enters a space transformed by `local` relative to the current one.
+    #[inline]
+    pub fn push(&mut self, local: Matrix3x2f) {
+        let parent = self.current();
+        self.entries.push((local, local * parent));
+    }
+
+    /// Leaves the innermost space, returning the local transform that was
+    /// pushed for it. Returns `None` if only the base is left.
+    #[inline]
+    pub fn pop(&mut self) -> Option<Matrix3x2f> {
+        self.entries.pop().map(|(local, _)| local)
+    }
+
+    /// Pushes `local` and returns a guard which pops it again when dropped.
+    /// The guard gives access to the stack, so nested scopes can push their
+    /// own guards. Anything left pushed within the scope is popped with it.
+    #[inline]
+    pub fn scoped(&mut self, local: Matrix3x2f) -> TransformGuard<'_> {
+        let depth = self.entries.len();
+        self.push(local);
+        TransformGuard { stack: self, depth }
+    }
+}
+
+impl Default for TransformStack {
+    #[inline]
+    fn default() -> Self {
+        TransformStack::new()
+    }
+}
+
+/// Keeps a transform pushed on a `TransformStack` until it is dropped.
+/// Created by `TransformStack::scoped`.
+#[derive(Debug)]
+pub struct TransformGuard<'a> {
+    stack: &'a mut TransformStack,
+    depth: usize,
+}
+
+impl<'a> Deref for TransformGuard<'a> {
+    type Target = TransformStack;
+
+    #[inline]
+    fn deref(&self) -> &TransformStack {
+        self.stack
+    }
+}
+
+impl<'a> DerefMut for TransformGuard<'a> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut TransformStack {
+        self.stack
+    }
+}
+
+impl<'a> Drop for TransformGuard<'a> {
+    #[inline]
+    fn drop(&mut self) {
+        self.stack.entries.truncate(self.depth);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix3x2f::Matrix3x2f;
+    use crate::point2f::Point2f;
+    use crate::transform_stack::TransformStack;
+
+    #[test]
+    fn nesting() {
+        let mut stack = TransformStack::new();
+        let parent = Matrix3x2f::translation([100.0, 0.0]);
+        let child = Matrix3x2f::scaling([2.0, 2.0], Point2f::ORIGIN);
+
+        stack.push(parent);
+        stack.push(child);
+        // The child's scale applies before its parent's translation
+        let p = stack.current().transform_point((1.0, 1.0));
+        assert_eq!(p, (102.0, 2.0).into());
+        assert_eq!(stack.depth(), 2);
+
+        assert_eq!(stack.pop(), Some(child));
+        assert_eq!(stack.current(), parent);
+        assert_eq!(stack.pop(), Some(parent));
+        assert_eq!(stack.pop(), None);
+        assert!(stack.current().is_identity());
+
+        {
+            let mut outer = stack.scoped(parent);
+            {
+                let mut inner = outer.scoped(child);
+                inner.push(child);
+                assert_eq!(inner.depth(), 3);
+            }
+            assert_eq!(outer.depth(), 1);
+            assert_eq!(outer.current(), parent);
+        }
+        assert_eq!(stack.depth(), 0);
+    }
+}
@@ -0,0 +1,234 @@
+//! Applying affine transformations to any shape.
+
+use crate::arc_segment::ArcSegment;
+use crate::bezier_segment::BezierSegment;
+use crate::circle::Circle;
+use crate::ellipse::Ellipse;
+use crate::geometry_group::{Geometry, GeometryGroup};
+use crate::matrix3x2f::Matrix3x2f;
+use crate::oriented_ellipse::OrientedEllipse;
+use crate::oriented_rect::OrientedRect;
+use crate::path::{Figure, Path, PathSegment};
+use crate::point2f::Point2f;
+use crate::polygon::Polygon;
+use crate::polyline::Polyline;
+use crate::quad_bezier_segment::QuadBezierSegment;
+use crate::rectf::Rectf;
+use crate::rounded_rect::RoundedRect;
+use crate::triangle::Triangle;
+use crate::vector2f::Vector2f;
+
+/// Values which can have an affine transformation applied to them.
+///
+/// Shapes which can represent any transformation of themselves produce the
+/// same type. The axis-aligned shapes cannot represent rotation or skew, so
+/// they produce the most specific type which can represent the result
+/// exactly: an `OrientedRect` for rectangles, an `OrientedEllipse` for
+/// ellipses and circles, and a `Path` for rounded rectangles.
+///
+/// This is the one definition of what each shape becomes. `Shape2D` and
+/// `Geometry` transform shapes through it, then store the result as an
+/// axis-aligned shape again when it still is one.
+///
+/// Segments of a path do not store their start point, so only the points
+/// which they hold are transformed. The start point must be transformed
+/// separately.
+pub trait Transformable {
+    /// The type of the transformed value.
+    type Output;
+
+    /// Applies the transformation to the value.
+    fn transformed(&self, matrix: &Matrix3x2f) -> Self::Output;
+}
+
+impl Transformable for Point2f {
+    type Output = Point2f;
+
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> Point2f {
+        *self * *matrix
+    }
+}
+
+/// Vectors are directions rather than positions, so the translation is
+/// ignored.
+impl Transformable for Vector2f {
+    type Output = Vector2f;
+
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> Vector2f {
+        *self * *matrix
+    }
+}
+
+impl Transformable for Triangle {
+    type Output = Triangle;
+
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> Triangle {
+        Triangle {
+            p1: self.p1 * *matrix,
+            p2: self.p2 * *matrix,
+            p3: self.p3 * *matrix,
+        }
+    }
+}
+
+/// The top left corner and the top and left edges are transformed, so the
+/// result is `OrientedRect::from(rect)` under the transformation. Use
+/// `Matrix3x2f::transform_rect` for the bounds instead.
+impl Transformable for Rectf {
+    type Output = OrientedRect;
+
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> OrientedRect {
+        OrientedRect::from(*self).transformed(matrix)
+    }
+}
+
+impl Transformable for OrientedRect {
+    type Output = OrientedRect;
+
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> OrientedRect {
+        OrientedRect {
+            origin: self.origin * *matrix,
+            x_axis: self.x_axis * *matrix,
+            y_axis: self.y_axis * *matrix,
+        }
+    }
+}
+
+/// The corner arcs of a skewed rounded rectangle are no longer aligned with
+/// its edges, which only a path can represent.
+impl Transformable for RoundedRect {
+    type Output = Path;
+
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> Path {
+        Path::from(*self).transformed(matrix)
+    }
+}
+
+impl Transformable for Ellipse {
+    type Output = OrientedEllipse;
+
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> OrientedEllipse {
+        OrientedEllipse::from(*self).transformed(matrix)
+    }
+}
+
+/// The image of the ellipse is found from the images of its two radii, which
+/// are conjugate radii of the result.
+impl Transformable for OrientedEllipse {
+    type Output = OrientedEllipse;
+
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> OrientedEllipse {
+        let (sin, cos) = self.rotation.sin_cos();
+        let u = Vector2f::new(cos, sin) * self.radius_x;
+        let v = Vector2f::new(-sin, cos) * self.radius_y;
+        OrientedEllipse::from_conjugate_radii(self.center * *matrix, u * *matrix, v * *matrix)
+    }
+}
+
+impl Transformable for Circle {
+    type Output = OrientedEllipse;
+
+    #[inline]
+    fn transformed(&self, matrix: &Matrix3x2f) -> OrientedEllipse {
+        Ellipse::from(*self).transformed(matrix)
+    }
+}
+
+macro_rules! inherent_transformable {
+    ($($ty:ty),*) => {$(
+        impl Transformable for $ty {
+            type Output = $ty;
+
+            #[inline]
+            fn transformed(&self, matrix: &Matrix3x2f) -> $ty {
+                <$ty>::transformed(self, matrix)
+            }
+        }
+    )*};
+}
+
+inherent_transformable!(
+    BezierSegment,
+    QuadBezierSegment,
+    ArcSegment,
+    PathSegment,
+    Figure,
+    Path,
+    Polyline,
+    Polygon,
+    Geometry,
+    GeometryGroup
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::ellipse::Ellipse;
+    use crate::matrix3x2f::Matrix3x2f;
+    use crate::oriented_rect::OrientedRect;
+    use crate::path::Path;
+    use crate::point2f::Point2f;
+    use crate::rectf::Rectf;
+    use crate::transformable::Transformable;
+    use crate::triangle::Triangle;
+    use crate::vector2f::Vector2f;
+
+    /// Transforms a shape without knowing its type, as scene code would.
+    fn transform_all<T: Transformable>(shapes: &[T], matrix: &Matrix3x2f) -> Vec<T::Output> {
+        shapes.iter().map(|s| s.transformed(matrix)).collect()
+    }
+
+    #[test]
+    fn generic_transforms() {
+        let m = Matrix3x2f::rotation(std::f32::consts::FRAC_PI_2, (0.0, 0.0))
+            * Matrix3x2f::translation([10.0, 0.0]);
+
+        let p = Point2f::new(1.0, 0.0).transformed(&m);
+        assert!(p.is_approx_eq((10.0, 1.0), 1e-5));
+        let v = Vector2f::new(1.0, 0.0).transformed(&m);
+        assert!(v.is_approx_eq([0.0, 1.0], 1e-5));
+
+        let rects = [Rectf::new(0.0, 0.0, 2.0, 1.0)];
+        let rotated: Vec<OrientedRect> = transform_all(&rects, &m);
+        assert!((rotated[0].area() - 2.0).abs() < 1e-5);
+        assert!(rotated[0].corners()[2].is_approx_eq((9.0, 2.0), 1e-5));
+
+        let triangle = Triangle::from(((0.0, 0.0), (1.0, 0.0), (0.0, 1.0))).transformed(&m);
+        assert!(triangle.p3.is_approx_eq((9.0, 0.0), 1e-5));
+
+        let scale = Matrix3x2f::scaling([2.0, 3.0], (0.0, 0.0));
+        let ellipse = Ellipse::new((1.0, 1.0), 1.0, 1.0).transformed(&scale);
+        let bounds = ellipse.bounds();
+        assert!((bounds.left - 0.0).abs() < 1e-4 && (bounds.right - 4.0).abs() < 1e-4);
+        assert!((bounds.top - 0.0).abs() < 1e-4 && (bounds.bottom - 6.0).abs() < 1e-4);
+        assert_eq!(
+            ellipse.to_ellipse(),
+            Some(Ellipse::new((2.0, 3.0), 2.0, 3.0))
+        );
+
+        // Skewing an ellipse agrees with skewing the points on its outline
+        let skew = Matrix3x2f::skew(0.5, 0.2, (1.0, 1.0)) * m;
+        let ellipse = Ellipse::new((1.0, 1.0), 3.0, 1.0);
+        let skewed = ellipse.transformed(&skew);
+        let center = ellipse.center * skew;
+        assert!(skewed.center.is_approx_eq(center, 1e-5));
+        for i in 0..16 {
+            let (sin, cos) = (i as f32 * std::f32::consts::PI / 8.0).sin_cos();
+            let p = (ellipse.center + [3.0 * cos, sin]) * skew;
+            assert!(skewed.contains_point(center + (p - center) * 0.999));
+            assert!(!skewed.contains_point(center + (p - center) * 1.001));
+        }
+        // The transformed path only approximates the skewed arcs
+        let outline = Path::from(ellipse).transformed(&skew).bounds();
+        let bounds = skewed.bounds();
+        assert!((bounds.left - outline.left).abs() < 1e-3);
+        assert!((bounds.bottom - outline.bottom).abs() < 1e-3);
+    }
+}
@@ -1,13 +1,27 @@
 //! Represents a triangle described by its 3 corners.
 
+use crate::area_moments::{self, AreaMoments};
+use crate::fill_rule::FillRule;
 use crate::point2f::Point2f;
+use crate::predicates::orient2d;
+use crate::raster::{self, PolygonSpans};
 
+#[cfg(feature = "rand")]
+use rand::Rng;
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::d2d1::D2D1_TRIANGLE;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D as d2d;
 
 /// Represents a triangle described by its 3 corners.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(C)]
 pub struct Triangle {
     /// The first point
@@ -18,6 +32,63 @@ pub struct Triangle {
     pub p3: Point2f,
 }
 
+impl Triangle {
+    /// The signed area of the triangle, positive when its points wind
+    /// clockwise on screen.
+    #[inline]
+    pub fn area(&self) -> f32 {
+        (self.p2 - self.p1).cross(self.p3 - self.p1) / 2.0
+    }
+
+    /// Determines if the points of the triangle are collinear, which is
+    /// decided exactly with `predicates::orient2d`.
+    #[inline]
+    pub fn is_degenerate(&self) -> bool {
+        orient2d(self.p1, self.p2, self.p3) == 0.0
+    }
+
+    /// The center of mass of the triangle, the average of its points.
+    #[inline]
+    pub fn centroid(&self) -> Point2f {
+        let sum = self.p1.to_vector() + self.p2.to_vector() + self.p3.to_vector();
+        (sum / 3.0).to_point()
+    }
+
+    /// The second moments of the triangle's area about the x and y axes.
+    #[inline]
+    pub fn second_moment_of_area(&self) -> AreaMoments {
+        area_moments::polygon_moments(&[self.p1, self.p2, self.p3], Point2f::ORIGIN)
+    }
+
+    /// The second moments of the triangle's area about axes through its
+    /// centroid.
+    #[inline]
+    pub fn centroidal_moments(&self) -> AreaMoments {
+        area_moments::polygon_moments(&[self.p1, self.p2, self.p3], self.centroid())
+    }
+
+    /// The rows of pixels covered by the triangle, for filling it on an
+    /// integer grid. See `raster::PolygonSpans`.
+    #[inline]
+    pub fn spans(&self) -> PolygonSpans {
+        raster::polygon_spans(&[self.p1, self.p2, self.p3], FillRule::NonZero)
+    }
+
+    /// Picks a point inside the triangle at random, with every part of its
+    /// area equally likely.
+    #[cfg(feature = "rand")]
+    #[inline]
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point2f {
+        // Points beyond the diagonal of the unit square fold back inside
+        let (mut u, mut v) = (rng.gen::<f32>(), rng.gen::<f32>());
+        if u + v > 1.0 {
+            u = 1.0 - u;
+            v = 1.0 - v;
+        }
+        self.p1 + (self.p2 - self.p1) * u + (self.p3 - self.p1) * v
+    }
+}
+
 impl<P1, P2, P3> From<(P1, P2, P3)> for Triangle
 where
     P1: Into<Point2f>,
@@ -57,3 +128,64 @@ impl From<D2D1_TRIANGLE> for Triangle {
         }
     }
 }
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<Triangle> for d2d::D2D1_TRIANGLE {
+    #[inline]
+    fn from(seg: Triangle) -> d2d::D2D1_TRIANGLE {
+        d2d::D2D1_TRIANGLE {
+            point1: seg.p1.into(),
+            point2: seg.p2.into(),
+            point3: seg.p3.into(),
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "windows"))]
+impl From<d2d::D2D1_TRIANGLE> for Triangle {
+    #[inline]
+    fn from(seg: d2d::D2D1_TRIANGLE) -> Triangle {
+        Triangle {
+            p1: seg.point1.into(),
+            p2: seg.point2.into(),
+            p3: seg.point3.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circle::Circle;
+    use crate::ellipse::Ellipse;
+    use crate::rectf::Rectf;
+    use crate::triangle::Triangle;
+
+    #[test]
+    fn degenerate_shapes() {
+        assert!(Triangle::from(((0.0, 0.0), (1.0, 1.0), (3.0, 3.0))).is_degenerate());
+        assert!(!Triangle::from(((0.0, 0.0), (1.0, 1.0), (3.0, 3.001))).is_degenerate());
+        assert!(Rectf::new(0.0, 0.0, 0.0, 10.0).is_degenerate());
+        assert!(Rectf::new(10.0, 0.0, 0.0, 10.0).is_degenerate());
+        assert!(!Rectf::new(0.0, 0.0, 1.0, 10.0).is_degenerate());
+        assert!(Ellipse::new((0.0, 0.0), 5.0, 0.0).is_degenerate());
+        assert!(Circle::new((0.0, 0.0), f32::NAN).is_degenerate());
+    }
+
+    #[test]
+    fn moments() {
+        // A right triangle with legs b = 3 and h = 6 along the axes
+        let tri = Triangle::from(((0.0, 0.0), (3.0, 0.0), (0.0, 6.0)));
+        assert_eq!(tri.area(), 9.0);
+        assert_eq!(tri.centroid(), (1.0, 2.0).into());
+
+        let m = tri.second_moment_of_area();
+        assert!((m.ixx - 3.0 * 216.0 / 12.0).abs() < 1e-4);
+        assert!((m.iyy - 6.0 * 27.0 / 12.0).abs() < 1e-4);
+        assert!((m.ixy - 9.0 * 36.0 / 24.0).abs() < 1e-4);
+
+        let c = tri.centroidal_moments();
+        assert!((c.ixx - 3.0 * 216.0 / 36.0).abs() < 1e-4);
+        assert!((c.iyy - 6.0 * 27.0 / 36.0).abs() < 1e-4);
+        assert!((c.ixy + 9.0 * 36.0 / 72.0).abs() < 1e-4);
+    }
+}
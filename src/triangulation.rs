@@ -0,0 +1,289 @@
+//! Triangulation of point sets into meshes.
+
+use crate::point2f::Point2f;
+use crate::polygon::Polygon;
+use crate::predicates::{in_circle, orient2d};
+use crate::triangle::Triangle;
+
+/// How many times larger than the input the enclosing "super triangle" is.
+/// It must be large enough that its vertices do not distort the hull of the
+/// triangulation.
+const SUPER_TRIANGLE_SCALE: f64 = 1000.0;
+
+#[derive(Copy, Clone)]
+struct MeshTriangle {
+    vertices: [usize; 3],
+}
+
+impl MeshTriangle {
+    #[inline]
+    fn new(a: usize, b: usize, c: usize) -> MeshTriangle {
+        MeshTriangle {
+            vertices: [a, b, c],
+        }
+    }
+
+    /// Every mesh triangle winds clockwise, so the predicate is positive
+    /// strictly inside the circumcircle.
+    #[inline]
+    fn circumcircle_contains(&self, points: &[(f64, f64)], p: (f64, f64)) -> bool {
+        let [a, b, c] = self.vertices;
+        in_circle(points[a], points[b], points[c], p) > 0.0
+    }
+}
+
+/// Computes the Delaunay triangulation of a set of points with the
+/// Bowyer-Watson algorithm, so that no point lies inside the circumcircle of
+/// any triangle. The triangles cover the convex hull of the points, and each
+/// winds clockwise.
+///
+/// Duplicate points are ignored. Where four or more points lie on a common
+/// circle, such as the corners of a square, any of the valid triangulations
+/// may be returned. Fewer than 3 distinct points, or points which all lie on
+/// a single line, produce no triangles.
+///
+/// This takes quadratic time in the worst case, which is fine for the few
+/// thousand points of a typical visualization.
+pub fn delaunay(points: &[Point2f]) -> Vec<Triangle> {
+    let mut unique: Vec<Point2f> = points.to_vec();
+    unique.sort_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap());
+    unique.dedup();
+    if unique.len() < 3 {
+        return Vec::new();
+    }
+
+    // Work in double precision relative to the center of the input
+    let (mut min, mut max) = (unique[0], unique[0]);
+    for p in &unique {
+        min = Point2f::new(min.x.min(p.x), min.y.min(p.y));
+        max = Point2f::new(max.x.max(p.x), max.y.max(p.y));
+    }
+    let mid = (
+        (min.x as f64 + max.x as f64) / 2.0,
+        (min.y as f64 + max.y as f64) / 2.0,
+    );
+    let extent = ((max.x - min.x) as f64).max((max.y - min.y) as f64);
+
+    let n = unique.len();
+    let mut coords: Vec<(f64, f64)> = unique
+        .iter()
+        .map(|p| (p.x as f64 - mid.0, p.y as f64 - mid.1))
+        .collect();
+    let s = extent * SUPER_TRIANGLE_SCALE;
+    coords.extend_from_slice(&[(-s, -s), (s, -s), (0.0, s)]);
+
+    let mut triangles = vec![MeshTriangle::new(n, n + 1, n + 2)];
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+    for i in 0..n {
+        let p = coords[i];
+
+        // Carve out the cavity of triangles whose circumcircles contain the
+        // point, keeping the edges which are not shared within it
+        edges.clear();
+        triangles.retain(|t| {
+            if !t.circumcircle_contains(&coords, p) {
+                return true;
+            }
+            let [a, b, c] = t.vertices;
+            for &(u, v) in &[(a, b), (b, c), (c, a)] {
+                match edges.iter().position(|&(x, y)| x == v && y == u) {
+                    Some(shared) => {
+                        edges.swap_remove(shared);
+                    }
+                    None => edges.push((u, v)),
+                }
+            }
+            false
+        });
+
+        for &(u, v) in &edges {
+            triangles.push(MeshTriangle::new(u, v, i));
+        }
+    }
+
+    // Every triangle keeps the clockwise winding of the super triangle, and
+    // collinear input leaves only triangles attached to it
+    triangles
+        .iter()
+        .filter(|t| t.vertices.iter().all(|&v| v < n))
+        .map(|t| {
+            let [a, b, c] = t.vertices;
+            Triangle {
+                p1: unique[a],
+                p2: unique[b],
+                p3: unique[c],
+            }
+        })
+        .collect()
+}
+
+/// Splits a simple polygon into triangles by ear clipping, so that the
+/// triangles exactly cover the polygon's area. The polygon may wind either
+/// way, but the triangles each wind clockwise. Collinear vertices produce no
+/// slivers, and a polygon with no area produces no triangles.
+///
+/// Self-intersecting polygons have no well-defined triangulation; the result
+/// for them covers some of their area but is otherwise unspecified. This
+/// takes quadratic time in the number of vertices.
+pub fn ear_clip(polygon: &[Point2f]) -> Vec<Triangle> {
+    let mut indices: Vec<usize> = (0..polygon.len()).collect();
+    if Polygon::new(polygon.to_vec()).area() < 0.0 {
+        indices.reverse();
+    }
+
+    let mut triangles = Vec::with_capacity(polygon.len().saturating_sub(2));
+    let mut i = 0;
+    let mut since_clip = 0;
+    while indices.len() >= 3 && since_clip < indices.len() {
+        let n = indices.len();
+        let (ia, ib, ic) = (
+            indices[(i + n - 1) % n],
+            indices[i % n],
+            indices[(i + 1) % n],
+        );
+        let (a, b, c) = (polygon[ia], polygon[ib], polygon[ic]);
+        let turn = orient2d(a, b, c);
+
+        let is_ear = turn > 0.0
+            && indices.iter().all(|&j| {
+                let p = polygon[j];
+                j == ia
+                    || j == ib
+                    || j == ic
+                    || p == a
+                    || p == b
+                    || p == c
+                    || orient2d(a, b, p) < 0.0
+                    || orient2d(b, c, p) < 0.0
+                    || orient2d(c, a, p) < 0.0
+            });
+
+        if is_ear || turn == 0.0 {
+            if is_ear {
+                triangles.push(Triangle {
+                    p1: a,
+                    p2: b,
+                    p3: c,
+                });
+            }
+            indices.remove(i % n);
+            since_clip = 0;
+        } else {
+            i += 1;
+            since_clip += 1;
+        }
+        i %= indices.len().max(1);
+    }
+    triangles
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::point2f::Point2f;
+    use crate::polygon::Polygon;
+    use crate::triangle::Triangle;
+    use crate::triangulation::{delaunay, ear_clip};
+    use rand::{Rng, SeedableRng, XorShiftRng};
+
+    const SEED: [u8; 16] = [
+        0x3c, 0x91, 0x0e, 0x7a, 0x55, 0xd2, 0x18, 0xb4, 0x6f, 0x02, 0xe7, 0x4b, 0xa9, 0x30, 0xc5,
+        0x81,
+    ];
+
+    fn area(t: &Triangle) -> f32 {
+        (t.p2 - t.p1).cross(t.p3 - t.p1) / 2.0
+    }
+
+    #[test]
+    fn random_points() {
+        let mut rng = XorShiftRng::from_seed(SEED);
+        let points: Vec<Point2f> = (0..200)
+            .map(|_| (rng.gen::<f32>() * 100.0, rng.gen::<f32>() * 100.0).into())
+            .collect();
+        let triangles = delaunay(&points);
+
+        // Euler's formula bounds the triangle count by 2n - 5
+        assert!(triangles.len() > 300 && triangles.len() <= 395);
+        for t in &triangles {
+            assert!(area(t) > 0.0);
+
+            // No other point may lie inside any circumcircle
+            let f = |p: Point2f| (p.x as f64, p.y as f64);
+            let (a, b, c) = (f(t.p1), f(t.p2), f(t.p3));
+            for &p in &points {
+                let (px, py) = f(p);
+                let (ax, ay) = (a.0 - px, a.1 - py);
+                let (bx, by) = (b.0 - px, b.1 - py);
+                let (cx, cy) = (c.0 - px, c.1 - py);
+                let det = (ax * ax + ay * ay) * (bx * cy - cx * by)
+                    - (bx * bx + by * by) * (ax * cy - cx * ay)
+                    + (cx * cx + cy * cy) * (ax * by - bx * ay);
+                assert!(det <= 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_input() {
+        let square: Vec<Point2f> = vec![
+            (0.0, 0.0).into(),
+            (1.0, 0.0).into(),
+            (1.0, 1.0).into(),
+            (0.0, 1.0).into(),
+            (1.0, 1.0).into(),
+        ];
+        let triangles = delaunay(&square);
+        assert_eq!(triangles.len(), 2);
+        assert_eq!(triangles.iter().map(area).sum::<f32>(), 1.0);
+
+        let grid: Vec<Point2f> = (0..9)
+            .map(|i| ((i % 3) as f32, (i / 3) as f32).into())
+            .collect();
+        assert_eq!(delaunay(&grid).len(), 8);
+
+        let line: Vec<Point2f> = (0..5).map(|i| (i as f32, i as f32 * 2.0).into()).collect();
+        assert!(delaunay(&line).is_empty());
+        assert!(delaunay(&square[..2]).is_empty());
+        assert!(delaunay(&[]).is_empty());
+    }
+
+    #[test]
+    fn ear_clipping() {
+        // A comb shape with deep concave notches, wound counterclockwise
+        let mut comb: Vec<Point2f> = vec![(0.0, 0.0).into(), (0.0, 10.0).into()];
+        for i in 0..4 {
+            let x = i as f32 * 3.0;
+            comb.extend_from_slice(&[
+                (x + 1.0, 10.0).into(),
+                (x + 1.0, 2.0).into(),
+                (x + 2.0, 2.0).into(),
+                (x + 2.0, 10.0).into(),
+            ]);
+        }
+        comb.extend_from_slice(&[(12.0, 10.0).into(), (12.0, 0.0).into()]);
+        comb.reverse();
+
+        let polygon = Polygon::new(comb.clone());
+        let triangles = ear_clip(&comb);
+        assert!(triangles.len() <= comb.len() - 2);
+        let total: f32 = triangles.iter().map(area).sum();
+        assert!((total - polygon.area().abs()).abs() < 1e-4);
+        for t in &triangles {
+            assert!(area(t) > 0.0);
+            assert!(polygon.contains_point(t.centroid(), Default::default()));
+        }
+
+        // Collinear vertices are dropped rather than producing slivers
+        let square: Vec<Point2f> = vec![
+            (0.0, 0.0).into(),
+            (1.0, 0.0).into(),
+            (2.0, 0.0).into(),
+            (2.0, 2.0).into(),
+            (0.0, 2.0).into(),
+        ];
+        let triangles = ear_clip(&square);
+        assert_eq!(triangles.iter().map(area).sum::<f32>(), 4.0);
+        assert!(triangles.iter().all(|t| area(t) > 0.0));
+        assert!(ear_clip(&square[..3]).is_empty());
+    }
+}
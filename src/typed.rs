@@ -0,0 +1,547 @@
+//! Points, vectors, rectangles and transforms tagged with the coordinate
+//! space they belong to, so that the compiler catches mixing up screen and
+//! world coordinates.
+//!
+//! The tag is any type, usually an empty enum, which only exists at compile
+//! time:
+//!
+//! ```
+//! use math2d::typed::{TypedPoint2f, TypedTransform};
+//! use math2d::Matrix3x2f;
+//!
+//! enum World {}
+//! enum Screen {}
+//!
+//! let to_screen: TypedTransform<World, Screen> =
+//!     TypedTransform::from_untyped(Matrix3x2f::translation([10.0, 0.0]));
+//! let p: TypedPoint2f<World> = TypedPoint2f::new(1.0, 2.0);
+//! let q: TypedPoint2f<Screen> = to_screen.transform_point(p);
+//! assert_eq!(q.to_untyped(), (11.0, 2.0).into());
+//! ```
+//!
+//! Each tagged type has the same layout as its untyped counterpart, so
+//! converting between them with `from_untyped` and `to_untyped` is free.
+
+use crate::matrix3x2f::Matrix3x2f;
+use crate::point2f::Point2f;
+use crate::rectf::Rectf;
+use crate::vector2f::Vector2f;
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Implements the standard traits without requiring them of the tag types,
+/// which `derive` would.
+macro_rules! impl_tagged_traits {
+    ($name:ident<$($tag:ident),+>, $untyped:ident) => {
+        impl<$($tag),+> Copy for $name<$($tag),+> {}
+
+        impl<$($tag),+> Clone for $name<$($tag),+> {
+            #[inline]
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<$($tag),+> PartialEq for $name<$($tag),+> {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.to_untyped() == other.to_untyped()
+            }
+        }
+
+        impl<$($tag),+> Default for $name<$($tag),+> {
+            #[inline]
+            fn default() -> Self {
+                $name::from_untyped($untyped::default())
+            }
+        }
+
+        impl<$($tag),+> fmt::Debug for $name<$($tag),+> {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Debug::fmt(&self.to_untyped(), f)
+            }
+        }
+    };
+}
+
+/// A point in the coordinate space `S`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+#[repr(C)]
+pub struct TypedPoint2f<S> {
+    /// Horizontal component
+    pub x: f32,
+    /// Vertical component
+    pub y: f32,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    space: PhantomData<S>,
+}
+
+impl<S> TypedPoint2f<S> {
+    /// Constructs a point from its components.
+    #[inline]
+    pub fn new(x: f32, y: f32) -> Self {
+        TypedPoint2f {
+            x,
+            y,
+            space: PhantomData,
+        }
+    }
+
+    /// Tags an untyped point with the space `S`.
+    #[inline]
+    pub fn from_untyped(point: impl Into<Point2f>) -> Self {
+        let point = point.into();
+        TypedPoint2f::new(point.x, point.y)
+    }
+
+    /// The point without its space.
+    #[inline]
+    pub fn to_untyped(self) -> Point2f {
+        Point2f::new(self.x, self.y)
+    }
+
+    /// Reinterprets the point as being in another space.
+    #[inline]
+    pub fn cast_space<T>(self) -> TypedPoint2f<T> {
+        TypedPoint2f::new(self.x, self.y)
+    }
+}
+
+impl_tagged_traits!(TypedPoint2f<S>, Point2f);
+
+impl<S> Add<TypedVector2f<S>> for TypedPoint2f<S> {
+    type Output = TypedPoint2f<S>;
+
+    #[inline]
+    fn add(self, rhs: TypedVector2f<S>) -> TypedPoint2f<S> {
+        TypedPoint2f::from_untyped(self.to_untyped() + rhs.to_untyped())
+    }
+}
+
+impl<S> Sub<TypedVector2f<S>> for TypedPoint2f<S> {
+    type Output = TypedPoint2f<S>;
+
+    #[inline]
+    fn sub(self, rhs: TypedVector2f<S>) -> TypedPoint2f<S> {
+        TypedPoint2f::from_untyped(self.to_untyped() - rhs.to_untyped())
+    }
+}
+
+impl<S> Sub for TypedPoint2f<S> {
+    type Output = TypedVector2f<S>;
+
+    #[inline]
+    fn sub(self, rhs: TypedPoint2f<S>) -> TypedVector2f<S> {
+        TypedVector2f::from_untyped(self.to_untyped() - rhs.to_untyped())
+    }
+}
+
+/// A displacement in the coordinate space `S`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+#[repr(C)]
+pub struct TypedVector2f<S> {
+    /// Horizontal component
+    pub x: f32,
+    /// Vertical component
+    pub y: f32,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    space: PhantomData<S>,
+}
+
+impl<S> TypedVector2f<S> {
+    /// Constructs a vector from its components.
+    #[inline]
+    pub fn new(x: f32, y: f32) -> Self {
+        TypedVector2f {
+            x,
+            y,
+            space: PhantomData,
+        }
+    }
+
+    /// Tags an untyped vector with the space `S`.
+    #[inline]
+    pub fn from_untyped(vector: impl Into<Vector2f>) -> Self {
+        let vector = vector.into();
+        TypedVector2f::new(vector.x, vector.y)
+    }
+
+    /// The vector without its space.
+    #[inline]
+    pub fn to_untyped(self) -> Vector2f {
+        Vector2f::new(self.x, self.y)
+    }
+
+    /// Reinterprets the vector as being in another space.
+    #[inline]
+    pub fn cast_space<T>(self) -> TypedVector2f<T> {
+        TypedVector2f::new(self.x, self.y)
+    }
+
+    /// The length of the vector.
+    #[inline]
+    pub fn len(self) -> f32 {
+        self.to_untyped().len()
+    }
+}
+
+impl_tagged_traits!(TypedVector2f<S>, Vector2f);
+
+impl<S> Add for TypedVector2f<S> {
+    type Output = TypedVector2f<S>;
+
+    #[inline]
+    fn add(self, rhs: TypedVector2f<S>) -> TypedVector2f<S> {
+        TypedVector2f::from_untyped(self.to_untyped() + rhs.to_untyped())
+    }
+}
+
+impl<S> Sub for TypedVector2f<S> {
+    type Output = TypedVector2f<S>;
+
+    #[inline]
+    fn sub(self, rhs: TypedVector2f<S>) -> TypedVector2f<S> {
+        TypedVector2f::from_untyped(self.to_untyped() - rhs.to_untyped())
+    }
+}
+
+impl<S> Neg for TypedVector2f<S> {
+    type Output = TypedVector2f<S>;
+
+    #[inline]
+    fn neg(self) -> TypedVector2f<S> {
+        TypedVector2f::new(-self.x, -self.y)
+    }
+}
+
+impl<S> Mul<f32> for TypedVector2f<S> {
+    type Output = TypedVector2f<S>;
+
+    #[inline]
+    fn mul(self, rhs: f32) -> TypedVector2f<S> {
+        TypedVector2f::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/// An axis-aligned rectangle in the coordinate space `S`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+#[repr(C)]
+pub struct TypedRectf<S> {
+    /// The x-coordinate of the upper-left corner of the rectangle.
+    pub left: f32,
+    /// The y-coordinate of the upper-left corner of the rectangle.
+    pub top: f32,
+    /// The x-coordinate of the lower-right corner of the rectangle.
+    pub right: f32,
+    /// The y-coordinate of the lower-right corner of the rectangle.
+    pub bottom: f32,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    space: PhantomData<S>,
+}
+
+impl<S> TypedRectf<S> {
+    /// Constructs the rectangle from components.
+    #[inline]
+    pub fn new(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        TypedRectf {
+            left,
+            top,
+            right,
+            bottom,
+            space: PhantomData,
+        }
+    }
+
+    /// Constructs the smallest rectangle containing both points.
+    #[inline]
+    pub fn from_points(p1: TypedPoint2f<S>, p2: TypedPoint2f<S>) -> Self {
+        TypedRectf::from_untyped(Rectf::from_points(p1.to_untyped(), p2.to_untyped()))
+    }
+
+    /// Tags an untyped rectangle with the space `S`.
+    #[inline]
+    pub fn from_untyped(rect: impl Into<Rectf>) -> Self {
+        let r = rect.into();
+        TypedRectf::new(r.left, r.top, r.right, r.bottom)
+    }
+
+    /// The rectangle without its space.
+    #[inline]
+    pub fn to_untyped(self) -> Rectf {
+        Rectf::new(self.left, self.top, self.right, self.bottom)
+    }
+
+    /// Reinterprets the rectangle as being in another space.
+    #[inline]
+    pub fn cast_space<T>(self) -> TypedRectf<T> {
+        TypedRectf::new(self.left, self.top, self.right, self.bottom)
+    }
+
+    /// The center point of the rectangle.
+    #[inline]
+    pub fn center(self) -> TypedPoint2f<S> {
+        TypedPoint2f::from_untyped(self.to_untyped().center())
+    }
+
+    /// Determines if the specified point is located inside the rectangle.
+    #[inline]
+    pub fn contains_point(self, point: TypedPoint2f<S>) -> bool {
+        self.to_untyped().contains_point(point.to_untyped())
+    }
+
+    /// Determines if two rects overlap at all
+    #[inline]
+    pub fn overlaps(self, other: TypedRectf<S>) -> bool {
+        self.to_untyped().overlaps(&other.to_untyped())
+    }
+
+    /// Translates the rectangle by the given vector.
+    #[inline]
+    pub fn translated_by(self, translation: TypedVector2f<S>) -> Self {
+        TypedRectf::from_untyped(self.to_untyped().translated_by(translation.to_untyped()))
+    }
+}
+
+impl_tagged_traits!(TypedRectf<S>, Rectf);
+
+/// A transform from the coordinate space `Src` to the space `Dst`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+#[repr(transparent)]
+pub struct TypedTransform<Src, Dst> {
+    matrix: Matrix3x2f,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    spaces: PhantomData<(Src, Dst)>,
+}
+
+impl<Src, Dst> TypedTransform<Src, Dst> {
+    /// Tags an untyped matrix as transforming from `Src` to `Dst`.
+    #[inline]
+    pub fn from_untyped(matrix: Matrix3x2f) -> Self {
+        TypedTransform {
+            matrix,
+            spaces: PhantomData,
+        }
+    }
+
+    /// The matrix without its spaces.
+    #[inline]
+    pub fn to_untyped(self) -> Matrix3x2f {
+        self.matrix
+    }
+
+    /// A transform which maps every point to the same coordinates in the
+    /// other space.
+    #[inline]
+    pub fn identity() -> Self {
+        TypedTransform::from_untyped(Matrix3x2f::IDENTITY)
+    }
+
+    /// Maps a point from `Src` to `Dst`.
+    #[inline]
+    pub fn transform_point(self, point: TypedPoint2f<Src>) -> TypedPoint2f<Dst> {
+        TypedPoint2f::from_untyped(self.matrix.transform_point(point.to_untyped()))
+    }
+
+    /// Maps a vector from `Src` to `Dst`, ignoring translation.
+    #[inline]
+    pub fn transform_vector(self, vector: TypedVector2f<Src>) -> TypedVector2f<Dst> {
+        TypedVector2f::from_untyped(self.matrix.transform_vector(vector.to_untyped()))
+    }
+
+    /// The smallest rectangle in `Dst` containing a rectangle from `Src`
+    /// after it is transformed.
+    #[inline]
+    pub fn transform_rect(self, rect: TypedRectf<Src>) -> TypedRectf<Dst> {
+        let r = rect.to_untyped();
+        let corners = [
+            (r.left, r.top),
+            (r.right, r.top),
+            (r.right, r.bottom),
+            (r.left, r.bottom),
+        ];
+        let first = self.matrix.transform_point(corners[0]);
+        let bounds = corners[1..]
+            .iter()
+            .fold(Rectf::from_points(first, first), |acc, &c| {
+                let p = self.matrix.transform_point(c);
+                acc.combined_with(Rectf::from_points(p, p))
+            });
+        TypedRectf::from_untyped(bounds)
+    }
+
+    /// This transform followed by `next`.
+    #[inline]
+    pub fn then<Next>(self, next: TypedTransform<Dst, Next>) -> TypedTransform<Src, Next> {
+        TypedTransform::from_untyped(self.matrix * next.matrix)
+    }
+
+    /// The transform back from `Dst` to `Src`, if there is one.
+    #[inline]
+    pub fn inverse(self) -> Option<TypedTransform<Dst, Src>> {
+        self.matrix.try_inverse().map(TypedTransform::from_untyped)
+    }
+}
+
+impl<Src, Dst> Copy for TypedTransform<Src, Dst> {}
+
+impl<Src, Dst> Clone for TypedTransform<Src, Dst> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Src, Dst> PartialEq for TypedTransform<Src, Dst> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.matrix == other.matrix
+    }
+}
+
+impl<Src, Dst> Default for TypedTransform<Src, Dst> {
+    #[inline]
+    fn default() -> Self {
+        TypedTransform::identity()
+    }
+}
+
+impl<Src, Dst> fmt::Debug for TypedTransform<Src, Dst> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.matrix, f)
+    }
+}
+
+// euclid tags its types with units the same way, so the space carries over
+// as the unit.
+
+#[cfg(feature = "euclid")]
+impl<S> From<TypedPoint2f<S>> for euclid::Point2D<f32, S> {
+    #[inline]
+    fn from(p: TypedPoint2f<S>) -> euclid::Point2D<f32, S> {
+        euclid::Point2D::new(p.x, p.y)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<S> From<euclid::Point2D<f32, S>> for TypedPoint2f<S> {
+    #[inline]
+    fn from(p: euclid::Point2D<f32, S>) -> TypedPoint2f<S> {
+        TypedPoint2f::new(p.x, p.y)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<S> From<TypedVector2f<S>> for euclid::Vector2D<f32, S> {
+    #[inline]
+    fn from(v: TypedVector2f<S>) -> euclid::Vector2D<f32, S> {
+        euclid::Vector2D::new(v.x, v.y)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<S> From<euclid::Vector2D<f32, S>> for TypedVector2f<S> {
+    #[inline]
+    fn from(v: euclid::Vector2D<f32, S>) -> TypedVector2f<S> {
+        TypedVector2f::new(v.x, v.y)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<S> From<TypedRectf<S>> for euclid::Box2D<f32, S> {
+    #[inline]
+    fn from(r: TypedRectf<S>) -> euclid::Box2D<f32, S> {
+        r.to_untyped().into()
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<S> From<euclid::Box2D<f32, S>> for TypedRectf<S> {
+    #[inline]
+    fn from(b: euclid::Box2D<f32, S>) -> TypedRectf<S> {
+        TypedRectf::from_untyped(Rectf::from(b))
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<Src, Dst> From<TypedTransform<Src, Dst>> for euclid::Transform2D<f32, Src, Dst> {
+    #[inline]
+    fn from(t: TypedTransform<Src, Dst>) -> euclid::Transform2D<f32, Src, Dst> {
+        t.matrix.into()
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<Src, Dst> From<euclid::Transform2D<f32, Src, Dst>> for TypedTransform<Src, Dst> {
+    #[inline]
+    fn from(t: euclid::Transform2D<f32, Src, Dst>) -> TypedTransform<Src, Dst> {
+        TypedTransform::from_untyped(t.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix3x2f::Matrix3x2f;
+    use crate::point2f::Point2f;
+    use crate::typed::{TypedPoint2f, TypedRectf, TypedTransform, TypedVector2f};
+
+    enum World {}
+    enum View {}
+    enum Screen {}
+
+    #[test]
+    fn spaces() {
+        let to_view: TypedTransform<World, View> =
+            TypedTransform::from_untyped(Matrix3x2f::translation([-50.0, 0.0]));
+        let to_screen: TypedTransform<View, Screen> =
+            TypedTransform::from_untyped(Matrix3x2f::scaling([2.0, 2.0], Point2f::ORIGIN));
+        let world_to_screen = to_view.then(to_screen);
+
+        let p = TypedPoint2f::<World>::new(60.0, 5.0);
+        let q = world_to_screen.transform_point(p);
+        assert_eq!(q, TypedPoint2f::<Screen>::new(20.0, 10.0));
+
+        let back = world_to_screen.inverse().unwrap().transform_point(q);
+        assert!((back - p).len() < 1e-4);
+
+        let rect = TypedRectf::<World>::new(50.0, 0.0, 60.0, 10.0);
+        let on_screen = world_to_screen.transform_rect(rect);
+        assert_eq!(on_screen, TypedRectf::new(0.0, 0.0, 20.0, 20.0));
+        assert!(on_screen.contains_point(q));
+
+        let moved = p + TypedVector2f::new(1.0, 1.0);
+        assert_eq!(moved.to_untyped(), (61.0, 6.0).into());
+        assert_eq!(
+            std::mem::size_of::<TypedPoint2f<World>>(),
+            std::mem::size_of::<Point2f>()
+        );
+    }
+
+    #[cfg(feature = "euclid")]
+    #[test]
+    fn euclid() {
+        let t: TypedTransform<World, View> = TypedTransform::from_untyped(
+            Matrix3x2f::rotation(0.5, (1.0, 2.0)) * Matrix3x2f::translation([3.0, -4.0]),
+        );
+        let e: euclid::Transform2D<f32, World, View> = t.into();
+        let p = TypedPoint2f::<World>::new(7.0, 9.0);
+
+        let ours = t.transform_point(p);
+        let theirs: TypedPoint2f<View> = e.transform_point(p.into()).into();
+        assert!((ours - theirs).len() < 1e-4);
+        assert_eq!(TypedTransform::from(e), t);
+
+        let rect = TypedRectf::<World>::new(1.0, 2.0, 5.0, 8.0);
+        let b: euclid::Box2D<f32, World> = rect.into();
+        assert_eq!(TypedRectf::from(b), rect);
+        let r: euclid::Rect<f32, World> = rect.to_untyped().into();
+        assert_eq!(r.size, euclid::Size2D::new(4.0, 6.0));
+    }
+}
@@ -0,0 +1,122 @@
+//! Float comparisons measured in units in the last place (ULPs).
+//!
+//! An absolute epsilon that suits coordinates near the origin is far too
+//! strict for coordinates in the millions, where neighbouring `f32` values
+//! are a sixteenth of a unit apart. Counting how many representable values lie
+//! between two floats scales with their magnitude instead, so the same
+//! tolerance works anywhere in a document.
+//!
+//! ULP distances grow very large approaching zero, so results which should
+//! be exactly zero are better compared with an absolute epsilon.
+//! Values of opposite sign are only equal if they are both within
+//! `max_ulps` of zero.
+
+/// Maps the bits of an `f32` onto integers that sort the same way as the
+/// floats, with both zeros mapping to 0.
+#[inline]
+fn ordered_f32(value: f32) -> i64 {
+    let bits = value.to_bits() as i32;
+    if bits < 0 {
+        i64::from(i32::MIN) - i64::from(bits)
+    } else {
+        i64::from(bits)
+    }
+}
+
+/// Maps the bits of an `f64` onto integers that sort the same way as the
+/// floats, with both zeros mapping to 0.
+#[inline]
+fn ordered_f64(value: f64) -> i128 {
+    let bits = value.to_bits() as i64;
+    if bits < 0 {
+        i128::from(i64::MIN) - i128::from(bits)
+    } else {
+        i128::from(bits)
+    }
+}
+
+/// How many steps between adjacent `f32` values it takes to get from `a` to
+/// `b`, or `None` if either is NaN. `0.0` and `-0.0` are 0 ULPs apart, and
+/// `f32::MAX` is 1 ULP from infinity.
+#[inline]
+pub fn ulps_between(a: f32, b: f32) -> Option<u32> {
+    if a.is_nan() || b.is_nan() {
+        return None;
+    }
+    Some((ordered_f32(a) - ordered_f32(b)).unsigned_abs() as u32)
+}
+
+/// How many steps between adjacent `f64` values it takes to get from `a` to
+/// `b`, or `None` if either is NaN.
+#[inline]
+pub fn ulps_between_f64(a: f64, b: f64) -> Option<u64> {
+    if a.is_nan() || b.is_nan() {
+        return None;
+    }
+    Some((ordered_f64(a) - ordered_f64(b)).unsigned_abs() as u64)
+}
+
+/// Checks that `a` and `b` are at most `max_ulps` representable values
+/// apart. NaN is never equal to anything.
+#[inline]
+pub fn approx_eq_ulps(a: f32, b: f32, max_ulps: u32) -> bool {
+    ulps_between(a, b).is_some_and(|ulps| ulps <= max_ulps)
+}
+
+/// Checks that `a` and `b` are at most `max_ulps` representable values
+/// apart. NaN is never equal to anything.
+#[inline]
+pub fn approx_eq_ulps_f64(a: f64, b: f64, max_ulps: u64) -> bool {
+    ulps_between_f64(a, b).is_some_and(|ulps| ulps <= max_ulps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix3x2f::Matrix3x2f;
+    use crate::point2f::Point2f;
+    use crate::rectf::Rectf;
+
+    #[test]
+    fn ulp_distances() {
+        assert_eq!(ulps_between(1.0, 1.0), Some(0));
+        assert_eq!(ulps_between(0.0, -0.0), Some(0));
+        assert_eq!(ulps_between(1.0, 1.0 + f32::EPSILON), Some(1));
+        assert_eq!(ulps_between(f32::MAX, f32::INFINITY), Some(1));
+        assert_eq!(
+            ulps_between(-f32::MIN_POSITIVE, f32::MIN_POSITIVE),
+            Some(0x0100_0000)
+        );
+        assert_eq!(ulps_between(f32::NAN, 1.0), None);
+        assert_eq!(
+            ulps_between(f32::NEG_INFINITY, f32::INFINITY),
+            Some(0xFF00_0000)
+        );
+        assert_eq!(ulps_between_f64(1.0, 1.0 + f64::EPSILON * 2.0), Some(2));
+
+        // Two neighbouring floats near 1e6 differ by 0.0625, which an
+        // absolute epsilon suited to unit coordinates would reject
+        let big = 1.0e6f32;
+        let next = f32::from_bits(big.to_bits() + 1);
+        assert!(approx_eq_ulps(big, next, 1));
+        assert!(!approx_eq_ulps(big, next, 0));
+        assert!(!approx_eq_ulps(f32::NAN, f32::NAN, u32::MAX));
+        assert!(approx_eq_ulps_f64(-2.0, -2.0, 0));
+    }
+
+    #[test]
+    fn ulp_methods() {
+        let p = Point2f::new(1.0e6, -3.5e6);
+        let q = Point2f::new(1.0e6 + 0.0625, -3.5e6);
+        assert!(p.is_approx_eq_ulps(q, 1));
+        assert!(!p.is_approx_eq_ulps((1.0e6 + 0.25, -3.5e6), 1));
+
+        let r = Rectf::new(0.5, 1.0e6, 2.0, 3.0e6);
+        assert!(r.is_approx_eq_ulps(&Rectf::new(0.5, 1.0e6 + 0.0625, 2.0, 3.0e6), 1));
+        assert!(!r.is_approx_eq_ulps(&Rectf::new(0.5000001, 1.0e6, 2.0, 3.0e6), 0));
+
+        let m = Matrix3x2f::translation([1.0e6, 2.0e6]) * Matrix3x2f::rotation(0.5, (0.0, 0.0));
+        let round_trip = m * m.inverse() * m;
+        assert!(round_trip.is_approx_eq_ulps(&m, 64));
+    }
+}
@@ -0,0 +1,486 @@
+//! Vector on the 2D (x, y) plane, generic over its component type.
+
+use crate::axis::Axis;
+use crate::parse::{self, ParseError};
+use crate::point2::Point2;
+use crate::size::Size;
+
+use num_traits::{AsPrimitive, Float, Num, Signed};
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Mathematical vector on the 2D (x, y) plane, generic over the type of its
+/// components. The arithmetic is defined for any `num_traits::Num`
+/// component, and lengths and directions for any `num_traits::Float`.
+/// `Vector2f`, `Vector2d`, `Vector2i` and `Vector2u` name the common
+/// component types.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde-compact",
+    serde(
+        into = "crate::compact::Array<[T; 2]>",
+        from = "crate::compact::Array<[T; 2]>",
+        bound(
+            serialize = "T: Clone + serde::Serialize",
+            deserialize = "T: serde::Deserialize<'de>"
+        )
+    )
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[repr(C)]
+pub struct Vector2<T> {
+    /// Horizontal component.
+    pub x: T,
+    /// Vertical component.
+    pub y: T,
+}
+
+impl<T> Vector2<T> {
+    /// Construct a vector from the components.
+    #[inline]
+    pub fn new(x: T, y: T) -> Self {
+        Vector2 { x, y }
+    }
+
+    /// Converts each component with `f`, such as to change to another
+    /// numeric type.
+    #[inline]
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Vector2<U> {
+        Vector2 {
+            x: f(self.x),
+            y: f(self.y),
+        }
+    }
+
+    /// Constructs a vector from its components along `axis` and the other
+    /// axis.
+    #[inline]
+    pub fn from_axes(axis: Axis, along: T, across: T) -> Self {
+        match axis {
+            Axis::X => Vector2::new(along, across),
+            Axis::Y => Vector2::new(across, along),
+        }
+    }
+
+    /// The component along `axis`.
+    #[inline]
+    pub fn get(&self, axis: Axis) -> T
+    where
+        T: Copy,
+    {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+        }
+    }
+
+    /// Borrows the component along `axis` mutably.
+    #[inline]
+    pub fn get_mut(&mut self, axis: Axis) -> &mut T {
+        match axis {
+            Axis::X => &mut self.x,
+            Axis::Y => &mut self.y,
+        }
+    }
+
+    /// Replaces the component along `axis`.
+    #[inline]
+    pub fn set(&mut self, axis: Axis, value: T) {
+        *self.get_mut(axis) = value;
+    }
+
+    /// The vector with its components swapped.
+    #[inline]
+    pub fn yx(self) -> Self {
+        Vector2::new(self.y, self.x)
+    }
+
+    /// The vector with its x-component replaced by `x`.
+    #[inline]
+    pub fn with_x(self, x: T) -> Self {
+        Vector2 { x, ..self }
+    }
+
+    /// The vector with its y-component replaced by `y`.
+    #[inline]
+    pub fn with_y(self, y: T) -> Self {
+        Vector2 { y, ..self }
+    }
+}
+
+impl<T: Num + Copy> Vector2<T> {
+    /// Dot product of two vectors.
+    #[inline]
+    pub fn dot(self, rhs: Vector2<T>) -> T {
+        self.x * rhs.x + self.y * rhs.y
+    }
+
+    /// The 2D cross product (perpendicular dot product) of two vectors i.e.
+    /// the z component of the 3D cross product. Positive when `rhs` is
+    /// clockwise from `self` in the y-down coordinate system.
+    #[inline]
+    pub fn cross(self, rhs: Vector2<T>) -> T {
+        self.x * rhs.y - self.y * rhs.x
+    }
+
+    /// The squared length of the vector
+    #[inline]
+    pub fn len_squared(self) -> T {
+        self.dot(self)
+    }
+
+    /// The point this vector reaches from the origin.
+    #[inline]
+    pub fn to_point(self) -> Point2<T> {
+        Point2::new(self.x, self.y)
+    }
+
+    /// Converts this vector to a size value with the x representing width
+    /// and the y representing height.
+    #[inline]
+    pub fn to_size(self) -> Size<T> {
+        Size::new(self.x, self.y)
+    }
+}
+
+impl<T: Signed + Copy> Vector2<T> {
+    /// Absolute value of the vector components.
+    #[inline]
+    pub fn abs(self) -> Self {
+        self.map(|c| c.abs())
+    }
+}
+
+impl<T: Float> Vector2<T> {
+    /// The length of the vector. This requires performing a square root,
+    /// so the squared length should be preferred where possible.
+    #[inline]
+    pub fn len(self) -> T {
+        self.len_squared().sqrt()
+    }
+
+    /// Scales the vector to unit length. The zero vector has no direction,
+    /// so normalizing it will produce NaN components.
+    #[inline]
+    pub fn normalized(self) -> Self {
+        self / self.len()
+    }
+
+    /// Rounds the components of the vector to the nearest integer. Rounds
+    /// half-way values away from 0.
+    #[inline]
+    pub fn rounded(self) -> Self {
+        self.map(T::round)
+    }
+
+    /// Component-wise reciprocal
+    #[inline]
+    pub fn reciprocal(self) -> Self {
+        self.map(T::recip)
+    }
+}
+
+impl<T: Copy + 'static> Vector2<T> {
+    /// Converts the components with `as`, so floats are truncated towards
+    /// zero and saturate at the limits of integer types. Round the vector
+    /// first if another behavior is desired.
+    #[inline]
+    pub fn cast<U: Copy + 'static>(self) -> Vector2<U>
+    where
+        T: AsPrimitive<U>,
+    {
+        self.map(T::as_)
+    }
+
+    /// Converts the components to `f32`, as with `cast`.
+    #[inline]
+    pub fn to_f32(self) -> Vector2<f32>
+    where
+        T: AsPrimitive<f32>,
+    {
+        self.cast()
+    }
+
+    /// Converts the components to `f64`, as with `cast`.
+    #[inline]
+    pub fn to_f64(self) -> Vector2<f64>
+    where
+        T: AsPrimitive<f64>,
+    {
+        self.cast()
+    }
+
+    /// Converts the components to `i32`, as with `cast`.
+    #[inline]
+    pub fn to_i32(self) -> Vector2<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast()
+    }
+
+    /// Converts the components to `u32`, as with `cast`. Negative
+    /// components become 0.
+    #[inline]
+    pub fn to_u32(self) -> Vector2<u32>
+    where
+        T: AsPrimitive<u32>,
+    {
+        self.cast()
+    }
+}
+
+impl<T: Num + Copy, V: Into<Vector2<T>>> Add<V> for Vector2<T> {
+    type Output = Vector2<T>;
+
+    #[inline]
+    fn add(self, rhs: V) -> Vector2<T> {
+        let rhs = rhs.into();
+        Vector2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Num + Copy, V: Into<Vector2<T>>> Sub<V> for Vector2<T> {
+    type Output = Vector2<T>;
+
+    #[inline]
+    fn sub(self, rhs: V) -> Vector2<T> {
+        let rhs = rhs.into();
+        Vector2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+/// Multiplies component-wise. Scalars convert into vectors with both
+/// components equal, so this also scales the vector.
+impl<T: Num + Copy, V: Into<Vector2<T>>> Mul<V> for Vector2<T> {
+    type Output = Vector2<T>;
+
+    #[inline]
+    fn mul(self, rhs: V) -> Vector2<T> {
+        let rhs = rhs.into();
+        Vector2::new(self.x * rhs.x, self.y * rhs.y)
+    }
+}
+
+/// Divides component-wise, as with `Mul`.
+impl<T: Num + Copy, V: Into<Vector2<T>>> Div<V> for Vector2<T> {
+    type Output = Vector2<T>;
+
+    #[inline]
+    fn div(self, rhs: V) -> Vector2<T> {
+        let rhs = rhs.into();
+        Vector2::new(self.x / rhs.x, self.y / rhs.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vector2<T> {
+    type Output = Vector2<T>;
+
+    #[inline]
+    fn neg(self) -> Vector2<T> {
+        Vector2::new(-self.x, -self.y)
+    }
+}
+
+impl<T, V> AddAssign<V> for Vector2<T>
+where
+    Vector2<T>: Add<V, Output = Vector2<T>> + Copy,
+{
+    #[inline]
+    fn add_assign(&mut self, v: V) {
+        *self = *self + v;
+    }
+}
+
+impl<T, V> SubAssign<V> for Vector2<T>
+where
+    Vector2<T>: Sub<V, Output = Vector2<T>> + Copy,
+{
+    #[inline]
+    fn sub_assign(&mut self, v: V) {
+        *self = *self - v;
+    }
+}
+
+impl<T, V> MulAssign<V> for Vector2<T>
+where
+    Vector2<T>: Mul<V, Output = Vector2<T>> + Copy,
+{
+    #[inline]
+    fn mul_assign(&mut self, v: V) {
+        *self = *self * v;
+    }
+}
+
+impl<T, V> DivAssign<V> for Vector2<T>
+where
+    Vector2<T>: Div<V, Output = Vector2<T>> + Copy,
+{
+    #[inline]
+    fn div_assign(&mut self, v: V) {
+        *self = *self / v;
+    }
+}
+
+// Generic impls cannot put the scalar on the left, so each primitive gets
+// its own.
+macro_rules! scalar_ops {
+    ($($ty:ty),*) => {$(
+        impl Mul<Vector2<$ty>> for $ty {
+            type Output = Vector2<$ty>;
+
+            #[inline]
+            fn mul(self, rhs: Vector2<$ty>) -> Vector2<$ty> {
+                Vector2::new(self * rhs.x, self * rhs.y)
+            }
+        }
+
+        impl Div<Vector2<$ty>> for $ty {
+            type Output = Vector2<$ty>;
+
+            #[inline]
+            fn div(self, rhs: Vector2<$ty>) -> Vector2<$ty> {
+                Vector2::new(self / rhs.x, self / rhs.y)
+            }
+        }
+    )*};
+}
+
+scalar_ops!(f32, f64, i32, i64, u32);
+
+/// Both components are set to the scalar.
+impl<T: Copy> From<T> for Vector2<T> {
+    #[inline]
+    fn from(s: T) -> Vector2<T> {
+        Vector2::new(s, s)
+    }
+}
+
+impl<T> From<[T; 2]> for Vector2<T> {
+    #[inline]
+    fn from([x, y]: [T; 2]) -> Vector2<T> {
+        Vector2::new(x, y)
+    }
+}
+
+impl<T> From<Vector2<T>> for [T; 2] {
+    #[inline]
+    fn from(v: Vector2<T>) -> [T; 2] {
+        [v.x, v.y]
+    }
+}
+
+/// Mathematical vector on the 2D (x, y) plane, with double precision
+/// components.
+pub type Vector2d = Vector2<f64>;
+
+/// Widens each component exactly.
+impl From<Vector2<f32>> for Vector2<f64> {
+    #[inline]
+    fn from(v: Vector2<f32>) -> Vector2<f64> {
+        v.map(f64::from)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Vector2<T> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        parse::write_components(fmt, ('(', ')'), &[&self.x, &self.y])
+    }
+}
+
+impl<T: FromStr> FromStr for Vector2<T> {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Vector2<T>, ParseError> {
+        let [x, y] = parse::parse_components(s, ('(', ')'))?;
+        Ok(Vector2 { x, y })
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<T, U> From<Vector2<T>> for euclid::Vector2D<T, U> {
+    #[inline]
+    fn from(v: Vector2<T>) -> euclid::Vector2D<T, U> {
+        euclid::Vector2D::new(v.x, v.y)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<T, U> From<euclid::Vector2D<T, U>> for Vector2<T> {
+    #[inline]
+    fn from(v: euclid::Vector2D<T, U>) -> Vector2<T> {
+        Vector2 { x: v.x, y: v.y }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<T> From<Vector2<T>> for mint::Vector2<T> {
+    #[inline]
+    fn from(v: Vector2<T>) -> mint::Vector2<T> {
+        mint::Vector2 { x: v.x, y: v.y }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<T> From<mint::Vector2<T>> for Vector2<T> {
+    #[inline]
+    fn from(v: mint::Vector2<T>) -> Vector2<T> {
+        Vector2 { x: v.x, y: v.y }
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar> From<Vector2<T>> for nalgebra::Vector2<T> {
+    #[inline]
+    fn from(v: Vector2<T>) -> nalgebra::Vector2<T> {
+        nalgebra::Vector2::new(v.x, v.y)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar> From<nalgebra::Vector2<T>> for Vector2<T> {
+    #[inline]
+    fn from(v: nalgebra::Vector2<T>) -> Vector2<T> {
+        let [x, y]: [T; 2] = v.into();
+        Vector2 { x, y }
+    }
+}
+
+// The struct is `#[repr(C)]` with every field of type `T`, so it has
+// the same layout as `[T; 2]`.
+#[cfg(feature = "encase")]
+impl<T: encase::vector::VectorScalar> encase::vector::AsRefVectorParts<T, 2> for Vector2<T> {
+    #[inline]
+    fn as_ref_parts(&self) -> &[T; 2] {
+        unsafe { &*(self as *const Vector2<T> as *const [T; 2]) }
+    }
+}
+
+#[cfg(feature = "encase")]
+impl<T: encase::vector::VectorScalar> encase::vector::AsMutVectorParts<T, 2> for Vector2<T> {
+    #[inline]
+    fn as_mut_parts(&mut self) -> &mut [T; 2] {
+        unsafe { &mut *(self as *mut Vector2<T> as *mut [T; 2]) }
+    }
+}
+
+#[cfg(feature = "encase")]
+impl<T: encase::vector::VectorScalar> encase::vector::FromVectorParts<T, 2> for Vector2<T> {
+    #[inline]
+    fn from_parts([x, y]: [T; 2]) -> Vector2<T> {
+        Vector2 { x, y }
+    }
+}
+
+// Vectors are laid out as a `vec2` in shader buffers.
+#[cfg(feature = "encase")]
+encase::impl_vector!(2, Vector2<T>; (T: encase::vector::VectorScalar));
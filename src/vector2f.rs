@@ -1,24 +1,20 @@
 //! Mathematical vector on the 2D (x, y) plane.
 
-use crate::point2f::Point2f;
-use crate::sizef::Sizef;
-use crate::vector2i::Vector2i;
-
-use std::ops::{Add, Div, Mul, Neg, Sub};
+use crate::tolerance::Tolerance;
+use crate::ulps::approx_eq_ulps;
+use crate::vector2::Vector2;
 
 #[cfg(all(windows, feature = "d2d"))]
 use winapi::um::dcommon::D2D_VECTOR_2F;
+#[cfg(all(windows, feature = "d2d"))]
+use winapi::um::dwrite::DWRITE_GLYPH_OFFSET;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::Direct2D::Common as d2d_common;
+#[cfg(all(windows, feature = "windows"))]
+use windows::Win32::Graphics::DirectWrite as dwrite;
 
 /// Mathematical vector on the 2D (x, y) plane.
-#[derive(Copy, Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
-#[repr(C)]
-pub struct Vector2f {
-    /// Horizontal component.
-    pub x: f32,
-    /// Vertical component.
-    pub y: f32,
-}
+pub type Vector2f = Vector2<f32>;
 
 /// Zero vector, addition identity value.
 pub const ZERO: Vector2f = Vector2f::ZERO;
@@ -44,256 +40,195 @@ impl Vector2f {
     /// 2D drawing systems.
     pub const LEFT: Vector2f = Vector2f { x: -1.0, y: 0.0 };
 
-    /// Construct a vector from the components.
-    #[inline]
-    pub fn new(x: f32, y: f32) -> Self {
-        Vector2f { x, y }
-    }
-
-    /// Converts the vector to unsigned integer values. Truncates integers, if
-    /// you want your components to be rounded you must do this manually first.
-    #[inline]
-    pub fn to_i32(self) -> Vector2i {
-        Vector2i {
-            x: self.x as i32,
-            y: self.y as i32,
-        }
-    }
-
+    /// Tests if two vectors are approximately equal to each other within a
+    /// given epsilon. The epsilon is applied component-wise. If you would like
+    /// to check that two vectors are within a specified distance of each
+    /// other, you should subtract one from the other and check the length of
+    /// the resulting distance vector between them.
     #[inline]
-    pub fn to_point(self) -> Point2f {
-        Point2f::ORIGIN + self
+    pub fn is_approx_eq(self, other: impl Into<Vector2f>, epsilon: impl Into<Tolerance>) -> bool {
+        let (other, epsilon) = (other.into(), epsilon.into());
+        return epsilon.approx_eq(self.x, other.x) && epsilon.approx_eq(self.y, other.y);
     }
 
-    /// Converts this vector to a size value with the x representing width
-    /// and the y representing height.
+    /// Tests if the components of two vectors are at most `max_ulps`
+    /// representable values apart, which scales the tolerance with the
+    /// magnitude of the components. See `math2d::ulps`.
     #[inline]
-    pub fn to_size(self) -> Sizef {
-        Sizef {
-            width: self.x,
-            height: self.y,
-        }
+    pub fn is_approx_eq_ulps(self, other: impl Into<Vector2f>, max_ulps: u32) -> bool {
+        let other = other.into();
+        approx_eq_ulps(self.x, other.x, max_ulps) && approx_eq_ulps(self.y, other.y, max_ulps)
     }
+}
 
-    /// Rounds the components of the vector to the nearest integer. Rounds
-    /// half-way values away from 0.
+#[cfg(all(windows, feature = "d2d"))]
+impl From<Vector2f> for D2D_VECTOR_2F {
     #[inline]
-    pub fn rounded(self) -> Vector2f {
-        Vector2f {
-            x: self.x.round(),
-            y: self.y.round(),
-        }
+    fn from(vec: Vector2f) -> D2D_VECTOR_2F {
+        D2D_VECTOR_2F { x: vec.x, y: vec.y }
     }
+}
 
-    /// Dot product of two vectors.
+#[cfg(all(windows, feature = "d2d"))]
+impl From<D2D_VECTOR_2F> for Vector2f {
     #[inline]
-    pub fn dot(self, rhs: Vector2f) -> f32 {
-        self.x * rhs.x + self.y * rhs.y
+    fn from(vec: D2D_VECTOR_2F) -> Vector2f {
+        Vector2f { x: vec.x, y: vec.y }
     }
+}
 
-    /// The squared length of the vector
+#[cfg(all(windows, feature = "windows"))]
+impl From<Vector2f> for d2d_common::D2D_VECTOR_2F {
     #[inline]
-    pub fn len_squared(self) -> f32 {
-        self.dot(self)
+    fn from(vec: Vector2f) -> d2d_common::D2D_VECTOR_2F {
+        d2d_common::D2D_VECTOR_2F { x: vec.x, y: vec.y }
     }
+}
 
-    /// The length of the vector. This requires performing a square root,
-    /// so the squared length should be preferred where possible.
+#[cfg(all(windows, feature = "windows"))]
+impl From<d2d_common::D2D_VECTOR_2F> for Vector2f {
     #[inline]
-    pub fn len(self) -> f32 {
-        self.len_squared().sqrt()
+    fn from(vec: d2d_common::D2D_VECTOR_2F) -> Vector2f {
+        Vector2f { x: vec.x, y: vec.y }
     }
+}
 
-    /// Absolute value of the vector components.
+/// The components are copied unchanged. DirectWrite's ascender offset
+/// points up, away from the baseline, which is the opposite of Direct2D's
+/// y axis.
+#[cfg(all(windows, feature = "d2d"))]
+impl From<Vector2f> for DWRITE_GLYPH_OFFSET {
     #[inline]
-    pub fn abs(self) -> Self {
-        Vector2f {
-            x: self.x.abs(),
-            y: self.y.abs(),
+    fn from(vec: Vector2f) -> DWRITE_GLYPH_OFFSET {
+        DWRITE_GLYPH_OFFSET {
+            advanceOffset: vec.x,
+            ascenderOffset: vec.y,
         }
     }
+}
 
-    /// Component-wise reciprocal
+#[cfg(all(windows, feature = "d2d"))]
+impl From<DWRITE_GLYPH_OFFSET> for Vector2f {
     #[inline]
-    pub fn reciprocal(self) -> Self {
+    fn from(offset: DWRITE_GLYPH_OFFSET) -> Vector2f {
         Vector2f {
-            x: 1.0 / self.x,
-            y: 1.0 / self.y,
+            x: offset.advanceOffset,
+            y: offset.ascenderOffset,
         }
     }
-
-    /// Tests if two vectors are approximately equal to each other within a
-    /// given epsilon. The epsilon is applied component-wise. If you would like
-    /// to check that two vectors are within a specified distance of each
-    /// other, you should subtract one from the other and check the length of
-    /// the resulting distance vector between them.
-    #[inline]
-    pub fn is_approx_eq(self, other: impl Into<Vector2f>, epsilon: f32) -> bool {
-        let other = other.into();
-        return (self.x - other.x).abs() <= epsilon && (self.y - other.y).abs() <= epsilon;
-    }
 }
 
-impl<V> Add<V> for Vector2f
-where
-    V: Into<Vector2f>,
-{
-    type Output = Vector2f;
-
+#[cfg(all(windows, feature = "windows"))]
+impl From<Vector2f> for dwrite::DWRITE_GLYPH_OFFSET {
     #[inline]
-    fn add(self, rhs: V) -> Vector2f {
-        let rhs = rhs.into();
-        Vector2f {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
+    fn from(vec: Vector2f) -> dwrite::DWRITE_GLYPH_OFFSET {
+        dwrite::DWRITE_GLYPH_OFFSET {
+            advanceOffset: vec.x,
+            ascenderOffset: vec.y,
         }
     }
 }
 
-impl<V> Sub<V> for Vector2f
-where
-    V: Into<Vector2f>,
-{
-    type Output = Vector2f;
-
+#[cfg(all(windows, feature = "windows"))]
+impl From<dwrite::DWRITE_GLYPH_OFFSET> for Vector2f {
     #[inline]
-    fn sub(self, rhs: V) -> Vector2f {
-        let rhs = rhs.into();
+    fn from(offset: dwrite::DWRITE_GLYPH_OFFSET) -> Vector2f {
         Vector2f {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
+            x: offset.advanceOffset,
+            y: offset.ascenderOffset,
         }
     }
 }
 
-impl Neg for Vector2f {
-    type Output = Vector2f;
-
+#[cfg(feature = "kurbo")]
+impl From<kurbo::Vec2> for Vector2f {
     #[inline]
-    fn neg(self) -> Vector2f {
+    fn from(p: kurbo::Vec2) -> Vector2f {
         Vector2f {
-            x: -self.x,
-            y: -self.y,
+            x: p.x as f32,
+            y: p.y as f32,
         }
     }
 }
 
-impl<V> Mul<V> for Vector2f
-where
-    V: Into<Vector2f>,
-{
-    type Output = Vector2f;
-
+#[cfg(feature = "kurbo")]
+impl From<Vector2f> for kurbo::Vec2 {
     #[inline]
-    fn mul(self, rhs: V) -> Self {
-        let rhs = rhs.into();
-        Vector2f {
-            x: self.x * rhs.x,
-            y: self.y * rhs.y,
-        }
+    fn from(v: Vector2f) -> kurbo::Vec2 {
+        kurbo::Vec2::new(v.x.into(), v.y.into())
     }
 }
 
-impl Mul<Vector2f> for f32 {
-    type Output = Vector2f;
-
+#[cfg(feature = "glam")]
+impl From<Vector2f> for glam::Vec2 {
     #[inline]
-    fn mul(self, rhs: Vector2f) -> Vector2f {
-        Vector2f {
-            x: self * rhs.x,
-            y: self * rhs.y,
-        }
+    fn from(p: Vector2f) -> glam::Vec2 {
+        glam::Vec2::new(p.x, p.y)
     }
 }
 
-impl<V> Div<V> for Vector2f
-where
-    V: Into<Vector2f>,
-{
-    type Output = Vector2f;
-
+#[cfg(feature = "glam")]
+impl From<glam::Vec2> for Vector2f {
     #[inline]
-    fn div(self, rhs: V) -> Vector2f {
-        self * rhs.into().reciprocal()
+    fn from(p: glam::Vec2) -> Vector2f {
+        Vector2f { x: p.x, y: p.y }
     }
 }
 
-impl Div<Vector2f> for f32 {
-    type Output = Vector2f;
+#[cfg(all(test, windows, feature = "d2d"))]
+#[test]
+fn vec2f_d2d_bin_compat() {
+    use std::mem::size_of_val;
 
-    #[inline]
-    fn div(self, rhs: Vector2f) -> Vector2f {
-        self * rhs.reciprocal()
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
     }
-}
 
-impl From<f32> for Vector2f {
-    #[inline]
-    fn from(s: f32) -> Vector2f {
-        Vector2f::new(s, s)
-    }
-}
+    let vec = Vector2f::ZERO;
+    let d2d = unsafe { &*((&vec) as *const _ as *const D2D_VECTOR_2F) };
 
-impl From<[f32; 2]> for Vector2f {
-    #[inline]
-    fn from(v: [f32; 2]) -> Vector2f {
-        Vector2f::new(v[0], v[1])
-    }
+    assert!(ptr_eq(&vec.x, &d2d.x));
+    assert!(ptr_eq(&vec.y, &d2d.y));
+    assert_eq!(size_of_val(&vec), size_of_val(d2d));
 }
 
-impl From<Vector2f> for [f32; 2] {
-    #[inline]
-    fn from(v: Vector2f) -> [f32; 2] {
-        [v.x, v.y]
-    }
-}
+#[cfg(all(test, windows, feature = "windows"))]
+#[test]
+fn vec2f_windows_bin_compat() {
+    use std::mem::size_of_val;
 
-#[cfg(all(windows, feature = "d2d"))]
-impl From<Vector2f> for D2D_VECTOR_2F {
-    #[inline]
-    fn from(vec: Vector2f) -> D2D_VECTOR_2F {
-        D2D_VECTOR_2F { x: vec.x, y: vec.y }
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
     }
-}
 
-#[cfg(all(windows, feature = "d2d"))]
-impl From<D2D_VECTOR_2F> for Vector2f {
-    #[inline]
-    fn from(vec: D2D_VECTOR_2F) -> Vector2f {
-        Vector2f { x: vec.x, y: vec.y }
-    }
-}
+    let vec = Vector2f::ZERO;
+    let d2d = unsafe { &*((&vec) as *const _ as *const d2d_common::D2D_VECTOR_2F) };
 
-#[cfg(feature = "mint")]
-impl From<Vector2f> for mint::Vector2<f32> {
-    #[inline]
-    fn from(p: Vector2f) -> mint::Vector2<f32> {
-        mint::Vector2 { x: p.x, y: p.y }
-    }
+    assert!(ptr_eq(&vec.x, &d2d.x));
+    assert!(ptr_eq(&vec.y, &d2d.y));
+    assert_eq!(size_of_val(&vec), size_of_val(d2d));
 }
 
-#[cfg(feature = "mint")]
-impl From<mint::Vector2<f32>> for Vector2f {
-    #[inline]
-    fn from(p: mint::Vector2<f32>) -> Vector2f {
-        Vector2f { x: p.x, y: p.y }
-    }
-}
+#[cfg(all(test, windows, feature = "d2d"))]
+#[test]
+fn glyph_offset_d2d_bin_compat() {
+    use std::mem::size_of_val;
 
-#[cfg(feature = "kurbo")]
-impl From<kurbo::Vec2> for Vector2f {
-    #[inline]
-    fn from(p: kurbo::Vec2) -> Vector2f {
-        Vector2f {
-            x: p.x as f32,
-            y: p.y as f32,
-        }
+    fn ptr_eq<T>(a: &T, b: &T) -> bool {
+        (a as *const T) == (b as *const T)
     }
+
+    let vec = Vector2f::ZERO;
+    let offset = unsafe { &*((&vec) as *const _ as *const DWRITE_GLYPH_OFFSET) };
+
+    assert!(ptr_eq(&vec.x, &offset.advanceOffset));
+    assert!(ptr_eq(&vec.y, &offset.ascenderOffset));
+    assert_eq!(size_of_val(&vec), size_of_val(offset));
 }
 
-#[cfg(all(test, windows, feature = "d2d"))]
+#[cfg(all(test, windows, feature = "windows"))]
 #[test]
-fn vec2f_d2d_bin_compat() {
+fn glyph_offset_windows_bin_compat() {
     use std::mem::size_of_val;
 
     fn ptr_eq<T>(a: &T, b: &T) -> bool {
@@ -301,9 +236,9 @@ fn vec2f_d2d_bin_compat() {
     }
 
     let vec = Vector2f::ZERO;
-    let d2d = unsafe { &*((&vec) as *const _ as *const D2D_VECTOR_2F) };
+    let offset = unsafe { &*((&vec) as *const _ as *const dwrite::DWRITE_GLYPH_OFFSET) };
 
-    assert!(ptr_eq(&vec.x, &d2d.x));
-    assert!(ptr_eq(&vec.y, &d2d.y));
-    assert_eq!(size_of_val(&vec), size_of_val(d2d));
+    assert!(ptr_eq(&vec.x, &offset.advanceOffset));
+    assert!(ptr_eq(&vec.y, &offset.ascenderOffset));
+    assert_eq!(size_of_val(&vec), size_of_val(offset));
 }
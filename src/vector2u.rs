@@ -0,0 +1,22 @@
+//! Mathematical vector on the 2D (x, y) plane.
+
+use crate::sizeu::Sizeu;
+use crate::vector2::Vector2;
+
+/// Mathematical vector on the 2D (x, y) plane with unsigned components,
+/// such as a step through a grid of pixels.
+pub type Vector2u = Vector2<u32>;
+
+impl Vector2u {
+    /// The zero vector. Addition with the zero vector is the identity function.
+    pub const ZERO: Vector2u = Vector2u { x: 0, y: 0 };
+
+    /// Converts this vector to a size.
+    #[inline]
+    pub fn as_size(self) -> Sizeu {
+        Sizeu {
+            width: self.x,
+            height: self.y,
+        }
+    }
+}
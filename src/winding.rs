@@ -0,0 +1,108 @@
+//! The direction in which a closed shape's outline travels around it.
+
+use crate::arc_segment::SweepDirection;
+use crate::point2f::Point2f;
+use crate::predicates::orient2d;
+
+/// The direction in which a closed shape's outline travels around it, as it
+/// appears in the y-down coordinate system common to 2D drawing APIs.
+/// Clockwise shapes have a positive signed area in this crate.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Winding {
+    /// The outline travels clockwise on screen.
+    Clockwise,
+    /// The outline travels counterclockwise on screen.
+    CounterClockwise,
+}
+
+impl Winding {
+    /// The winding of a shape with the given signed area. Returns `None` for
+    /// shapes with no area, which have no meaningful direction.
+    #[inline]
+    pub fn from_signed_area(area: f32) -> Option<Winding> {
+        if area > 0.0 {
+            Some(Winding::Clockwise)
+        } else if area < 0.0 {
+            Some(Winding::CounterClockwise)
+        } else {
+            None
+        }
+    }
+
+    /// The opposite winding direction.
+    #[inline]
+    pub fn reversed(self) -> Winding {
+        match self {
+            Winding::Clockwise => Winding::CounterClockwise,
+            Winding::CounterClockwise => Winding::Clockwise,
+        }
+    }
+}
+
+impl From<SweepDirection> for Winding {
+    #[inline]
+    fn from(sweep: SweepDirection) -> Winding {
+        match sweep {
+            SweepDirection::Clockwise => Winding::Clockwise,
+            SweepDirection::CounterClockwise => Winding::CounterClockwise,
+        }
+    }
+}
+
+impl From<Winding> for SweepDirection {
+    #[inline]
+    fn from(winding: Winding) -> SweepDirection {
+        match winding {
+            Winding::Clockwise => SweepDirection::Clockwise,
+            Winding::CounterClockwise => SweepDirection::CounterClockwise,
+        }
+    }
+}
+
+/// The direction in which the path `p1`, `p2`, `p3` turns, or `None` if the
+/// points are collinear. This uses the exact `orient2d` predicate, so nearly
+/// collinear points are never given the wrong direction.
+#[inline]
+pub fn orientation(
+    p1: impl Into<Point2f>,
+    p2: impl Into<Point2f>,
+    p3: impl Into<Point2f>,
+) -> Option<Winding> {
+    let det = orient2d(p1.into(), p2.into(), p3.into());
+    if det > 0.0 {
+        Some(Winding::Clockwise)
+    } else if det < 0.0 {
+        Some(Winding::CounterClockwise)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn orientations() {
+    // Right, then down the screen
+    assert_eq!(
+        orientation((0.0, 0.0), (1.0, 0.0), (1.0, 1.0)),
+        Some(Winding::Clockwise)
+    );
+    assert_eq!(
+        orientation((1.0, 1.0), (1.0, 0.0), (0.0, 0.0)),
+        Some(Winding::CounterClockwise)
+    );
+    assert_eq!(orientation((0.0, 0.0), (1.0, 1.0), (3.0, 3.0)), None);
+
+    let sweep: SweepDirection = Winding::Clockwise.into();
+    assert_eq!(sweep, SweepDirection::Clockwise);
+    assert_eq!(
+        Winding::from(SweepDirection::CounterClockwise).reversed(),
+        Winding::Clockwise
+    );
+}
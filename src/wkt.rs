@@ -0,0 +1,521 @@
+//! Exchange of simple 2D shapes as Well-Known Text and GeoJSON geometries.
+//!
+//! Only the two dimensional geometry types are supported. Polygon rings are
+//! written closed, repeating the first point at the end, as both formats
+//! require, and the repeated point is dropped again when reading them.
+//!
+//! GeoJSON support requires the `geojson` feature, which pulls in
+//! `serde_json`.
+
+use crate::point2f::Point2f;
+use crate::polygon::Polygon;
+use crate::polyline::Polyline;
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A geometry from the simple features model shared by WKT and GeoJSON.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SimpleGeometry {
+    /// A single point.
+    Point(Point2f),
+    /// An open chain of straight lines.
+    LineString(Polyline),
+    /// A polygon given by its rings. The first ring is the exterior and any
+    /// others are holes in it.
+    Polygon(Vec<Polygon>),
+    /// Any number of points.
+    MultiPoint(Vec<Point2f>),
+    /// Any number of line strings.
+    MultiLineString(Vec<Polyline>),
+    /// Any number of polygons, each given by its rings.
+    MultiPolygon(Vec<Vec<Polygon>>),
+    /// Any number of geometries of mixed types.
+    GeometryCollection(Vec<SimpleGeometry>),
+}
+
+/// The reason text or JSON could not be read as a `SimpleGeometry`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GeometryFormatError {
+    /// The input ended before the geometry was complete.
+    UnexpectedEnd,
+    /// The WKT text had something unexpected at this byte offset.
+    UnexpectedToken(usize),
+    /// The geometry type is unknown, or is not a two dimensional type.
+    UnsupportedType(String),
+    /// A coordinate was not a number, or a position had too few
+    /// coordinates.
+    BadCoordinate,
+    /// The GeoJSON object was missing a member or had one of the wrong type.
+    #[cfg(feature = "geojson")]
+    BadGeoJson,
+}
+
+impl fmt::Display for GeometryFormatError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GeometryFormatError::UnexpectedEnd => write!(fmt, "Unexpected end of input"),
+            GeometryFormatError::UnexpectedToken(at) => {
+                write!(fmt, "Unexpected token at offset {}", at)
+            }
+            GeometryFormatError::UnsupportedType(ref name) => {
+                write!(fmt, "Unsupported geometry type {:?}", name)
+            }
+            GeometryFormatError::BadCoordinate => write!(fmt, "Invalid coordinate"),
+            #[cfg(feature = "geojson")]
+            GeometryFormatError::BadGeoJson => write!(fmt, "Malformed GeoJSON geometry"),
+        }
+    }
+}
+
+impl Error for GeometryFormatError {}
+
+impl From<Point2f> for SimpleGeometry {
+    #[inline]
+    fn from(point: Point2f) -> SimpleGeometry {
+        SimpleGeometry::Point(point)
+    }
+}
+
+impl From<Polyline> for SimpleGeometry {
+    #[inline]
+    fn from(line: Polyline) -> SimpleGeometry {
+        SimpleGeometry::LineString(line)
+    }
+}
+
+impl From<Polygon> for SimpleGeometry {
+    #[inline]
+    fn from(polygon: Polygon) -> SimpleGeometry {
+        SimpleGeometry::Polygon(vec![polygon])
+    }
+}
+
+/// Writes the geometry as WKT, e.g. `POLYGON ((0 0, 4 0, 4 3, 0 0))`.
+impl fmt::Display for SimpleGeometry {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SimpleGeometry::Point(p) => write!(fmt, "POINT ({} {})", p.x, p.y),
+            SimpleGeometry::LineString(ref line) => {
+                fmt.write_str("LINESTRING ")?;
+                write_points(fmt, &line.points)
+            }
+            SimpleGeometry::Polygon(ref rings) => {
+                fmt.write_str("POLYGON ")?;
+                write_rings(fmt, rings)
+            }
+            SimpleGeometry::MultiPoint(ref points) => {
+                fmt.write_str("MULTIPOINT ")?;
+                write_list(fmt, points, |fmt, p| write!(fmt, "({} {})", p.x, p.y))
+            }
+            SimpleGeometry::MultiLineString(ref lines) => {
+                fmt.write_str("MULTILINESTRING ")?;
+                write_list(fmt, lines, |fmt, line| write_points(fmt, &line.points))
+            }
+            SimpleGeometry::MultiPolygon(ref polygons) => {
+                fmt.write_str("MULTIPOLYGON ")?;
+                write_list(fmt, polygons, |fmt, rings| write_rings(fmt, rings))
+            }
+            SimpleGeometry::GeometryCollection(ref geometries) => {
+                fmt.write_str("GEOMETRYCOLLECTION ")?;
+                write_list(fmt, geometries, |fmt, g| g.fmt(fmt))
+            }
+        }
+    }
+}
+
+/// Parses WKT text. Keywords are case insensitive, and `EMPTY` is accepted
+/// for every type except `POINT`.
+impl FromStr for SimpleGeometry {
+    type Err = GeometryFormatError;
+
+    fn from_str(s: &str) -> Result<SimpleGeometry, GeometryFormatError> {
+        let mut parser = Parser { s, pos: 0 };
+        let geometry = parser.geometry()?;
+        parser.skip_whitespace();
+        match parser.pos < s.len() {
+            true => Err(GeometryFormatError::UnexpectedToken(parser.pos)),
+            false => Ok(geometry),
+        }
+    }
+}
+
+fn write_list<T>(
+    fmt: &mut fmt::Formatter,
+    items: &[T],
+    mut write_item: impl FnMut(&mut fmt::Formatter, &T) -> fmt::Result,
+) -> fmt::Result {
+    if items.is_empty() {
+        return fmt.write_str("EMPTY");
+    }
+    fmt.write_str("(")?;
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            fmt.write_str(", ")?;
+        }
+        write_item(fmt, item)?;
+    }
+    fmt.write_str(")")
+}
+
+fn write_points(fmt: &mut fmt::Formatter, points: &[Point2f]) -> fmt::Result {
+    write_list(fmt, points, |fmt, p| write!(fmt, "{} {}", p.x, p.y))
+}
+
+fn write_rings(fmt: &mut fmt::Formatter, rings: &[Polygon]) -> fmt::Result {
+    write_list(fmt, rings, |fmt, ring| {
+        write_points(fmt, &closed_ring(ring))
+    })
+}
+
+/// The points of the polygon with the first repeated at the end.
+fn closed_ring(ring: &Polygon) -> Vec<Point2f> {
+    let mut points = ring.points.clone();
+    if let (Some(&first), Some(&last)) = (points.first(), points.last()) {
+        if first != last {
+            points.push(first);
+        }
+    }
+    points
+}
+
+/// A polygon from the points of a closed ring.
+fn open_ring(mut points: Vec<Point2f>) -> Polygon {
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    Polygon::new(points)
+}
+
+struct Parser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.s[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn word(&mut self) -> &'a str {
+        self.take_while(|c| c.is_ascii_alphabetic())
+    }
+
+    fn error(&self) -> GeometryFormatError {
+        match self.pos < self.s.len() {
+            true => GeometryFormatError::UnexpectedToken(self.pos),
+            false => GeometryFormatError::UnexpectedEnd,
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        match self.rest().starts_with(c) {
+            true => {
+                self.pos += c.len_utf8();
+                true
+            }
+            false => false,
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), GeometryFormatError> {
+        match self.eat(c) {
+            true => Ok(()),
+            false => Err(self.error()),
+        }
+    }
+
+    /// Consumes `EMPTY` if it comes next.
+    fn empty(&mut self) -> bool {
+        let start = self.pos;
+        if self.word().eq_ignore_ascii_case("EMPTY") {
+            return true;
+        }
+        self.pos = start;
+        false
+    }
+
+    fn number(&mut self) -> Result<f32, GeometryFormatError> {
+        let text = self.take_while(|c| c.is_ascii_digit() || "+-.eE".contains(c));
+        match text {
+            "" => Err(self.error()),
+            _ => text.parse().map_err(|_| GeometryFormatError::BadCoordinate),
+        }
+    }
+
+    fn point(&mut self) -> Result<Point2f, GeometryFormatError> {
+        Ok(Point2f::new(self.number()?, self.number()?))
+    }
+
+    /// Parses `EMPTY`, or a parenthesized, comma separated list of items.
+    fn list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, GeometryFormatError>,
+    ) -> Result<Vec<T>, GeometryFormatError> {
+        if self.empty() {
+            return Ok(Vec::new());
+        }
+        self.expect('(')?;
+        let mut items = vec![item(self)?];
+        while self.eat(',') {
+            items.push(item(self)?);
+        }
+        self.expect(')')?;
+        Ok(items)
+    }
+
+    fn points(&mut self) -> Result<Vec<Point2f>, GeometryFormatError> {
+        self.list(Self::point)
+    }
+
+    fn rings(&mut self) -> Result<Vec<Polygon>, GeometryFormatError> {
+        self.list(|p| p.points().map(open_ring))
+    }
+
+    fn geometry(&mut self) -> Result<SimpleGeometry, GeometryFormatError> {
+        let name = self.word().to_ascii_uppercase();
+        Ok(match &name[..] {
+            "POINT" => {
+                self.expect('(')?;
+                let point = self.point()?;
+                self.expect(')')?;
+                SimpleGeometry::Point(point)
+            }
+            "LINESTRING" => SimpleGeometry::LineString(Polyline::new(self.points()?)),
+            "POLYGON" => SimpleGeometry::Polygon(self.rings()?),
+            // Both `MULTIPOINT ((1 2), (3 4))` and `MULTIPOINT (1 2, 3 4)`
+            // are in common use
+            "MULTIPOINT" => SimpleGeometry::MultiPoint(self.list(|p| match p.eat('(') {
+                true => {
+                    let point = p.point()?;
+                    p.expect(')')?;
+                    Ok(point)
+                }
+                false => p.point(),
+            })?),
+            "MULTILINESTRING" => {
+                SimpleGeometry::MultiLineString(self.list(|p| p.points().map(Polyline::new))?)
+            }
+            "MULTIPOLYGON" => SimpleGeometry::MultiPolygon(self.list(Self::rings)?),
+            "GEOMETRYCOLLECTION" => SimpleGeometry::GeometryCollection(self.list(Self::geometry)?),
+            "" => return Err(self.error()),
+            _ => return Err(GeometryFormatError::UnsupportedType(name)),
+        })
+    }
+}
+
+#[cfg(feature = "geojson")]
+impl SimpleGeometry {
+    /// Converts the geometry to a GeoJSON geometry object.
+    pub fn to_geojson(&self) -> serde_json::Value {
+        use serde_json::json;
+
+        fn position(p: Point2f) -> serde_json::Value {
+            json!([p.x, p.y])
+        }
+        fn positions(points: &[Point2f]) -> serde_json::Value {
+            points.iter().copied().map(position).collect()
+        }
+        fn rings(rings: &[Polygon]) -> serde_json::Value {
+            rings.iter().map(|r| positions(&closed_ring(r))).collect()
+        }
+
+        match *self {
+            SimpleGeometry::Point(p) => json!({ "type": "Point", "coordinates": position(p) }),
+            SimpleGeometry::LineString(ref line) => {
+                json!({ "type": "LineString", "coordinates": positions(&line.points) })
+            }
+            SimpleGeometry::Polygon(ref r) => json!({ "type": "Polygon", "coordinates": rings(r) }),
+            SimpleGeometry::MultiPoint(ref points) => {
+                json!({ "type": "MultiPoint", "coordinates": positions(points) })
+            }
+            SimpleGeometry::MultiLineString(ref lines) => json!({
+                "type": "MultiLineString",
+                "coordinates": lines.iter().map(|l| positions(&l.points)).collect::<Vec<_>>(),
+            }),
+            SimpleGeometry::MultiPolygon(ref polygons) => json!({
+                "type": "MultiPolygon",
+                "coordinates": polygons.iter().map(|r| rings(r)).collect::<Vec<_>>(),
+            }),
+            SimpleGeometry::GeometryCollection(ref geometries) => json!({
+                "type": "GeometryCollection",
+                "geometries": geometries.iter().map(|g| g.to_geojson()).collect::<Vec<_>>(),
+            }),
+        }
+    }
+
+    /// Reads a GeoJSON geometry object. Positions may have an altitude or
+    /// further elements, which are ignored.
+    pub fn from_geojson(value: &serde_json::Value) -> Result<SimpleGeometry, GeometryFormatError> {
+        use serde_json::Value;
+
+        fn array(value: &Value) -> Result<&Vec<Value>, GeometryFormatError> {
+            value.as_array().ok_or(GeometryFormatError::BadGeoJson)
+        }
+        fn position(value: &Value) -> Result<Point2f, GeometryFormatError> {
+            match &array(value)?[..] {
+                [x, y, ..] => match (x.as_f64(), y.as_f64()) {
+                    (Some(x), Some(y)) => Ok(Point2f::new(x as f32, y as f32)),
+                    _ => Err(GeometryFormatError::BadCoordinate),
+                },
+                _ => Err(GeometryFormatError::BadCoordinate),
+            }
+        }
+        fn positions(value: &Value) -> Result<Vec<Point2f>, GeometryFormatError> {
+            array(value)?.iter().map(position).collect()
+        }
+        fn rings(value: &Value) -> Result<Vec<Polygon>, GeometryFormatError> {
+            array(value)?
+                .iter()
+                .map(|r| positions(r).map(open_ring))
+                .collect()
+        }
+
+        let kind = value["type"]
+            .as_str()
+            .ok_or(GeometryFormatError::BadGeoJson)?;
+        if kind == "GeometryCollection" {
+            let geometries = array(&value["geometries"])?;
+            return geometries
+                .iter()
+                .map(SimpleGeometry::from_geojson)
+                .collect::<Result<_, _>>()
+                .map(SimpleGeometry::GeometryCollection);
+        }
+
+        let coords = &value["coordinates"];
+        Ok(match kind {
+            "Point" => SimpleGeometry::Point(position(coords)?),
+            "LineString" => SimpleGeometry::LineString(Polyline::new(positions(coords)?)),
+            "Polygon" => SimpleGeometry::Polygon(rings(coords)?),
+            "MultiPoint" => SimpleGeometry::MultiPoint(positions(coords)?),
+            "MultiLineString" => SimpleGeometry::MultiLineString(
+                array(coords)?
+                    .iter()
+                    .map(|l| positions(l).map(Polyline::new))
+                    .collect::<Result<_, _>>()?,
+            ),
+            "MultiPolygon" => SimpleGeometry::MultiPolygon(
+                array(coords)?.iter().map(rings).collect::<Result<_, _>>()?,
+            ),
+            _ => return Err(GeometryFormatError::UnsupportedType(kind.to_string())),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GeometryFormatError, SimpleGeometry};
+    use crate::point2f::Point2f;
+    use crate::polygon::Polygon;
+    use crate::polyline::Polyline;
+
+    fn square() -> Polygon {
+        Polygon::new(vec![
+            Point2f::new(0.0, 0.0),
+            Point2f::new(4.0, 0.0),
+            Point2f::new(4.0, 4.0),
+            Point2f::new(0.0, 4.0),
+        ])
+    }
+
+    #[test]
+    fn wkt_round_trip() {
+        let polygon = SimpleGeometry::from(square());
+        let text = polygon.to_string();
+        assert_eq!(text, "POLYGON ((0 0, 4 0, 4 4, 0 4, 0 0))");
+        assert_eq!(text.parse::<SimpleGeometry>(), Ok(polygon.clone()));
+
+        let collection = SimpleGeometry::GeometryCollection(vec![
+            Point2f::new(1.5, -2.0).into(),
+            Polyline::new(vec![Point2f::new(0.0, 0.0), Point2f::new(1.0, 1.0)]).into(),
+            SimpleGeometry::MultiPolygon(vec![vec![square(), square()], Vec::new()]),
+            SimpleGeometry::MultiPoint(Vec::new()),
+        ]);
+        let text = collection.to_string();
+        assert_eq!(text.parse::<SimpleGeometry>(), Ok(collection));
+    }
+
+    #[test]
+    fn wkt_parsing() {
+        let points = "multipoint (1 2, (3 4))".parse::<SimpleGeometry>();
+        assert_eq!(
+            points,
+            Ok(SimpleGeometry::MultiPoint(vec![
+                Point2f::new(1.0, 2.0),
+                Point2f::new(3.0, 4.0)
+            ]))
+        );
+        assert_eq!(
+            "LINESTRING EMPTY".parse::<SimpleGeometry>(),
+            Ok(SimpleGeometry::LineString(Polyline::default()))
+        );
+        assert_eq!(
+            "POINT (1e2 -3.5)".parse::<SimpleGeometry>(),
+            Ok(SimpleGeometry::Point(Point2f::new(100.0, -3.5)))
+        );
+
+        assert_eq!(
+            "POINT Z (1 2 3)".parse::<SimpleGeometry>(),
+            Err(GeometryFormatError::UnexpectedToken(6))
+        );
+        assert_eq!(
+            "CIRCULARSTRING (0 0, 1 1, 2 0)".parse::<SimpleGeometry>(),
+            Err(GeometryFormatError::UnsupportedType(
+                "CIRCULARSTRING".into()
+            ))
+        );
+        assert_eq!(
+            "POINT (1 2".parse::<SimpleGeometry>(),
+            Err(GeometryFormatError::UnexpectedEnd)
+        );
+        assert_eq!(
+            "POINT (1 2) x".parse::<SimpleGeometry>(),
+            Err(GeometryFormatError::UnexpectedToken(12))
+        );
+    }
+
+    #[cfg(feature = "geojson")]
+    #[test]
+    fn geojson_round_trip() {
+        let geometry = SimpleGeometry::GeometryCollection(vec![
+            Point2f::new(1.5, -2.0).into(),
+            SimpleGeometry::from(square()),
+            SimpleGeometry::MultiLineString(vec![Polyline::new(vec![
+                Point2f::new(0.0, 0.0),
+                Point2f::new(1.0, 1.0),
+            ])]),
+        ]);
+        let json = geometry.to_geojson();
+        assert_eq!(
+            json["geometries"][1]["coordinates"][0][4],
+            serde_json::json!([0.0, 0.0])
+        );
+        assert_eq!(SimpleGeometry::from_geojson(&json), Ok(geometry));
+
+        let point = serde_json::json!({ "type": "Point", "coordinates": [1.0, 2.0, 30.0] });
+        assert_eq!(
+            SimpleGeometry::from_geojson(&point),
+            Ok(SimpleGeometry::Point(Point2f::new(1.0, 2.0)))
+        );
+        assert_eq!(
+            SimpleGeometry::from_geojson(&serde_json::json!({ "type": "Point" })),
+            Err(GeometryFormatError::BadGeoJson)
+        );
+    }
+}
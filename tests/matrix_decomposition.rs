@@ -3,6 +3,7 @@ extern crate rand;
 
 use std::f32::consts::PI;
 
+use math2d::angle::shortest_delta;
 use math2d::matrix3x2f::IDENTITY;
 use math2d::point2f::ORIGIN;
 use math2d::vector2f::{ONE, ZERO};
@@ -138,9 +139,8 @@ fn random_compositions() {
 }
 
 fn assert_angle_approx(a1: f32, a2: f32) {
-    let diff = (a1 - a2).abs();
     assert!(
-        diff <= EPSILON || (diff - 2.0 * PI).abs() <= EPSILON,
+        shortest_delta(a1, a2).abs() <= EPSILON,
         "Angles which should have been equivalent were different: {} and {}",
         a1,
         a2